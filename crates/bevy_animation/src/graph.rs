@@ -883,7 +883,7 @@ pub(crate) fn thread_animation_graphs(
                     .insert(id, threaded_animation_graph);
             }
 
-            AssetEvent::Removed { id } => {
+            AssetEvent::Removed { id } | AssetEvent::Evicted { id } => {
                 threaded_animation_graphs.0.remove(&id);
             }
             AssetEvent::Unused { .. } => {}
@@ -9,7 +9,7 @@ use alloc::{
 };
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
-    component::RequiredComponentsError,
+    component::{ArchetypeInvariant, InvariantViolationBehavior, RequiredComponentsError},
     error::{ErrorHandler, FallbackErrorHandler},
     intern::Interned,
     message::{message_update_system, MessageCursor},
@@ -1170,6 +1170,39 @@ impl App {
             .try_register_required_components_with::<T, R>(constructor)
     }
 
+    /// Registers an [`ArchetypeInvariant`] in the main [`World`].
+    ///
+    /// See [`World::register_archetype_invariant`] for more information.
+    pub fn register_archetype_invariant(&mut self, invariant: ArchetypeInvariant) -> &mut Self {
+        self.world_mut().register_archetype_invariant(invariant);
+        self
+    }
+
+    /// Registers the components of the bundle `B` as mutually exclusive:
+    /// no entity may have more than one of them.
+    ///
+    /// See [`World::register_exclusive_components`] for more information.
+    pub fn register_exclusive_components<B: Bundle>(
+        &mut self,
+        behavior: InvariantViolationBehavior,
+    ) -> &mut Self {
+        self.world_mut()
+            .register_exclusive_components::<B>(behavior);
+        self
+    }
+
+    /// Requires entities with the component `S` to have exactly one of the components of the bundle `B`.
+    ///
+    /// See [`World::register_exactly_one_component`] for more information.
+    pub fn register_exactly_one_component<S: Component, B: Bundle>(
+        &mut self,
+        behavior: InvariantViolationBehavior,
+    ) -> &mut Self {
+        self.world_mut()
+            .register_exactly_one_component::<S, B>(behavior);
+        self
+    }
+
     /// Registers a component type as "disabling",
     /// using [default query filters](bevy_ecs::entity_disabling::DefaultQueryFilters) to exclude entities with the component from queries.
     ///
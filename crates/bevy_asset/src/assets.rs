@@ -1,12 +1,18 @@
 use crate::asset_changed::AssetChanges;
-use crate::{Asset, AssetEvent, AssetHandleProvider, AssetId, AssetServer, Handle, UntypedHandle};
+use crate::{
+    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetServer, ErasedAssetIndex, Handle,
+    UntypedHandle,
+};
 use alloc::{sync::Arc, vec::Vec};
 use bevy_ecs::{
     message::MessageWriter,
     resource::Resource,
     system::{Res, ResMut, SystemChangeTick},
 };
-use bevy_platform::collections::HashMap;
+use bevy_platform::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
 use bevy_reflect::{Reflect, TypePath};
 use core::ops::{Deref, DerefMut};
 use core::{any::TypeId, iter::Enumerate, marker::PhantomData, sync::atomic::AtomicU32};
@@ -77,9 +83,7 @@ impl AssetIndexAllocator {
             recycled
         } else {
             AssetIndex {
-                index: self
-                    .next_index
-                    .fetch_add(1, core::sync::atomic::Ordering::Relaxed),
+                index: self.next_index.fetch_add(1, Ordering::Relaxed),
                 generation: 0,
             }
         }
@@ -111,6 +115,19 @@ enum Entry<A: Asset> {
     Some { value: Option<A>, generation: u32 },
 }
 
+/// Tracks the accesses to an asset marked with [`Assets::set_evictable`].
+#[derive(Default)]
+struct EvictableAsset {
+    /// The value of [`Assets::access_clock`] when the asset was last accessed.
+    last_access: AtomicU64,
+    /// Set when the asset is accessed while it is evicted, to request a reload.
+    accessed_while_evicted: AtomicBool,
+    /// Whether the asset is currently evicted.
+    evicted: bool,
+    /// Whether the evicted asset is being reloaded.
+    reloading: bool,
+}
+
 /// Stores [`Asset`] values in a Vec-like storage identified by [`AssetIndex`].
 struct DenseAssetStorage<A: Asset> {
     storage: Vec<Entry<A>>,
@@ -232,10 +249,7 @@ impl<A: Asset> DenseAssetStorage<A> {
 
     pub(crate) fn flush(&mut self) {
         // NOTE: this assumes the allocator index is monotonically increasing.
-        let new_len = self
-            .allocator
-            .next_index
-            .load(core::sync::atomic::Ordering::Relaxed);
+        let new_len = self.allocator.next_index.load(Ordering::Relaxed);
         self.storage.resize_with(new_len as usize, || Entry::Some {
             value: None,
             generation: 0,
@@ -284,6 +298,20 @@ impl<A: Asset> DenseAssetStorage<A> {
 /// This tracks (and queues) [`AssetEvent`] events whenever changes to the collection occur.
 /// To check whether the asset used by a given component has changed (due to a change in the handle or the underlying asset)
 /// use the [`AssetChanged`](crate::asset_changed::AssetChanged) query filter.
+///
+/// # Memory budgets
+///
+/// An asset is normally kept until its last strong [`Handle`] is dropped. To stream assets in and
+/// out of memory, a collection can be given a memory budget with [`Assets::set_memory_budget`],
+/// and assets can be marked as evictable with [`Assets::set_evictable`]. When the
+/// [`memory_size`](Asset::memory_size) of the assets in the collection exceeds the budget, the
+/// evictable assets that were least recently accessed are unloaded until the collection is within
+/// its budget again, and an [`AssetEvent::Evicted`] is emitted for each of them.
+///
+/// Evicted assets keep their handles. Accessing an evicted asset with [`Assets::get`] or
+/// [`Assets::get_mut`] returns [`None`] and reloads it from its path, so it is available again a
+/// few frames later. Only assets loaded by the [`AssetServer`] can be evicted, since the others
+/// have no path to be reloaded from.
 #[derive(Resource)]
 pub struct Assets<A: Asset> {
     dense_storage: DenseAssetStorage<A>,
@@ -293,6 +321,22 @@ pub struct Assets<A: Asset> {
     /// Assets managed by the `Assets` struct with live strong `Handle`s
     /// originating from `get_strong_handle`.
     duplicate_handles: HashMap<AssetIndex, u16>,
+    /// The memory budget of this collection, in bytes.
+    memory_budget: Option<usize>,
+    /// The sum of the [`memory_size`](Asset::memory_size) of the assets, except for those in
+    /// `memory_remeasure`.
+    memory_total: usize,
+    /// Assets that were mutably borrowed since the last [`Assets::update_memory_total`], and
+    /// whose size is therefore not counted in `memory_total`.
+    memory_remeasure: HashSet<AssetId<A>>,
+    /// Set when all assets were mutably borrowed, in which case `memory_total` is ignored.
+    memory_remeasure_all: bool,
+    /// The assets that can be evicted when this collection is over its memory budget.
+    evictable: HashMap<AssetIndex, EvictableAsset>,
+    /// Incremented every frame in which there are evictable assets, to order their accesses.
+    access_clock: u64,
+    /// Evicted assets that must be reloaded because they are no longer evictable.
+    pending_reloads: Vec<AssetIndex>,
 }
 
 impl<A: Asset> Default for Assets<A> {
@@ -306,6 +350,13 @@ impl<A: Asset> Default for Assets<A> {
             hash_map: Default::default(),
             queued_events: Default::default(),
             duplicate_handles: Default::default(),
+            memory_budget: None,
+            memory_total: 0,
+            memory_remeasure: Default::default(),
+            memory_remeasure_all: false,
+            evictable: Default::default(),
+            access_clock: 0,
+            pending_reloads: Vec::new(),
         }
     }
 }
@@ -370,6 +421,8 @@ impl<A: Asset> Assets<A> {
     }
 
     pub(crate) fn insert_with_uuid(&mut self, uuid: Uuid, asset: A) -> Option<A> {
+        self.uncount_memory(uuid.into());
+        self.count_memory(uuid.into(), asset.memory_size());
         let result = self.hash_map.insert(uuid, asset);
         if result.is_some() {
             self.queued_events
@@ -385,7 +438,15 @@ impl<A: Asset> Assets<A> {
         index: AssetIndex,
         asset: A,
     ) -> Result<bool, InvalidGenerationError> {
+        let size = asset.memory_size();
+        self.uncount_memory(index.into());
         let replaced = self.dense_storage.insert(index, asset)?;
+        self.count_memory(index.into(), size);
+        if let Some(evictable) = self.evictable.get_mut(&index) {
+            evictable.evicted = false;
+            evictable.reloading = false;
+            *evictable.last_access.get_mut() = self.access_clock;
+        }
         if replaced {
             self.queued_events
                 .push(AssetEvent::Modified { id: index.into() });
@@ -429,7 +490,10 @@ impl<A: Asset> Assets<A> {
     #[inline]
     pub fn get(&self, id: impl Into<AssetId<A>>) -> Option<&A> {
         match id.into() {
-            AssetId::Index { index, .. } => self.dense_storage.get(index),
+            AssetId::Index { index, .. } => {
+                self.record_access(index);
+                self.dense_storage.get(index)
+            }
             AssetId::Uuid { uuid } => self.hash_map.get(&uuid),
         }
     }
@@ -439,8 +503,12 @@ impl<A: Asset> Assets<A> {
     #[inline]
     pub fn get_mut(&mut self, id: impl Into<AssetId<A>>) -> Option<AssetMut<'_, A>> {
         let id: AssetId<A> = id.into();
+        self.remeasure_memory(id);
         let result = match id {
-            AssetId::Index { index, .. } => self.dense_storage.get_mut(index),
+            AssetId::Index { index, .. } => {
+                self.record_access(index);
+                self.dense_storage.get_mut(index)
+            }
             AssetId::Uuid { uuid } => self.hash_map.get_mut(&uuid),
         };
         Some(AssetMut {
@@ -459,12 +527,34 @@ impl<A: Asset> Assets<A> {
     #[inline]
     pub fn get_mut_untracked(&mut self, id: impl Into<AssetId<A>>) -> Option<&mut A> {
         let id: AssetId<A> = id.into();
+        self.remeasure_memory(id);
         match id {
-            AssetId::Index { index, .. } => self.dense_storage.get_mut(index),
+            AssetId::Index { index, .. } => {
+                self.record_access(index);
+                self.dense_storage.get_mut(index)
+            }
             AssetId::Uuid { uuid } => self.hash_map.get_mut(&uuid),
         }
     }
 
+    /// Records an access to the asset at `index`, if it is evictable.
+    #[inline]
+    fn record_access(&self, index: AssetIndex) {
+        if self.evictable.is_empty() {
+            return;
+        }
+        if let Some(evictable) = self.evictable.get(&index) {
+            evictable
+                .last_access
+                .store(self.access_clock, Ordering::Relaxed);
+            if evictable.evicted {
+                evictable
+                    .accessed_while_evicted
+                    .store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
     /// Removes (and returns) the [`Asset`] with the given `id`, if it exists.
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
     pub fn remove(&mut self, id: impl Into<AssetId<A>>) -> Option<A> {
@@ -482,6 +572,8 @@ impl<A: Asset> Assets<A> {
     /// This is the same as [`Assets::remove`] except it doesn't emit [`AssetEvent::Removed`].
     pub fn remove_untracked(&mut self, id: impl Into<AssetId<A>>) -> Option<A> {
         let id: AssetId<A> = id.into();
+        self.uncount_memory(id);
+        self.memory_remeasure.remove(&id);
         match id {
             AssetId::Index { index, .. } => self.dense_storage.remove_still_alive(index),
             AssetId::Uuid { uuid } => self.hash_map.remove(&uuid),
@@ -501,6 +593,9 @@ impl<A: Asset> Assets<A> {
             }
         }
 
+        self.evictable.remove(&index);
+        self.uncount_memory(index.into());
+        self.memory_remeasure.remove(&AssetId::from(index));
         let existed = self.dense_storage.remove_dropped(index).is_some();
 
         self.queued_events
@@ -558,6 +653,7 @@ impl<A: Asset> Assets<A> {
     /// Returns an iterator over the [`AssetId`] and mutable [`Asset`] ref of every asset in this collection.
     // PERF: this could be accelerated if we implement a skip list. Consider the cost/benefits
     pub fn iter_mut(&mut self) -> AssetsMutIterator<'_, A> {
+        self.memory_remeasure_all = true;
         AssetsMutIterator {
             dense_storage: self.dense_storage.storage.iter_mut().enumerate(),
             hash_map: self.hash_map.iter_mut(),
@@ -565,6 +661,132 @@ impl<A: Asset> Assets<A> {
         }
     }
 
+    /// Sets the memory budget of this collection, in bytes, or removes it if `budget` is [`None`].
+    ///
+    /// When the [`memory_usage`](Self::memory_usage) of this collection exceeds its budget, the
+    /// least recently accessed [evictable](Self::set_evictable) assets are evicted. See
+    /// [`Assets`] for details.
+    pub fn set_memory_budget(&mut self, budget: Option<usize>) {
+        self.memory_budget = budget;
+    }
+
+    /// Returns the memory budget of this collection, in bytes, if it has one.
+    pub fn memory_budget(&self) -> Option<usize> {
+        self.memory_budget
+    }
+
+    /// Returns the sum of the [`memory_size`](Asset::memory_size) of the assets in this
+    /// collection.
+    ///
+    /// This is a running total, so only the assets that were mutably borrowed since it was last
+    /// updated need to be measured again.
+    pub fn memory_usage(&self) -> usize {
+        if self.memory_remeasure_all {
+            return self.iter().map(|(_, asset)| asset.memory_size()).sum();
+        }
+        self.memory_total
+            + self
+                .memory_remeasure
+                .iter()
+                .filter_map(|id| self.get_untracked(*id))
+                .map(Asset::memory_size)
+                .sum::<usize>()
+    }
+
+    /// Returns the asset with the given `id` without recording an access to it.
+    fn get_untracked(&self, id: AssetId<A>) -> Option<&A> {
+        match id {
+            AssetId::Index { index, .. } => self.dense_storage.get(index),
+            AssetId::Uuid { uuid } => self.hash_map.get(&uuid),
+        }
+    }
+
+    /// Adds the `size` of the asset newly stored for `id` to the running memory total.
+    fn count_memory(&mut self, id: AssetId<A>, size: usize) {
+        if !self.memory_remeasure_all {
+            self.memory_remeasure.remove(&id);
+            self.memory_total += size;
+        }
+    }
+
+    /// Subtracts the size of the asset stored for `id` from the running memory total, if it is
+    /// counted there, because it is about to be replaced or removed.
+    fn uncount_memory(&mut self, id: AssetId<A>) {
+        if self.memory_remeasure_all || self.memory_remeasure.contains(&id) {
+            return;
+        }
+        if let Some(asset) = self.get_untracked(id) {
+            self.memory_total = self.memory_total.saturating_sub(asset.memory_size());
+        }
+    }
+
+    /// Stops counting the asset stored for `id` in the running memory total until the next
+    /// [`update_memory_total`](Self::update_memory_total), because it is about to be mutably
+    /// borrowed and its size may change.
+    fn remeasure_memory(&mut self, id: AssetId<A>) {
+        if !self.memory_remeasure.contains(&id) && self.get_untracked(id).is_some() {
+            self.uncount_memory(id);
+            if !self.memory_remeasure_all {
+                self.memory_remeasure.insert(id);
+            }
+        }
+    }
+
+    /// Measures the assets that were mutably borrowed since the last update again, and adds them
+    /// to the running memory total.
+    fn update_memory_total(&mut self) {
+        self.memory_total = self.memory_usage();
+        self.memory_remeasure.clear();
+        self.memory_remeasure_all = false;
+    }
+
+    /// Sets whether the asset with the given `id` can be evicted when this collection is over its
+    /// [memory budget](Self::set_memory_budget).
+    ///
+    /// This can be called as soon as a [`Handle`] to the asset exists, even before it is loaded.
+    /// An asset stops being evictable when its last strong handle is dropped. Assets identified by
+    /// [`AssetId::Uuid`] can't be evicted.
+    pub fn set_evictable(&mut self, id: impl Into<AssetId<A>>, evictable: bool) {
+        let AssetId::Index { index, .. } = id.into() else {
+            return;
+        };
+        if evictable {
+            self.evictable
+                .entry(index)
+                .or_insert_with(|| EvictableAsset {
+                    last_access: AtomicU64::new(self.access_clock),
+                    ..Default::default()
+                });
+        } else if self
+            .evictable
+            .remove(&index)
+            .is_some_and(|evictable| evictable.evicted && !evictable.reloading)
+        {
+            // The asset can't be evicted anymore, so bring it back now.
+            self.pending_reloads.push(index);
+        }
+    }
+
+    /// Returns `true` if the asset with the given `id` can be evicted when this collection is over
+    /// its [memory budget](Self::set_memory_budget).
+    pub fn is_evictable(&self, id: impl Into<AssetId<A>>) -> bool {
+        match id.into() {
+            AssetId::Index { index, .. } => self.evictable.contains_key(&index),
+            AssetId::Uuid { .. } => false,
+        }
+    }
+
+    /// Returns `true` if the asset with the given `id` is currently evicted.
+    pub fn is_evicted(&self, id: impl Into<AssetId<A>>) -> bool {
+        match id.into() {
+            AssetId::Index { index, .. } => self
+                .evictable
+                .get(&index)
+                .is_some_and(|evictable| evictable.evicted),
+            AssetId::Uuid { .. } => false,
+        }
+    }
+
     /// A system that synchronizes the state of assets in this collection with the [`AssetServer`]. This manages
     /// [`Handle`] drop events.
     pub fn track_assets(mut assets: ResMut<Self>, asset_server: Res<AssetServer>) {
@@ -586,6 +808,79 @@ impl<A: Asset> Assets<A> {
         }
     }
 
+    /// A system that reloads the evicted assets that were accessed since it last ran, and evicts
+    /// the least recently accessed evictable assets while this collection is over its memory
+    /// budget.
+    pub(crate) fn evict_assets(mut assets: ResMut<Self>, asset_server: Res<AssetServer>) {
+        let assets = &mut *assets;
+        assets.access_clock += 1;
+
+        let mut reloads = core::mem::take(&mut assets.pending_reloads);
+        for (index, evictable) in &mut assets.evictable {
+            if evictable.evicted
+                && !evictable.reloading
+                && *evictable.accessed_while_evicted.get_mut()
+            {
+                evictable.reloading = true;
+                *evictable.accessed_while_evicted.get_mut() = false;
+                reloads.push(*index);
+            }
+        }
+        for index in reloads {
+            if let Some(path) = asset_server.get_path(AssetId::<A>::from(index)) {
+                asset_server.reload(path);
+            }
+        }
+
+        let Some(budget) = assets.memory_budget else {
+            return;
+        };
+        assets.update_memory_total();
+        if assets.memory_total <= budget {
+            return;
+        }
+
+        let mut candidates = assets
+            .evictable
+            .iter_mut()
+            .filter(|(_, evictable)| !evictable.evicted)
+            .map(|(index, evictable)| (*evictable.last_access.get_mut(), *index))
+            .collect::<Vec<_>>();
+        candidates.sort_unstable();
+
+        // Hold the lock for the whole eviction, so that loads of the evicted assets that happen
+        // during it see them as not loaded and reload them.
+        let mut infos = asset_server.write_infos();
+        for (_, index) in candidates {
+            if assets.memory_total <= budget {
+                break;
+            }
+            if assets.dense_storage.get(index).is_none()
+                || !infos.process_asset_eviction(ErasedAssetIndex::new(index, TypeId::of::<A>()))
+            {
+                continue;
+            }
+            let Some(asset) = assets.dense_storage.remove_still_alive(index) else {
+                continue;
+            };
+            assets.memory_total = assets.memory_total.saturating_sub(asset.memory_size());
+            if let Some(evictable) = assets.evictable.get_mut(&index) {
+                evictable.evicted = true;
+            }
+            assets
+                .queued_events
+                .push(AssetEvent::Evicted { id: index.into() });
+        }
+    }
+
+    /// A run condition for [`evict_assets`]. The system will not run if there are no evictable
+    /// assets and no evicted assets to reload.
+    ///
+    /// [`evict_assets`]: Self::evict_assets
+    pub(crate) fn evict_assets_condition(assets: Res<Self>) -> bool {
+        !assets.evictable.is_empty() || !assets.pending_reloads.is_empty()
+    }
+
     /// A system that applies accumulated asset change events to the [`Messages`] resource.
     ///
     /// [`Messages`]: bevy_ecs::message::Messages
@@ -595,12 +890,14 @@ impl<A: Asset> Assets<A> {
         asset_changes: Option<ResMut<AssetChanges<A>>>,
         ticks: SystemChangeTick,
     ) {
-        use AssetEvent::{Added, LoadedWithDependencies, Modified, Removed};
+        use AssetEvent::{Added, Evicted, LoadedWithDependencies, Modified, Removed};
 
         if let Some(mut asset_changes) = asset_changes {
             for new_event in &assets.queued_events {
                 match new_event {
-                    Removed { id } | AssetEvent::Unused { id } => asset_changes.remove(id),
+                    Removed { id } | AssetEvent::Unused { id } | Evicted { id } => {
+                        asset_changes.remove(id);
+                    }
                     Added { id } | Modified { id } | LoadedWithDependencies { id } => {
                         asset_changes.insert(*id, ticks.this_run());
                     }
@@ -747,7 +1044,8 @@ pub enum InvalidGenerationError {
 #[cfg(test)]
 mod test {
     use crate::tests::create_app;
-    use crate::{Asset, AssetApp, AssetEvent, AssetIndex, Assets};
+    use crate::{Asset, AssetApp, AssetEvent, AssetIndex, Assets, VisitAssetDependencies};
+    use alloc::{vec, vec::Vec};
     use bevy_ecs::prelude::Messages;
     use bevy_reflect::TypePath;
 
@@ -761,6 +1059,40 @@ mod test {
         assert_eq!(asset_index, roundtripped);
     }
 
+    #[test]
+    fn tracks_memory_usage() {
+        #[derive(VisitAssetDependencies, TypePath)]
+        struct Buffer(Vec<u8>);
+
+        impl Asset for Buffer {
+            fn memory_size(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        let mut assets = Assets::<Buffer>::default();
+        let a = assets.add(Buffer(vec![0; 10]));
+        let b = assets.add(Buffer(vec![0; 20]));
+        assert_eq!(assets.memory_usage(), 30);
+
+        assets.get_mut(&a).unwrap().0.resize(50, 0);
+        assert_eq!(assets.memory_usage(), 70);
+        assets.update_memory_total();
+        assert_eq!(assets.memory_total, 70);
+
+        assets.insert(&b, Buffer(vec![0; 5])).unwrap();
+        assert_eq!(assets.memory_usage(), 55);
+        assets.get_mut_untracked(&b).unwrap().0.clear();
+        assets.remove(&a);
+        assert_eq!(assets.memory_usage(), 0);
+
+        for (_, buffer) in assets.iter_mut() {
+            buffer.0.resize(8, 0);
+        }
+        assets.update_memory_total();
+        assert_eq!(assets.memory_total, 8);
+    }
+
     #[test]
     fn assets_mut_change_detection() {
         #[derive(Asset, TypePath, Default)]
@@ -58,6 +58,11 @@ pub enum AssetEvent<A: Asset> {
     Unused { id: AssetId<A> },
     /// Emitted whenever an [`Asset`] has been fully loaded (including its dependencies and all "recursive dependencies").
     LoadedWithDependencies { id: AssetId<A> },
+    /// Emitted when an [`Asset`] is unloaded to keep its [`Assets`](crate::Assets) collection within
+    /// its memory budget. Its handles stay valid, and it is reloaded when it is next accessed.
+    ///
+    /// See [`Assets::set_memory_budget`](crate::Assets::set_memory_budget).
+    Evicted { id: AssetId<A> },
 }
 
 impl<A: Asset> AssetEvent<A> {
@@ -85,6 +90,11 @@ impl<A: Asset> AssetEvent<A> {
     pub fn is_unused(&self, asset_id: impl Into<AssetId<A>>) -> bool {
         matches!(self, AssetEvent::Unused { id } if *id == asset_id.into())
     }
+
+    /// Returns `true` if this event is [`AssetEvent::Evicted`] and matches the given `id`.
+    pub fn is_evicted(&self, asset_id: impl Into<AssetId<A>>) -> bool {
+        matches!(self, AssetEvent::Evicted { id } if *id == asset_id.into())
+    }
 }
 
 impl<A: Asset> Clone for AssetEvent<A> {
@@ -106,6 +116,7 @@ impl<A: Asset> Debug for AssetEvent<A> {
                 .debug_struct("LoadedWithDependencies")
                 .field("id", id)
                 .finish(),
+            Self::Evicted { id } => f.debug_struct("Evicted").field("id", id).finish(),
         }
     }
 }
@@ -117,6 +128,7 @@ impl<A: Asset> PartialEq for AssetEvent<A> {
             | (Self::Modified { id: l_id }, Self::Modified { id: r_id })
             | (Self::Removed { id: l_id }, Self::Removed { id: r_id })
             | (Self::Unused { id: l_id }, Self::Unused { id: r_id })
+            | (Self::Evicted { id: l_id }, Self::Evicted { id: r_id })
             | (
                 Self::LoadedWithDependencies { id: l_id },
                 Self::LoadedWithDependencies { id: r_id },
@@ -0,0 +1,212 @@
+//! An asset source made of a stack of readers, such as a base game with DLC, mods and patches on
+//! top of it.
+//!
+//! See [`LayeredAssetReader`] and [`AssetSourceBuilder::layered`](crate::io::AssetSourceBuilder::layered).
+
+use crate::io::{
+    AssetReader, AssetReaderError, AssetWatcher, ErasedAssetReader, PathStream, Reader,
+};
+use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
+use bevy_platform::collections::HashSet;
+use futures_lite::StreamExt;
+use std::path::{Path, PathBuf};
+
+/// An [`AssetReader`] that resolves paths through an ordered stack of layers.
+///
+/// Layers are given from the bottom up: each layer overrides the layers before it. A path is
+/// looked up from the top layer down, and the first layer that has the asset is used.
+///
+/// The meta of an asset is read from the same layer as the asset itself, so that a layer
+/// replacing an asset without providing a meta doesn't pick up the meta of the asset it replaces.
+/// A layer can still provide a meta without the asset, to change the settings of an asset of a
+/// lower layer.
+///
+/// Directory listings contain the entries of all layers that have the directory.
+pub struct LayeredAssetReader {
+    layers: Vec<Box<dyn ErasedAssetReader>>,
+}
+
+impl LayeredAssetReader {
+    /// Creates a new reader from the given `layers`, from the bottom up.
+    pub fn new(layers: impl IntoIterator<Item = Box<dyn ErasedAssetReader>>) -> Self {
+        Self {
+            layers: layers.into_iter().collect(),
+        }
+    }
+
+    /// Adds a layer on top of all existing layers.
+    pub fn with_layer(mut self, layer: impl AssetReader) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+
+    /// Returns the layers of this reader, from the bottom up.
+    pub fn layers(&self) -> &[Box<dyn ErasedAssetReader>] {
+        &self.layers
+    }
+}
+
+impl AssetReader for LayeredAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        for layer in self.layers.iter().rev() {
+            match layer.read(path).await {
+                Err(AssetReaderError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        Err(AssetReaderError::NotFound(path.to_owned()))
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        for layer in self.layers.iter().rev() {
+            match layer.read_meta(path).await {
+                Err(AssetReaderError::NotFound(_)) => {}
+                result => return result,
+            }
+            // This layer provides the asset but no meta, so the asset uses the default meta.
+            match layer.read(path).await {
+                Ok(_) => return Err(AssetReaderError::NotFound(path.to_owned())),
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Err(AssetReaderError::NotFound(path.to_owned()))
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let mut found = false;
+        let mut seen = HashSet::<PathBuf>::default();
+        let mut entries = Vec::new();
+        for layer in self.layers.iter().rev() {
+            let mut stream = match layer.read_directory(path).await {
+                Ok(stream) => stream,
+                Err(AssetReaderError::NotFound(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            found = true;
+            while let Some(entry) = stream.next().await {
+                if seen.insert(entry.clone()) {
+                    entries.push(entry);
+                }
+            }
+        }
+        if !found {
+            return Err(AssetReaderError::NotFound(path.to_owned()));
+        }
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(entries));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        for layer in self.layers.iter().rev() {
+            match layer.is_directory(path).await {
+                Ok(true) => return Ok(true),
+                Ok(false) | Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// An [`AssetWatcher`] that keeps the watchers of all layers of a [`LayeredAssetReader`] alive.
+///
+/// Every layer sends its events to the same channel. A change in a layer that is overridden by
+/// a higher layer still reloads the asset, which then loads the same data again.
+pub struct LayeredAssetWatcher {
+    _watchers: Vec<Box<dyn AssetWatcher>>,
+}
+
+impl LayeredAssetWatcher {
+    /// Creates a new watcher from the watchers of each layer.
+    pub fn new(watchers: Vec<Box<dyn AssetWatcher>>) -> Self {
+        Self {
+            _watchers: watchers,
+        }
+    }
+}
+
+impl AssetWatcher for LayeredAssetWatcher {}
+
+#[cfg(test)]
+mod tests {
+    use super::LayeredAssetReader;
+    use crate::io::{
+        memory::{Dir, MemoryAssetReader},
+        AssetReader, AssetReaderError, Reader,
+    };
+    use alloc::{vec, vec::Vec};
+    use futures_lite::StreamExt;
+    use std::path::{Path, PathBuf};
+
+    fn read(reader: &LayeredAssetReader, path: &str) -> Result<Vec<u8>, AssetReaderError> {
+        bevy_tasks::block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(bytes)
+        })
+    }
+
+    fn read_meta(reader: &LayeredAssetReader, path: &str) -> Option<Vec<u8>> {
+        bevy_tasks::block_on(async {
+            let mut bytes = Vec::new();
+            let mut meta = reader.read_meta(Path::new(path)).await.ok()?;
+            meta.read_to_end(&mut bytes).await.unwrap();
+            Some(bytes)
+        })
+    }
+
+    #[test]
+    fn layers_override_lower_layers() {
+        let base = Dir::default();
+        base.insert_asset(Path::new("a.txt"), b"base a".to_vec());
+        base.insert_meta(Path::new("a.txt"), b"base a meta".to_vec());
+        base.insert_asset(Path::new("b.txt"), b"base b".to_vec());
+        base.insert_meta(Path::new("b.txt"), b"base b meta".to_vec());
+        base.insert_asset(Path::new("dir/c.txt"), b"base c".to_vec());
+
+        let patch = Dir::default();
+        patch.insert_asset(Path::new("a.txt"), b"patch a".to_vec());
+        patch.insert_meta(Path::new("b.txt"), b"patch b meta".to_vec());
+        patch.insert_asset(Path::new("dir/d.txt"), b"patch d".to_vec());
+
+        let reader = LayeredAssetReader::new(vec![])
+            .with_layer(MemoryAssetReader { root: base })
+            .with_layer(MemoryAssetReader { root: patch });
+        assert_eq!(reader.layers().len(), 2);
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), b"patch a");
+        assert_eq!(read(&reader, "b.txt").unwrap(), b"base b");
+        assert_eq!(read(&reader, "dir/c.txt").unwrap(), b"base c");
+        assert!(matches!(
+            read(&reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        // The patch replaces `a.txt` without a meta, so the base meta must not be used.
+        assert_eq!(read_meta(&reader, "a.txt"), None);
+        assert_eq!(read_meta(&reader, "b.txt").unwrap(), b"patch b meta");
+
+        let mut entries = bevy_tasks::block_on(async {
+            reader
+                .read_directory(Path::new("dir"))
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+                .await
+        });
+        entries.sort();
+        assert_eq!(
+            entries,
+            [PathBuf::from("dir/c.txt"), PathBuf::from("dir/d.txt")]
+        );
+        assert!(bevy_tasks::block_on(reader.is_directory(Path::new("dir"))).unwrap());
+    }
+}
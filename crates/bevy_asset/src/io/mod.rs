@@ -15,7 +15,10 @@ pub mod android;
 pub mod embedded;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod file;
+pub mod layered;
 pub mod memory;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod packed;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
@@ -418,6 +421,13 @@ pub trait AssetWriter: Send + Sync + 'static {
             Ok(())
         }
     }
+    /// Makes all previous writes durable, for writers that batch or buffer them.
+    ///
+    /// This is called by the [`AssetProcessor`](crate::processor::AssetProcessor) whenever it
+    /// finishes processing. Does nothing by default.
+    fn flush(&self) -> impl ConditionalSendFuture<Output = Result<(), AssetWriterError>> {
+        async { Ok(()) }
+    }
 }
 
 /// Equivalent to an [`AssetWriter`] but using boxed futures, necessary eg. when using a `dyn AssetWriter`,
@@ -486,6 +496,8 @@ pub trait ErasedAssetWriter: Send + Sync + 'static {
         path: &'a Path,
         bytes: &'a [u8],
     ) -> BoxedFuture<'a, Result<(), AssetWriterError>>;
+    /// Makes all previous writes durable, for writers that batch or buffer them.
+    fn flush(&self) -> BoxedFuture<'_, Result<(), AssetWriterError>>;
 }
 
 impl<T: AssetWriter> ErasedAssetWriter for T {
@@ -559,6 +571,9 @@ impl<T: AssetWriter> ErasedAssetWriter for T {
     ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
         Box::pin(Self::write_meta_bytes(self, path, bytes))
     }
+    fn flush(&self) -> BoxedFuture<'_, Result<(), AssetWriterError>> {
+        Box::pin(Self::flush(self))
+    }
 }
 
 /// An "asset source change event" that occurs whenever asset (or asset metadata) is created/added/removed
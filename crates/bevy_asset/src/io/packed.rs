@@ -0,0 +1,1166 @@
+//! Asset storage backed by a single archive file.
+//!
+//! Shipping thousands of loose files is slow to copy, slow to open and prone to hitting
+//! file-handle limits. A [`PackedArchive`] stores assets and their `.meta` files in one file
+//! with a table of contents, and can be read with a [`PackedAssetReader`]. A
+//! [`PackedAssetWriter`] lets the [`AssetProcessor`](crate::processor::AssetProcessor) write its
+//! processed output straight into an archive, see
+//! [`AssetSourceBuilder::with_packed_processed`](crate::io::AssetSourceBuilder::with_packed_processed).
+//!
+//! # Format
+//!
+//! All integers are little-endian.
+//!
+//! | Section  | Contents                                                                 |
+//! |----------|--------------------------------------------------------------------------|
+//! | Header   | The magic `BPAK`, the format version (`u32`), and the offset and length of the table of contents (`u64` each) |
+//! | Data     | The (possibly compressed) bytes of each entry                            |
+//! | Contents | The entries, followed by the explicitly created directories              |
+//!
+//! Each entry of the table of contents is its kind (asset or meta, `u8`), its
+//! [`PackedCompression`] (`u8`), the offset, stored length and uncompressed length of its data
+//! (`u64` each), and its `/`-separated path (`u32` length followed by UTF-8 bytes).
+//!
+//! Writing an entry appends its data to the file, but the table of contents is only kept in
+//! memory until [`PackedArchive::flush`] appends it after the data and then points the header to
+//! it. The previous table of contents is never overwritten, so an interrupted write leaves the
+//! archive as it was at the last flush. The space used by old tables of contents and by
+//! overwritten and removed entries is only reclaimed by [`PackedArchive::compact`].
+
+use crate::io::{
+    AssetReader, AssetReaderError, AssetWriter, AssetWriterError, PathStream, Reader, VecReader,
+    Writer,
+};
+use alloc::{borrow::ToOwned, boxed::Box, string::String, sync::Arc, vec, vec::Vec};
+use async_fs::{File, OpenOptions};
+use async_lock::Mutex;
+use bevy_platform::{
+    cell::SyncCell,
+    collections::{HashMap, HashSet},
+    sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+use bevy_tasks::BoxedFuture;
+use core::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use futures_io::AsyncWrite;
+use futures_lite::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use std::{
+    fs,
+    io::{self, Error, ErrorKind, SeekFrom},
+    path::{Component, Path, PathBuf},
+};
+use thiserror::Error;
+use tracing::{error, warn};
+
+const MAGIC: [u8; 4] = *b"BPAK";
+const VERSION: u32 = 1;
+const HEADER_LEN: u64 = 24;
+
+/// How the data of an entry of a [`PackedArchive`] is stored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PackedCompression {
+    /// The data is stored as is.
+    #[default]
+    None,
+    /// The data is compressed with deflate.
+    ///
+    /// Reading or writing such entries requires the `packed_deflate` feature.
+    Deflate,
+}
+
+impl PackedCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Deflate => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::None),
+            1 => Some(Self::Deflate),
+            _ => None,
+        }
+    }
+
+    fn compress(self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(bytes.to_vec()),
+            #[cfg(feature = "packed_deflate")]
+            Self::Deflate => {
+                use std::io::Write;
+
+                let mut encoder =
+                    flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+            #[cfg(not(feature = "packed_deflate"))]
+            Self::Deflate => Err(deflate_unsupported()),
+        }
+    }
+
+    fn decompress(self, bytes: Vec<u8>, len: u64) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(bytes),
+            #[cfg(feature = "packed_deflate")]
+            Self::Deflate => {
+                use std::io::Read;
+
+                // `len` comes from the archive, so it's only used to bound the output rather than
+                // to preallocate it.
+                let mut decompressed = Vec::new();
+                flate2::read::DeflateDecoder::new(bytes.as_slice())
+                    .take(len.saturating_add(1))
+                    .read_to_end(&mut decompressed)?;
+                if decompressed.len() as u64 != len {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "decompressed entry does not match its stored length",
+                    ));
+                }
+                Ok(decompressed)
+            }
+            #[cfg(not(feature = "packed_deflate"))]
+            Self::Deflate => {
+                let _ = len;
+                Err(deflate_unsupported())
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "packed_deflate"))]
+fn deflate_unsupported() -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        "deflate compressed entries require the `packed_deflate` feature",
+    )
+}
+
+/// An error that occurs when opening a [`PackedArchive`].
+#[derive(Error, Debug)]
+pub enum PackedArchiveError {
+    /// The archive file could not be read.
+    #[error(transparent)]
+    Io(#[from] Error),
+    /// The file does not start with the magic bytes of a packed archive.
+    #[error("the file is not a packed asset archive")]
+    InvalidMagic,
+    /// The archive was written with a format version this version of Bevy can't read.
+    #[error("unsupported packed archive version {0}")]
+    UnsupportedVersion(u32),
+    /// The table of contents of the archive is malformed.
+    #[error("the table of contents of the archive is invalid")]
+    InvalidTableOfContents,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EntryKind {
+    Asset,
+    Meta,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PackedEntry {
+    offset: u64,
+    stored_len: u64,
+    len: u64,
+    compression: PackedCompression,
+}
+
+#[derive(Debug)]
+struct PackedIndex {
+    assets: HashMap<PathBuf, PackedEntry>,
+    metas: HashMap<PathBuf, PackedEntry>,
+    /// Directories that were created explicitly, and may not contain any entries.
+    directories: HashSet<PathBuf>,
+    /// The end of the data written to the file, which is where new data is appended.
+    data_end: u64,
+    /// Whether the table of contents was modified since it was last written to the file.
+    dirty: bool,
+}
+
+impl Default for PackedIndex {
+    fn default() -> Self {
+        Self {
+            assets: HashMap::default(),
+            metas: HashMap::default(),
+            directories: HashSet::default(),
+            data_end: HEADER_LEN,
+            dirty: false,
+        }
+    }
+}
+
+impl PackedIndex {
+    fn entries(&self, kind: EntryKind) -> &HashMap<PathBuf, PackedEntry> {
+        match kind {
+            EntryKind::Asset => &self.assets,
+            EntryKind::Meta => &self.metas,
+        }
+    }
+
+    fn entries_mut(&mut self, kind: EntryKind) -> &mut HashMap<PathBuf, PackedEntry> {
+        match kind {
+            EntryKind::Asset => &mut self.assets,
+            EntryKind::Meta => &mut self.metas,
+        }
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        path.as_os_str().is_empty()
+            || self.directories.iter().any(|dir| dir.starts_with(path))
+            || self
+                .assets
+                .keys()
+                .chain(self.metas.keys())
+                .any(|file| file != path && file.starts_with(path))
+    }
+
+    /// Returns the paths of the assets and directories directly inside `path`.
+    fn children(&self, path: &Path) -> Vec<PathBuf> {
+        let mut children = HashSet::<PathBuf>::default();
+        for entry in self.assets.keys().chain(self.directories.iter()) {
+            if let Ok(rest) = entry.strip_prefix(path)
+                && let Some(child) = rest.components().next()
+            {
+                children.insert(path.join(child));
+            }
+        }
+        children.into_iter().collect()
+    }
+
+    /// Removes all entries and directories inside `path`, returning whether anything was removed.
+    fn remove_all_in(&mut self, path: &Path) -> bool {
+        let (assets, metas, directories) =
+            (self.assets.len(), self.metas.len(), self.directories.len());
+        self.assets.retain(|file, _| !file.starts_with(path));
+        self.metas.retain(|file, _| !file.starts_with(path));
+        self.directories.retain(|dir| !dir.starts_with(path));
+        assets != self.assets.len()
+            || metas != self.metas.len()
+            || directories != self.directories.len()
+    }
+
+    fn encode(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        let entries = self
+            .assets
+            .iter()
+            .map(|(path, entry)| (EntryKind::Asset, path, entry))
+            .chain(
+                self.metas
+                    .iter()
+                    .map(|(path, entry)| (EntryKind::Meta, path, entry)),
+            );
+        bytes.extend_from_slice(&((self.assets.len() + self.metas.len()) as u32).to_le_bytes());
+        for (kind, path, entry) in entries {
+            bytes.push(match kind {
+                EntryKind::Asset => 0,
+                EntryKind::Meta => 1,
+            });
+            bytes.push(entry.compression.to_byte());
+            bytes.extend_from_slice(&entry.offset.to_le_bytes());
+            bytes.extend_from_slice(&entry.stored_len.to_le_bytes());
+            bytes.extend_from_slice(&entry.len.to_le_bytes());
+            encode_path(&mut bytes, path)?;
+        }
+        bytes.extend_from_slice(&(self.directories.len() as u32).to_le_bytes());
+        for dir in &self.directories {
+            encode_path(&mut bytes, dir)?;
+        }
+        Ok(bytes)
+    }
+
+    fn decode(bytes: &[u8], data_end: u64) -> Result<Self, PackedArchiveError> {
+        let mut reader = ContentsReader(bytes);
+        let mut index = PackedIndex {
+            data_end,
+            ..Default::default()
+        };
+        for _ in 0..reader.u32()? {
+            let kind = match reader.u8()? {
+                0 => EntryKind::Asset,
+                1 => EntryKind::Meta,
+                _ => return Err(PackedArchiveError::InvalidTableOfContents),
+            };
+            let compression = PackedCompression::from_byte(reader.u8()?)
+                .ok_or(PackedArchiveError::InvalidTableOfContents)?;
+            let entry = PackedEntry {
+                offset: reader.u64()?,
+                stored_len: reader.u64()?,
+                len: reader.u64()?,
+                compression,
+            };
+            if entry.offset < HEADER_LEN
+                || entry
+                    .offset
+                    .checked_add(entry.stored_len)
+                    .is_none_or(|end| end > data_end)
+            {
+                return Err(PackedArchiveError::InvalidTableOfContents);
+            }
+            index.entries_mut(kind).insert(reader.path()?, entry);
+        }
+        for _ in 0..reader.u32()? {
+            index.directories.insert(reader.path()?);
+        }
+        Ok(index)
+    }
+}
+
+fn encode_path(bytes: &mut Vec<u8>, path: &Path) -> io::Result<()> {
+    let mut key = String::new();
+    for component in path.components() {
+        let component = component.as_os_str().to_str().ok_or_else(|| {
+            Error::new(ErrorKind::InvalidInput, "packed asset paths must be UTF-8")
+        })?;
+        if !key.is_empty() {
+            key.push('/');
+        }
+        key.push_str(component);
+    }
+    bytes.extend_from_slice(&(key.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(key.as_bytes());
+    Ok(())
+}
+
+/// Reads the values written by [`PackedIndex::encode`].
+struct ContentsReader<'a>(&'a [u8]);
+
+impl<'a> ContentsReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], PackedArchiveError> {
+        if self.0.len() < len {
+            return Err(PackedArchiveError::InvalidTableOfContents);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, PackedArchiveError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, PackedArchiveError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, PackedArchiveError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn path(&mut self) -> Result<PathBuf, PackedArchiveError> {
+        let len = self.u32()? as usize;
+        let path = core::str::from_utf8(self.take(len)?)
+            .map_err(|_| PackedArchiveError::InvalidTableOfContents)?;
+        Ok(normalize(Path::new(path)))
+    }
+}
+
+fn encode_header(contents_offset: u64, contents_len: u64) -> [u8; HEADER_LEN as usize] {
+    let mut header = [0; HEADER_LEN as usize];
+    header[0..4].copy_from_slice(&MAGIC);
+    header[4..8].copy_from_slice(&VERSION.to_le_bytes());
+    header[8..16].copy_from_slice(&contents_offset.to_le_bytes());
+    header[16..24].copy_from_slice(&contents_len.to_le_bytes());
+    header
+}
+
+/// Returns the offset and length of the table of contents.
+fn decode_header(header: &[u8; HEADER_LEN as usize]) -> Result<(u64, u64), PackedArchiveError> {
+    if header[0..4] != MAGIC {
+        return Err(PackedArchiveError::InvalidMagic);
+    }
+    let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+    if version != VERSION {
+        return Err(PackedArchiveError::UnsupportedVersion(version));
+    }
+    Ok((
+        u64::from_le_bytes(header[8..16].try_into().unwrap()),
+        u64::from_le_bytes(header[16..24].try_into().unwrap()),
+    ))
+}
+
+/// Keeps only the normal components of `path`, so that `./a/b` and `a/b/` refer to the same entry.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
+
+fn not_found(path: &Path) -> AssetWriterError {
+    AssetWriterError::Io(Error::new(
+        ErrorKind::NotFound,
+        alloc::format!("no such entry: {}", path.display()),
+    ))
+}
+
+/// A single file containing assets and their `.meta` files.
+///
+/// This is internally [`Arc`]-ed, so a clone refers to the same archive. The table of contents is
+/// read once when opening the archive and kept in memory. All modifications should go through
+/// clones of the same [`PackedArchive`].
+///
+/// See the [module docs](self) for the file format.
+#[derive(Clone)]
+pub struct PackedArchive(Arc<PackedArchiveInner>);
+
+struct PackedArchiveInner {
+    path: PathBuf,
+    index: RwLock<PackedIndex>,
+    /// Held while modifying the archive file.
+    file_lock: Mutex<()>,
+}
+
+impl PackedArchiveInner {
+    async fn open_for_write(&self) -> io::Result<File> {
+        if let Some(parent) = self.path.parent() {
+            async_fs::create_dir_all(parent).await?;
+        }
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)
+            .await
+    }
+
+    /// Appends the table of contents to the file and then points the header to it, if it was
+    /// modified since it was last written.
+    ///
+    /// The caller must hold the file lock.
+    async fn write_contents(&self) -> io::Result<()> {
+        let (contents, data_end) = {
+            let index = self.index.read().unwrap_or_else(PoisonError::into_inner);
+            if !index.dirty {
+                return Ok(());
+            }
+            (index.encode()?, index.data_end)
+        };
+        let mut file = self.open_for_write().await?;
+        file.seek(SeekFrom::Start(data_end)).await?;
+        file.write_all(&contents).await?;
+        file.flush().await?;
+        // The new table of contents must be on disk before the header points to it.
+        file.sync_data().await?;
+        file.seek(SeekFrom::Start(0)).await?;
+        file.write_all(&encode_header(data_end, contents.len() as u64))
+            .await?;
+        file.flush().await?;
+        file.sync_data().await?;
+
+        let mut index = self.index.write().unwrap_or_else(PoisonError::into_inner);
+        index.data_end = data_end + contents.len() as u64;
+        index.dirty = false;
+        Ok(())
+    }
+}
+
+impl Drop for PackedArchiveInner {
+    fn drop(&mut self) {
+        let index = self.index.get_mut().unwrap_or_else(PoisonError::into_inner);
+        if index.dirty {
+            warn!(
+                "Packed asset archive {} was dropped without flushing its table of contents, \
+                entries written since the last flush are lost",
+                self.path.display()
+            );
+        }
+    }
+}
+
+impl PackedArchive {
+    /// Opens the archive at `path`.
+    ///
+    /// If there is no file at `path`, the archive is empty and the file is created by the first
+    /// write.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, PackedArchiveError> {
+        let path = path.into();
+        let index = match fs::File::open(&path) {
+            Ok(file) => read_index(file)?,
+            Err(err) if err.kind() == ErrorKind::NotFound => PackedIndex::default(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self::from_index(path, index))
+    }
+
+    fn from_index(path: PathBuf, index: PackedIndex) -> Self {
+        Self(Arc::new(PackedArchiveInner {
+            path,
+            index: RwLock::new(index),
+            file_lock: Mutex::new(()),
+        }))
+    }
+
+    /// Opens the archive at `path`, relative to the
+    /// [base path](crate::io::file::FileAssetReader::get_base_path).
+    ///
+    /// If the archive can't be opened, the error is logged and the archive starts out empty.
+    /// Writing to it will then replace the old file.
+    pub(crate) fn open_in_base_path(path: &str) -> Self {
+        let path = super::file::get_base_path().join(path);
+        Self::open(&path).unwrap_or_else(|err| {
+            error!(
+                "Failed to open packed asset archive {}: {err}",
+                path.display()
+            );
+            Self::from_index(path, PackedIndex::default())
+        })
+    }
+
+    /// Returns the path of the archive file.
+    pub fn path(&self) -> &Path {
+        &self.0.path
+    }
+
+    /// Returns whether the archive contains an asset at `path`.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.index().assets.contains_key(&normalize(path))
+    }
+
+    /// Returns the paths of all assets in the archive.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.index().assets.keys().cloned().collect()
+    }
+
+    fn index(&self) -> RwLockReadGuard<'_, PackedIndex> {
+        self.0.index.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn index_mut(&self) -> RwLockWriteGuard<'_, PackedIndex> {
+        self.0.index.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn read_entry(&self, kind: EntryKind, path: &Path) -> Result<Vec<u8>, AssetReaderError> {
+        let entry = self
+            .index()
+            .entries(kind)
+            .get(&normalize(path))
+            .copied()
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+        let mut file = File::open(&self.0.path).await?;
+        file.seek(SeekFrom::Start(entry.offset)).await?;
+        let mut bytes = vec![0; entry.stored_len as usize];
+        file.read_exact(&mut bytes).await?;
+        Ok(entry.compression.decompress(bytes, entry.len)?)
+    }
+
+    async fn open_for_write(&self) -> io::Result<File> {
+        self.0.open_for_write().await
+    }
+
+    async fn insert(
+        &self,
+        kind: EntryKind,
+        path: &Path,
+        bytes: &[u8],
+        compression: PackedCompression,
+    ) -> io::Result<()> {
+        let stored = compression.compress(bytes)?;
+        let _guard = self.0.file_lock.lock().await;
+        let mut file = self.open_for_write().await?;
+        let offset = self.index().data_end;
+        file.seek(SeekFrom::Start(offset)).await?;
+        file.write_all(&stored).await?;
+        file.flush().await?;
+        let mut index = self.index_mut();
+        index.data_end = offset + stored.len() as u64;
+        index.entries_mut(kind).insert(
+            normalize(path),
+            PackedEntry {
+                offset,
+                stored_len: stored.len() as u64,
+                len: bytes.len() as u64,
+                compression,
+            },
+        );
+        index.dirty = true;
+        Ok(())
+    }
+
+    /// Applies `f` to the table of contents, marking it as modified if `f` returns `true`.
+    async fn modify(&self, f: impl FnOnce(&mut PackedIndex) -> bool) -> bool {
+        let _guard = self.0.file_lock.lock().await;
+        let mut index = self.index_mut();
+        let modified = f(&mut index);
+        index.dirty |= modified;
+        modified
+    }
+
+    /// Writes the table of contents to the file if it was modified since it was last written.
+    ///
+    /// Entries are readable through this [`PackedArchive`] as soon as they are written, but
+    /// they are only visible to archives opened later once the table of contents is flushed.
+    /// The [`AssetProcessor`](crate::processor::AssetProcessor) flushes after processing,
+    /// and [`compact`](Self::compact) flushes as part of rewriting the file. Dropping the archive
+    /// does not flush it.
+    pub async fn flush(&self) -> io::Result<()> {
+        let _guard = self.0.file_lock.lock().await;
+        self.0.write_contents().await
+    }
+
+    /// Rewrites the archive file without the data of overwritten and removed entries.
+    ///
+    /// Entries that are being read while the archive is compacted may fail to load.
+    pub async fn compact(&self) -> io::Result<()> {
+        let _guard = self.0.file_lock.lock().await;
+        let (entries, directories) = {
+            let index = self.index();
+            let entries = index
+                .assets
+                .iter()
+                .map(|(path, entry)| (EntryKind::Asset, path.clone(), *entry))
+                .chain(
+                    index
+                        .metas
+                        .iter()
+                        .map(|(path, entry)| (EntryKind::Meta, path.clone(), *entry)),
+                )
+                .collect::<Vec<_>>();
+            (entries, index.directories.clone())
+        };
+
+        let mut temp_path = self.0.path.clone().into_os_string();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let mut source = File::open(&self.0.path).await?;
+        let mut target = File::create(&temp_path).await?;
+        target.write_all(&[0; HEADER_LEN as usize]).await?;
+        let mut compacted = PackedIndex {
+            directories,
+            ..Default::default()
+        };
+        for (kind, path, entry) in entries {
+            let mut bytes = vec![0; entry.stored_len as usize];
+            source.seek(SeekFrom::Start(entry.offset)).await?;
+            source.read_exact(&mut bytes).await?;
+            target.write_all(&bytes).await?;
+            let offset = compacted.data_end;
+            compacted
+                .entries_mut(kind)
+                .insert(path, PackedEntry { offset, ..entry });
+            compacted.data_end += entry.stored_len;
+        }
+        let contents = compacted.encode()?;
+        target.write_all(&contents).await?;
+        target.seek(SeekFrom::Start(0)).await?;
+        target
+            .write_all(&encode_header(compacted.data_end, contents.len() as u64))
+            .await?;
+        target.flush().await?;
+        target.sync_all().await?;
+        drop((source, target));
+        compacted.data_end += contents.len() as u64;
+
+        // Swap the file and the table of contents together, so readers never see a mix of both.
+        let mut index = self.index_mut();
+        fs::rename(&temp_path, &self.0.path)?;
+        *index = compacted;
+        Ok(())
+    }
+
+    /// Packs all files in `directory` into a new archive at `path`, replacing any existing file.
+    ///
+    /// Files ending in `.meta` are stored as the meta of the asset with the same path minus that
+    /// extension.
+    pub fn pack_directory(
+        directory: &Path,
+        path: impl Into<PathBuf>,
+        compression: PackedCompression,
+    ) -> io::Result<Self> {
+        use std::io::{Seek, Write};
+
+        fn visit(
+            directory: &Path,
+            relative: &Path,
+            files: &mut Vec<PathBuf>,
+            directories: &mut HashSet<PathBuf>,
+        ) -> io::Result<()> {
+            for entry in fs::read_dir(directory.join(relative))? {
+                let entry = entry?;
+                let relative = relative.join(entry.file_name());
+                if entry.file_type()?.is_dir() {
+                    directories.insert(relative.clone());
+                    visit(directory, &relative, files, directories)?;
+                } else {
+                    files.push(relative);
+                }
+            }
+            Ok(())
+        }
+
+        let path = path.into();
+        let mut files = Vec::new();
+        let mut index = PackedIndex::default();
+        visit(directory, Path::new(""), &mut files, &mut index.directories)?;
+
+        let mut file = fs::File::create(&path)?;
+        file.write_all(&[0; HEADER_LEN as usize])?;
+        for relative in files {
+            let bytes = fs::read(directory.join(&relative))?;
+            let stored = compression.compress(&bytes)?;
+            file.write_all(&stored)?;
+            let (kind, key) = match relative.to_str().and_then(|p| p.strip_suffix(".meta")) {
+                Some(asset_path) => (EntryKind::Meta, normalize(Path::new(asset_path))),
+                None => (EntryKind::Asset, normalize(&relative)),
+            };
+            let entry = PackedEntry {
+                offset: index.data_end,
+                stored_len: stored.len() as u64,
+                len: bytes.len() as u64,
+                compression,
+            };
+            index.data_end += entry.stored_len;
+            index.entries_mut(kind).insert(key, entry);
+        }
+        let contents = index.encode()?;
+        file.write_all(&contents)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&encode_header(index.data_end, contents.len() as u64))?;
+        file.flush()?;
+        index.data_end += contents.len() as u64;
+        Ok(Self::from_index(path, index))
+    }
+}
+
+fn read_index(mut file: fs::File) -> Result<PackedIndex, PackedArchiveError> {
+    use std::io::{Read, Seek};
+
+    let mut header = [0; HEADER_LEN as usize];
+    file.read_exact(&mut header)?;
+    let (contents_offset, contents_len) = decode_header(&header)?;
+    let file_len = file.metadata()?.len();
+    if contents_offset < HEADER_LEN
+        || contents_offset
+            .checked_add(contents_len)
+            .is_none_or(|end| end > file_len)
+    {
+        return Err(PackedArchiveError::InvalidTableOfContents);
+    }
+    let mut contents = vec![0; contents_len as usize];
+    file.seek(SeekFrom::Start(contents_offset))?;
+    file.read_exact(&mut contents)?;
+    let mut index = PackedIndex::decode(&contents, contents_offset)?;
+    // New data is appended after the table of contents, so that it is never overwritten.
+    index.data_end = contents_offset + contents_len;
+    Ok(index)
+}
+
+/// An [`AssetReader`] that reads assets and their meta files from a [`PackedArchive`].
+#[derive(Clone)]
+pub struct PackedAssetReader {
+    archive: PackedArchive,
+}
+
+impl PackedAssetReader {
+    /// Creates a new reader for `archive`.
+    pub fn new(archive: PackedArchive) -> Self {
+        Self { archive }
+    }
+
+    /// Returns the archive this reader reads from.
+    pub fn archive(&self) -> &PackedArchive {
+        &self.archive
+    }
+}
+
+impl AssetReader for PackedAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let bytes = self.archive.read_entry(EntryKind::Asset, path).await?;
+        Ok(VecReader::new(bytes))
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let bytes = self.archive.read_entry(EntryKind::Meta, path).await?;
+        Ok(VecReader::new(bytes))
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let children = {
+            let index = self.archive.index();
+            let dir = normalize(path);
+            if !index.is_directory(&dir) {
+                return Err(AssetReaderError::NotFound(path.to_owned()));
+            }
+            index.children(&dir)
+        };
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(self.archive.index().is_directory(&normalize(path)))
+    }
+}
+
+/// An [`AssetWriter`] that writes assets and their meta files into a [`PackedArchive`].
+#[derive(Clone)]
+pub struct PackedAssetWriter {
+    archive: PackedArchive,
+    compression: PackedCompression,
+}
+
+impl PackedAssetWriter {
+    /// Creates a new writer for `archive`, storing entries uncompressed.
+    pub fn new(archive: PackedArchive) -> Self {
+        Self {
+            archive,
+            compression: PackedCompression::None,
+        }
+    }
+
+    /// Sets the compression of the entries written by this writer.
+    pub fn with_compression(mut self, compression: PackedCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Returns the archive this writer writes to.
+    pub fn archive(&self) -> &PackedArchive {
+        &self.archive
+    }
+
+    fn entry_writer(&self, kind: EntryKind, path: &Path) -> Box<Writer> {
+        Box::new(PackedEntryWriter {
+            archive: self.archive.clone(),
+            kind,
+            path: path.to_owned(),
+            compression: self.compression,
+            bytes: Vec::new(),
+            dirty: false,
+            insert: None,
+        })
+    }
+
+    async fn rename_entry(
+        &self,
+        kind: EntryKind,
+        old_path: &Path,
+        new_path: &Path,
+    ) -> Result<(), AssetWriterError> {
+        let renamed = self
+            .archive
+            .modify(|index| {
+                let entries = index.entries_mut(kind);
+                let Some(entry) = entries.remove(&normalize(old_path)) else {
+                    return false;
+                };
+                entries.insert(normalize(new_path), entry);
+                true
+            })
+            .await;
+        if !renamed {
+            return Err(not_found(old_path));
+        }
+        Ok(())
+    }
+}
+
+impl AssetWriter for PackedAssetWriter {
+    async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        Ok(self.entry_writer(EntryKind::Asset, path))
+    }
+
+    async fn write_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        Ok(self.entry_writer(EntryKind::Meta, path))
+    }
+
+    async fn write_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        self.archive
+            .insert(EntryKind::Asset, path, bytes, self.compression)
+            .await?;
+        Ok(())
+    }
+
+    async fn write_meta_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        self.archive
+            .insert(EntryKind::Meta, path, bytes, self.compression)
+            .await?;
+        Ok(())
+    }
+
+    async fn remove<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        let removed = self
+            .archive
+            .modify(|index| index.assets.remove(&normalize(path)).is_some())
+            .await;
+        if !removed {
+            return Err(not_found(path));
+        }
+        Ok(())
+    }
+
+    async fn remove_meta<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.archive
+            .modify(|index| index.metas.remove(&normalize(path)).is_some())
+            .await;
+        Ok(())
+    }
+
+    async fn rename<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        self.rename_entry(EntryKind::Asset, old_path, new_path)
+            .await
+    }
+
+    async fn rename_meta<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        self.rename_entry(EntryKind::Meta, old_path, new_path).await
+    }
+
+    async fn create_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        let dir = normalize(path);
+        self.archive
+            .modify(|index| !index.is_directory(&dir) && index.directories.insert(dir))
+            .await;
+        Ok(())
+    }
+
+    async fn remove_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        let dir = normalize(path);
+        let removed = self
+            .archive
+            .modify(|index| index.is_directory(&dir) && index.remove_all_in(&dir))
+            .await;
+        if !removed {
+            return Err(not_found(path));
+        }
+        Ok(())
+    }
+
+    async fn remove_empty_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        let dir = normalize(path);
+        let mut result = Ok(());
+        self.archive
+            .modify(|index| {
+                if !index.is_directory(&dir) {
+                    result = Err(not_found(path));
+                    return false;
+                }
+                if !index.children(&dir).is_empty()
+                    || index.metas.keys().any(|m| m.starts_with(&dir))
+                {
+                    result = Err(AssetWriterError::Io(Error::new(
+                        ErrorKind::DirectoryNotEmpty,
+                        "not empty",
+                    )));
+                    return false;
+                }
+                index.directories.remove(&dir)
+            })
+            .await;
+        result
+    }
+
+    async fn remove_assets_in_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        let dir = normalize(path);
+        let mut found = true;
+        self.archive
+            .modify(|index| {
+                if !index.is_directory(&dir) {
+                    found = false;
+                    return false;
+                }
+                index.remove_all_in(&dir);
+                if !dir.as_os_str().is_empty() {
+                    index.directories.insert(dir);
+                }
+                true
+            })
+            .await;
+        if !found {
+            return Err(not_found(path));
+        }
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<(), AssetWriterError> {
+        self.archive.flush().await?;
+        Ok(())
+    }
+}
+
+/// A writer for a single entry of a [`PackedArchive`], buffering internally until flushed/closed.
+struct PackedEntryWriter {
+    archive: PackedArchive,
+    kind: EntryKind,
+    path: PathBuf,
+    compression: PackedCompression,
+    /// All data written so far, including data that has been flushed already.
+    bytes: Vec<u8>,
+    /// Whether data was written since the entry was last inserted into the archive.
+    dirty: bool,
+    /// The in-progress insertion of the entry into the archive.
+    insert: Option<SyncCell<BoxedFuture<'static, io::Result<()>>>>,
+}
+
+impl AsyncWrite for PackedEntryWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.bytes.extend_from_slice(buf);
+        this.dirty = true;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.insert.is_none() {
+            if !this.dirty {
+                return Poll::Ready(Ok(()));
+            }
+            this.dirty = false;
+            let archive = this.archive.clone();
+            let (kind, path, bytes, compression) = (
+                this.kind,
+                this.path.clone(),
+                this.bytes.clone(),
+                this.compression,
+            );
+            this.insert = Some(SyncCell::new(Box::pin(async move {
+                archive.insert(kind, &path, &bytes, compression).await
+            })));
+        }
+        let insert = this.insert.as_mut().unwrap();
+        let result = ready!(insert.get().as_mut().poll(cx));
+        this.insert = None;
+        Poll::Ready(result)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PackedArchive, PackedAssetReader, PackedAssetWriter};
+    use crate::io::{AssetReader, AssetWriter, AssetWriterError, Reader};
+    use alloc::vec::Vec;
+    use futures_lite::{AsyncWriteExt, StreamExt};
+    use std::path::{Path, PathBuf};
+
+    fn read_asset(reader: &PackedAssetReader, path: &str) -> Vec<u8> {
+        bevy_tasks::block_on(async {
+            let mut bytes = Vec::new();
+            let mut asset = reader.read(Path::new(path)).await.unwrap();
+            asset.read_to_end(&mut bytes).await.unwrap();
+            bytes
+        })
+    }
+
+    #[test]
+    fn write_reopen_and_read() {
+        let path = std::env::temp_dir().join(alloc::format!(
+            "bevy_packed_archive_test_{}.bpak",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let writer = PackedAssetWriter::new(PackedArchive::open(&path).unwrap());
+        bevy_tasks::block_on(async {
+            writer.write_bytes(Path::new("a.txt"), b"a").await.unwrap();
+            writer
+                .write_meta_bytes(Path::new("a.txt"), b"a meta")
+                .await
+                .unwrap();
+            let mut b = writer.write(Path::new("x/y/b.txt")).await.unwrap();
+            b.write_all(b"b").await.unwrap();
+            b.flush().await.unwrap();
+            writer.write_bytes(Path::new("a.txt"), b"a2").await.unwrap();
+            writer
+                .rename(Path::new("x/y/b.txt"), Path::new("x/b.txt"))
+                .await
+                .unwrap();
+            assert!(matches!(
+                writer.remove(Path::new("missing.txt")).await,
+                Err(AssetWriterError::Io(_))
+            ));
+            writer.archive().compact().await.unwrap();
+        });
+
+        let reader = PackedAssetReader::new(PackedArchive::open(&path).unwrap());
+        assert_eq!(read_asset(&reader, "a.txt"), b"a2");
+        assert_eq!(read_asset(&reader, "x/b.txt"), b"b");
+        assert!(!reader.archive().contains(Path::new("x/y/b.txt")));
+        bevy_tasks::block_on(async {
+            let mut meta = Vec::new();
+            let mut meta_reader = reader.read_meta(Path::new("a.txt")).await.unwrap();
+            meta_reader.read_to_end(&mut meta).await.unwrap();
+            assert_eq!(meta, b"a meta");
+
+            let mut root = reader
+                .read_directory(Path::new(""))
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+                .await;
+            root.sort();
+            assert_eq!(root, [PathBuf::from("a.txt"), PathBuf::from("x")]);
+            assert!(reader.is_directory(Path::new("x")).await.unwrap());
+            assert!(!reader.is_directory(Path::new("a.txt")).await.unwrap());
+        });
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn contents_are_written_on_flush() {
+        let path = std::env::temp_dir().join(alloc::format!(
+            "bevy_packed_archive_flush_test_{}.bpak",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let archive = PackedArchive::open(&path).unwrap();
+        let writer = PackedAssetWriter::new(archive.clone());
+        bevy_tasks::block_on(async {
+            writer.write_bytes(Path::new("a.txt"), b"a").await.unwrap();
+            writer.flush().await.unwrap();
+            writer.write_bytes(Path::new("b.txt"), b"b").await.unwrap();
+        });
+        assert!(archive.contains(Path::new("b.txt")));
+
+        // Until the next flush, the file still holds the previous table of contents.
+        let reopened = PackedArchive::open(&path).unwrap();
+        assert!(reopened.contains(Path::new("a.txt")));
+        assert!(!reopened.contains(Path::new("b.txt")));
+
+        bevy_tasks::block_on(archive.flush()).unwrap();
+        let reader = PackedAssetReader::new(PackedArchive::open(&path).unwrap());
+        assert_eq!(read_asset(&reader, "a.txt"), b"a");
+        assert_eq!(read_asset(&reader, "b.txt"), b"b");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
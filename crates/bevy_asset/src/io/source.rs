@@ -6,6 +6,7 @@ use alloc::{
     boxed::Box,
     string::{String, ToString},
     sync::Arc,
+    vec::Vec,
 };
 use atomicow::CowArc;
 use bevy_ecs::resource::Resource;
@@ -14,7 +15,12 @@ use core::{fmt::Display, hash::Hash, time::Duration};
 use thiserror::Error;
 use tracing::warn;
 
-use super::{ErasedAssetReader, ErasedAssetWriter};
+#[cfg(not(target_arch = "wasm32"))]
+use super::packed::{PackedArchive, PackedAssetReader, PackedAssetWriter};
+use super::{
+    layered::{LayeredAssetReader, LayeredAssetWatcher},
+    ErasedAssetReader, ErasedAssetWriter,
+};
 
 /// A reference to an "asset source", which maps to an [`AssetReader`](crate::io::AssetReader) and/or [`AssetWriter`](crate::io::AssetWriter).
 ///
@@ -321,6 +327,68 @@ impl AssetSourceBuilder {
             default
         }
     }
+
+    /// Returns a builder for a source that resolves paths through the given `layers`, from the
+    /// bottom up. See [`LayeredAssetReader`] for how the layers are combined.
+    ///
+    /// Only the readers and watchers of the layers are used. The returned builder has no writer
+    /// and no processed reader, writer or watcher, which can be added as usual.
+    ///
+    /// ```
+    /// # use bevy_asset::io::AssetSourceBuilder;
+    /// let source = AssetSourceBuilder::layered([
+    ///     AssetSourceBuilder::platform_default("assets", None),
+    ///     AssetSourceBuilder::platform_default("mods/my_mod", None),
+    ///     AssetSourceBuilder::platform_default("patches", None),
+    /// ]);
+    /// ```
+    pub fn layered(layers: impl IntoIterator<Item = AssetSourceBuilder>) -> Self {
+        let (mut readers, watchers): (Vec<_>, Vec<_>) = layers
+            .into_iter()
+            .map(|layer| (layer.reader, layer.watcher))
+            .unzip();
+        let mut watchers = watchers.into_iter().flatten().collect::<Vec<_>>();
+        Self::new(move || {
+            Box::new(LayeredAssetReader::new(
+                readers.iter_mut().map(|reader| reader()),
+            ))
+        })
+        .with_watcher(move |sender| {
+            let watchers = watchers
+                .iter_mut()
+                .filter_map(|watcher| watcher(sender.clone()))
+                .collect::<Vec<_>>();
+            if watchers.is_empty() {
+                return None;
+            }
+            Some(Box::new(LayeredAssetWatcher::new(watchers)))
+        })
+    }
+
+    /// Returns a builder for a source that reads its assets from the
+    /// [`PackedArchive`](crate::io::packed::PackedArchive) at `path`, relative to the
+    /// [base path](crate::io::file::FileAssetReader::get_base_path).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn packed(path: &str) -> Self {
+        let archive = PackedArchive::open_in_base_path(path);
+        Self::new(move || Box::new(PackedAssetReader::new(archive.clone())))
+    }
+
+    /// Reads and writes the processed assets of this source from and to the
+    /// [`PackedArchive`](crate::io::packed::PackedArchive) at `path`, relative to the
+    /// [base path](crate::io::file::FileAssetReader::get_base_path).
+    ///
+    /// This lets the [`AssetProcessor`](crate::processor::AssetProcessor) emit its output straight
+    /// into the archive that is shipped.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_packed_processed(self, path: &str) -> Self {
+        let archive = PackedArchive::open_in_base_path(path);
+        let writer_archive = archive.clone();
+        self.with_processed_reader(move || Box::new(PackedAssetReader::new(archive.clone())))
+            .with_processed_writer(move |_create_root| {
+                Some(Box::new(PackedAssetWriter::new(writer_archive.clone())))
+            })
+    }
 }
 
 /// A [`Resource`] that hold (repeatable) functions capable of producing new [`AssetReader`](crate::io::AssetReader) and [`AssetWriter`](crate::io::AssetWriter) instances
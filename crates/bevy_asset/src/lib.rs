@@ -451,7 +451,16 @@ impl Plugin for AssetPlugin {
     label = "invalid `Asset`",
     note = "consider annotating `{Self}` with `#[derive(Asset)]`"
 )]
-pub trait Asset: VisitAssetDependencies + TypePath + Send + Sync + 'static {}
+pub trait Asset: VisitAssetDependencies + TypePath + Send + Sync + 'static {
+    /// Returns an estimate of the memory used by this asset, in bytes.
+    ///
+    /// This is used to keep [`Assets`] collections within their memory budget (see
+    /// [`Assets::set_memory_budget`]). The default implementation only counts the size of the type
+    /// itself, so assets that own large allocations, such as pixel data, should override it.
+    fn memory_size(&self) -> usize {
+        size_of_val(self)
+    }
+}
 
 /// A trait for components that can be used as asset identifiers, e.g. handle wrappers.
 pub trait AsAssetId: Component {
@@ -677,9 +686,14 @@ impl AssetApp for App {
             .register_type::<Handle<A>>()
             .add_systems(
                 PostUpdate,
-                Assets::<A>::asset_events
-                    .run_if(Assets::<A>::asset_events_condition)
-                    .in_set(AssetEventSystems),
+                (
+                    Assets::<A>::evict_assets
+                        .run_if(Assets::<A>::evict_assets_condition)
+                        .before(AssetEventSystems),
+                    Assets::<A>::asset_events
+                        .run_if(Assets::<A>::asset_events_condition)
+                        .in_set(AssetEventSystems),
+                ),
             )
             .add_systems(
                 PreUpdate,
@@ -1573,6 +1587,142 @@ mod tests {
         assert_eq!(get_started_load_count(app.world()), 3);
     }
 
+    #[test]
+    fn dependency_graph() {
+        let (mut app, dir) = create_app();
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            r#"
+(
+    text: "a",
+    dependencies: ["b.cool.ron", "c.cool.ron"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+        dir.insert_asset_text(
+            Path::new("c.cool.ron"),
+            r#"
+(
+    text: "c",
+    dependencies: ["d.cool.ron"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+        dir.insert_asset_text(Path::new("d.cool.ron"), SIMPLE_TEXT);
+
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        run_app_until(&mut app, |_| {
+            asset_server.is_loaded_with_dependencies(&a).then_some(())
+        });
+
+        let id = |path: &'static str| asset_server.get_path_id(path).unwrap();
+        let (a_id, b_id, c_id, d_id) = (
+            id("a.cool.ron"),
+            id("b.cool.ron"),
+            id("c.cool.ron"),
+            id("d.cool.ron"),
+        );
+
+        let graph = asset_server.dependency_graph();
+        assert_eq!(graph.len(), 4);
+        let a_node = graph.get(a_id).unwrap();
+        assert_eq!(a_node.type_path, Some(CoolText::type_path()));
+        assert!(a_node.load_state.is_loaded());
+        assert_eq!(a_node.dependencies.len(), 2);
+        assert!(a_node.dependents.is_empty());
+        assert_eq!(graph.get(d_id).unwrap().dependents, vec![c_id]);
+        assert_eq!(
+            graph.recursive_dependencies(a_id),
+            [b_id, c_id, d_id].into_iter().collect()
+        );
+        assert_eq!(
+            graph.recursive_dependents(d_id),
+            [a_id, c_id].into_iter().collect()
+        );
+
+        let b_graph = asset_server.dependency_graph_of(b_id);
+        assert_eq!(b_graph.len(), 2);
+        assert_eq!(b_graph.get(a_id).unwrap().dependencies, vec![b_id]);
+        let dot = b_graph.to_dot();
+        assert!(dot.starts_with("digraph assets {"));
+        assert!(dot.contains("b.cool.ron"));
+        assert!(!dot.contains("c.cool.ron"));
+        assert_eq!(dot.matches(" -> ").count(), 1);
+        let serialized = ron::ser::to_string(&b_graph).unwrap();
+        assert!(serialized.contains("\"Loaded\""));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_assets_over_budget() {
+        let (mut app, dir) = create_app();
+        dir.insert_asset_text(Path::new("a.cool.ron"), SIMPLE_TEXT);
+        dir.insert_asset_text(Path::new("b.cool.ron"), SIMPLE_TEXT);
+
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .init_resource::<StoredEvents>()
+            .register_asset_loader(CoolTextLoader)
+            .add_systems(Update, store_asset_events);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let b: Handle<CoolText> = asset_server.load("b.cool.ron");
+        run_app_until(&mut app, |world| {
+            let texts = world.resource::<Assets<CoolText>>();
+            (texts.contains(&a) && texts.contains(&b)).then_some(())
+        });
+
+        let mut texts = app.world_mut().resource_mut::<Assets<CoolText>>();
+        texts.set_evictable(&a, true);
+        texts.set_evictable(&b, true);
+        assert_eq!(texts.memory_usage(), 2 * size_of::<CoolText>());
+        app.update();
+
+        // Only one asset fits in the budget, and `b` was accessed more recently.
+        let mut texts = app.world_mut().resource_mut::<Assets<CoolText>>();
+        assert!(texts.get(&b).is_some());
+        texts.set_memory_budget(Some(size_of::<CoolText>()));
+        app.update();
+
+        let texts = app.world().resource::<Assets<CoolText>>();
+        assert!(texts.is_evicted(&a));
+        assert!(!texts.is_evicted(&b));
+        assert!(!texts.contains(&a));
+        assert_eq!(texts.memory_usage(), size_of::<CoolText>());
+        assert!(matches!(
+            asset_server.get_load_state(&a),
+            Some(LoadState::NotLoaded)
+        ));
+
+        // Accessing `a` reloads it, which evicts `b` instead.
+        run_app_until(&mut app, |world| {
+            world
+                .resource::<Assets<CoolText>>()
+                .get(&a)
+                .map(|text| assert_eq!(text.text, "dep"))
+        });
+        let texts = app.world().resource::<Assets<CoolText>>();
+        assert!(!texts.is_evicted(&a));
+        assert!(texts.is_evicted(&b));
+        let events = &app.world().resource::<StoredEvents>().0;
+        assert!(events.contains(&AssetEvent::Evicted { id: a.id() }));
+    }
+
     const SIMPLE_TEXT: &str = r#"
 (
     text: "dep",
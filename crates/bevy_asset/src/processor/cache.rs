@@ -0,0 +1,169 @@
+use crate::meta::AssetHash;
+use alloc::{borrow::ToOwned, boxed::Box, format, string::ToString, vec::Vec};
+use bevy_ecs::error::BevyError;
+use bevy_tasks::BoxedFuture;
+use core::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+
+/// Identifies the output of processing an asset in a [`ProcessorCache`].
+///
+/// This is a hash of the asset bytes, its `.meta` (which includes the processor settings), and the
+/// type path of the processor. The hashes of the process dependencies are only known once the asset
+/// has been processed, so they are stored in the [`ProcessedInfo`](crate::meta::ProcessedInfo) of
+/// the cached meta instead, and are checked against the current dependencies before a cached output
+/// is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProcessorCacheKey(pub AssetHash);
+
+impl ProcessorCacheKey {
+    /// Creates the key for an asset with the given `hash` (see
+    /// [`ProcessedInfo::hash`](crate::meta::ProcessedInfo::hash)), processed by the processor with
+    /// the given type path.
+    pub fn new(hash: AssetHash, processor_type_path: &str) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&hash);
+        hasher.update(processor_type_path.as_bytes());
+        Self(*hasher.finalize().as_bytes())
+    }
+}
+
+impl Display for ProcessorCacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The output of processing an asset, as stored in a [`ProcessorCache`].
+#[derive(Clone, Debug)]
+pub struct CachedProcessedAsset {
+    /// The bytes of the processed asset.
+    pub asset: Vec<u8>,
+    /// The bytes of the processed `.meta`, including its
+    /// [`ProcessedInfo`](crate::meta::ProcessedInfo).
+    pub meta: Vec<u8>,
+}
+
+/// A content-addressed store of processed assets, shared between runs (and machines) of the
+/// [`AssetProcessor`](crate::processor::AssetProcessor).
+///
+/// Before processing an asset, the processor looks up its [`ProcessorCacheKey`] in the cache. If
+/// there is an entry whose process dependencies are unchanged, the cached output is written to the
+/// processed source instead of running the processor. Otherwise, the asset is processed and the
+/// output is stored in the cache. Writing a cached output goes through the
+/// [`ProcessorTransactionLog`](crate::processor::ProcessorTransactionLog) like any other
+/// processed asset.
+///
+/// Errors returned by the cache are logged and otherwise ignored, so a failing cache only makes
+/// processing slower.
+pub trait ProcessorCache: Send + Sync + 'static {
+    /// Returns the output stored for `key`, or [`None`] if there is none.
+    fn get(
+        &self,
+        key: ProcessorCacheKey,
+    ) -> BoxedFuture<'_, Result<Option<CachedProcessedAsset>, BevyError>>;
+
+    /// Stores `output` for `key`, replacing any previous output.
+    fn put<'a>(
+        &'a self,
+        key: ProcessorCacheKey,
+        output: &'a CachedProcessedAsset,
+    ) -> BoxedFuture<'a, Result<(), BevyError>>;
+}
+
+/// A [`ProcessorCache`] that stores its entries as files in a directory, which can be shared
+/// between checkouts or machines.
+///
+/// Each entry is a single file holding both the processed meta and asset, written to a temporary
+/// file first and then renamed. Concurrent processors therefore never observe partially written
+/// entries, nor an asset paired with the meta written by another processor.
+pub struct FileProcessorCache {
+    /// The directory the entries are stored in.
+    pub root: PathBuf,
+}
+
+impl FileProcessorCache {
+    /// Creates a new cache storing its entries in `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the path of the file of the entry for `key`.
+    fn entry_path(&self, key: ProcessorCacheKey) -> PathBuf {
+        let name = key.to_string();
+        self.root.join(&name[..2]).join(format!("{name}.entry"))
+    }
+}
+
+/// Encodes an entry as the length of the meta (`u64`, little-endian), the meta, then the asset.
+fn encode_entry(output: &CachedProcessedAsset) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(8 + output.meta.len() + output.asset.len());
+    bytes.extend_from_slice(&(output.meta.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&output.meta);
+    bytes.extend_from_slice(&output.asset);
+    bytes
+}
+
+/// Decodes an entry written by [`encode_entry`].
+fn decode_entry(mut bytes: Vec<u8>) -> Result<CachedProcessedAsset, BevyError> {
+    let meta_len = bytes
+        .first_chunk::<8>()
+        .and_then(|len| usize::try_from(u64::from_le_bytes(*len)).ok())
+        .filter(|len| *len <= bytes.len() - 8)
+        .ok_or("the processor cache entry is invalid")?;
+    let asset = bytes.split_off(8 + meta_len);
+    bytes.drain(..8);
+    Ok(CachedProcessedAsset { asset, meta: bytes })
+}
+
+/// Reads the file at `path`, returning [`None`] if it doesn't exist.
+async fn read_if_exists(path: &Path) -> Result<Option<Vec<u8>>, BevyError> {
+    match async_fs::read(path).await {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(err) if err.kind() == futures_io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Writes `bytes` to a temporary file next to `path`, then renames it to `path`.
+async fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), BevyError> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(format!(".{}.tmp", uuid::Uuid::new_v4()));
+    async_fs::write(&temp_path, bytes).await?;
+    if let Err(err) = async_fs::rename(&temp_path, path).await {
+        let _ = async_fs::remove_file(&temp_path).await;
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+impl ProcessorCache for FileProcessorCache {
+    fn get(
+        &self,
+        key: ProcessorCacheKey,
+    ) -> BoxedFuture<'_, Result<Option<CachedProcessedAsset>, BevyError>> {
+        let path = self.entry_path(key);
+        Box::pin(async move {
+            match read_if_exists(&path).await? {
+                Some(bytes) => decode_entry(bytes).map(Some),
+                None => Ok(None),
+            }
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: ProcessorCacheKey,
+        output: &'a CachedProcessedAsset,
+    ) -> BoxedFuture<'a, Result<(), BevyError>> {
+        let path = self.entry_path(key);
+        Box::pin(async move {
+            if let Some(parent_folder) = path.parent() {
+                async_fs::create_dir_all(parent_folder).await?;
+            }
+            write_atomically(&path, &encode_entry(output)).await
+        })
+    }
+}
@@ -37,17 +37,19 @@
 //!
 //! In most cases, [`LoadTransformAndSave`] should be sufficient.
 
+mod cache;
 mod log;
 mod process;
 
 use async_lock::RwLockReadGuardArc;
+pub use cache::*;
 pub use log::*;
 pub use process::*;
 
 use crate::{
     io::{
         AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent, AssetSourceId,
-        AssetSources, AssetWriterError, ErasedAssetReader, MissingAssetSourceError,
+        AssetSources, AssetWriterError, ErasedAssetReader, MissingAssetSourceError, Writer,
     },
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
@@ -113,6 +115,8 @@ pub struct AssetProcessorData {
     log: async_lock::RwLock<Option<Box<dyn ProcessorTransactionLog>>>,
     /// The processors that will be used to process assets.
     processors: RwLock<Processors>,
+    /// The cache that processed assets are fetched from and stored in, if any.
+    cache: RwLock<Option<Arc<dyn ProcessorCache>>>,
     sources: Arc<AssetSources>,
 }
 
@@ -354,6 +358,7 @@ impl AssetProcessor {
         // to the finished state (otherwise we'd be sitting around stuck in the `Initialized`
         // state).
         if new_task_receiver.is_empty() {
+            self.flush_processed_writers().await;
             self.data
                 .processing_state
                 .set_state(ProcessorState::Finished)
@@ -413,6 +418,7 @@ impl AssetProcessor {
                     if pending_tasks == 0 {
                         // clean up metadata in asset server
                         self.server.write_infos().consume_handle_drop_events();
+                        self.flush_processed_writers().await;
                         self.data
                             .processing_state
                             .set_state(ProcessorState::Finished)
@@ -423,6 +429,22 @@ impl AssetProcessor {
         }
     }
 
+    /// Flushes the processed writers of all sources, so that processed assets written so far are
+    /// durable.
+    async fn flush_processed_writers(&self) {
+        for source in self.sources().iter_processed() {
+            let Ok(processed_writer) = source.processed_writer() else {
+                continue;
+            };
+            if let Err(err) = processed_writer.flush().await {
+                error!(
+                    "Failed to flush the processed assets of source {}: {err}",
+                    source.id()
+                );
+            }
+        }
+    }
+
     /// Writes the default meta file for the provided `path`.
     ///
     /// This function generates the appropriate meta file to process `path` with the default
@@ -1144,6 +1166,15 @@ impl AssetProcessor {
             }
         }
 
+        let cache = processor.as_ref().and_then(|processor| {
+            let key = ProcessorCacheKey::new(new_hash, processor.type_path());
+            self.data.cache().map(|cache| (cache, key))
+        });
+        let cached = match &cache {
+            Some((cache, key)) => self.get_cached(asset_path, &**cache, *key).await,
+            None => None,
+        };
+
         // Note: this lock must remain alive until all processed asset and meta writes have finished (or failed)
         // See ProcessedAssetInfo::file_transaction_lock docs for more info
         let _transaction_lock = {
@@ -1162,7 +1193,18 @@ impl AssetProcessor {
         // Directly writing to the asset destination in the processor necessitates this behavior
         // TODO: this class of failure can be recovered via re-processing + smarter log validation that allows for duplicate transactions in the event of failures
         self.log_begin_processing(asset_path).await;
-        if let Some(processor) = processor {
+        if let Some((cached, processed_info)) = cached {
+            debug!("Using the cached output for {}", asset_path);
+            processed_writer
+                .write_bytes(path, &cached.asset)
+                .await
+                .map_err(writer_err)?;
+            processed_writer
+                .write_meta_bytes(path, &cached.meta)
+                .await
+                .map_err(writer_err)?;
+            new_processed_info = processed_info;
+        } else if let Some(processor) = processor {
             // Unwrap is ok since we have a processor, so the `AssetAction` must have been
             // `AssetAction::Process` (which includes its settings).
             let settings = source_meta.process_settings().unwrap();
@@ -1178,7 +1220,12 @@ impl AssetProcessor {
             // reads or not.
             let reader_for_process = reader.read(path).await.map_err(reader_err)?;
 
-            let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
+            // With a cache, the output is buffered so that it can be stored in the cache as well.
+            let mut output = Vec::new();
+            let mut writer = match cache {
+                Some(_) => None,
+                None => Some(processed_writer.write(path).await.map_err(writer_err)?),
+            };
             let mut processed_meta = {
                 let mut context = ProcessContext::new(
                     self,
@@ -1186,7 +1233,11 @@ impl AssetProcessor {
                     reader_for_process,
                     &mut new_processed_info,
                 );
-                let process = processor.process(&mut context, settings, &mut *writer);
+                let output_writer: &mut Writer = match &mut writer {
+                    Some(writer) => &mut **writer,
+                    None => &mut output,
+                };
+                let process = processor.process(&mut context, settings, output_writer);
                 #[cfg(feature = "trace")]
                 let process = {
                     let span = info_span!(
@@ -1199,13 +1250,23 @@ impl AssetProcessor {
                 process.await?
             };
 
-            writer
-                .flush()
-                .await
-                .map_err(|e| ProcessError::AssetWriterError {
-                    path: asset_path.clone(),
-                    err: AssetWriterError::Io(e),
-                })?;
+            match writer {
+                Some(mut writer) => {
+                    writer
+                        .flush()
+                        .await
+                        .map_err(|e| ProcessError::AssetWriterError {
+                            path: asset_path.clone(),
+                            err: AssetWriterError::Io(e),
+                        })?;
+                }
+                None => {
+                    processed_writer
+                        .write_bytes(path, &output)
+                        .await
+                        .map_err(writer_err)?;
+                }
+            }
 
             let full_hash = get_full_asset_hash(
                 new_hash,
@@ -1222,6 +1283,16 @@ impl AssetProcessor {
                 .write_meta_bytes(path, &meta_bytes)
                 .await
                 .map_err(writer_err)?;
+
+            if let Some((cache, key)) = cache {
+                let cached = CachedProcessedAsset {
+                    asset: output,
+                    meta: meta_bytes,
+                };
+                if let Err(err) = cache.put(key, &cached).await {
+                    warn!("Failed to store {asset_path} in the processor cache: {err}");
+                }
+            }
         } else {
             // See the reasoning for processing why it's ok to do a second read here.
             let mut reader_for_copy = reader.read(path).await.map_err(reader_err)?;
@@ -1244,6 +1315,45 @@ impl AssetProcessor {
         Ok(ProcessResult::Processed(new_processed_info))
     }
 
+    /// Returns the output of `asset_path` stored in `cache` for `key`, along with its
+    /// [`ProcessedInfo`], if all of its process dependencies are unchanged.
+    async fn get_cached(
+        &self,
+        asset_path: &AssetPath<'static>,
+        cache: &dyn ProcessorCache,
+        key: ProcessorCacheKey,
+    ) -> Option<(CachedProcessedAsset, ProcessedInfo)> {
+        let cached = match cache.get(key).await {
+            Ok(cached) => cached?,
+            Err(err) => {
+                warn!("Failed to read {asset_path} from the processor cache: {err}");
+                return None;
+            }
+        };
+        let Ok(ProcessedInfoMinimal {
+            processed_info: Some(processed_info),
+        }) = ron::de::from_bytes::<ProcessedInfoMinimal>(&cached.meta)
+        else {
+            warn!("The processor cache contains an invalid meta for {asset_path}. Processing it again.");
+            return None;
+        };
+        for dependency in &processed_info.process_dependencies {
+            self.data
+                .processing_state
+                .wait_until_processed(dependency.path.clone())
+                .await;
+            let infos = self.data.processing_state.asset_infos.read().await;
+            let live_hash = infos
+                .get(&dependency.path)
+                .and_then(|info| info.processed_info.as_ref())
+                .map(|info| info.full_hash);
+            if live_hash != Some(dependency.full_hash) {
+                return None;
+            }
+        }
+        Some((cached, processed_info))
+    }
+
     async fn validate_transaction_log_and_recover(&self) {
         let log_factory = self
             .data
@@ -1348,6 +1458,7 @@ impl AssetProcessorData {
             log_factory: Mutex::new(Some(Box::new(FileTransactionLogFactory::default()))),
             log: Default::default(),
             processors: Default::default(),
+            cache: Default::default(),
         }
     }
 
@@ -1373,6 +1484,21 @@ impl AssetProcessorData {
         Ok(())
     }
 
+    /// Sets the [`ProcessorCache`] that processed assets are fetched from and stored in.
+    ///
+    /// This only affects assets processed after this call. By default, there is no cache.
+    pub fn set_cache(&self, cache: impl ProcessorCache) {
+        *self.cache.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(cache));
+    }
+
+    /// Returns the [`ProcessorCache`] of the processor, if any.
+    pub fn cache(&self) -> Option<Arc<dyn ProcessorCache>> {
+        self.cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
     /// Returns a future that will not finish until the path has been processed.
     pub async fn wait_until_processed(&self, path: AssetPath<'static>) -> ProcessStatus {
         self.processing_state.wait_until_processed(path).await
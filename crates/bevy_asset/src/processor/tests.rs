@@ -29,8 +29,9 @@ use crate::{
         AssetSourceId, AssetWatcher, PathStream, Reader,
     },
     processor::{
-        AssetProcessor, GetProcessorError, LoadTransformAndSave, LogEntry, Process, ProcessContext,
-        ProcessError, ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
+        AssetProcessor, CachedProcessedAsset, FileProcessorCache, GetProcessorError,
+        LoadTransformAndSave, LogEntry, Process, ProcessContext, ProcessError, ProcessorCache,
+        ProcessorCacheKey, ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
     },
     saver::{tests::CoolTextSaver, AssetSaver},
     tests::{
@@ -546,6 +547,136 @@ fn asset_processor_transforms_asset_default_processor() {
     );
 }
 
+#[derive(Clone, Default)]
+struct MemoryProcessorCache(Arc<Mutex<HashMap<ProcessorCacheKey, CachedProcessedAsset>>>);
+
+impl ProcessorCache for MemoryProcessorCache {
+    fn get(
+        &self,
+        key: ProcessorCacheKey,
+    ) -> BoxedFuture<'_, Result<Option<CachedProcessedAsset>, BevyError>> {
+        let cached = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+            .cloned();
+        Box::pin(async move { Ok(cached) })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: ProcessorCacheKey,
+        output: &'a CachedProcessedAsset,
+    ) -> BoxedFuture<'a, Result<(), BevyError>> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, output.clone());
+        Box::pin(async move { Ok(()) })
+    }
+}
+
+#[test]
+fn asset_processor_uses_cached_output() {
+    fn process_with_cache(cache: &MemoryProcessorCache) -> String {
+        let AppWithProcessor {
+            mut app,
+            source_gate,
+            default_source_dirs:
+                ProcessingDirs {
+                    source: source_dir,
+                    processed: processed_dir,
+                    ..
+                },
+            ..
+        } = create_app_with_asset_processor(&[]);
+
+        type CoolTextProcessor = LoadTransformAndSave<
+            CoolTextLoader,
+            RootAssetTransformer<AddText, CoolText>,
+            CoolTextSaver,
+        >;
+        app.register_asset_loader(CoolTextLoader)
+            .register_asset_processor(CoolTextProcessor::new(
+                RootAssetTransformer::new(AddText("_def".into())),
+                CoolTextSaver,
+            ))
+            .set_default_asset_processor::<CoolTextProcessor>("cool.ron");
+        app.world()
+            .resource::<AssetProcessor>()
+            .data()
+            .set_cache(cache.clone());
+
+        let guard = source_gate.write_blocking();
+
+        let path = Path::new("abc.cool.ron");
+        source_dir.insert_asset_text(
+            path,
+            r#"(
+    text: "abc",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+
+        run_app_until_finished_processing(&mut app, guard);
+
+        let processed_asset = processed_dir.get_asset(path).unwrap();
+        String::from(str::from_utf8(processed_asset.value()).unwrap())
+    }
+
+    let cache = MemoryProcessorCache::default();
+    let processed_asset = process_with_cache(&cache);
+    assert!(processed_asset.contains("abc_def"));
+
+    // Replace the cached output, so we can tell whether the second run used it.
+    {
+        let mut entries = cache.0.lock().unwrap();
+        assert_eq!(entries.len(), 1);
+        let cached = entries.values_mut().next().unwrap();
+        assert_eq!(cached.asset, processed_asset.as_bytes());
+        cached.asset = processed_asset
+            .replace("abc_def", "abc_cached")
+            .into_bytes();
+    }
+
+    let processed_asset = process_with_cache(&cache);
+    assert!(processed_asset.contains("abc_cached"));
+}
+
+#[test]
+fn file_processor_cache_stores_asset_and_meta_together() {
+    let root = std::env::temp_dir().join(alloc::format!(
+        "bevy_file_processor_cache_test_{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&root);
+
+    let cache = FileProcessorCache::new(&root);
+    let key = ProcessorCacheKey::new([7; 32], "MyProcessor");
+    let output = CachedProcessedAsset {
+        asset: b"asset".to_vec(),
+        meta: b"meta".to_vec(),
+    };
+    bevy_tasks::block_on(async {
+        assert!(cache.get(key).await.unwrap().is_none());
+        cache.put(key, &output).await.unwrap();
+        let cached = cache.get(key).await.unwrap().unwrap();
+        assert_eq!(cached.asset, output.asset);
+        assert_eq!(cached.meta, output.meta);
+    });
+
+    // The entry is a single file, so the asset can't be replaced without its meta.
+    let files = std::fs::read_dir(root.join(&key.to_string()[..2]))
+        .unwrap()
+        .count();
+    assert_eq!(files, 1);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
 #[test]
 fn asset_processor_transforms_asset_with_meta() {
     let AppWithProcessor {
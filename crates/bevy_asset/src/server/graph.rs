@@ -0,0 +1,364 @@
+use crate::{
+    AssetPath, DependencyLoadState, LoadState, RecursiveDependencyLoadState, UntypedAssetId,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_platform::collections::{HashMap, HashSet};
+use core::fmt::Write;
+use serde::{ser::SerializeSeq, Serialize, Serializer};
+
+use super::info::AssetInfos;
+
+/// A snapshot of the dependencies between the assets managed by an
+/// [`AssetServer`](crate::AssetServer), together with their load states.
+///
+/// An edge goes from an asset to each of its direct dependencies, which are the handles visited by
+/// [`VisitAssetDependencies`](crate::VisitAssetDependencies) when the asset was last loaded.
+/// Dependencies on assets that aren't managed by the [`AssetServer`](crate::AssetServer), such as
+/// assets added with [`Assets::add`](crate::Assets::add), are not included.
+///
+/// Returned by [`AssetServer::dependency_graph`](crate::AssetServer::dependency_graph) and
+/// [`AssetServer::dependency_graph_of`](crate::AssetServer::dependency_graph_of). It can be
+/// exported with [`AssetDependencyGraph::to_dot`], or serialized (for example to JSON) with
+/// [`serde`].
+#[derive(Clone, Debug, Default)]
+pub struct AssetDependencyGraph {
+    nodes: HashMap<UntypedAssetId, AssetDependencyNode>,
+}
+
+/// An asset in an [`AssetDependencyGraph`].
+#[derive(Clone, Debug)]
+pub struct AssetDependencyNode {
+    /// The id of the asset.
+    pub id: UntypedAssetId,
+    /// The type path of the asset, if its type was registered with
+    /// [`AssetServer::register_asset`](crate::AssetServer::register_asset).
+    pub type_path: Option<&'static str>,
+    /// The path of the asset, if it has one.
+    pub path: Option<AssetPath<'static>>,
+    /// The load state of the asset.
+    pub load_state: LoadState,
+    /// The load state of the direct dependencies of the asset.
+    pub dependency_load_state: DependencyLoadState,
+    /// The load state of all the dependencies of the asset.
+    pub recursive_dependency_load_state: RecursiveDependencyLoadState,
+    /// The direct dependencies of the asset.
+    pub dependencies: Vec<UntypedAssetId>,
+    /// The assets that directly depend on this asset.
+    pub dependents: Vec<UntypedAssetId>,
+    /// The paths of the assets read by the loader of this asset.
+    ///
+    /// This is only tracked when the [`AssetServer`](crate::AssetServer) is watching for changes.
+    pub loader_dependencies: Vec<AssetPath<'static>>,
+}
+
+impl AssetDependencyNode {
+    /// Returns a string identifying this node, made of its type path (or [`TypeId`] if the type
+    /// path is unknown) and its index.
+    ///
+    /// [`TypeId`]: core::any::TypeId
+    pub fn key(&self) -> String {
+        let mut key = match self.type_path {
+            Some(type_path) => type_path.to_string(),
+            None => format!("{:?}", self.id.type_id()),
+        };
+        match self.id {
+            UntypedAssetId::Index { index, .. } => {
+                let _ = write!(key, "#{}v{}", index.index, index.generation);
+            }
+            UntypedAssetId::Uuid { uuid, .. } => {
+                let _ = write!(key, "#{uuid}");
+            }
+        }
+        key
+    }
+}
+
+impl AssetDependencyGraph {
+    pub(crate) fn from_infos(infos: &AssetInfos) -> Self {
+        let mut nodes = HashMap::<UntypedAssetId, AssetDependencyNode>::default();
+        for (index, info) in infos.iter() {
+            let id = UntypedAssetId::from(index);
+            let mut dependencies = info
+                .dependencies
+                .iter()
+                .filter(|dependency| infos.contains_key(**dependency))
+                .map(|dependency| UntypedAssetId::from(*dependency))
+                .collect::<Vec<_>>();
+            dependencies.sort();
+            let mut loader_dependencies =
+                info.loader_dependencies.keys().cloned().collect::<Vec<_>>();
+            loader_dependencies.sort_by_cached_key(ToString::to_string);
+            nodes.insert(
+                id,
+                AssetDependencyNode {
+                    id,
+                    type_path: infos.type_paths.get(&index.type_id).copied(),
+                    path: info.path.clone(),
+                    load_state: info.load_state.clone(),
+                    dependency_load_state: info.dep_load_state.clone(),
+                    recursive_dependency_load_state: info.rec_dep_load_state.clone(),
+                    dependencies,
+                    dependents: Vec::new(),
+                    loader_dependencies,
+                },
+            );
+        }
+
+        let edges = nodes
+            .values()
+            .flat_map(|node| {
+                node.dependencies
+                    .iter()
+                    .map(|dependency| (*dependency, node.id))
+            })
+            .collect::<Vec<_>>();
+        for (dependency, dependent) in edges {
+            if let Some(node) = nodes.get_mut(&dependency) {
+                node.dependents.push(dependent);
+            }
+        }
+        for node in nodes.values_mut() {
+            node.dependents.sort();
+        }
+
+        Self { nodes }
+    }
+
+    /// Returns the node of the asset with the given `id`, if it is in the graph.
+    pub fn get(&self, id: impl Into<UntypedAssetId>) -> Option<&AssetDependencyNode> {
+        self.nodes.get(&id.into())
+    }
+
+    /// Returns an iterator over all the nodes of the graph, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &AssetDependencyNode> {
+        self.nodes.values()
+    }
+
+    /// Returns the number of assets in the graph.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the graph contains no assets.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the direct and indirect dependencies of the asset with the given `id`, not
+    /// including the asset itself.
+    pub fn recursive_dependencies(&self, id: impl Into<UntypedAssetId>) -> HashSet<UntypedAssetId> {
+        self.reachable(id.into(), |node| node.dependencies.as_slice())
+    }
+
+    /// Returns the assets that directly or indirectly depend on the asset with the given `id`, not
+    /// including the asset itself.
+    pub fn recursive_dependents(&self, id: impl Into<UntypedAssetId>) -> HashSet<UntypedAssetId> {
+        self.reachable(id.into(), |node| node.dependents.as_slice())
+    }
+
+    fn reachable(
+        &self,
+        id: UntypedAssetId,
+        edges: impl Fn(&AssetDependencyNode) -> &[UntypedAssetId],
+    ) -> HashSet<UntypedAssetId> {
+        let mut visited = HashSet::<UntypedAssetId>::default();
+        let mut stack = Vec::from([id]);
+        while let Some(id) = stack.pop() {
+            let Some(node) = self.nodes.get(&id) else {
+                continue;
+            };
+            for next in edges(node) {
+                if visited.insert(*next) {
+                    stack.push(*next);
+                }
+            }
+        }
+        visited.remove(&id);
+        visited
+    }
+
+    /// Returns the subgraph made of the asset with the given `id` and its recursive dependencies
+    /// and dependents. The subgraph is empty if the asset is not in this graph.
+    pub fn subgraph(&self, id: impl Into<UntypedAssetId>) -> Self {
+        let id = id.into();
+        if !self.nodes.contains_key(&id) {
+            return Self::default();
+        }
+        let mut kept = self.recursive_dependencies(id);
+        kept.extend(self.recursive_dependents(id));
+        kept.insert(id);
+
+        let nodes = kept
+            .iter()
+            .map(|id| {
+                let mut node = self.nodes[id].clone();
+                node.dependencies.retain(|id| kept.contains(id));
+                node.dependents.retain(|id| kept.contains(id));
+                (*id, node)
+            })
+            .collect();
+        Self { nodes }
+    }
+
+    /// Returns the nodes of the graph sorted by key, so that exports are deterministic.
+    fn sorted_nodes(&self) -> Vec<(String, &AssetDependencyNode)> {
+        let mut nodes = self
+            .nodes
+            .values()
+            .map(|node| (node.key(), node))
+            .collect::<Vec<_>>();
+        nodes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        nodes
+    }
+
+    fn key(&self, id: UntypedAssetId) -> String {
+        self.nodes[&id].key()
+    }
+
+    /// Exports the graph in the [DOT] format, which can be rendered with Graphviz.
+    ///
+    /// Each asset is labeled with its path (if it has one), its type and its load state. Assets
+    /// that failed to load are drawn in red, and assets that are still loading in gray.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn to_dot(&self) -> String {
+        let nodes = self.sorted_nodes();
+        let mut dot = String::from("digraph assets {\n    node [shape=box];\n");
+        for (key, node) in &nodes {
+            let mut label = String::new();
+            if let Some(path) = &node.path {
+                let _ = writeln!(label, "{path}");
+            }
+            let _ = write!(
+                label,
+                "{}\n{}",
+                node.type_path.unwrap_or("<unknown type>"),
+                load_state_name(&node.load_state)
+            );
+            let color = match node.load_state {
+                LoadState::Failed(_) => ", color=red",
+                LoadState::NotLoaded | LoadState::Loading => ", color=gray",
+                LoadState::Loaded => "",
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\"{color}];",
+                escape_dot(key),
+                escape_dot(&label)
+            );
+        }
+        for (key, node) in &nodes {
+            for dependency in &node.dependencies {
+                let _ = writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\";",
+                    escape_dot(key),
+                    escape_dot(&self.key(*dependency))
+                );
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Escapes a string to be used in a quoted DOT identifier.
+fn escape_dot(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn load_state_name(state: &LoadState) -> &'static str {
+    match state {
+        LoadState::NotLoaded => "NotLoaded",
+        LoadState::Loading => "Loading",
+        LoadState::Loaded => "Loaded",
+        LoadState::Failed(_) => "Failed",
+    }
+}
+
+/// A load state, as serialized in an [`AssetDependencyGraph`].
+#[derive(Serialize)]
+struct SerializedLoadState {
+    state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl From<&LoadState> for SerializedLoadState {
+    fn from(state: &LoadState) -> Self {
+        Self {
+            state: load_state_name(state),
+            error: match state {
+                LoadState::Failed(error) => Some(error.to_string()),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl From<&DependencyLoadState> for SerializedLoadState {
+    fn from(state: &DependencyLoadState) -> Self {
+        let (state, error) = match state {
+            DependencyLoadState::NotLoaded => ("NotLoaded", None),
+            DependencyLoadState::Loading => ("Loading", None),
+            DependencyLoadState::Loaded => ("Loaded", None),
+            DependencyLoadState::Failed(error) => ("Failed", Some(error.to_string())),
+        };
+        Self { state, error }
+    }
+}
+
+impl From<&RecursiveDependencyLoadState> for SerializedLoadState {
+    fn from(state: &RecursiveDependencyLoadState) -> Self {
+        let (state, error) = match state {
+            RecursiveDependencyLoadState::NotLoaded => ("NotLoaded", None),
+            RecursiveDependencyLoadState::Loading => ("Loading", None),
+            RecursiveDependencyLoadState::Loaded => ("Loaded", None),
+            RecursiveDependencyLoadState::Failed(error) => ("Failed", Some(error.to_string())),
+        };
+        Self { state, error }
+    }
+}
+
+/// A node, as serialized in an [`AssetDependencyGraph`]. Assets are referred to by their
+/// [`AssetDependencyNode::key`].
+#[derive(Serialize)]
+struct SerializedNode<'a> {
+    key: String,
+    type_path: Option<&'static str>,
+    path: Option<&'a AssetPath<'static>>,
+    load_state: SerializedLoadState,
+    dependency_load_state: SerializedLoadState,
+    recursive_dependency_load_state: SerializedLoadState,
+    dependencies: Vec<String>,
+    dependents: Vec<String>,
+    loader_dependencies: &'a [AssetPath<'static>],
+}
+
+impl Serialize for AssetDependencyGraph {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let nodes = self.sorted_nodes();
+        let mut seq = serializer.serialize_seq(Some(nodes.len()))?;
+        for (key, node) in nodes {
+            seq.serialize_element(&SerializedNode {
+                key,
+                type_path: node.type_path,
+                path: node.path.as_ref(),
+                load_state: (&node.load_state).into(),
+                dependency_load_state: (&node.dependency_load_state).into(),
+                recursive_dependency_load_state: (&node.recursive_dependency_load_state).into(),
+                dependencies: node.dependencies.iter().map(|id| self.key(*id)).collect(),
+                dependents: node.dependents.iter().map(|id| self.key(*id)).collect(),
+                loader_dependencies: &node.loader_dependencies,
+            })?;
+        }
+        seq.end()
+    }
+}
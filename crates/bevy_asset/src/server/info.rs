@@ -29,6 +29,8 @@ pub(crate) struct AssetInfo {
     pub(crate) load_state: LoadState,
     pub(crate) dep_load_state: DependencyLoadState,
     pub(crate) rec_dep_load_state: RecursiveDependencyLoadState,
+    /// The direct dependencies of this asset, as of its last load.
+    pub(crate) dependencies: HashSet<ErasedAssetIndex>,
     loading_dependencies: HashSet<ErasedAssetIndex>,
     failed_dependencies: HashSet<ErasedAssetIndex>,
     loading_rec_dependencies: HashSet<ErasedAssetIndex>,
@@ -41,7 +43,7 @@ pub(crate) struct AssetInfo {
     /// save memory.
     ///
     /// [`LoadedAsset`]: crate::loader::LoadedAsset
-    loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
+    pub(crate) loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
@@ -57,6 +59,7 @@ impl AssetInfo {
             load_state: LoadState::NotLoaded,
             dep_load_state: DependencyLoadState::NotLoaded,
             rec_dep_load_state: RecursiveDependencyLoadState::NotLoaded,
+            dependencies: HashSet::default(),
             loading_dependencies: HashSet::default(),
             failed_dependencies: HashSet::default(),
             loading_rec_dependencies: HashSet::default(),
@@ -91,6 +94,8 @@ pub(crate) struct AssetInfos {
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) living_labeled_assets: HashMap<AssetPath<'static>, HashSet<Box<str>>>,
     pub(crate) handle_providers: TypeIdMap<AssetHandleProvider>,
+    /// The type paths of the registered asset types.
+    pub(crate) type_paths: TypeIdMap<&'static str>,
     pub(crate) dependency_loaded_event_sender: TypeIdMap<fn(&mut World, AssetIndex)>,
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, AssetIndex, AssetPath<'static>, AssetLoadError)>,
@@ -302,6 +307,26 @@ impl AssetInfos {
         self.infos.get_mut(&index)
     }
 
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (ErasedAssetIndex, &AssetInfo)> {
+        self.infos.iter().map(|(index, info)| (*index, info))
+    }
+
+    /// Marks a loaded asset as evicted from its [`Assets`](crate::Assets) collection, so that
+    /// requesting a load of its path loads it again. Returns `false` if the asset can't be evicted
+    /// because it has no path to be reloaded from, or isn't loaded.
+    pub(crate) fn process_asset_eviction(&mut self, index: ErasedAssetIndex) -> bool {
+        let Some(info) = self.get_mut(index) else {
+            return false;
+        };
+        if info.path.is_none() || !info.load_state.is_loaded() {
+            return false;
+        }
+        info.load_state = LoadState::NotLoaded;
+        info.dep_load_state = DependencyLoadState::NotLoaded;
+        info.rec_dep_load_state = RecursiveDependencyLoadState::NotLoaded;
+        true
+    }
+
     pub(crate) fn get_path_and_type_id_handle(
         &self,
         path: &AssetPath<'_>,
@@ -425,6 +450,7 @@ impl AssetInfos {
 
         loaded_asset.value.insert(loaded_asset_index.index, world);
         let mut loading_deps = loaded_asset.dependencies;
+        let dependencies = loading_deps.clone();
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
         let mut loading_rec_deps = loading_deps.clone();
@@ -520,6 +546,7 @@ impl AssetInfos {
             let info = self
                 .get_mut(loaded_asset_index)
                 .expect("Asset info should always exist at this point");
+            info.dependencies = dependencies;
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
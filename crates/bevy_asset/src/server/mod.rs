@@ -1,3 +1,4 @@
+mod graph;
 mod info;
 mod loaders;
 
@@ -41,6 +42,7 @@ use core::{
 };
 use crossbeam_channel::{Receiver, Sender};
 use futures_lite::{FutureExt, StreamExt};
+pub use graph::*;
 use info::*;
 use loaders::*;
 use std::path::{Path, PathBuf};
@@ -225,6 +227,8 @@ impl AssetServer {
 
         let mut infos = self.write_infos();
 
+        infos.type_paths.insert(TypeId::of::<A>(), A::type_path());
+
         infos
             .dependency_loaded_event_sender
             .insert(TypeId::of::<A>(), sender::<A>);
@@ -1500,6 +1504,23 @@ impl AssetServer {
         Some(info.path.as_ref()?.clone())
     }
 
+    /// Returns the dependency graph of all the assets managed by this server, together with their
+    /// load states.
+    ///
+    /// # See also
+    /// [`dependency_graph_of`][Self::dependency_graph_of] for the graph of a single asset.
+    pub fn dependency_graph(&self) -> AssetDependencyGraph {
+        AssetDependencyGraph::from_infos(&self.read_infos())
+    }
+
+    /// Returns the dependency graph of the asset with the given `id`: the asset itself, its direct
+    /// and indirect dependencies, and the assets that directly or indirectly depend on it.
+    ///
+    /// The graph is empty if the asset is not managed by this server.
+    pub fn dependency_graph_of(&self, id: impl Into<UntypedAssetId>) -> AssetDependencyGraph {
+        self.dependency_graph().subgraph(id)
+    }
+
     /// Returns the [`AssetServerMode`] this server is currently in.
     pub fn mode(&self) -> AssetServerMode {
         self.data.mode
@@ -7,7 +7,10 @@ use core::time::Duration;
 
 use bevy_app::prelude::*;
 use bevy_color::{palettes, prelude::*};
-use bevy_diagnostic::{Diagnostic, DiagnosticPath, DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy_diagnostic::{
+    Diagnostic, DiagnosticPath, DiagnosticsStore, FrameTimeDiagnosticsPlugin,
+    ScheduleDiagnosticsPlugin,
+};
 use bevy_ecs::{prelude::*, relationship::Relationship};
 use bevy_pbr::{diagnostic::MaterialAllocatorDiagnosticPlugin, StandardMaterial};
 use bevy_picking::prelude::*;
@@ -157,6 +160,29 @@ impl DiagnosticsOverlay {
     }
 }
 
+/// Keeps the items of a [`DiagnosticsOverlay`] pointed at the slowest systems measured by
+/// [`ScheduleDiagnosticsPlugin`], which must be added for the overlay to show anything.
+///
+/// ```
+/// # use bevy_dev_tools::diagnostics_overlay::SlowestSystemsOverlay;
+/// # use bevy_ecs::prelude::World;
+/// # let mut world = World::new();
+/// # let mut commands = world.commands();
+/// commands.spawn(SlowestSystemsOverlay { count: 10 });
+/// ```
+#[derive(Component, Clone, Copy)]
+#[require(DiagnosticsOverlay = DiagnosticsOverlay::new("Slowest systems", Vec::new()))]
+pub struct SlowestSystemsOverlay {
+    /// How many systems to show
+    pub count: usize,
+}
+
+impl Default for SlowestSystemsOverlay {
+    fn default() -> Self {
+        Self { count: 5 }
+    }
+}
+
 /// Configures the style of diagnostic overlays
 #[derive(Resource)]
 pub struct DiagnosticsOverlayStyle {
@@ -254,7 +280,8 @@ impl Plugin for DiagnosticsOverlayPlugin {
         app.add_systems(PreStartup, build_plane);
         app.add_systems(
             Update,
-            rebuild_diagnostics_list
+            (track_slowest_systems, rebuild_diagnostics_list)
+                .chain()
                 .run_if(on_timer(Duration::from_secs(1)))
                 .in_set(DiagnosticsOverlaySystems::Rebuild),
         );
@@ -292,6 +319,23 @@ struct DiagnosticsOverlayHeader;
 #[derive(Component)]
 struct DiagnosticsOverlayContents;
 
+fn track_slowest_systems(
+    mut overlays: Query<(&mut DiagnosticsOverlay, &SlowestSystemsOverlay)>,
+    diagnostics_store: Res<DiagnosticsStore>,
+) {
+    for (mut overlay, slowest_systems) in &mut overlays {
+        overlay.items =
+            ScheduleDiagnosticsPlugin::slowest_systems(&diagnostics_store, slowest_systems.count)
+                .into_iter()
+                .map(|path| DiagnosticsOverlayItem {
+                    path,
+                    statistic: DiagnosticsOverlayStatistic::Smoothed,
+                    precision: 2,
+                })
+                .collect();
+    }
+}
+
 fn rebuild_diagnostics_list(
     mut commands: Commands,
     diagnostics_overlays: Query<&DiagnosticsOverlay>,
@@ -7,8 +7,8 @@
 use bevy_ecs::{
     component::{ComponentId, Components},
     schedule::{
-        ApplyDeferred, ConditionWithAccess, InternedScheduleLabel, NodeId, Schedule,
-        ScheduleBuildMetadata, Schedules,
+        ApplyDeferred, ConditionWithAccess, ConflictingAccess, InternedScheduleLabel, NodeId,
+        Schedule, ScheduleBuildMetadata, Schedules, SystemAmbiguity, SystemKey,
     },
     system::SystemStateFlags,
 };
@@ -118,17 +118,68 @@ pub enum ScheduleIndex {
 }
 
 /// Data about an access conflict between two systems.
+///
+/// This is the serializable counterpart of [`SystemAmbiguity`].
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 pub struct SystemConflict {
-    /// The first system index.
+    /// The index of the system that runs first in the schedule's topological order.
     pub system_1: u32,
-    /// The second system index.
+    /// The index of the system that runs second in the schedule's topological order.
     pub system_2: u32,
     /// The kind of conflict between these systems.
     pub conflicting_access: AccessConflict,
 }
 
+impl SystemConflict {
+    /// Creates the data from a [`SystemAmbiguity`] of the schedule's [`AmbiguityReport`].
+    ///
+    /// `component_id_to_index` and `components` are extended with any component that isn't in
+    /// them yet.
+    ///
+    /// [`AmbiguityReport`]: bevy_ecs::schedule::AmbiguityReport
+    fn from_ambiguity(
+        ambiguity: &SystemAmbiguity,
+        system_key_to_index: &HashMap<SystemKey, usize>,
+        component_id_to_index: &mut HashMap<ComponentId, usize>,
+        components: &mut Vec<ComponentData>,
+    ) -> Self {
+        let system_index = |key| {
+            *system_key_to_index
+                .get(&key)
+                .expect("the system this key refers to should have already been seen")
+                as u32
+        };
+
+        SystemConflict {
+            system_1: system_index(ambiguity.first),
+            system_2: system_index(ambiguity.second),
+            conflicting_access: match &ambiguity.access {
+                ConflictingAccess::World => AccessConflict::World,
+                ConflictingAccess::Components(conflicts) => AccessConflict::Components(
+                    conflicts
+                        .iter()
+                        .map(
+                            |component| match component_id_to_index.entry(component.id) {
+                                Entry::Occupied(entry) => *entry.get() as _,
+                                Entry::Vacant(entry) => {
+                                    components.push(ComponentData {
+                                        name: format!("{}", component.name),
+                                    });
+                                    *entry.insert(components.len() - 1) as _
+                                }
+                            },
+                        )
+                        .collect(),
+                ),
+            },
+        }
+    }
+}
+
 /// Data for describing the kind of access conflict.
+///
+/// This is the serializable counterpart of [`ConflictingAccess`], referring to components by
+/// their index in [`ScheduleData::components`].
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 pub enum AccessConflict {
     /// There is a conflict on the **whole world**, since one of the systems requires world access
@@ -261,44 +312,18 @@ impl ScheduleData {
         let mut component_id_to_index = HashMap::<ComponentId, usize>::new();
         let mut components = vec![];
 
-        let conflicts = graph
-            .conflicting_systems()
+        let ambiguities = schedule.ambiguity_report(world_components).map_err(|_| {
+            ExtractAppDataError::ScheduleNotInitialized(format!("{:?}", schedule.label()))
+        })?;
+        let conflicts = ambiguities
             .iter()
-            .map(|(system_1, system_2, conflicts)| {
-                let system_1 = system_key_to_index
-                    .get(system_1)
-                    .expect("the system this key refers to should have already been seen");
-                let system_2 = system_key_to_index
-                    .get(system_2)
-                    .expect("the system this key refers to should have already been seen");
-
-                SystemConflict {
-                    system_1: *system_1 as _,
-                    system_2: *system_2 as _,
-                    conflicting_access: if conflicts.is_empty() {
-                        // The systems conflict on the world if there's no particular component IDs.
-                        AccessConflict::World
-                    } else {
-                        AccessConflict::Components(
-                            conflicts
-                                .iter()
-                                .map(|id| match component_id_to_index.entry(*id) {
-                                    Entry::Occupied(entry) => *entry.get() as _,
-                                    Entry::Vacant(entry) => {
-                                        let component = world_components.get_info(*id).expect(
-                                    "the component has already been registered by the system",
-                                );
-
-                                        components.push(ComponentData {
-                                            name: format!("{}", component.name()),
-                                        });
-                                        *entry.insert(components.len() - 1) as _
-                                    }
-                                })
-                                .collect(),
-                        )
-                    },
-                }
+            .map(|ambiguity| {
+                SystemConflict::from_ambiguity(
+                    ambiguity,
+                    &system_key_to_index,
+                    &mut component_id_to_index,
+                    &mut components,
+                )
             })
             .collect();
 
@@ -0,0 +1,205 @@
+use alloc::{format, vec::Vec};
+use core::time::Duration;
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleDiagnostics, SystemKey},
+};
+use bevy_platform::{collections::HashMap, time::Instant};
+
+use crate::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore, DEFAULT_MAX_HISTORY_LENGTH,
+};
+
+/// Adds a "system time" diagnostic for every system in the App, measured by the schedule
+/// executors through [`ScheduleDiagnostics`].
+///
+/// Each system that has run at least once gets a diagnostic at
+/// `system_time/<schedule>/<system>`, holding the time in milliseconds that the system spent
+/// running during each frame. Use [`ScheduleDiagnosticsPlugin::slowest_systems`] to find the
+/// most expensive ones.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct ScheduleDiagnosticsPlugin {
+    /// The total number of values to keep for averaging.
+    pub max_history_length: usize,
+    /// The smoothing factor for the exponential moving average. Usually `2.0 / (history_length + 1.0)`.
+    pub smoothing_factor: f64,
+}
+
+impl Default for ScheduleDiagnosticsPlugin {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_HISTORY_LENGTH)
+    }
+}
+
+impl ScheduleDiagnosticsPlugin {
+    /// Creates a new `ScheduleDiagnosticsPlugin` with the specified `max_history_length` and a
+    /// reasonable `smoothing_factor`.
+    pub fn new(max_history_length: usize) -> Self {
+        Self {
+            max_history_length,
+            smoothing_factor: 2.0 / (max_history_length as f64 + 1.0),
+        }
+    }
+}
+
+impl Plugin for ScheduleDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScheduleDiagnostics>()
+            .init_resource::<DiagnosticsStore>()
+            .insert_resource(SystemTimeDiagnosticsConfig {
+                max_history_length: self.max_history_length,
+                smoothing_factor: self.smoothing_factor,
+            })
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+/// Settings used to register the diagnostics of newly measured systems.
+#[derive(Resource)]
+struct SystemTimeDiagnosticsConfig {
+    max_history_length: usize,
+    smoothing_factor: f64,
+}
+
+/// A system's diagnostic path and its total run time when the diagnostics were last updated.
+struct TrackedSystem {
+    path: DiagnosticPath,
+    total_duration: Duration,
+}
+
+impl ScheduleDiagnosticsPlugin {
+    /// Prefix of the diagnostic path of every system.
+    pub const SYSTEM_TIME: &'static str = "system_time";
+
+    /// Returns the path of the diagnostic holding the run time of a system.
+    pub fn system_path(schedule: InternedScheduleLabel, system: &str) -> DiagnosticPath {
+        DiagnosticPath::new(format!("{}/{schedule:?}/{system}", Self::SYSTEM_TIME))
+    }
+
+    /// Returns the paths of up to `count` systems with the highest smoothed run time per frame,
+    /// slowest first.
+    pub fn slowest_systems(store: &DiagnosticsStore, count: usize) -> Vec<DiagnosticPath> {
+        let mut systems: Vec<_> = store
+            .iter()
+            .filter(|diagnostic| {
+                diagnostic.is_enabled
+                    && diagnostic.path().components().next() == Some(Self::SYSTEM_TIME)
+            })
+            .filter_map(|diagnostic| Some((diagnostic.path(), diagnostic.smoothed()?)))
+            .collect();
+        systems.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        systems
+            .into_iter()
+            .take(count)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Updates the run time measurements of every system, registering diagnostics for systems
+    /// that ran for the first time.
+    fn diagnostic_system(
+        mut store: ResMut<DiagnosticsStore>,
+        schedule_diagnostics: Res<ScheduleDiagnostics>,
+        config: Res<SystemTimeDiagnosticsConfig>,
+        mut tracked: Local<HashMap<(InternedScheduleLabel, SystemKey), TrackedSystem>>,
+        mut frame_times: Local<HashMap<DiagnosticPath, Duration>>,
+    ) {
+        for (schedule, key, system) in schedule_diagnostics.iter() {
+            if system.run_count == 0 {
+                continue;
+            }
+            let tracked = tracked.entry((schedule, key)).or_insert_with(|| {
+                let path = Self::system_path(schedule, &format!("{}", system.name.shortname()));
+                if store.get(&path).is_none() {
+                    store.add(
+                        Diagnostic::new(path.clone())
+                            .with_suffix("ms")
+                            .with_max_history_length(config.max_history_length)
+                            .with_smoothing_factor(config.smoothing_factor),
+                    );
+                }
+                TrackedSystem {
+                    path,
+                    total_duration: Duration::ZERO,
+                }
+            });
+            // Several systems may share a name, in which case their times add up.
+            *frame_times.entry(tracked.path.clone()).or_default() +=
+                system.total_duration.saturating_sub(tracked.total_duration);
+            tracked.total_duration = system.total_duration;
+        }
+
+        let time = Instant::now();
+        for (path, frame_time) in frame_times.drain() {
+            if let Some(diagnostic) = store.get_mut(&path)
+                && diagnostic.is_enabled
+            {
+                diagnostic.add_measurement(DiagnosticMeasurement {
+                    time,
+                    value: frame_time.as_secs_f64() * 1000.0,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::schedule::ScheduleLabel;
+
+    #[derive(ScheduleLabel, Hash, Debug, PartialEq, Eq, Clone)]
+    struct Busy;
+
+    fn busy_system() {}
+
+    #[test]
+    fn measures_system_time() {
+        let mut app = App::new();
+        app.add_plugins(ScheduleDiagnosticsPlugin::default())
+            .add_schedule(Schedule::new(Busy))
+            .add_systems(Busy, busy_system)
+            .add_systems(Update, |world: &mut World| world.run_schedule(Busy));
+
+        app.update();
+        app.update();
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        let schedule_diagnostic = |schedule: &str| {
+            store
+                .iter()
+                .find(|diagnostic| diagnostic.path().components().nth(1) == Some(schedule))
+                .unwrap()
+        };
+        let busy = schedule_diagnostic("Busy");
+        assert_eq!(
+            busy.path().components().next(),
+            Some(ScheduleDiagnosticsPlugin::SYSTEM_TIME)
+        );
+        assert_eq!(busy.path().components().count(), 3);
+        assert_eq!(busy.history_len(), 2);
+        assert!(busy.value().is_some());
+
+        // The system running the `Busy` schedule is measured as well.
+        let update = schedule_diagnostic("Update");
+        assert_eq!(update.history_len(), 2);
+
+        let slowest = ScheduleDiagnosticsPlugin::slowest_systems(store, usize::MAX);
+        assert!(slowest.contains(busy.path()));
+        assert!(slowest.contains(update.path()));
+        let smoothed: Vec<_> = slowest
+            .iter()
+            .map(|path| store.get(path).unwrap().smoothed().unwrap())
+            .collect();
+        assert!(smoothed.is_sorted_by(|a, b| a >= b));
+        assert_eq!(
+            ScheduleDiagnosticsPlugin::slowest_systems(store, 1),
+            slowest[..1]
+        );
+    }
+}
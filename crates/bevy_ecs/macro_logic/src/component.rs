@@ -43,8 +43,14 @@ pub struct DeriveComponent {
     pub relationship: Option<Relationship>,
     /// The relationship target attribute information.
     pub relationship_target: Option<RelationshipTarget>,
+    /// The many-to-many relationship attribute information.
+    pub many_to_many: Option<ManyToMany>,
+    /// The many-to-many relationship target attribute information.
+    pub many_to_many_target: Option<ManyToManyTarget>,
     /// Whether or not this component is immutable.
     pub immutable: bool,
+    /// Whether or not this component is tracked by a component index.
+    pub index: bool,
     /// The clone behavior for this component.
     pub clone_behavior: Option<Expr>,
     /// The `map_entities` attribute information.
@@ -66,7 +72,10 @@ impl DeriveComponent {
             requires: None,
             relationship: None,
             relationship_target: None,
+            many_to_many: None,
+            many_to_many_target: None,
             immutable: false,
+            index: false,
             clone_behavior: None,
             map_entities: None,
             additional_requires: Vec::new(),
@@ -117,6 +126,9 @@ impl DeriveComponent {
                     } else if nested.path.is_ident(IMMUTABLE) {
                         attrs.immutable = true;
                         Ok(())
+                    } else if nested.path.is_ident(INDEX) {
+                        attrs.index = true;
+                        Ok(())
                     } else if nested.path.is_ident(CLONE_BEHAVIOR) {
                         attrs.clone_behavior = Some(nested.value()?.parse()?);
                         Ok(())
@@ -150,6 +162,12 @@ impl DeriveComponent {
             } else if attr.path().is_ident(RELATIONSHIP_TARGET) {
                 let relationship_target = attr.parse_args::<RelationshipTarget>()?;
                 attrs.relationship_target = Some(relationship_target);
+            } else if attr.path().is_ident(MANY_TO_MANY) {
+                let many_to_many = attr.parse_args::<ManyToMany>()?;
+                attrs.many_to_many = Some(many_to_many);
+            } else if attr.path().is_ident(MANY_TO_MANY_TARGET) {
+                let many_to_many_target = attr.parse_args::<ManyToManyTarget>()?;
+                attrs.many_to_many_target = Some(many_to_many_target);
             }
         }
 
@@ -160,6 +178,30 @@ impl DeriveComponent {
             ));
         }
 
+        if attrs.many_to_many_target.is_some() && attrs.clone_behavior.is_some() {
+            return Err(syn::Error::new(
+                attrs.clone_behavior.span(),
+                "A many-to-many Relationship Target already has its own clone behavior, please remove `clone_behavior = ...`",
+            ));
+        }
+
+        if [
+            attrs.relationship.is_some(),
+            attrs.relationship_target.is_some(),
+            attrs.many_to_many.is_some(),
+            attrs.many_to_many_target.is_some(),
+        ]
+        .into_iter()
+        .filter(|is_some| *is_some)
+        .count()
+            > 1
+        {
+            return Err(syn::Error::new(
+                ast.span(),
+                "A component can only be one of `relationship`, `relationship_target`, `many_to_many` or `many_to_many_target`.",
+            ));
+        }
+
         Ok(attrs)
     }
 
@@ -195,13 +237,21 @@ impl DeriveComponent {
             Ok(value) => value,
             Err(err) => Some(err.into_compile_error()),
         };
+        let many_to_many = match self.derive_many_to_many(ast, bevy_ecs) {
+            Ok(value) => value,
+            Err(err) => Some(err.into_compile_error()),
+        };
+        let many_to_many_target = match self.derive_many_to_many_target(ast, bevy_ecs) {
+            Ok(value) => value,
+            Err(err) => Some(err.into_compile_error()),
+        };
 
         let map_entities = map_entities(
             &ast.data,
             bevy_ecs,
             Ident::new("this", Span::call_site()),
-            relationship.is_some(),
-            relationship_target.is_some(),
+            relationship.is_some() || many_to_many.is_some(),
+            relationship_target.is_some() || many_to_many_target.is_some(),
             self.map_entities,
         )
         .map(|map_entities_impl| {
@@ -233,6 +283,27 @@ impl DeriveComponent {
             on_discard_path
                 .push(quote!(<Self as #bevy_ecs::relationship::Relationship>::on_discard));
         }
+        if many_to_many.is_some() {
+            on_insert_path
+                .push(quote!(<Self as #bevy_ecs::relationship::ManyToManyRelationship>::on_insert));
+            on_discard_path.push(
+                quote!(<Self as #bevy_ecs::relationship::ManyToManyRelationship>::on_discard),
+            );
+        }
+        if let Some(target) = &self.many_to_many_target {
+            on_discard_path.push(
+                quote!(<Self as #bevy_ecs::relationship::ManyToManyRelationshipTarget>::on_discard),
+            );
+            if target.despawn_policy != UNLINK {
+                on_despawn_path.push(
+                    quote!(<Self as #bevy_ecs::relationship::ManyToManyRelationshipTarget>::on_despawn),
+                );
+            }
+        }
+        if self.index {
+            on_insert_path.push(quote!(#bevy_ecs::index::ComponentIndex::<Self>::on_insert));
+            on_discard_path.push(quote!(#bevy_ecs::index::ComponentIndex::<Self>::on_discard));
+        }
         if let Some(target) = self.relationship_target {
             on_discard_path
                 .push(quote!(<Self as #bevy_ecs::relationship::RelationshipTarget>::on_discard));
@@ -285,10 +356,19 @@ impl DeriveComponent {
             }
         });
 
-        let mutable_type = (self.immutable || relationship.is_some())
+        let mutable_type = (self.immutable
+            || self.index
+            || relationship.is_some()
+            || many_to_many.is_some())
             .then_some(quote! { #bevy_ecs::component::Immutable })
             .unwrap_or(quote! { #bevy_ecs::component::Mutable });
 
+        let indexed_component = self.index.then(|| {
+            quote! {
+                impl #impl_generics #bevy_ecs::index::IndexedComponent for #struct_name #type_generics #where_clause {}
+            }
+        });
+
         let clone_behavior = if relationship_target.is_some() || relationship.is_some() {
             quote!(
                 use #bevy_ecs::relationship::{
@@ -297,6 +377,9 @@ impl DeriveComponent {
                     };
                 (&&&&&&&#bevy_ecs::relationship::RelationshipCloneBehaviorSpecialization::<Self>::default()).default_clone_behavior()
             )
+        } else if many_to_many_target.is_some() {
+            // Targets are rebuilt by the hooks of the cloned sources
+            quote!(#bevy_ecs::component::ComponentCloneBehavior::Ignore)
         } else if let Some(behavior) = self.clone_behavior {
             quote!(#bevy_ecs::component::ComponentCloneBehavior::#behavior)
         } else {
@@ -364,9 +447,15 @@ impl DeriveComponent {
                 }
             }
 
+            #indexed_component
+
             #relationship
 
             #relationship_target
+
+            #many_to_many
+
+            #many_to_many_target
         })
     }
     fn derive_relationship(
@@ -492,6 +581,127 @@ impl DeriveComponent {
             }
         }))
     }
+
+    fn derive_many_to_many(&self, ast: &DeriveInput, bevy_ecs: &Path) -> Result<Option<TokenStream>> {
+        let Some(many_to_many) = &self.many_to_many else {
+            return Ok(None);
+        };
+        let Data::Struct(DataStruct {
+            fields,
+            struct_token,
+            ..
+        }) = &ast.data
+        else {
+            return Err(syn::Error::new(
+                ast.span(),
+                "ManyToManyRelationship can only be derived for structs.",
+            ));
+        };
+        let field = relationship_field(fields, "ManyToManyRelationship", struct_token.span())?;
+
+        let collection = &field.ty;
+        let relationship_member = field.ident.clone().map_or(Member::from(0), Member::Named);
+        let members = fields
+            .members()
+            .filter(|member| member != &relationship_member);
+
+        let struct_name = &ast.ident;
+        let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+        let relationship_target = &many_to_many.relationship_target;
+        let allow_self_referential = many_to_many.allow_self_referential;
+        let fqdefault = FQDefault.into_token_stream();
+
+        Ok(Some(quote! {
+            impl #impl_generics #bevy_ecs::relationship::ManyToManyRelationship for #struct_name #type_generics #where_clause {
+                type RelationshipTarget = #relationship_target;
+                type Collection = #collection;
+                const ALLOW_SELF_REFERENTIAL: bool = #allow_self_referential;
+
+                #[inline]
+                fn collection(&self) -> &Self::Collection {
+                    &self.#relationship_member
+                }
+
+                #[inline]
+                fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+                    &mut self.#relationship_member
+                }
+
+                #[inline]
+                fn from_collection(collection: Self::Collection) -> Self {
+                    Self {
+                        #(#members: #fqdefault::default(),)*
+                        #relationship_member: collection
+                    }
+                }
+            }
+        }))
+    }
+
+    fn derive_many_to_many_target(
+        &self,
+        ast: &DeriveInput,
+        bevy_ecs: &Path,
+    ) -> Result<Option<TokenStream>> {
+        let Some(many_to_many_target) = &self.many_to_many_target else {
+            return Ok(None);
+        };
+        let Data::Struct(DataStruct {
+            fields,
+            struct_token,
+            ..
+        }) = &ast.data
+        else {
+            return Err(syn::Error::new(
+                ast.span(),
+                "ManyToManyRelationshipTarget can only be derived for structs.",
+            ));
+        };
+        let field =
+            relationship_field(fields, "ManyToManyRelationshipTarget", struct_token.span())?;
+
+        if field.vis != Visibility::Inherited {
+            return Err(syn::Error::new(field.span(), "The collection in ManyToManyRelationshipTarget must be private to prevent users from directly mutating it, which could invalidate the correctness of relationships."));
+        }
+        let collection = &field.ty;
+        let relationship_member = field.ident.clone().map_or(Member::from(0), Member::Named);
+        let members = fields
+            .members()
+            .filter(|member| member != &relationship_member);
+
+        let relationship = &many_to_many_target.relationship;
+        let despawn_policy = Ident::new(&many_to_many_target.despawn_policy, Span::call_site());
+        let struct_name = &ast.ident;
+        let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+        let fqdefault = FQDefault.into_token_stream();
+        Ok(Some(quote! {
+            impl #impl_generics #bevy_ecs::relationship::ManyToManyRelationshipTarget for #struct_name #type_generics #where_clause {
+                const DESPAWN_POLICY: #bevy_ecs::relationship::ManyToManyDespawnPolicy =
+                    #bevy_ecs::relationship::ManyToManyDespawnPolicy::#despawn_policy;
+                type Relationship = #relationship;
+                type Collection = #collection;
+
+                #[inline]
+                fn collection(&self) -> &Self::Collection {
+                    &self.#relationship_member
+                }
+
+                #[inline]
+                fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+                    &mut self.#relationship_member
+                }
+
+                #[inline]
+                fn from_collection_risky(collection: Self::Collection) -> Self {
+                    Self {
+                        #(#members: #fqdefault::default(),)*
+                        #relationship_member: collection
+                    }
+                }
+            }
+        }))
+    }
 }
 
 const COMPONENT: &str = "component";
@@ -500,6 +710,8 @@ const STORAGE: &str = "storage";
 const REQUIRE: &str = "require";
 const RELATIONSHIP: &str = "relationship";
 const RELATIONSHIP_TARGET: &str = "relationship_target";
+const MANY_TO_MANY: &str = "many_to_many";
+const MANY_TO_MANY_TARGET: &str = "many_to_many_target";
 
 const ON_ADD: &str = "on_add";
 const ON_INSERT: &str = "on_insert";
@@ -508,6 +720,7 @@ const ON_REMOVE: &str = "on_remove";
 const ON_DESPAWN: &str = "on_despawn";
 
 const IMMUTABLE: &str = "immutable";
+const INDEX: &str = "index";
 const CLONE_BEHAVIOR: &str = "clone_behavior";
 
 /// All allowed attribute value expression kinds for component hooks.
@@ -597,6 +810,23 @@ pub struct RelationshipTarget {
     linked_spawn: bool,
 }
 
+/// Derived `#[many_to_many]` attribute information.
+pub struct ManyToMany {
+    relationship_target: Type,
+    allow_self_referential: bool,
+}
+
+/// Derived `#[many_to_many_target]` attribute information.
+pub struct ManyToManyTarget {
+    relationship: Type,
+    despawn_policy: String,
+}
+
+// values for `despawn_policy` attribute
+const UNLINK: &str = "Unlink";
+const DESPAWN_ORPHANS: &str = "DespawnOrphans";
+const DESPAWN_ALL: &str = "DespawnAll";
+
 // values for `storage` attribute
 const TABLE: &str = "Table";
 const SPARSE_SET: &str = "SparseSet";
@@ -702,6 +932,7 @@ mod kw {
     syn::custom_keyword!(relationship);
     syn::custom_keyword!(linked_spawn);
     syn::custom_keyword!(allow_self_referential);
+    syn::custom_keyword!(despawn_policy);
 }
 
 impl Parse for Relationship {
@@ -764,6 +995,57 @@ impl Parse for RelationshipTarget {
     }
 }
 
+impl Parse for ManyToMany {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let Relationship {
+            relationship_target,
+            allow_self_referential,
+        } = input.parse()?;
+        Ok(ManyToMany {
+            relationship_target,
+            allow_self_referential,
+        })
+    }
+}
+
+impl Parse for ManyToManyTarget {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let mut relationship: Option<Type> = None;
+        let mut despawn_policy = UNLINK.to_string();
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::despawn_policy) {
+                input.parse::<kw::despawn_policy>()?;
+                input.parse::<Token![=]>()?;
+                let policy = input.parse::<Ident>()?;
+                despawn_policy = policy.to_string();
+                if ![UNLINK, DESPAWN_ORPHANS, DESPAWN_ALL].contains(&despawn_policy.as_str()) {
+                    return Err(syn::Error::new(
+                        policy.span(),
+                        format!("Invalid despawn policy `{despawn_policy}`, expected '{UNLINK}', '{DESPAWN_ORPHANS}' or '{DESPAWN_ALL}'."),
+                    ));
+                }
+            } else if lookahead.peek(kw::relationship) {
+                input.parse::<kw::relationship>()?;
+                input.parse::<Token![=]>()?;
+                relationship = Some(input.parse()?);
+            } else {
+                return Err(lookahead.error());
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(ManyToManyTarget {
+            relationship: relationship.ok_or_else(|| {
+                syn::Error::new(input.span(), "Missing `relationship = X` attribute")
+            })?,
+            despawn_policy,
+        })
+    }
+}
+
 /// Returns the field with the `#[relationship]` attribute, the only field if unnamed,
 /// or the only field in a [`Fields::Named`] with one field, otherwise `Err`.
 pub(crate) fn relationship_field<'a>(
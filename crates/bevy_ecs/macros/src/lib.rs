@@ -737,6 +737,15 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
 /// struct MyComponent;
 /// ```
 ///
+/// ## Indexing
+/// Track which entities hold each value of this component, so they can be looked up with `QueryByIndex`.
+/// This implies `immutable`, and requires the component to implement `Eq`, `Hash` and `Clone`.
+/// ```ignore
+/// #[derive(Component, PartialEq, Eq, Hash, Clone)]
+/// #[component(index)]
+/// struct MyComponent;
+/// ```
+///
 /// ## Sparse instead of table-based storage
 /// ```ignore
 /// #[derive(Component)]
@@ -799,6 +808,18 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
 /// #[relationship(relationship_target = PeopleILike, allow_self_referential)]
 /// pub struct LikedBy(pub Entity);
 /// ```
+/// Many-to-many relationships, where each source can point to several targets:
+/// ```ignore
+/// #[derive(Component)]
+/// #[many_to_many(relationship_target = ContainedItems)]
+/// pub struct InContainers(Vec<Entity>);
+///
+/// #[derive(Component)]
+/// // `despawn_policy` is one of `Unlink` (the default), `DespawnOrphans` or `DespawnAll`
+/// #[many_to_many_target(relationship = InContainers, despawn_policy = DespawnOrphans)]
+/// pub struct ContainedItems(Vec<Entity>);
+/// ```
+///
 /// ## Warning
 ///
 /// When `allow_self_referential` is enabled, be careful when using recursive traversal methods
@@ -824,7 +845,15 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro_derive(
     Component,
-    attributes(component, require, relationship, relationship_target, entities)
+    attributes(
+        component,
+        require,
+        relationship,
+        relationship_target,
+        many_to_many,
+        many_to_many_target,
+        entities
+    )
 )]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
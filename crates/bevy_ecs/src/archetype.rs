@@ -28,8 +28,11 @@ use crate::{
     query::DebugCheckedUnwrap,
     storage::{ImmutableSparseSet, SparseArray, SparseSet, TableId, TableRow},
 };
-use alloc::{boxed::Box, vec::Vec};
-use bevy_platform::collections::{hash_map::Entry, HashMap};
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use bevy_platform::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
 use core::{
     hash::Hash,
     ops::{Index, IndexMut, RangeFrom},
@@ -371,9 +374,16 @@ bitflags::bitflags! {
         const ON_DISCARD_OBSERVER = (1 << 7);
         const ON_REMOVE_OBSERVER = (1 << 8);
         const ON_DESPAWN_OBSERVER = (1 << 9);
+        const HAS_INVARIANTS = (1 << 10);
     }
 }
 
+/// Keeps an [`Archetype`] recording the entities added to or removed from it.
+///
+/// Returned by [`Archetype::log_moves`].
+#[derive(Clone)]
+pub struct MoveLogGuard(Arc<()>);
+
 /// Metadata for a single archetype within a [`World`].
 ///
 /// For more information, see the *[module level documentation]*.
@@ -385,6 +395,11 @@ pub struct Archetype {
     table_id: TableId,
     edges: Edges,
     entities: Vec<ArchetypeEntity>,
+    version: u64,
+    /// The most recent entities added to or removed from this archetype, the last one being
+    /// recorded at `version`. Only kept while a [`MoveLogGuard`] for this archetype exists.
+    moves: VecDeque<Entity>,
+    move_log_guard: MoveLogGuard,
     components: ImmutableSparseSet<ComponentId, ArchetypeComponentInfo>,
     pub(crate) flags: ArchetypeFlags,
 }
@@ -444,6 +459,9 @@ impl Archetype {
             id,
             table_id,
             entities: Vec::new(),
+            version: 0,
+            moves: VecDeque::new(),
+            move_log_guard: MoveLogGuard(Arc::new(())),
             components: archetype_components.into_immutable(),
             edges: Default::default(),
             flags,
@@ -476,6 +494,58 @@ impl Archetype {
         &self.entities
     }
 
+    /// Returns a counter that increases every time an entity is added to or removed from this archetype.
+    ///
+    /// Comparing it to a previously observed value tells whether [`entities`](Self::entities)
+    /// may have changed since, without scanning them.
+    #[inline]
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Returns the entities that were added to or removed from this archetype since it was at the
+    /// given [`version`](Self::version), oldest first. An entity appears once per move.
+    ///
+    /// Moves are only recorded while a guard returned by [`log_moves`](Self::log_moves) is alive,
+    /// and only the most recent ones are kept, at least as many as there are entities in the
+    /// archetype. Returns `None` if the moves made since `version` are no longer known, in which
+    /// case [`entities`](Self::entities) should be scanned instead.
+    pub fn moved_entities_since(&self, version: u64) -> Option<impl Iterator<Item = Entity> + '_> {
+        let count = usize::try_from(self.version.checked_sub(version)?).ok()?;
+        let start = self.moves.len().checked_sub(count)?;
+        Some(self.moves.range(start..).copied())
+    }
+
+    /// Starts recording the entities added to or removed from this archetype, for
+    /// [`moved_entities_since`](Self::moved_entities_since).
+    ///
+    /// Moves are recorded until all clones of the returned guard are dropped. Only the moves made
+    /// after this call are known, so the versions observed before it should not be used.
+    pub fn log_moves(&self) -> MoveLogGuard {
+        self.move_log_guard.clone()
+    }
+
+    /// Records that `entity` was added to or removed from this archetype.
+    #[inline]
+    fn record_move(&mut self, entity: Entity) {
+        /// The number of moves kept beyond the number of entities in the archetype.
+        const MOVE_LOG_SLACK: usize = 64;
+
+        self.version += 1;
+        if Arc::strong_count(&self.move_log_guard.0) == 1 {
+            // Nobody is reading the moves. Forgetting the old ones makes sure that they aren't
+            // mistaken for the moves made while they weren't recorded.
+            if !self.moves.is_empty() {
+                self.moves = VecDeque::new();
+            }
+            return;
+        }
+        self.moves.push_back(entity);
+        while self.moves.len() > self.entities.len() + MOVE_LOG_SLACK {
+            self.moves.pop_front();
+        }
+    }
+
     /// Fetches the entities contained in this archetype.
     #[inline]
     pub fn entities_with_location(&self) -> impl Iterator<Item = (Entity, EntityLocation)> {
@@ -600,6 +670,7 @@ impl Archetype {
         // SAFETY: An entity can not have multiple archetype rows and there can not be more than u32::MAX entities.
         let archetype_row = unsafe { ArchetypeRow::new(NonMaxU32::new_unchecked(self.len())) };
         self.entities.push(ArchetypeEntity { entity, table_row });
+        self.record_move(entity);
 
         EntityLocation {
             archetype_id: self.id,
@@ -623,6 +694,7 @@ impl Archetype {
     pub(crate) fn swap_remove(&mut self, row: ArchetypeRow) -> ArchetypeSwapRemoveResult {
         let is_last = row.index() == self.entities.len() - 1;
         let entity = self.entities.swap_remove(row.index());
+        self.record_move(entity.entity);
         ArchetypeSwapRemoveResult {
             swapped_entity: if is_last {
                 None
@@ -666,6 +738,10 @@ impl Archetype {
     /// Clears all entities from the archetype.
     pub(crate) fn clear_entities(&mut self) {
         self.entities.clear();
+        // The removed entities are not recorded, so that scanning the now empty archetype is
+        // preferred over replaying its moves.
+        self.version += 1;
+        self.moves.clear();
     }
 
     /// Returns true if any of the components in this archetype have `on_add` hooks
@@ -737,6 +813,13 @@ impl Archetype {
     pub fn has_despawn_observer(&self) -> bool {
         self.flags().contains(ArchetypeFlags::ON_DESPAWN_OBSERVER)
     }
+
+    /// Returns true if any of the components in this archetype are part of an
+    /// [`ArchetypeInvariant`](crate::component::ArchetypeInvariant).
+    #[inline]
+    pub fn has_invariants(&self) -> bool {
+        self.flags().contains(ArchetypeFlags::HAS_INVARIANTS)
+    }
 }
 
 /// The next [`ArchetypeId`] in an [`Archetypes`] collection.
@@ -9,7 +9,7 @@ use crate::{
     },
     bundle::{ArchetypeMoveType, Bundle, BundleId, BundleInfo, DynamicBundle, InsertMode},
     change_detection::{MaybeLocation, Tick},
-    component::{Components, StorageType},
+    component::{check_archetype_invariants, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
     event::EntityComponentsTrigger,
     lifecycle::{Add, Discard, Insert, ADD, DISCARD, INSERT},
@@ -17,7 +17,7 @@ use crate::{
     query::DebugCheckedUnwrap as _,
     relationship::RelationshipHookMode,
     storage::{SparseSets, Storages, Table},
-    world::{unsafe_world_cell::UnsafeWorldCell, World},
+    world::{unsafe_world_cell::UnsafeWorldCell, ChangeJournal, World},
 };
 
 // SAFETY: We have exclusive world access so our pointers can't be invalidated externally
@@ -367,6 +367,16 @@ impl<'w> BundleInserter<'w> {
         // - we run hooks, which can immutably access `Archetypes`, but cannot get a reference to edges through that
         let archetype_after_insert = unsafe { self.archetype_after_insert.as_ref() };
 
+        let replaced = match insert_mode {
+            InsertMode::Replace => archetype_after_insert.existing(),
+            InsertMode::Keep => &[],
+        };
+        // SAFETY: `entity` is in the source archetype, which contains the replaced components,
+        // and there are no outstanding references to the world.
+        let previous = unsafe {
+            ChangeJournal::replaced_values(self.world, entity, self.archetype.as_ref(), replaced)
+        };
+
         let (new_archetype, new_location) = {
             // Non-generic prelude extracted to improve compile time by minimizing monomorphized code.
             // SAFETY: before_insert is functionally part of this function
@@ -406,6 +416,20 @@ impl<'w> BundleInserter<'w> {
             (new_archetype, new_location)
         };
 
+        // SAFETY: `entity` was moved to `new_archetype`, which contains the added and replaced
+        // components, and there are no outstanding mutable references to the world.
+        unsafe {
+            ChangeJournal::record_insert(
+                self.world,
+                entity,
+                new_archetype,
+                archetype_after_insert.added(),
+                replaced,
+                previous,
+                caller,
+            );
+        }
+
         // SAFETY: We have no outstanding mutable references to world as they were dropped
         let deferred_world = unsafe { self.world.into_deferred() };
 
@@ -513,6 +537,14 @@ impl<'w> BundleInserter<'w> {
                 }
             }
         }
+        if new_archetype.has_invariants() {
+            check_archetype_invariants(
+                deferred_world,
+                entity,
+                archetype_after_insert.added(),
+                caller,
+            );
+        }
     }
 
     #[inline]
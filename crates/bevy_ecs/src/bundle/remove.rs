@@ -6,14 +6,14 @@ use crate::{
     archetype::{Archetype, ArchetypeCreated, ArchetypeId, Archetypes},
     bundle::{Bundle, BundleId, BundleInfo},
     change_detection::MaybeLocation,
-    component::{ComponentId, Components, StorageType},
+    component::{check_archetype_invariants, ComponentId, Components, StorageType},
     entity::{Entity, EntityLocation},
     event::EntityComponentsTrigger,
     lifecycle::{Discard, Remove, DISCARD, REMOVE},
     observer::Observers,
     relationship::RelationshipHookMode,
     storage::{SparseSets, Storages, Table, TableId},
-    world::{unsafe_world_cell::UnsafeWorldCell, World},
+    world::{unsafe_world_cell::UnsafeWorldCell, ChangeJournal, World},
 };
 
 // SAFETY:
@@ -136,6 +136,23 @@ impl<'w> BundleRemover<'w> {
             &[ComponentId],
         ) -> (bool, T),
     ) -> (EntityLocation, T) {
+        // SAFETY: `entity` is in the old archetype, and there are no outstanding references to
+        // the world.
+        unsafe {
+            let old_archetype = self.old_archetype.as_ref();
+            ChangeJournal::record_remove(
+                self.world,
+                entity,
+                old_archetype,
+                self.bundle_info
+                    .as_ref()
+                    .iter_explicit_components()
+                    .filter(|&id| old_archetype.contains(id)),
+                false,
+                caller,
+            );
+        }
+
         // Hooks
         // SAFETY: all bundle components exist in World
         unsafe {
@@ -335,6 +352,11 @@ impl<'w> BundleRemover<'w> {
                 .update_existing_location(entity.index(), Some(new_location));
         }
 
+        // SAFETY: pointer valid for 'w, reference life only for this line, no other references into world.archetypes currently exist
+        if unsafe { self.new_archetype.as_ref() }.has_invariants() {
+            check_archetype_invariants(world.into(), entity, &[], caller);
+        }
+
         (new_location, pre_remove_result)
     }
 }
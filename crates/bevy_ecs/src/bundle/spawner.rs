@@ -6,12 +6,13 @@ use crate::{
     archetype::{Archetype, ArchetypeCreated, ArchetypeId, SpawnBundleStatus},
     bundle::{Bundle, BundleId, BundleInfo, DynamicBundle, InsertMode},
     change_detection::{MaybeLocation, Tick},
+    component::check_archetype_invariants,
     entity::{Entity, EntityAllocator, EntityLocation},
     event::EntityComponentsTrigger,
     lifecycle::{Add, Insert, ADD, INSERT},
     relationship::RelationshipHookMode,
     storage::Table,
-    world::{unsafe_world_cell::UnsafeWorldCell, World},
+    world::{unsafe_world_cell::UnsafeWorldCell, ChangeJournal, World},
 };
 
 // SAFETY: We have exclusive world access so our pointers can't be invalidated externally
@@ -142,6 +143,18 @@ impl<'w> BundleSpawner<'w> {
             location
         };
 
+        // SAFETY: The entity was just spawned in `archetype` with the contributed components,
+        // and there are no outstanding references to the world.
+        unsafe {
+            ChangeJournal::record_spawn(
+                self.world,
+                entity,
+                self.archetype.as_ref(),
+                bundle_info.iter_contributed_components(),
+                caller,
+            );
+        }
+
         // SAFETY: We have no outstanding mutable references to world as they were dropped
         let mut deferred_world = unsafe { self.world.into_deferred() };
         // SAFETY: `DeferredWorld` cannot provide mutable access to `Archetypes`.
@@ -189,6 +202,14 @@ impl<'w> BundleSpawner<'w> {
                 );
             }
         };
+        if archetype.has_invariants() {
+            check_archetype_invariants(
+                deferred_world,
+                entity,
+                bundle_info.contributed_components(),
+                caller,
+            );
+        }
 
         location
     }
@@ -15,8 +15,8 @@ use indexmap::IndexSet;
 use crate::{
     archetype::ArchetypeFlags,
     component::{
-        Component, ComponentCloneBehavior, ComponentMutability, QueuedComponents,
-        RequiredComponents, StorageType,
+        ArchetypeInvariant, Component, ComponentCloneBehavior, ComponentMutability,
+        QueuedComponents, RequiredComponents, StorageType,
     },
     lifecycle::ComponentHooks,
     query::DebugCheckedUnwrap as _,
@@ -36,6 +36,8 @@ pub struct ComponentInfo {
     /// The set of components that require this components.
     /// Invariant: components in this set always appear after the components that they require.
     pub(super) required_by: IndexSet<ComponentId, FixedHasher>,
+    /// Whether this component is part of any [`ArchetypeInvariant`](crate::component::ArchetypeInvariant).
+    pub(super) has_invariants: bool,
 }
 
 impl ComponentInfo {
@@ -109,6 +111,7 @@ impl ComponentInfo {
             hooks: Default::default(),
             required_components: Default::default(),
             required_by: Default::default(),
+            has_invariants: false,
         }
     }
 
@@ -130,6 +133,9 @@ impl ComponentInfo {
         if self.hooks().on_despawn.is_some() {
             flags.insert(ArchetypeFlags::ON_DESPAWN_HOOK);
         }
+        if self.has_invariants {
+            flags.insert(ArchetypeFlags::HAS_INVARIANTS);
+        }
     }
 
     /// Provides a reference to the collection of hooks associated with this [`Component`]
@@ -363,6 +369,7 @@ pub struct Components {
     pub(super) indices: TypeIdMap<ComponentId>,
     // This is kept internal and local to verify that no deadlocks can occur.
     pub(super) queued: bevy_platform::sync::RwLock<QueuedComponents>,
+    pub(super) invariants: Vec<ArchetypeInvariant>,
 }
 
 impl Components {
@@ -0,0 +1,479 @@
+use alloc::{format, string::String, vec::Vec};
+use bevy_utils::prelude::DebugName;
+use thiserror::Error;
+
+use crate::{
+    change_detection::MaybeLocation,
+    component::{ComponentId, Components},
+    entity::Entity,
+    error::ErrorContext,
+    world::{DeferredWorld, EntityWorldMut},
+};
+
+/// A rule about which combinations of components an entity may have.
+///
+/// Where [required components](crate::component::Component#required-components) express "`A` implies `B`",
+/// invariants express rules like "`A` and `B` must never coexist" or
+/// "entities with `A` must have exactly one of `B`, `C` or `D`".
+///
+/// Invariants are registered with [`World::register_archetype_invariant`](crate::world::World::register_archetype_invariant),
+/// or its typed shorthands [`World::register_exclusive_components`](crate::world::World::register_exclusive_components)
+/// and [`World::register_exactly_one_component`](crate::world::World::register_exactly_one_component).
+/// They are checked whenever components are inserted into or removed from an entity,
+/// after all hooks and observers for the change have run.
+/// What happens when an invariant is violated is controlled by its [`InvariantViolationBehavior`].
+///
+/// Only archetypes containing at least one of the components involved in an invariant are checked,
+/// so invariants have no cost for unrelated entities.
+/// Entities that already violate an invariant when it is registered are not affected until their components change.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::component::InvariantViolationBehavior;
+/// #[derive(Component)]
+/// struct Grounded;
+///
+/// #[derive(Component)]
+/// struct Airborne;
+///
+/// let mut world = World::new();
+/// // Inserting one of the two states removes the other one.
+/// world.register_exclusive_components::<(Grounded, Airborne)>(InvariantViolationBehavior::AutoRemove);
+///
+/// let player = world.spawn(Grounded).id();
+/// world.entity_mut(player).insert(Airborne);
+/// assert!(!world.entity(player).contains::<Grounded>());
+/// assert!(world.entity(player).contains::<Airborne>());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchetypeInvariant {
+    kind: ArchetypeInvariantKind,
+    components: Vec<ComponentId>,
+    behavior: InvariantViolationBehavior,
+}
+
+/// The rule enforced by an [`ArchetypeInvariant`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArchetypeInvariantKind {
+    /// Entities may have at most one of the components.
+    AtMostOne,
+    /// Entities with the `scope` component must have exactly one of the components.
+    ///
+    /// Entities without the `scope` component may have at most one of the components.
+    ExactlyOne {
+        /// The component that requires exactly one of the components to be present.
+        scope: ComponentId,
+    },
+}
+
+/// What happens when an [`ArchetypeInvariant`] is violated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InvariantViolationBehavior {
+    /// Panic with an [`ArchetypeInvariantError`] describing the violation.
+    #[default]
+    Panic,
+    /// Report an [`ArchetypeInvariantError`] to the [fallback error handler](crate::error::FallbackErrorHandler).
+    ///
+    /// The entity is left as it is.
+    Error,
+    /// Remove the components of the invariant that the entity had before the change,
+    /// keeping the one that was just inserted.
+    ///
+    /// The removal is queued as a command, so it is applied at the next flush,
+    /// which happens right after the change for [`EntityWorldMut`] and command-based changes.
+    ///
+    /// Violations that can't be resolved by removing components, such as inserting several of the
+    /// components at once or removing the only component an [`ArchetypeInvariantKind::ExactlyOne`]
+    /// invariant required, are reported like [`InvariantViolationBehavior::Error`].
+    AutoRemove,
+}
+
+impl ArchetypeInvariant {
+    /// Creates an invariant that allows entities to have at most one of the given `components`.
+    pub fn at_most_one(components: impl IntoIterator<Item = ComponentId>) -> Self {
+        Self {
+            kind: ArchetypeInvariantKind::AtMostOne,
+            components: dedup(components),
+            behavior: InvariantViolationBehavior::default(),
+        }
+    }
+
+    /// Creates an invariant that requires entities with the `scope` component to have exactly one of the given `components`.
+    pub fn exactly_one(
+        scope: ComponentId,
+        components: impl IntoIterator<Item = ComponentId>,
+    ) -> Self {
+        Self {
+            kind: ArchetypeInvariantKind::ExactlyOne { scope },
+            components: dedup(components),
+            behavior: InvariantViolationBehavior::default(),
+        }
+    }
+
+    /// Sets what happens when this invariant is violated.
+    ///
+    /// Defaults to [`InvariantViolationBehavior::Panic`].
+    pub fn with_behavior(mut self, behavior: InvariantViolationBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+
+    /// Returns the rule enforced by this invariant.
+    pub fn kind(&self) -> ArchetypeInvariantKind {
+        self.kind
+    }
+
+    /// Returns the components constrained by this invariant.
+    pub fn components(&self) -> &[ComponentId] {
+        &self.components
+    }
+
+    /// Returns what happens when this invariant is violated.
+    pub fn behavior(&self) -> InvariantViolationBehavior {
+        self.behavior
+    }
+
+    /// Returns every component whose presence affects this invariant.
+    pub(crate) fn participants(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        let scope = match self.kind {
+            ArchetypeInvariantKind::AtMostOne => None,
+            ArchetypeInvariantKind::ExactlyOne { scope } => Some(scope),
+        };
+        self.components.iter().copied().chain(scope)
+    }
+
+    /// Returns `true` if a set of components satisfies this invariant.
+    pub fn is_satisfied_by(&self, contains: impl Fn(ComponentId) -> bool) -> bool {
+        let count = self.components.iter().filter(|id| contains(**id)).count();
+        match self.kind {
+            ArchetypeInvariantKind::ExactlyOne { scope } if contains(scope) => count == 1,
+            _ => count <= 1,
+        }
+    }
+
+    fn error(
+        &self,
+        entity: Entity,
+        present: &[ComponentId],
+        components: &Components,
+    ) -> ArchetypeInvariantError {
+        let name = |id: ComponentId| {
+            components
+                .get_name(id)
+                .unwrap_or_else(|| DebugName::borrowed("<unregistered>"))
+        };
+        match self.kind {
+            ArchetypeInvariantKind::ExactlyOne { scope } if present.is_empty() => {
+                ArchetypeInvariantError::Missing {
+                    entity,
+                    scope: name(scope),
+                    components: self.components.iter().copied().map(name).collect(),
+                }
+            }
+            _ => ArchetypeInvariantError::Conflicting {
+                entity,
+                components: present.iter().copied().map(name).collect(),
+            },
+        }
+    }
+}
+
+fn dedup(components: impl IntoIterator<Item = ComponentId>) -> Vec<ComponentId> {
+    let mut deduped = Vec::new();
+    for id in components {
+        if !deduped.contains(&id) {
+            deduped.push(id);
+        }
+    }
+    deduped
+}
+
+/// An error describing how an entity violated an [`ArchetypeInvariant`].
+#[derive(Error, Debug, Clone)]
+pub enum ArchetypeInvariantError {
+    /// The entity has more than one of the invariant's components.
+    #[error("Entity {entity} has the mutually exclusive components {}", join(.components))]
+    Conflicting {
+        /// The entity that violated the invariant.
+        entity: Entity,
+        /// The names of the conflicting components present on the entity.
+        components: Vec<DebugName>,
+    },
+    /// The entity has the scope component of an [`ArchetypeInvariantKind::ExactlyOne`] invariant,
+    /// but none of its components.
+    #[error("Entity {entity} with {scope} must have exactly one of {}, but has none", join(.components))]
+    Missing {
+        /// The entity that violated the invariant.
+        entity: Entity,
+        /// The name of the scope component.
+        scope: DebugName,
+        /// The names of the components of which exactly one is required.
+        components: Vec<DebugName>,
+    },
+}
+
+fn join(names: &[DebugName]) -> String {
+    names
+        .iter()
+        .map(|name| format!("{name}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Components {
+    /// Returns all [`ArchetypeInvariant`]s registered in this world.
+    pub fn archetype_invariants(&self) -> &[ArchetypeInvariant] {
+        &self.invariants
+    }
+
+    /// Registers an [`ArchetypeInvariant`], marking all of its components as having invariants.
+    ///
+    /// Returns the components whose archetype flags need to be updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the components of the invariant have not been registered.
+    pub(crate) fn register_archetype_invariant(
+        &mut self,
+        invariant: ArchetypeInvariant,
+    ) -> Vec<ComponentId> {
+        let participants = invariant.participants().collect::<Vec<_>>();
+        for &id in &participants {
+            let info = self
+                .components
+                .get_mut(id.index())
+                .and_then(Option::as_mut)
+                .unwrap_or_else(|| {
+                    panic!("Archetype invariants can only be registered for registered components, but {id:?} is not registered")
+                });
+            info.has_invariants = true;
+        }
+        self.invariants.push(invariant);
+        participants
+    }
+}
+
+/// Checks the [`ArchetypeInvariant`]s for the current archetype of `entity`
+/// and handles any violations according to their [`InvariantViolationBehavior`].
+///
+/// `added` are the components that were just added to the entity,
+/// which are kept by [`InvariantViolationBehavior::AutoRemove`].
+pub(crate) fn check_archetype_invariants(
+    mut world: DeferredWorld,
+    entity: Entity,
+    added: &[ComponentId],
+    caller: MaybeLocation,
+) {
+    let Ok(location) = world.entities().get_spawned(entity) else {
+        return;
+    };
+    let archetype = &world.archetypes()[location.archetype_id];
+    if !archetype.has_invariants() {
+        return;
+    }
+
+    let mut to_remove = Vec::new();
+    let mut errors = Vec::new();
+    for invariant in world.components().archetype_invariants() {
+        if invariant.is_satisfied_by(|id| archetype.contains(id)) {
+            continue;
+        }
+        let present = invariant
+            .components()
+            .iter()
+            .copied()
+            .filter(|id| archetype.contains(*id))
+            .collect::<Vec<_>>();
+
+        if invariant.behavior() == InvariantViolationBehavior::AutoRemove && present.len() > 1 {
+            let mut kept = present.iter().filter(|id| added.contains(id));
+            if let (Some(_), None) = (kept.next(), kept.next()) {
+                to_remove.extend(present.iter().filter(|id| !added.contains(id)));
+                continue;
+            }
+        }
+
+        let error = invariant.error(entity, &present, world.components());
+        if invariant.behavior() == InvariantViolationBehavior::Panic {
+            panic!(
+                "{}{error}",
+                caller
+                    .map(|location| format!("{location}: "))
+                    .unwrap_or_default()
+            );
+        }
+        errors.push(error);
+    }
+
+    if !to_remove.is_empty() {
+        world
+            .commands()
+            .entity(entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                entity.remove_by_ids(&to_remove);
+            });
+    }
+
+    if !errors.is_empty() {
+        let handler = world.fallback_error_handler();
+        for error in errors {
+            handler(error.into(), ErrorContext::ArchetypeInvariant { entity });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::{ArchetypeInvariant, InvariantViolationBehavior},
+        error::{ErrorContext, FallbackErrorHandler},
+        prelude::*,
+    };
+    use alloc::{format, string::String, vec::Vec};
+    use std::sync::Mutex;
+
+    #[derive(Component)]
+    struct Grounded;
+
+    #[derive(Component)]
+    struct Airborne;
+
+    #[derive(Component)]
+    struct Swimming;
+
+    #[derive(Component)]
+    struct Character;
+
+    #[derive(Component, Default)]
+    struct Walking;
+
+    #[derive(Component)]
+    #[require(Walking)]
+    struct Legs;
+
+    #[test]
+    #[should_panic = "has the mutually exclusive components"]
+    fn exclusive_components_panic_on_insert() {
+        let mut world = World::new();
+        world.register_exclusive_components::<(Grounded, Airborne)>(
+            InvariantViolationBehavior::Panic,
+        );
+        let entity = world.spawn(Grounded).id();
+        world.entity_mut(entity).insert(Airborne);
+    }
+
+    #[test]
+    #[should_panic = "has the mutually exclusive components"]
+    fn exclusive_components_panic_on_spawn() {
+        let mut world = World::new();
+        world.register_exclusive_components::<(Grounded, Airborne)>(
+            InvariantViolationBehavior::Panic,
+        );
+        world.spawn((Grounded, Airborne));
+    }
+
+    #[test]
+    fn exclusive_components_auto_remove() {
+        let mut world = World::new();
+        world.register_exclusive_components::<(Grounded, Airborne, Swimming)>(
+            InvariantViolationBehavior::AutoRemove,
+        );
+        let entity = world.spawn((Grounded, Character)).id();
+
+        world.entity_mut(entity).insert(Airborne);
+        assert!(!world.entity(entity).contains::<Grounded>());
+        assert!(world.entity(entity).contains::<Airborne>());
+        assert!(world.entity(entity).contains::<Character>());
+
+        world.commands().entity(entity).insert(Swimming);
+        world.flush();
+        assert!(!world.entity(entity).contains::<Airborne>());
+        assert!(world.entity(entity).contains::<Swimming>());
+
+        // Re-inserting an existing component is not a violation
+        world.entity_mut(entity).insert(Swimming);
+        assert!(world.entity(entity).contains::<Swimming>());
+    }
+
+    #[test]
+    fn required_components_are_checked() {
+        let mut world = World::new();
+        world.register_exclusive_components::<(Walking, Swimming)>(
+            InvariantViolationBehavior::AutoRemove,
+        );
+        let entity = world.spawn(Swimming).id();
+        world.entity_mut(entity).insert(Legs);
+        assert!(world.entity(entity).contains::<Walking>());
+        assert!(!world.entity(entity).contains::<Swimming>());
+    }
+
+    static ERRORS: Mutex<Vec<(Entity, String)>> = Mutex::new(Vec::new());
+
+    fn record_error(error: BevyError, context: ErrorContext) {
+        let ErrorContext::ArchetypeInvariant { entity } = context else {
+            panic!("unexpected error context {context:?}");
+        };
+        ERRORS.lock().unwrap().push((entity, format!("{error}")));
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let mut world = World::new();
+        world.insert_resource(FallbackErrorHandler(record_error));
+        world.register_exclusive_components::<(Grounded, Airborne)>(
+            InvariantViolationBehavior::Error,
+        );
+        world.register_exactly_one_component::<Character, (Swimming, Walking)>(
+            InvariantViolationBehavior::AutoRemove,
+        );
+
+        let a = world.spawn((Character, Swimming)).id();
+        assert!(ERRORS.lock().unwrap().is_empty());
+
+        let b = world.spawn((Grounded, Airborne)).id();
+        // `AutoRemove` can't fix a missing component, so this is reported as an error
+        world.entity_mut(a).remove::<Swimming>();
+
+        let errors = core::mem::take(&mut *ERRORS.lock().unwrap());
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, b);
+        assert!(errors[0].1.contains("mutually exclusive"));
+        assert_eq!(errors[1].0, a);
+        assert!(errors[1].1.contains("must have exactly one of"));
+        assert!(world.entity(b).contains::<Grounded>());
+        assert!(world.entity(b).contains::<Airborne>());
+    }
+
+    #[test]
+    #[should_panic = "must have exactly one of"]
+    fn exactly_one_panics_on_remove() {
+        let mut world = World::new();
+        world.register_exactly_one_component::<Character, (Grounded, Airborne)>(
+            InvariantViolationBehavior::Panic,
+        );
+        let entity = world.spawn((Character, Grounded)).id();
+        world.entity_mut(entity).insert(Swimming);
+        world.entity_mut(entity).remove::<Grounded>();
+    }
+
+    #[test]
+    fn exactly_one_without_scope() {
+        let mut world = World::new();
+        let character = world.register_component::<Character>();
+        let grounded = world.register_component::<Grounded>();
+        let airborne = world.register_component::<Airborne>();
+        world.register_archetype_invariant(ArchetypeInvariant::exactly_one(
+            character,
+            [grounded, airborne],
+        ));
+
+        // Entities without the scope component only need at most one of the components
+        let entity = world.spawn(Grounded).id();
+        world.entity_mut(entity).remove::<Grounded>();
+        world.spawn((Character, Airborne)).remove::<Character>();
+
+        assert_eq!(world.components().archetype_invariants().len(), 1);
+    }
+}
@@ -3,12 +3,14 @@
 mod clone;
 mod constants;
 mod info;
+mod invariant;
 mod register;
 mod required;
 
 pub use clone::*;
 pub use constants::*;
 pub use info::*;
+pub use invariant::*;
 pub use register::*;
 pub use required::*;
 
@@ -349,6 +351,13 @@ use core::{fmt::Debug, marker::PhantomData, ops::Deref};
 /// the [`Children`](crate::hierarchy::Children)
 /// [`RelationshipTarget`](crate::relationship::RelationshipTarget).
 ///
+/// # Looking up entities by component value
+///
+/// Components that implement [`Eq`], [`Hash`](core::hash::Hash) and [`Clone`] can be indexed
+/// with `#[component(index)]`, which makes them immutable and keeps track of which entities hold each value.
+/// Entities can then be looked up by value with [`QueryByIndex`](crate::index::QueryByIndex),
+/// see the [`index`](crate::index) module for more details.
+///
 /// # Adding component's hooks
 ///
 /// See [`ComponentHooks`] for a detailed explanation of component's hooks.
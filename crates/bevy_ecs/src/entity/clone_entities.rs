@@ -781,6 +781,84 @@ impl Default for EntityClonerState {
     }
 }
 
+/// Clones individual component values into a [`Bump`] allocator using their [`ComponentCloneFn`],
+/// without writing them to a target entity.
+///
+/// This is used by [`WorldSnapshot`](crate::world::WorldSnapshot) to copy component values into and out of its buffer.
+pub(crate) struct ComponentValueCloner<'a, 'b> {
+    bundle_scratch: BundleScratchSpace<'b>,
+    bundle_scratch_allocator: &'b Bump,
+    allocator: &'a EntityAllocator,
+    state: EntityClonerState,
+    #[cfg(feature = "bevy_reflect")]
+    type_registry: Option<&'a crate::reflect::AppTypeRegistry>,
+}
+
+impl<'a, 'b> ComponentValueCloner<'a, 'b> {
+    pub(crate) fn new(
+        bundle_scratch_allocator: &'b Bump,
+        allocator: &'a EntityAllocator,
+        #[cfg(feature = "bevy_reflect")] type_registry: Option<&'a crate::reflect::AppTypeRegistry>,
+    ) -> Self {
+        Self {
+            bundle_scratch: BundleScratchSpace::with_capacity(1),
+            bundle_scratch_allocator,
+            allocator,
+            state: EntityClonerState::default(),
+            #[cfg(feature = "bevy_reflect")]
+            type_registry,
+        }
+    }
+
+    /// Clones the component value at `source` using `clone_fn`, mapping the entities it references with `mapper`.
+    ///
+    /// Returns `None` if `clone_fn` did not write a value, for example because the component is
+    /// [ignored](ComponentCloneBehavior::Ignore) or because the handler [deferred](ComponentCloneCtx::queue_deferred) the clone.
+    ///
+    /// # Safety
+    /// - `info` must come from the same world as the entity allocator and type registry of this cloner.
+    /// - `source` must point to a valid value of the component described by `info`.
+    pub(crate) unsafe fn clone_value(
+        &mut self,
+        clone_fn: ComponentCloneFn,
+        info: &ComponentInfo,
+        source: Ptr<'_>,
+        entity: Entity,
+        mapper: &mut dyn EntityMapper,
+    ) -> Option<PtrMut<'b>> {
+        let source_component = SourceComponent { ptr: source, info };
+
+        #[cfg(feature = "bevy_reflect")]
+        let type_registry = self.type_registry;
+        #[cfg(not(feature = "bevy_reflect"))]
+        let type_registry = None;
+
+        // SAFETY:
+        // - `info` describes the component `source` points to, as guaranteed by the caller.
+        let mut ctx = unsafe {
+            ComponentCloneCtx::new(
+                info.id(),
+                entity,
+                entity,
+                self.bundle_scratch_allocator,
+                &mut self.bundle_scratch,
+                self.allocator,
+                info,
+                &mut self.state,
+                mapper,
+                type_registry,
+            )
+        };
+        (clone_fn)(&source_component, &mut ctx);
+
+        // Deferred clones need exclusive world access and a target entity, neither of which exist here.
+        self.state.deferred_commands.clear();
+        self.state.clone_queue.clear();
+        self.bundle_scratch.component_ids.clear();
+        self.bundle_scratch.component_ptrs.pop()
+    }
+}
+
 /// A builder for configuring [`EntityCloner`]. See [`EntityCloner`] for more information.
 pub struct EntityClonerBuilder<'w, Filter> {
     world: &'w mut World,
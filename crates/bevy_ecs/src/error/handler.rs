@@ -1,6 +1,9 @@
 use core::fmt::Display;
 
-use crate::{change_detection::Tick, error::BevyError, prelude::Resource};
+use crate::{
+    change_detection::Tick, component::ArchetypeInvariant, entity::Entity, error::BevyError,
+    prelude::Resource,
+};
 use bevy_ecs::error::Severity;
 use bevy_utils::prelude::DebugName;
 use derive_more::derive::{Deref, DerefMut};
@@ -38,6 +41,11 @@ pub enum ErrorContext {
         /// The last tick that the observer was run.
         last_run: Tick,
     },
+    /// An entity violated an [`ArchetypeInvariant`](crate::component::ArchetypeInvariant).
+    ArchetypeInvariant {
+        /// The entity whose components violated the invariant.
+        entity: Entity,
+    },
 }
 
 impl Display for ErrorContext {
@@ -50,6 +58,9 @@ impl Display for ErrorContext {
             Self::Observer { name, .. } => {
                 write!(f, "Observer `{name}` failed")
             }
+            Self::ArchetypeInvariant { entity } => {
+                write!(f, "Archetype invariant violated by entity {entity}")
+            }
             Self::RunCondition {
                 name,
                 system,
@@ -74,6 +85,7 @@ impl ErrorContext {
             | Self::Command { name, .. }
             | Self::Observer { name, .. }
             | Self::RunCondition { name, .. } => name.clone(),
+            Self::ArchetypeInvariant { .. } => DebugName::type_name::<ArchetypeInvariant>(),
         }
     }
 
@@ -86,6 +98,7 @@ impl ErrorContext {
             Self::Command { .. } => "command",
             Self::Observer { .. } => "observer",
             Self::RunCondition { .. } => "run condition",
+            Self::ArchetypeInvariant { .. } => "archetype invariant",
         }
     }
 }
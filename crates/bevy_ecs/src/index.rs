@@ -0,0 +1,447 @@
+//! Indexes that allow looking up entities by the value of one of their components.
+//!
+//! Searching for the entities whose component has a particular value normally requires
+//! iterating over every entity with that component, which becomes expensive as the number
+//! of entities grows.
+//! A [`ComponentIndex`] keeps track of which entities hold each distinct value of an
+//! [`IndexedComponent`], so that these lookups can be answered in `O(1)`.
+//!
+//! Indexes are opt-in. Components are made indexable with the `#[component(index)]` attribute,
+//! which requires the component to implement [`Eq`], [`Hash`] and [`Clone`], and implies
+//! `#[component(immutable)]`: since the only way to change the value of an immutable component
+//! is to re-insert it, the index is kept up to date using the [`Insert`] and [`Discard`] hooks.
+//!
+//! The index is most conveniently accessed through the [`QueryByIndex`] system parameter.
+//!
+//! ```
+//! use bevy_ecs::{index::QueryByIndex, prelude::*};
+//!
+//! #[derive(Component, PartialEq, Eq, Hash, Clone, Copy)]
+//! #[component(index)]
+//! struct TeamId(u32);
+//!
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! fn heal_blue_team(mut query: QueryByIndex<TeamId, &mut Health>) {
+//!     for mut health in query.at_mut(&TeamId(1)) {
+//!         health.0 += 10;
+//!     }
+//! }
+//!
+//! let mut world = World::new();
+//! let red = world.spawn((TeamId(0), Health(50))).id();
+//! let blue = world.spawn((TeamId(1), Health(50))).id();
+//!
+//! world.run_system_once(heal_blue_team).unwrap();
+//! # use bevy_ecs::system::RunSystemOnce;
+//!
+//! assert_eq!(world.get::<Health>(red).unwrap().0, 50);
+//! assert_eq!(world.get::<Health>(blue).unwrap().0, 60);
+//! ```
+//!
+//! Outside of systems, the index can be built with [`ComponentIndex::init`],
+//! and read with [`ComponentIndex::of`].
+//!
+//! ## Lifecycle
+//!
+//! The [`ComponentIndex`] is created lazily: the first [`QueryByIndex`] that is initialized,
+//! or the first insertion of the component, builds it by scanning every entity holding the component.
+//! From then on, the component hooks update it on every insertion, replacement, removal and despawn.
+//!
+//! The index is stored in a private resource, so it can only be read and never modified
+//! or removed from outside of the hooks.
+//!
+//! [`Insert`]: crate::lifecycle::Insert
+//! [`Discard`]: crate::lifecycle::Discard
+
+use core::{any::type_name, hash::Hash};
+
+use bevy_platform::collections::HashMap;
+
+use crate::{
+    change_detection::Tick,
+    component::{Component, ComponentId, Immutable},
+    entity::{hash_set, Entity, EntityHashSet},
+    lifecycle::HookContext,
+    query::{
+        FilteredAccessSet, IterQueryData, QueryData, QueryFilter, QueryManyUniqueIter, QueryState,
+        ReadOnlyQueryData, With,
+    },
+    resource::Resource,
+    system::{
+        Query, ReadOnlySystemParam, Res, SystemMeta, SystemParam, SystemParamValidationError,
+    },
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, FromWorld, World},
+};
+
+/// A [`Component`] whose values are tracked by a [`ComponentIndex`].
+///
+/// This trait is implemented by the `Component` derive when the `#[component(index)]`
+/// attribute is present.
+///
+/// When implementing this trait manually, [`ComponentIndex::on_insert`] and [`ComponentIndex::on_discard`]
+/// must be registered as the component's [`Insert`](crate::lifecycle::Insert) and
+/// [`Discard`](crate::lifecycle::Discard) hooks, or the index will not be kept up to date.
+pub trait IndexedComponent: Component<Mutability = Immutable> + Eq + Hash + Clone {}
+
+/// A map from each distinct value of the [`IndexedComponent`] `C`
+/// to the set of entities that hold it.
+///
+/// See the [module docs](crate::index) for more information.
+pub struct ComponentIndex<C: IndexedComponent> {
+    entities: HashMap<C, EntityHashSet>,
+}
+
+/// The resource holding a [`ComponentIndex`], private so that only the hooks can modify it.
+#[derive(Resource)]
+struct IndexStorage<C: IndexedComponent>(ComponentIndex<C>);
+
+/// The set returned by [`ComponentIndex::get`] for values that no entity holds.
+static EMPTY: EntityHashSet = EntityHashSet::new();
+
+impl<C: IndexedComponent> ComponentIndex<C> {
+    /// Builds the index of `world` if it does not exist yet, and returns it.
+    pub fn init(world: &mut World) -> &Self {
+        world.init_resource::<IndexStorage<C>>();
+        &world.resource::<IndexStorage<C>>().0
+    }
+
+    /// Returns the index of `world`, or `None` if it has not been built yet.
+    #[inline]
+    pub fn of(world: &World) -> Option<&Self> {
+        world
+            .get_resource::<IndexStorage<C>>()
+            .map(|storage| &storage.0)
+    }
+
+    /// Returns the set of entities whose `C` component is equal to `value`.
+    ///
+    /// The set is empty if no entity currently holds this value.
+    #[inline]
+    pub fn get(&self, value: &C) -> &EntityHashSet {
+        self.entities.get(value).unwrap_or(&EMPTY)
+    }
+
+    /// Returns an iterator over the entities whose `C` component is equal to `value`.
+    #[inline]
+    pub fn iter(&self, value: &C) -> hash_set::Iter<'_, Entity> {
+        self.get(value).iter()
+    }
+
+    /// Returns `true` if `entity` holds a `C` component equal to `value`.
+    #[inline]
+    pub fn contains(&self, value: &C, entity: Entity) -> bool {
+        self.get(value).contains(&entity)
+    }
+
+    /// Returns the number of entities whose `C` component is equal to `value`.
+    #[inline]
+    pub fn count(&self, value: &C) -> usize {
+        self.get(value).len()
+    }
+
+    /// Returns an iterator over every distinct value of `C` currently held by at least one entity.
+    pub fn values(&self) -> impl Iterator<Item = &C> {
+        self.entities.keys()
+    }
+
+    /// Returns the number of distinct values of `C` currently held by at least one entity.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity holds a `C` component.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// The [`Insert`](crate::lifecycle::Insert) hook that adds the entity to the index.
+    ///
+    /// Builds the index if it does not exist yet.
+    pub fn on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        let Some(mut storage) = world.get_resource_mut::<IndexStorage<C>>() else {
+            // The scan will pick up this entity once the commands are applied.
+            log::debug!(
+                "Building the ComponentIndex<{}> on the first insertion",
+                type_name::<C>()
+            );
+            world.commands().queue(|world: &mut World| {
+                world.init_resource::<IndexStorage<C>>();
+            });
+            return;
+        };
+        storage.0.entities.entry(value).or_default().insert(entity);
+    }
+
+    /// The [`Discard`](crate::lifecycle::Discard) hook that removes the entity from the index.
+    pub fn on_discard(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        let Some(mut storage) = world.get_resource_mut::<IndexStorage<C>>() else {
+            // Any insertion builds the index, so it can only be missing if it is still being built
+            // or its resource was removed by id.
+            log::warn!(
+                "The ComponentIndex<{}> is missing while {entity} is discarding its value",
+                type_name::<C>()
+            );
+            return;
+        };
+        let index = &mut storage.0;
+        if let Some(entities) = index.entities.get_mut(&value) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                index.entities.remove(&value);
+            }
+        }
+    }
+}
+
+impl<C: IndexedComponent> FromWorld for IndexStorage<C> {
+    /// Builds the index by scanning every entity that holds a `C` component.
+    fn from_world(world: &mut World) -> Self {
+        let mut entities = HashMap::<C, EntityHashSet>::default();
+        let Some(component_id) = world.component_id::<C>() else {
+            return Self(ComponentIndex { entities });
+        };
+        for archetype in world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.contains(component_id))
+        {
+            for archetype_entity in archetype.entities() {
+                let entity = archetype_entity.id();
+                if let Some(value) = world.get::<C>(entity) {
+                    entities.entry(value.clone()).or_default().insert(entity);
+                }
+            }
+        }
+        Self(ComponentIndex { entities })
+    }
+}
+
+/// A [`SystemParam`] that looks up the entities whose [`IndexedComponent`] `C`
+/// is equal to a given value, and fetches their query data `D`.
+///
+/// Only entities that hold a `C` component and match the filter `F` are returned.
+/// Initializing this parameter builds the [`ComponentIndex<C>`] if it does not exist yet.
+///
+/// See the [module docs](crate::index) for an example.
+pub struct QueryByIndex<
+    'w,
+    's,
+    C: IndexedComponent,
+    D: QueryData + 'static,
+    F: QueryFilter + 'static = (),
+> {
+    index: Res<'w, IndexStorage<C>>,
+    query: Query<'w, 's, D, (F, With<C>)>,
+}
+
+impl<'w, 's, C: IndexedComponent, D: QueryData, F: QueryFilter> QueryByIndex<'w, 's, C, D, F> {
+    /// Returns an iterator over the read-only query items of the entities
+    /// whose `C` component is equal to `value`.
+    pub fn at(
+        &self,
+        value: &C,
+    ) -> QueryManyUniqueIter<'_, 's, D::ReadOnly, (F, With<C>), hash_set::Iter<'_, Entity>> {
+        self.query.iter_many_unique(self.index.0.iter(value))
+    }
+
+    /// Returns an iterator over the query items of the entities
+    /// whose `C` component is equal to `value`.
+    pub fn at_mut(
+        &mut self,
+        value: &C,
+    ) -> QueryManyUniqueIter<'_, 's, D, (F, With<C>), hash_set::Iter<'_, Entity>>
+    where
+        D: IterQueryData,
+    {
+        self.query.iter_many_unique_mut(self.index.0.iter(value))
+    }
+
+    /// Returns an iterator over the entities whose `C` component is equal to `value`,
+    /// without checking whether they match the query.
+    pub fn entities(&self, value: &C) -> hash_set::Iter<'_, Entity> {
+        self.index.0.iter(value)
+    }
+
+    /// Returns the underlying [`ComponentIndex`].
+    pub fn index(&self) -> &ComponentIndex<C> {
+        &self.index.0
+    }
+
+    /// Returns the underlying [`Query`], which matches all entities with a `C` component.
+    pub fn query(&self) -> &Query<'w, 's, D, (F, With<C>)> {
+        &self.query
+    }
+
+    /// Returns the underlying [`Query`] mutably, which matches all entities with a `C` component.
+    pub fn query_mut(&mut self) -> &mut Query<'w, 's, D, (F, With<C>)> {
+        &mut self.query
+    }
+}
+
+// SAFETY: Access to the index resource and the query is registered by delegating
+// to the `Res` and `Query` implementations, which panic on conflicting access.
+unsafe impl<C: IndexedComponent, D: QueryData + 'static, F: QueryFilter + 'static> SystemParam
+    for QueryByIndex<'_, '_, C, D, F>
+{
+    type State = (ComponentId, QueryState<D, (F, With<C>)>);
+    type Item<'w, 's> = QueryByIndex<'w, 's, C, D, F>;
+
+    fn init_state(world: &mut World) -> Self::State {
+        world.init_resource::<IndexStorage<C>>();
+        (
+            Res::<IndexStorage<C>>::init_state(world),
+            Query::<D, (F, With<C>)>::init_state(world),
+        )
+    }
+
+    fn init_access(
+        (index_state, query_state): &Self::State,
+        system_meta: &mut SystemMeta,
+        component_access_set: &mut FilteredAccessSet,
+        world: &mut World,
+    ) {
+        Res::<IndexStorage<C>>::init_access(index_state, system_meta, component_access_set, world);
+        Query::init_access(query_state, system_meta, component_access_set, world);
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        (index_state, query_state): &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Result<Self::Item<'w, 's>, SystemParamValidationError> {
+        // SAFETY: Delegate to existing `SystemParam` implementations.
+        let index = unsafe {
+            Res::<IndexStorage<C>>::get_param(index_state, system_meta, world, change_tick)
+        }?;
+        // SAFETY: Delegate to existing `SystemParam` implementations.
+        let query = unsafe { Query::get_param(query_state, system_meta, world, change_tick) }?;
+        Ok(QueryByIndex { index, query })
+    }
+}
+
+// SAFETY: The index resource is only read, and the query is constrained to read-only fetches.
+unsafe impl<C: IndexedComponent, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>
+    ReadOnlySystemParam for QueryByIndex<'_, '_, C, D, F>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::RunSystemOnce;
+    use alloc::vec::Vec;
+
+    #[derive(Component, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+    #[component(index)]
+    struct Team(u32);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Score(u32);
+
+    fn team_members(world: &World, team: u32) -> EntityHashSet {
+        ComponentIndex::<Team>::of(world)
+            .unwrap()
+            .get(&Team(team))
+            .clone()
+    }
+
+    #[test]
+    fn index_is_built_from_existing_entities() {
+        let mut world = World::new();
+        let a = world.spawn(Team(0)).id();
+        let b = world.spawn(Team(1)).id();
+        let c = world.spawn(Team(1)).id();
+
+        assert_eq!(ComponentIndex::<Team>::init(&mut world).len(), 2);
+        assert_eq!(team_members(&world, 0), EntityHashSet::from_iter([a]));
+        assert_eq!(team_members(&world, 1), EntityHashSet::from_iter([b, c]));
+    }
+
+    #[test]
+    fn index_is_built_on_first_insert() {
+        let mut world = World::new();
+        assert!(ComponentIndex::<Team>::of(&world).is_none());
+
+        let a = world.spawn(Team(0)).id();
+        assert_eq!(team_members(&world, 0), EntityHashSet::from_iter([a]));
+    }
+
+    #[test]
+    fn index_tracks_insert_replace_remove_and_despawn() {
+        let mut world = World::new();
+        ComponentIndex::<Team>::init(&mut world);
+
+        let a = world.spawn(Team(0)).id();
+        let b = world.spawn(Team(0)).id();
+        assert_eq!(team_members(&world, 0), EntityHashSet::from_iter([a, b]));
+
+        world.entity_mut(a).insert(Team(1));
+        assert_eq!(team_members(&world, 0), EntityHashSet::from_iter([b]));
+        assert_eq!(team_members(&world, 1), EntityHashSet::from_iter([a]));
+
+        world.entity_mut(b).remove::<Team>();
+        assert!(team_members(&world, 0).is_empty());
+        assert!(!ComponentIndex::<Team>::of(&world)
+            .unwrap()
+            .values()
+            .any(|team| *team == Team(0)));
+
+        world.despawn(a);
+        assert!(ComponentIndex::<Team>::of(&world).unwrap().is_empty());
+    }
+
+    #[test]
+    fn query_by_index() {
+        let mut world = World::new();
+        let a = world.spawn((Team(0), Score(1))).id();
+        world.spawn((Team(1), Score(2)));
+        world.spawn(Team(0));
+
+        world
+            .run_system_once(move |query: QueryByIndex<Team, (Entity, &Score)>| {
+                let matches = query.at(&Team(0)).collect::<Vec<_>>();
+                assert_eq!(matches, [(a, &Score(1))]);
+                assert_eq!(query.entities(&Team(0)).count(), 2);
+                assert!(query.index().contains(&Team(0), a));
+                assert_eq!(query.at(&Team(2)).count(), 0);
+            })
+            .unwrap();
+
+        world
+            .run_system_once(|mut query: QueryByIndex<Team, &mut Score>| {
+                for mut score in query.at_mut(&Team(1)) {
+                    score.0 *= 10;
+                }
+            })
+            .unwrap();
+
+        let mut scores = world
+            .query::<&Score>()
+            .iter(&world)
+            .map(|score| score.0)
+            .collect::<Vec<_>>();
+        scores.sort();
+        assert_eq!(scores, [1, 20]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn query_by_index_conflicts_with_other_queries() {
+        fn system(_: QueryByIndex<Team, &mut Score>, _: Query<&Score>) {}
+
+        let mut world = World::new();
+        world.run_system_once(system).unwrap();
+    }
+}
@@ -36,6 +36,7 @@ pub mod entity_disabling;
 pub mod error;
 pub mod event;
 pub mod hierarchy;
+pub mod index;
 pub mod intern;
 pub mod label;
 pub mod lifecycle;
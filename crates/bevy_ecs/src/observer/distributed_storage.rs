@@ -20,7 +20,9 @@ use crate::{
     lifecycle::{ComponentHook, HookContext},
     observer::{
         condition::{ObserverCondition, ObserverWithCondition, ObserverWithConditionMarker},
-        observer_system_runner, ObserverRunner,
+        observer_system_runner,
+        sticky_events::replay_sticky_events,
+        ObserverRunner,
     },
     prelude::*,
     system::{IntoObserverSystem, ObserverSystem},
@@ -493,6 +495,7 @@ fn hook_on_add<E: Event, B: Bundle, S: ObserverSystem<E, B>>(
         }
 
         world.register_observer(entity);
+        replay_sticky_events(world, entity);
     });
 }
 
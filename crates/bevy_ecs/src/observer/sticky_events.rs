@@ -0,0 +1,443 @@
+//! Retaining [`EntityEvent`]s on their target, to replay them to [`Observer`]s added later.
+//!
+//! Observers run as soon as an event is triggered, so an observer that starts watching an entity
+//! afterwards never sees the events that were triggered for it. [`World::trigger_sticky`] also
+//! retains the event in a [`StickyEvents`] component on the target entity, according to its
+//! [`RetentionPolicy`], and replays it to every entity observer that starts watching the target
+//! later on.
+
+use alloc::{collections::VecDeque, vec::Vec};
+use bevy_platform::collections::HashMap;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::std_traits::ReflectDefault;
+
+use crate::{
+    change_detection::MaybeLocation,
+    event::{EntityEvent, EventKey},
+    observer::{Observer, TriggerContext},
+    prelude::*,
+    world::DeferredWorld,
+};
+
+/// Decides which events a [`StickyEvents`] component retains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Clone, Debug, PartialEq, Default)
+)]
+pub enum RetentionPolicy {
+    /// Retains the last `n` events, and replays them to every observer that starts watching the
+    /// target entity.
+    LastN(usize),
+    /// Retains the events that no entity observer received, until they are replayed to the first
+    /// observer that starts watching the target entity.
+    UntilConsumed,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self::LastN(1)
+    }
+}
+
+/// The events of type `E` retained on their target entity by [`World::trigger_sticky`].
+///
+/// Inserting this component ahead of time configures the [`RetentionPolicy`] of the entity.
+/// Otherwise, the first sticky event inserts it with the default policy, which keeps the last
+/// event.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::observer::{RetentionPolicy, StickyEvents};
+///
+/// #[derive(EntityEvent, Clone)]
+/// struct Connected(Entity);
+///
+/// #[derive(Resource, Default)]
+/// struct Connections(usize);
+///
+/// let mut world = World::new();
+/// world.init_resource::<Connections>();
+/// let player = world
+///     .spawn(StickyEvents::<Connected>::new(RetentionPolicy::UntilConsumed))
+///     .id();
+///
+/// // No observer watches the player yet, so the event is retained.
+/// world.trigger_sticky(Connected(player));
+///
+/// // The event is replayed to the observer as soon as it is added.
+/// world
+///     .entity_mut(player)
+///     .observe(|_: On<Connected>, mut connections: ResMut<Connections>| connections.0 += 1);
+/// world.flush();
+/// assert_eq!(world.resource::<Connections>().0, 1);
+/// assert!(world.get::<StickyEvents<Connected>>(player).unwrap().is_empty());
+/// ```
+#[derive(Component)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Component)
+)]
+pub struct StickyEvents<E: EntityEvent> {
+    policy: RetentionPolicy,
+    /// The retained events, along with the location they were triggered from.
+    events: VecDeque<(E, MaybeLocation)>,
+}
+
+impl<E: EntityEvent> Default for StickyEvents<E> {
+    fn default() -> Self {
+        Self::new(RetentionPolicy::default())
+    }
+}
+
+impl<E: EntityEvent> StickyEvents<E> {
+    /// Creates an empty [`StickyEvents`] with the given retention `policy`.
+    pub fn new(policy: RetentionPolicy) -> Self {
+        Self {
+            policy,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Returns the retention policy.
+    pub fn policy(&self) -> RetentionPolicy {
+        self.policy
+    }
+
+    /// Sets the retention policy, discarding the oldest events if they no longer fit.
+    pub fn set_policy(&mut self, policy: RetentionPolicy) {
+        self.policy = policy;
+        self.truncate();
+    }
+
+    /// Returns an iterator over the retained events, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &E> {
+        self.events.iter().map(|(event, _)| event)
+    }
+
+    /// Returns the number of retained events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no events are retained.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Discards all retained events.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Retains `event`, triggered from `caller`, according to the policy, `delivered` being
+    /// whether an entity observer already received it.
+    fn retain(&mut self, event: E, caller: MaybeLocation, delivered: bool) {
+        if self.policy == RetentionPolicy::UntilConsumed && delivered {
+            return;
+        }
+        self.events.push_back((event, caller));
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        if let RetentionPolicy::LastN(n) = self.policy {
+            let excess = self.events.len().saturating_sub(n);
+            self.events.drain(..excess);
+        }
+    }
+
+    /// Returns the events to replay to a new observer, removing them if they are consumed.
+    fn take_replayed(&mut self) -> Vec<(E, MaybeLocation)>
+    where
+        E: Clone,
+    {
+        match self.policy {
+            RetentionPolicy::LastN(_) => self.events.iter().cloned().collect(),
+            RetentionPolicy::UntilConsumed => self.events.drain(..).collect(),
+        }
+    }
+}
+
+/// Replays the events retained on `target` to `observer`.
+type Replayer = fn(&mut World, observer: Entity, target: Entity);
+
+/// The [`Replayer`]s of every event type that was triggered with [`World::trigger_sticky`].
+#[derive(Resource, Default)]
+struct StickyEventReplayers(HashMap<EventKey, Replayer>);
+
+impl World {
+    /// Triggers the given [`EntityEvent`] like [`World::trigger`], and retains it on its target
+    /// in a [`StickyEvents`] component, so that it is replayed to entity observers that start
+    /// watching the target later on.
+    ///
+    /// Nothing is retained if the target doesn't exist once the observers have run.
+    #[track_caller]
+    pub fn trigger_sticky<E>(&mut self, event: E)
+    where
+        E: EntityEvent + Clone,
+        for<'a> E::Trigger<'a>: Default,
+    {
+        self.trigger_sticky_with_caller(event, MaybeLocation::caller());
+    }
+
+    pub(crate) fn trigger_sticky_with_caller<E>(&mut self, event: E, caller: MaybeLocation)
+    where
+        E: EntityEvent + Clone,
+        for<'a> E::Trigger<'a>: Default,
+    {
+        let target = event.event_target();
+        let event_key = self.register_event_key::<E>();
+        self.get_resource_or_init::<StickyEventReplayers>()
+            .0
+            .insert(event_key, replay::<E>);
+
+        let delivered = self
+            .observers()
+            .try_get_observers(event_key)
+            .and_then(|observers| observers.entity_observers().get(&target))
+            .is_some_and(|observers| !observers.is_empty());
+        let mut triggered = event.clone();
+        self.trigger_ref_with_caller(
+            &mut triggered,
+            &mut <E::Trigger<'_> as Default>::default(),
+            caller,
+        );
+
+        if let Ok(mut target) = self.get_entity_mut(target) {
+            target
+                .entry::<StickyEvents<E>>()
+                .or_default()
+                .into_mut()
+                .retain(event, caller, delivered);
+        }
+    }
+}
+
+/// Replays the events retained on the entities `observer` watches, if it watches events that were
+/// triggered with [`World::trigger_sticky`].
+pub(super) fn replay_sticky_events(world: &mut World, observer: Entity) {
+    let (Some(replayers), Some(state)) = (
+        world.get_resource::<StickyEventReplayers>(),
+        world.get::<Observer>(observer),
+    ) else {
+        return;
+    };
+    let descriptor = state.descriptor();
+    let replays: Vec<_> = descriptor
+        .event_keys()
+        .iter()
+        .filter_map(|event_key| replayers.0.get(event_key))
+        .flat_map(|replay| {
+            descriptor
+                .entities()
+                .iter()
+                .map(move |target| (*replay, *target))
+        })
+        .collect();
+    for (replay, target) in replays {
+        replay(world, observer, target);
+    }
+}
+
+fn replay<E>(world: &mut World, observer: Entity, target: Entity)
+where
+    E: EntityEvent + Clone,
+    for<'a> E::Trigger<'a>: Default,
+{
+    let Some(mut sticky) = world.get_mut::<StickyEvents<E>>(target) else {
+        return;
+    };
+    let events = sticky.take_replayed();
+    let Some(runner) = world.get::<Observer>(observer).map(|state| state.runner) else {
+        return;
+    };
+    let event_key = world.register_event_key::<E>();
+
+    let mut world = DeferredWorld::from(world);
+    for (mut event, caller) in events {
+        // Replayed events report the location they were originally triggered from.
+        let context = TriggerContext { event_key, caller };
+        let mut trigger = <E::Trigger<'_> as Default>::default();
+        // SAFETY:
+        // - `observer` watches `E`, since it was returned by `replayers` for its event key
+        // - the event and trigger pointers come from `E` and `E::Trigger`
+        // - `context.event_key` was registered for `E`
+        // - no other references to the world are alive
+        unsafe {
+            world.as_unsafe_world_cell().increment_trigger_id();
+            (runner)(
+                world.reborrow(),
+                observer,
+                &context,
+                (&mut event).into(),
+                (&mut trigger).into(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::{RetentionPolicy, StickyEvents};
+    use crate::{change_detection::MaybeLocation, prelude::*};
+
+    #[derive(EntityEvent, Clone)]
+    struct Ping {
+        entity: Entity,
+        value: u32,
+    }
+
+    #[derive(Resource, Default)]
+    struct Received(Vec<u32>);
+
+    fn record(ping: On<Ping>, mut received: ResMut<Received>) {
+        received.0.push(ping.value);
+    }
+
+    fn received(world: &World) -> &[u32] {
+        &world.resource::<Received>().0
+    }
+
+    #[test]
+    fn replays_last_n() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        let entity = world
+            .spawn(StickyEvents::<Ping>::new(RetentionPolicy::LastN(2)))
+            .id();
+        for value in 0..3 {
+            world.trigger_sticky(Ping { entity, value });
+        }
+
+        world.entity_mut(entity).observe(record);
+        world.flush();
+        assert_eq!(received(&world), [1, 2]);
+
+        // Every new observer gets the events, and new events are retained too.
+        world.trigger_sticky(Ping { entity, value: 3 });
+        assert_eq!(received(&world), [1, 2, 3]);
+        world.entity_mut(entity).observe(record);
+        world.flush();
+        assert_eq!(received(&world), [1, 2, 3, 2, 3]);
+    }
+
+    #[test]
+    fn replays_until_consumed() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        let entity = world
+            .spawn(StickyEvents::<Ping>::new(RetentionPolicy::UntilConsumed))
+            .id();
+        world.trigger_sticky(Ping { entity, value: 0 });
+        world.trigger_sticky(Ping { entity, value: 1 });
+
+        world.entity_mut(entity).observe(record);
+        world.flush();
+        assert_eq!(received(&world), [0, 1]);
+        assert!(world.get::<StickyEvents<Ping>>(entity).unwrap().is_empty());
+
+        // Events received by an observer are not retained.
+        world.trigger_sticky(Ping { entity, value: 2 });
+        assert!(world.get::<StickyEvents<Ping>>(entity).unwrap().is_empty());
+        world.entity_mut(entity).observe(record);
+        world.flush();
+        assert_eq!(received(&world), [0, 1, 2]);
+    }
+
+    #[test]
+    fn default_policy_keeps_last_event() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        let entity = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+
+        world.commands().trigger_sticky(Ping { entity, value: 0 });
+        world.commands().trigger_sticky(Ping { entity, value: 1 });
+        world.flush();
+        let sticky = world.get::<StickyEvents<Ping>>(entity).unwrap();
+        assert_eq!(sticky.policy(), RetentionPolicy::LastN(1));
+        assert_eq!(
+            sticky.iter().map(|ping| ping.value).collect::<Vec<_>>(),
+            vec![1]
+        );
+
+        // Observers of other entities are not replayed the events.
+        world.entity_mut(other).observe(record);
+        world.flush();
+        assert!(received(&world).is_empty());
+
+        world.spawn(Observer::new(record).with_entity(entity));
+        world.flush();
+        assert_eq!(received(&world), [1]);
+    }
+
+    #[test]
+    fn set_policy_truncates() {
+        let mut world = World::new();
+        let entity = world
+            .spawn(StickyEvents::<Ping>::new(RetentionPolicy::UntilConsumed))
+            .id();
+        for value in 0..4 {
+            world.trigger_sticky(Ping { entity, value });
+        }
+
+        let mut sticky = world.get_mut::<StickyEvents<Ping>>(entity).unwrap();
+        assert_eq!(sticky.len(), 4);
+        sticky.set_policy(RetentionPolicy::LastN(1));
+        assert_eq!(sticky.iter().next().unwrap().value, 3);
+    }
+
+    #[test]
+    fn replays_original_caller() {
+        #[derive(Resource, Default)]
+        struct Callers(Vec<MaybeLocation>);
+
+        fn record_caller(ping: On<Ping>, mut callers: ResMut<Callers>) {
+            callers.0.push(ping.caller());
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Callers>();
+        world.add_observer(record_caller);
+        let entity = world.spawn_empty().id();
+        world.trigger_sticky(Ping { entity, value: 0 });
+
+        world.entity_mut(entity).observe(record_caller);
+        world.flush();
+        let callers = &world.resource::<Callers>().0;
+        assert_eq!(callers.len(), 2);
+        assert_eq!(callers[0], callers[1]);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn reflect_sticky_events() {
+        use bevy_reflect::{PartialReflect, Reflect, ReflectRef};
+
+        #[derive(EntityEvent, Reflect, Clone)]
+        struct Tagged(Entity);
+
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        world.trigger_sticky(Tagged(entity));
+
+        let sticky = world.get::<StickyEvents<Tagged>>(entity).unwrap();
+        let ReflectRef::Struct(sticky) = sticky.reflect_ref() else {
+            panic!("expected a struct");
+        };
+        assert!(sticky
+            .field("policy")
+            .unwrap()
+            .reflect_partial_eq(&RetentionPolicy::LastN(1))
+            .unwrap());
+        let ReflectRef::List(events) = sticky.field("events").unwrap().reflect_ref() else {
+            panic!("expected a list");
+        };
+        assert_eq!(events.len(), 1);
+    }
+}
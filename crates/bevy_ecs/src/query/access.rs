@@ -937,6 +937,13 @@ impl AccessFilters {
 /// It stores multiple sets of accesses.
 /// - A "combined" set, which is the access of all filters in this set combined.
 /// - The set of access of each individual filters in this set.
+///
+/// Queries that access entities other than the one being fetched,
+/// such as [`RelatedData`](super::RelatedData) or [`Related`](super::Related),
+/// add a separate [`FilteredAccess`] for that access using the filters of the nested query.
+/// That access is checked against every other access in the set,
+/// including the access of the query it is nested in, since the filters of the outer query
+/// say nothing about which entities the nested query may reach.
 #[derive(Debug, PartialEq, Eq, Default)]
 pub struct FilteredAccessSet {
     combined_access: Access,
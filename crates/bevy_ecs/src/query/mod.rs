@@ -13,6 +13,7 @@ mod fetch;
 mod filter;
 mod iter;
 mod par_iter;
+mod related;
 mod state;
 mod world_query;
 
@@ -25,6 +26,7 @@ pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
 pub use par_iter::*;
+pub use related::*;
 pub use state::*;
 pub use world_query::*;
 
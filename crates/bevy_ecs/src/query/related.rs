@@ -0,0 +1,487 @@
+use crate::{
+    archetype::Archetype,
+    change_detection::Tick,
+    component::{ComponentId, Components},
+    entity::Entity,
+    query::{
+        EcsAccessType, FilteredAccess, FilteredAccessSet, IterQueryData, NestedQuery, QueryData,
+        QueryFilter, ReadOnlyQueryData, ReleaseStateQueryData, WorldQuery,
+    },
+    relationship::Relationship,
+    storage::{Table, TableRow},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+use core::marker::PhantomData;
+
+/// Filter that selects entities whose [`Relationship`] `R` targets an entity matching the filter `F`.
+///
+/// Entities without the relationship `R` never match.
+///
+/// This is the single-query equivalent of reading `R` and checking a second `Query<(), F>`
+/// for its target. The filter `F` is checked against the target entity,
+/// so any component access it performs is registered for all entities it could match,
+/// and conflicts with other system parameters are detected by the scheduler as usual.
+///
+/// Unlike [`With`](crate::query::With) and [`Without`](crate::query::Without),
+/// this filter is not archetypal.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::Related;
+/// #
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// #
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// #
+/// // Damages every entity that is a child of an enemy.
+/// fn damage_enemy_children(mut query: Query<&mut Health, Related<ChildOf, With<Enemy>>>) {
+///     for mut health in &mut query {
+///         health.0 = health.0.saturating_sub(1);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(damage_enemy_children);
+/// ```
+pub struct Related<R: Relationship, F: QueryFilter + 'static = ()>(PhantomData<(R, F)>);
+
+/// The [`WorldQuery`] that [`Related`] delegates to.
+type RelatedInner<R, F> = (&'static R, NestedQuery<(), F>);
+
+// SAFETY:
+// All methods delegate to `RelatedInner`, which reads `R` on the current entity
+// and registers the access of the nested query in `init_nested_access`.
+unsafe impl<R: Relationship, F: QueryFilter + 'static> WorldQuery for Related<R, F> {
+    type Fetch<'w> = <RelatedInner<R, F> as WorldQuery>::Fetch<'w>;
+    type State = <RelatedInner<R, F> as WorldQuery>::State;
+
+    fn shrink_fetch<'wlong: 'wshort, 'wshort>(fetch: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {
+        <RelatedInner<R, F> as WorldQuery>::shrink_fetch(fetch)
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w, 's>(
+        world: UnsafeWorldCell<'w>,
+        state: &'s Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe { <RelatedInner<R, F> as WorldQuery>::init_fetch(world, state, last_run, this_run) }
+    }
+
+    const IS_DENSE: bool = <RelatedInner<R, F> as WorldQuery>::IS_DENSE;
+
+    #[inline]
+    unsafe fn set_archetype<'w, 's>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &'s Self::State,
+        archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe {
+            <RelatedInner<R, F> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+        }
+    }
+
+    #[inline]
+    unsafe fn set_table<'w, 's>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &'s Self::State,
+        table: &'w Table,
+    ) {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe { <RelatedInner<R, F> as WorldQuery>::set_table(fetch, state, table) }
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess) {
+        <RelatedInner<R, F> as WorldQuery>::update_component_access(state, access);
+    }
+
+    fn init_nested_access(
+        state: &Self::State,
+        system_name: Option<&str>,
+        component_access_set: &mut FilteredAccessSet,
+        world: UnsafeWorldCell,
+    ) {
+        <RelatedInner<R, F> as WorldQuery>::init_nested_access(
+            state,
+            system_name,
+            component_access_set,
+            world,
+        );
+    }
+
+    fn init_state(world: &mut World) -> Self::State {
+        <RelatedInner<R, F> as WorldQuery>::init_state(world)
+    }
+
+    fn get_state(components: &Components) -> Option<Self::State> {
+        <RelatedInner<R, F> as WorldQuery>::get_state(components)
+    }
+
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        <RelatedInner<R, F> as WorldQuery>::matches_component_set(state, set_contains_id)
+    }
+
+    fn update_archetypes(state: &mut Self::State, world: UnsafeWorldCell) {
+        <RelatedInner<R, F> as WorldQuery>::update_archetypes(state, world);
+    }
+}
+
+// SAFETY: `filter_fetch` only reads `R` and performs read-only access through the nested query.
+unsafe impl<R: Relationship, F: QueryFilter + 'static> QueryFilter for Related<R, F> {
+    const IS_ARCHETYPAL: bool = false;
+
+    #[inline(always)]
+    unsafe fn filter_fetch(
+        state: &Self::State,
+        fetch: &mut Self::Fetch<'_>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> bool {
+        // SAFETY: The invariants are upheld by the caller.
+        let Some((relationship, targets)) =
+            (unsafe { <RelatedInner<R, F> as QueryData>::fetch(state, fetch, entity, table_row) })
+        else {
+            return false;
+        };
+        targets.contains(relationship.get())
+    }
+}
+
+/// Fetches the query data `D` from the target of the [`Relationship`] `R`,
+/// optionally requiring the target to match the filter `F`.
+///
+/// Entities without the relationship `R`, or whose target does not match `(D, F)`, are skipped.
+/// Wrap this in an [`Option`] to keep them.
+///
+/// The data is fetched in the same pass as the rest of the query, without a separate [`Query`](crate::system::Query) parameter.
+/// Its access is registered for all entities `(D, F)` could match,
+/// so it will conflict with queries that mutably access the same components,
+/// unless their filters are disjoint from `F`.
+/// For that reason, `D` must be [`ReadOnlyQueryData`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::RelatedData;
+/// #
+/// # #[derive(Component)]
+/// # struct Position(f32);
+/// #
+/// # #[derive(Component)]
+/// # struct Offset(f32);
+/// #
+/// // Places every child with an `Offset` relative to its parent.
+/// // The `Without<Offset>` filter on the parent keeps the read of its `Position`
+/// // disjoint from the mutable access to the child's `Position`.
+/// fn follow_parent(
+///     mut children: Query<(&mut Position, &Offset, RelatedData<ChildOf, &Position, Without<Offset>>)>,
+/// ) {
+///     for (mut position, offset, parent_position) in &mut children {
+///         position.0 = parent_position.0 + offset.0;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(follow_parent);
+/// ```
+///
+/// Without such a filter, the system would panic when it is initialized, reporting the conflicting access.
+pub struct RelatedData<
+    R: Relationship,
+    D: ReadOnlyQueryData + 'static,
+    F: QueryFilter + 'static = (),
+>(PhantomData<(R, D, F)>);
+
+/// The [`QueryData`] that [`RelatedData`] delegates to.
+type RelatedDataInner<R, D, F> = (&'static R, NestedQuery<D, F>);
+
+// SAFETY:
+// All methods delegate to `RelatedDataInner`, which reads `R` on the current entity
+// and registers the access of the nested query in `init_nested_access`.
+unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> WorldQuery
+    for RelatedData<R, D, F>
+{
+    type Fetch<'w> = <RelatedDataInner<R, D, F> as WorldQuery>::Fetch<'w>;
+    type State = <RelatedDataInner<R, D, F> as WorldQuery>::State;
+
+    fn shrink_fetch<'wlong: 'wshort, 'wshort>(fetch: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {
+        <RelatedDataInner<R, D, F> as WorldQuery>::shrink_fetch(fetch)
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w, 's>(
+        world: UnsafeWorldCell<'w>,
+        state: &'s Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe {
+            <RelatedDataInner<R, D, F> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+        }
+    }
+
+    const IS_DENSE: bool = <RelatedDataInner<R, D, F> as WorldQuery>::IS_DENSE;
+
+    #[inline]
+    unsafe fn set_archetype<'w, 's>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &'s Self::State,
+        archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe {
+            <RelatedDataInner<R, D, F> as WorldQuery>::set_archetype(
+                fetch, state, archetype, table,
+            );
+        }
+    }
+
+    #[inline]
+    unsafe fn set_table<'w, 's>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &'s Self::State,
+        table: &'w Table,
+    ) {
+        // SAFETY: The invariants are upheld by the caller.
+        unsafe { <RelatedDataInner<R, D, F> as WorldQuery>::set_table(fetch, state, table) }
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess) {
+        <RelatedDataInner<R, D, F> as WorldQuery>::update_component_access(state, access);
+    }
+
+    fn init_nested_access(
+        state: &Self::State,
+        system_name: Option<&str>,
+        component_access_set: &mut FilteredAccessSet,
+        world: UnsafeWorldCell,
+    ) {
+        <RelatedDataInner<R, D, F> as WorldQuery>::init_nested_access(
+            state,
+            system_name,
+            component_access_set,
+            world,
+        );
+    }
+
+    fn init_state(world: &mut World) -> Self::State {
+        <RelatedDataInner<R, D, F> as WorldQuery>::init_state(world)
+    }
+
+    fn get_state(components: &Components) -> Option<Self::State> {
+        <RelatedDataInner<R, D, F> as WorldQuery>::get_state(components)
+    }
+
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        <RelatedDataInner<R, D, F> as WorldQuery>::matches_component_set(state, set_contains_id)
+    }
+
+    fn update_archetypes(state: &mut Self::State, world: UnsafeWorldCell) {
+        <RelatedDataInner<R, D, F> as WorldQuery>::update_archetypes(state, world);
+    }
+}
+
+// SAFETY:
+// `Self::ReadOnly` is `Self`, and all access is read-only.
+// `IS_READ_ONLY` is delegated to `RelatedDataInner`, which is read-only.
+unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> QueryData
+    for RelatedData<R, D, F>
+{
+    const IS_READ_ONLY: bool = <RelatedDataInner<R, D, F> as QueryData>::IS_READ_ONLY;
+    // `fetch` returns `None` if the target does not match the nested query.
+    const IS_ARCHETYPAL: bool = false;
+    type ReadOnly = Self;
+    type Item<'w, 's> = D::Item<'w, 's>;
+
+    fn shrink<'wlong: 'wshort, 'wshort, 's>(
+        item: Self::Item<'wlong, 's>,
+    ) -> Self::Item<'wshort, 's> {
+        D::shrink(item)
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w, 's>(
+        state: &'s Self::State,
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Option<Self::Item<'w, 's>> {
+        // SAFETY: The invariants are upheld by the caller.
+        let (relationship, targets) = unsafe {
+            <RelatedDataInner<R, D, F> as QueryData>::fetch(state, fetch, entity, table_row)
+        }?;
+        // We need to use `_inner` methods to return the full `'w` lifetime.
+        targets.get_inner(relationship.get()).ok()
+    }
+
+    fn iter_access(state: &Self::State) -> impl Iterator<Item = EcsAccessType<'_>> {
+        <RelatedDataInner<R, D, F> as QueryData>::iter_access(state)
+    }
+}
+
+// SAFETY: All access is through `&R` and `D`, which are read-only.
+unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>
+    ReadOnlyQueryData for RelatedData<R, D, F>
+{
+}
+
+// SAFETY: All access to other entities is through `D`, which is read-only and does not conflict.
+unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> IterQueryData
+    for RelatedData<R, D, F>
+{
+}
+
+impl<
+        R: Relationship,
+        D: ReadOnlyQueryData + ReleaseStateQueryData + 'static,
+        F: QueryFilter + 'static,
+    > ReleaseStateQueryData for RelatedData<R, D, F>
+{
+    fn release_state<'w>(item: Self::Item<'w, '_>) -> Self::Item<'w, 'static> {
+        D::release_state(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::Component,
+        hierarchy::ChildOf,
+        prelude::*,
+        query::{Related, RelatedData},
+        system::RunSystemOnce,
+    };
+    use alloc::vec::Vec;
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[derive(Component, PartialEq, Debug, Clone, Copy)]
+    struct Value(u32);
+
+    #[test]
+    fn related_filter() {
+        let mut world = World::new();
+        let enemy = world.spawn(Enemy).id();
+        let friend = world.spawn_empty().id();
+        let a = world.spawn((Value(1), ChildOf(enemy))).id();
+        world.spawn((Value(2), ChildOf(friend)));
+        world.spawn(Value(3));
+
+        let mut query = world.query_filtered::<Entity, Related<ChildOf, With<Enemy>>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [a]);
+
+        let mut query = world.query_filtered::<Entity, Related<ChildOf>>();
+        assert_eq!(query.iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn related_filter_sees_new_archetypes() {
+        let mut world = World::new();
+        let mut query = world.query_filtered::<Entity, Related<ChildOf, With<Enemy>>>();
+
+        let enemy = world.spawn(Enemy).id();
+        let a = world.spawn(ChildOf(enemy)).id();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [a]);
+
+        world.entity_mut(enemy).remove::<Enemy>();
+        assert_eq!(query.iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn related_data() {
+        let mut world = World::new();
+        let parent = world.spawn(Value(10)).id();
+        let frozen = world.spawn((Value(20), Enemy)).id();
+        let a = world.spawn(ChildOf(parent)).id();
+        let b = world.spawn(ChildOf(frozen)).id();
+        let empty = world.spawn_empty().id();
+        let c = world.spawn(ChildOf(empty)).id();
+
+        let mut query = world.query::<RelatedData<ChildOf, &Value>>();
+        assert_eq!(query.get(&world, a).unwrap(), &Value(10));
+        assert_eq!(query.get(&world, b).unwrap(), &Value(20));
+        assert!(query.get(&world, c).is_err());
+        assert_eq!(query.iter(&world).count(), 2);
+
+        let mut query = world.query::<(Entity, RelatedData<ChildOf, &Value, Without<Enemy>>)>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [(a, &Value(10))]);
+
+        let mut query = world.query::<(Entity, Option<RelatedData<ChildOf, &Value>>)>();
+        assert_eq!(query.get(&world, c).unwrap(), (c, None));
+    }
+
+    #[test]
+    fn related_data_in_system() {
+        let mut world = World::new();
+        let parent = world.spawn(Value(3)).id();
+        let child = world.spawn((Value(0), ChildOf(parent), Enemy)).id();
+
+        world
+            .run_system_once(
+                |mut query: Query<&mut Value, With<Enemy>>,
+                 parents: Query<RelatedData<ChildOf, &Value, Without<Enemy>>, With<Enemy>>| {
+                    let parent_values = parents.iter().copied().collect::<Vec<_>>();
+                    for (mut value, parent_value) in query.iter_mut().zip(parent_values) {
+                        value.0 = parent_value.0 * 2;
+                    }
+                },
+            )
+            .unwrap();
+        assert_eq!(world.get::<Value>(child), Some(&Value(6)));
+    }
+
+    #[test]
+    #[should_panic = "error[B0001]"]
+    fn related_data_conflicts_with_main_query() {
+        fn sys(_: Query<(&mut Value, RelatedData<ChildOf, &Value>)>) {}
+
+        let mut world = World::new();
+        world.run_system_once(sys).unwrap();
+    }
+
+    #[test]
+    #[should_panic = "error[B0001]"]
+    fn related_data_conflicts_with_other_query() {
+        fn sys(_: Query<&mut Value, With<Enemy>>, _: Query<RelatedData<ChildOf, &Value>>) {}
+
+        let mut world = World::new();
+        world.run_system_once(sys).unwrap();
+    }
+
+    #[test]
+    #[should_panic = "error[B0001]"]
+    fn related_filter_conflicts_with_other_query() {
+        fn sys(_: Query<&mut Value>, _: Query<(), Related<ChildOf, Changed<Value>>>) {}
+
+        let mut world = World::new();
+        world.run_system_once(sys).unwrap();
+    }
+
+    #[test]
+    fn related_terms_with_disjoint_filters_do_not_conflict() {
+        fn sys(
+            _: Query<&mut Value, With<Enemy>>,
+            _: Query<RelatedData<ChildOf, &Value, Without<Enemy>>>,
+            _: Query<&mut Enemy>,
+            _: Query<&Value, (Without<Enemy>, Related<ChildOf, With<Enemy>>)>,
+        ) {
+        }
+
+        let mut world = World::new();
+        world.run_system_once(sys).unwrap();
+    }
+}
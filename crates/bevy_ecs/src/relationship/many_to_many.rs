@@ -0,0 +1,710 @@
+use alloc::{format, vec::Vec};
+
+use bevy_utils::prelude::DebugName;
+use log::warn;
+
+use crate::{
+    change_detection::MaybeLocation,
+    component::{Component, Mutable},
+    entity::Entity,
+    lifecycle::HookContext,
+    relationship::{RelationshipHookMode, RelationshipSourceCollection},
+    system::EntityCommand,
+    world::{DeferredWorld, EntityWorldMut},
+};
+
+/// A [`Component`] on a "source" [`Entity`] that references any number of "target" entities, creating a
+/// many-to-many relationship between them. Every [`ManyToManyRelationship`] has a corresponding
+/// [`ManyToManyRelationshipTarget`] type (and vice-versa), which exists on the "target" entities and contains
+/// the list of all "source" entities that relate to them.
+///
+/// Unlike a [`Relationship`](super::Relationship), which points to at most one target,
+/// a [`ManyToManyRelationship`] stores a collection of targets. This makes it possible to model
+/// an item stored in several containers, or a node with multiple parents in a directed acyclic graph.
+///
+/// The [`ManyToManyRelationship`] component is the "source of truth" and the [`ManyToManyRelationshipTarget`]
+/// component reflects that source of truth. Both collections are kept in sync by component hooks:
+/// when the [`ManyToManyRelationship`] is inserted, the source entity is added to the [`ManyToManyRelationshipTarget`]
+/// of every target (inserting it if it does not exist yet), and when it is removed, the source is removed from them again.
+/// Removing or despawning a target removes it from the collection of every source that points to it.
+///
+/// Empty collections are never kept around: a target whose last source is removed loses its
+/// [`ManyToManyRelationshipTarget`], and a source whose last target is removed loses its [`ManyToManyRelationship`].
+///
+/// [`ManyToManyRelationship`] and [`ManyToManyRelationshipTarget`] should always be derived via the [`Component`] trait
+/// to ensure the hooks are set up properly. The derived [`ManyToManyRelationship`] is immutable: use the
+/// [`add_related_targets`](EntityWorldMut::add_related_targets) and [`remove_related_targets`](EntityWorldMut::remove_related_targets)
+/// methods (or their target-side counterparts) to edit it.
+///
+/// ## Derive
+///
+/// Both components must be structs with a single field, or with one field annotated with `#[relationship]`,
+/// that stores a [`RelationshipSourceCollection`] holding multiple entities.
+/// If there are additional fields, they must all implement [`Default`].
+/// Like [`RelationshipTarget`](super::RelationshipTarget), the [`ManyToManyRelationshipTarget`] collection must be private.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::ManyToManyRelationshipTarget;
+/// #[derive(Component)]
+/// #[many_to_many(relationship_target = ContainedItems)]
+/// pub struct InContainers(Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[many_to_many_target(relationship = InContainers)]
+/// pub struct ContainedItems(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// let chest = world.spawn_empty().id();
+/// let backpack = world.spawn_empty().id();
+/// let rope = world.spawn(InContainers(vec![chest, backpack])).id();
+///
+/// assert_eq!(world.get::<ContainedItems>(chest).unwrap().collection(), &vec![rope]);
+/// assert_eq!(world.get::<ContainedItems>(backpack).unwrap().collection(), &vec![rope]);
+/// ```
+///
+/// The `despawn_policy` attribute of the target controls what happens to the sources when a target is despawned,
+/// see [`ManyToManyDespawnPolicy`]:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// #[many_to_many(relationship_target = Children)]
+/// pub struct Parents(Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[many_to_many_target(relationship = Parents, despawn_policy = DespawnOrphans)]
+/// pub struct Children(Vec<Entity>);
+/// ```
+///
+/// By default, a many-to-many relationship cannot point to its own entity.
+/// This can be allowed with `#[many_to_many(relationship_target = ..., allow_self_referential)]`.
+pub trait ManyToManyRelationship: Component + Sized {
+    /// The [`Component`] added to the "target" entities of this [`ManyToManyRelationship`], which contains the list
+    /// of all "source" entities that relate to the "target".
+    type RelationshipTarget: ManyToManyRelationshipTarget<Relationship = Self>;
+
+    /// The collection type that stores the "target" entities of this [`ManyToManyRelationship`].
+    type Collection: RelationshipSourceCollection;
+
+    /// If `true`, a relationship is allowed to point to its own entity.
+    const ALLOW_SELF_REFERENTIAL: bool = false;
+
+    /// Returns a reference to the stored [`ManyToManyRelationship::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`ManyToManyRelationship::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    /// If this method is used, then the hooks [`on_discard`](ManyToManyRelationship::on_discard) have to
+    /// run before and [`on_insert`](ManyToManyRelationship::on_insert) after it.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates this [`ManyToManyRelationship`] from the given [`ManyToManyRelationship::Collection`] of targets.
+    fn from_collection(collection: Self::Collection) -> Self;
+
+    /// Creates this [`ManyToManyRelationship`] from the given target entities.
+    fn from_targets(targets: impl IntoIterator<Item = Entity>) -> Self {
+        let mut collection = Self::Collection::new();
+        for target in targets {
+            add_unique(&mut collection, target);
+        }
+        Self::from_collection(collection)
+    }
+
+    /// Iterates the target entities stored in this relationship.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of target entities stored in this relationship.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this relationship does not point to any target.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+
+    /// The `on_insert` component hook that maintains the [`ManyToManyRelationship`] / [`ManyToManyRelationshipTarget`] connection.
+    fn on_insert(
+        world: DeferredWorld,
+        HookContext {
+            entity,
+            caller,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        if let RelationshipHookMode::Skip = relationship_hook_mode {
+            return;
+        }
+        let targets: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        link_targets::<Self>(world, entity, &targets, caller);
+    }
+
+    /// The `on_discard` component hook that maintains the [`ManyToManyRelationship`] / [`ManyToManyRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_discard(
+        world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        if let RelationshipHookMode::Skip = relationship_hook_mode {
+            return;
+        }
+        let targets: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        unlink_targets::<Self>(world, entity, &targets);
+    }
+}
+
+/// A [`Component`] containing the collection of entities that relate to this [`Entity`] via the associated
+/// [`ManyToManyRelationship`] type. See the [`ManyToManyRelationship`] documentation for more information.
+pub trait ManyToManyRelationshipTarget: Component<Mutability = Mutable> + Sized {
+    /// What happens to the source entities when this target is despawned.
+    ///
+    /// This defaults to [`ManyToManyDespawnPolicy::Unlink`] when derived.
+    const DESPAWN_POLICY: ManyToManyDespawnPolicy;
+
+    /// The [`ManyToManyRelationship`] that populates this [`ManyToManyRelationshipTarget`] collection.
+    type Relationship: ManyToManyRelationship<RelationshipTarget = Self>;
+
+    /// The collection type that stores the "source" entities for this [`ManyToManyRelationshipTarget`] component.
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`ManyToManyRelationshipTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`ManyToManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyToManyRelationshipTarget`] from the given [`ManyToManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as constructing the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// The `on_discard` component hook that removes this target from the collection of all of its sources.
+    // note: think of this as "on_drop"
+    fn on_discard(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        if let RelationshipHookMode::Skip = relationship_hook_mode {
+            return;
+        }
+        let (entities, mut commands) = world.entities_and_commands();
+        let relationship_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source_entity in relationship_target.iter() {
+            commands
+                .entity(source_entity)
+                .queue_silenced(remove_targets_unchecked::<Self::Relationship>(alloc::vec![
+                    entity
+                ]));
+        }
+    }
+
+    /// The `on_despawn` component hook that applies the [`DESPAWN_POLICY`](Self::DESPAWN_POLICY)
+    /// to the sources of this target.
+    fn on_despawn(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let despawned_target = entity;
+        let (entities, mut commands) = world.entities_and_commands();
+        let relationship_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        match Self::DESPAWN_POLICY {
+            ManyToManyDespawnPolicy::Unlink => {}
+            ManyToManyDespawnPolicy::DespawnAll => {
+                for source_entity in relationship_target.iter() {
+                    commands.entity(source_entity).try_despawn();
+                }
+            }
+            ManyToManyDespawnPolicy::DespawnOrphans => {
+                for source_entity in relationship_target.iter() {
+                    // The despawned target is still listed here, as `on_despawn` runs before `on_discard`
+                    let command = move |entity: EntityWorldMut| {
+                        let is_orphan = entity.get::<Self::Relationship>().is_none_or(|targets| {
+                            targets.iter().all(|target| {
+                                target == despawned_target
+                                    || target == source_entity
+                                    || entity.world().get_entity(target).is_err()
+                            })
+                        });
+                        if is_orphan {
+                            entity.despawn();
+                        }
+                    };
+                    commands.entity(source_entity).queue_silenced(command);
+                }
+            }
+        }
+    }
+
+    /// Creates this [`ManyToManyRelationshipTarget`] with the given pre-allocated entity capacity.
+    fn with_capacity(capacity: usize) -> Self {
+        let collection =
+            <Self::Collection as RelationshipSourceCollection>::with_capacity(capacity);
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the source entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of source entities in this collection.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this collection is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+}
+
+/// Describes what happens to the sources of a [`ManyToManyRelationshipTarget`] when the target is despawned.
+///
+/// Regardless of the policy, the despawned target is always removed from the collection of its sources.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ManyToManyDespawnPolicy {
+    /// The sources are only unlinked from the despawned target.
+    #[default]
+    Unlink,
+    /// Sources that are not related to any other (living) target are despawned.
+    ///
+    /// This is useful for containers or graphs where nodes are shared between several owners,
+    /// and should only be cleaned up once the last owner is gone.
+    DespawnOrphans,
+    /// All sources are despawned, even if they are still related to other targets.
+    DespawnAll,
+}
+
+/// Adds `entity` to the [`ManyToManyRelationshipTarget`] of each of the given `targets`,
+/// and removes any invalid or duplicate targets from the [`ManyToManyRelationship`] of `entity`.
+pub(crate) fn link_targets<R: ManyToManyRelationship>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    targets: &[Entity],
+    caller: MaybeLocation,
+) {
+    let mut invalid_targets = Vec::new();
+    for (index, &target) in targets.iter().enumerate() {
+        if targets[..index].contains(&target) {
+            // Duplicates are dropped, keeping the first occurrence
+            invalid_targets.push(target);
+            continue;
+        }
+        if !R::ALLOW_SELF_REFERENTIAL && target == entity {
+            warn!(
+                "{}The {} relationship on entity {entity:?} points to itself. The invalid target has been removed.\nIf this is intended behavior self-referential relations can be enabled with the allow_self_referential attribute: #[many_to_many(allow_self_referential)]",
+                caller.map(|location| format!("{location}: ")).unwrap_or_default(),
+                DebugName::type_name::<R>(),
+            );
+            invalid_targets.push(target);
+            continue;
+        }
+
+        // Targets that can only store a single source must drop their current one first
+        let current_source_to_remove = world
+            .get_entity(target)
+            .ok()
+            .and_then(|target_entity_ref| target_entity_ref.get::<R::RelationshipTarget>())
+            .and_then(|relationship_target| {
+                relationship_target
+                    .collection()
+                    .source_to_remove_before_add()
+            })
+            .filter(|current_source| *current_source != entity);
+        if let Some(current_source) = current_source_to_remove {
+            world
+                .commands()
+                .entity(current_source)
+                .queue_silenced(remove_targets_unchecked::<R>(alloc::vec![target]));
+        }
+
+        if let Ok(mut entity_commands) = world.commands().get_entity(target) {
+            // Deferring is necessary for batch mode
+            entity_commands
+                .entry::<R::RelationshipTarget>()
+                .and_modify(move |mut relationship_target| {
+                    add_unique(relationship_target.collection_mut_risky(), entity);
+                })
+                .or_insert_with(move || {
+                    let mut relationship_target = R::RelationshipTarget::with_capacity(1);
+                    relationship_target.collection_mut_risky().add(entity);
+                    relationship_target
+                });
+        } else {
+            warn!(
+                "{}The {} relationship on entity {entity:?} relates to an entity {target:?} that does not exist. The invalid target has been removed.",
+                caller.map(|location| format!("{location}: ")).unwrap_or_default(),
+                DebugName::type_name::<R>(),
+            );
+            invalid_targets.push(target);
+        }
+    }
+
+    if !invalid_targets.is_empty() {
+        world
+            .commands()
+            .entity(entity)
+            .queue_silenced(remove_targets_unchecked::<R>(invalid_targets));
+    }
+}
+
+/// Removes `entity` from the [`ManyToManyRelationshipTarget`] of each of the given `targets`.
+pub(crate) fn unlink_targets<R: ManyToManyRelationship>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    targets: &[Entity],
+) {
+    for &target in targets {
+        if let Ok(mut target_entity_mut) = world.get_entity_mut(target)
+            && let Some(mut relationship_target) =
+                target_entity_mut.get_mut::<R::RelationshipTarget>()
+        {
+            relationship_target.collection_mut_risky().remove(entity);
+            if relationship_target.is_empty() {
+                let command = |mut entity: EntityWorldMut| {
+                    // This must check emptiness again, as the source may have been re-added in the meantime.
+                    if entity
+                        .get::<R::RelationshipTarget>()
+                        .is_some_and(ManyToManyRelationshipTarget::is_empty)
+                    {
+                        entity.remove::<R::RelationshipTarget>();
+                    }
+                };
+
+                world.commands().queue_silenced(command.with_entity(target));
+            }
+        }
+    }
+}
+
+/// Adds `entity` to `collection` if it does not contain it already.
+pub(crate) fn add_unique<C: RelationshipSourceCollection>(collection: &mut C, entity: Entity) {
+    if !collection.iter().any(|existing| existing == entity) {
+        collection.add(entity);
+    }
+}
+
+/// Removes `targets` from the [`ManyToManyRelationship`] of an entity without running its hooks,
+/// and removes the relationship entirely if it becomes empty.
+///
+/// This is used to update the source of truth when the target side of the relationship changes.
+fn remove_targets_unchecked<R: ManyToManyRelationship>(targets: Vec<Entity>) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        let id = entity.id();
+        entity.world_scope(|world| {
+            _ = DeferredWorld::from(world).modify_component_with_relationship_hook_mode::<R, _>(
+                id,
+                RelationshipHookMode::Skip,
+                |relationship| {
+                    let collection = relationship.collection_mut_risky();
+                    for target in &targets {
+                        collection.remove(*target);
+                    }
+                },
+            );
+        });
+        if entity
+            .get::<R>()
+            .is_some_and(ManyToManyRelationship::is_empty)
+        {
+            entity.remove::<R>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entity::Entity,
+        prelude::*,
+        relationship::{ManyToManyRelationship, ManyToManyRelationshipTarget},
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component)]
+    #[many_to_many(relationship_target = Items)]
+    struct InContainers(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many_target(relationship = InContainers)]
+    struct Items(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many(relationship_target = DagChildren)]
+    struct DagParents(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many_target(relationship = DagParents, despawn_policy = DespawnOrphans)]
+    struct DagChildren(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many(relationship_target = OwnedParts)]
+    struct Owners(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many_target(relationship = Owners, despawn_policy = DespawnAll)]
+    struct OwnedParts(Vec<Entity>);
+
+    fn items(world: &World, container: Entity) -> Vec<Entity> {
+        world
+            .get::<Items>(container)
+            .map(|items| items.iter().collect())
+            .unwrap_or_default()
+    }
+
+    fn containers(world: &World, item: Entity) -> Vec<Entity> {
+        world
+            .get::<InContainers>(item)
+            .map(|containers| containers.iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn many_to_many_insert_and_remove() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(InContainers(vec![a, b])).id();
+        let y = world.spawn(InContainers(vec![a])).id();
+
+        assert_eq!(items(&world, a), [x, y]);
+        assert_eq!(items(&world, b), [x]);
+
+        world.entity_mut(x).remove::<InContainers>();
+        assert_eq!(items(&world, a), [y]);
+        assert!(!world.entity(b).contains::<Items>());
+
+        world.entity_mut(y).insert(InContainers(vec![b]));
+        assert!(!world.entity(a).contains::<Items>());
+        assert_eq!(items(&world, b), [y]);
+    }
+
+    #[test]
+    fn many_to_many_despawn_target_unlinks_sources() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(InContainers(vec![a, b])).id();
+        let y = world.spawn(InContainers(vec![a])).id();
+
+        world.despawn(a);
+        assert_eq!(containers(&world, x), [b]);
+        assert!(world.get_entity(y).is_ok());
+        assert!(!world.entity(y).contains::<InContainers>());
+        assert_eq!(items(&world, b), [x]);
+
+        world.entity_mut(b).remove::<Items>();
+        assert!(!world.entity(x).contains::<InContainers>());
+    }
+
+    #[test]
+    fn many_to_many_despawn_source_unlinks_targets() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(InContainers(vec![a, b])).id();
+        let y = world.spawn(InContainers(vec![b])).id();
+
+        world.despawn(x);
+        assert!(!world.entity(a).contains::<Items>());
+        assert_eq!(items(&world, b), [y]);
+    }
+
+    #[test]
+    fn many_to_many_invalid_targets_are_removed() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+
+        let x = world.spawn_empty().id();
+        world
+            .entity_mut(x)
+            .insert(InContainers(vec![a, missing, x, a]));
+        world.flush();
+
+        assert_eq!(containers(&world, x), [a]);
+        assert_eq!(items(&world, a), [x]);
+        assert!(!world.entity(x).contains::<Items>());
+    }
+
+    #[test]
+    fn many_to_many_despawn_orphans() {
+        let mut world = World::new();
+        let root_a = world.spawn_empty().id();
+        let root_b = world.spawn_empty().id();
+        let shared = world.spawn(DagParents(vec![root_a, root_b])).id();
+        let only_a = world.spawn(DagParents(vec![root_a])).id();
+
+        world.despawn(root_a);
+        assert!(world.get_entity(only_a).is_err());
+        assert!(world.get_entity(shared).is_ok());
+        assert_eq!(
+            world
+                .get::<DagParents>(shared)
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            [root_b]
+        );
+
+        world.despawn(root_b);
+        assert!(world.get_entity(shared).is_err());
+    }
+
+    #[test]
+    fn many_to_many_despawn_all() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let part = world.spawn(Owners(vec![a, b])).id();
+
+        world.despawn(a);
+        assert!(world.get_entity(part).is_err());
+        assert!(!world.entity(b).contains::<OwnedParts>());
+    }
+
+    #[test]
+    fn many_to_many_traversal_visits_shared_entities_once() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let a = world.spawn(DagParents(vec![root])).id();
+        let b = world.spawn(DagParents(vec![root])).id();
+        let shared = world.spawn(DagParents(vec![a, b])).id();
+        let leaf = world.spawn(DagParents(vec![shared])).id();
+
+        let mut children = world.query::<&DagChildren>();
+        let children = children.query(&world);
+        assert_eq!(
+            children.iter_many_descendants(root).collect::<Vec<_>>(),
+            [a, b, shared, leaf]
+        );
+        assert_eq!(
+            children.iter_many_descendants(a).collect::<Vec<_>>(),
+            [shared, leaf]
+        );
+        assert_eq!(children.iter_many_descendants(leaf).count(), 0);
+
+        let mut parents = world.query::<&DagParents>();
+        let parents = parents.query(&world);
+        assert_eq!(
+            parents.iter_many_ancestors(leaf).collect::<Vec<_>>(),
+            [shared, a, b, root]
+        );
+        assert_eq!(parents.iter_many_ancestors(root).count(), 0);
+    }
+
+    #[test]
+    fn many_to_many_traversal_terminates_on_cycles() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(DagParents(vec![a])).id();
+        let c = world.spawn(DagParents(vec![b])).id();
+        world.entity_mut(a).insert(DagParents(vec![c]));
+
+        let mut children = world.query::<&DagChildren>();
+        let children = children.query(&world);
+        assert_eq!(
+            children.iter_many_descendants(a).collect::<Vec<_>>(),
+            [b, c]
+        );
+
+        let mut parents = world.query::<&DagParents>();
+        let parents = parents.query(&world);
+        assert_eq!(parents.iter_many_ancestors(a).collect::<Vec<_>>(), [c, b]);
+    }
+
+    #[test]
+    fn many_to_many_related_methods() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn_empty().id();
+        let y = world.spawn_empty().id();
+
+        world
+            .entity_mut(x)
+            .add_related_targets::<InContainers>(&[a, b, a]);
+        assert_eq!(containers(&world, x), [a, b]);
+        assert_eq!(items(&world, a), [x]);
+
+        world
+            .entity_mut(a)
+            .add_related_many::<InContainers>(&[y, x]);
+        assert_eq!(items(&world, a), [x, y]);
+        assert_eq!(containers(&world, y), [a]);
+
+        world
+            .entity_mut(x)
+            .remove_related_targets::<InContainers>(&[a]);
+        assert_eq!(containers(&world, x), [b]);
+        assert_eq!(items(&world, a), [y]);
+
+        world
+            .entity_mut(b)
+            .remove_related_many::<InContainers>(&[x]);
+        assert!(!world.entity(x).contains::<InContainers>());
+        assert!(!world.entity(b).contains::<Items>());
+
+        world
+            .entity_mut(y)
+            .add_related_targets::<InContainers>(&[b]);
+        world
+            .entity_mut(a)
+            .detach_all_related_many::<InContainers>();
+        assert_eq!(containers(&world, y), [b]);
+
+        world.entity_mut(b).despawn_related_many::<InContainers>();
+        assert!(world.get_entity(y).is_err());
+        assert!(world.get_entity(b).is_ok());
+    }
+
+    #[test]
+    fn many_to_many_commands() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn_empty().id();
+
+        let mut commands = world.commands();
+        commands.entity(x).add_related_targets::<InContainers>(&[a]);
+        commands.entity(b).add_related_many::<InContainers>(&[x]);
+        world.flush();
+
+        assert_eq!(containers(&world, x), [a, b]);
+        assert_eq!(items(&world, b), [x]);
+
+        let mut commands = world.commands();
+        commands
+            .entity(x)
+            .remove_related_targets::<InContainers>(&[b]);
+        commands.entity(a).remove_related_many::<InContainers>(&[x]);
+        world.flush();
+
+        assert!(!world.entity(x).contains::<InContainers>());
+        assert!(!world.entity(a).contains::<Items>());
+    }
+}
@@ -1,14 +1,18 @@
 use crate::{
-    bundle::Bundle,
+    bundle::{Bundle, InsertMode},
+    change_detection::MaybeLocation,
     entity::{hash_set::EntityHashSet, Entity},
     prelude::Children,
     relationship::{
-        Relationship, RelationshipHookMode, RelationshipSourceCollection, RelationshipTarget,
+        many_to_many::{self, add_unique},
+        ManyToManyRelationship, ManyToManyRelationshipTarget, Relationship, RelationshipHookMode,
+        RelationshipSourceCollection, RelationshipTarget,
     },
     system::{Commands, EntityCommands},
     world::{DeferredWorld, EntityWorldMut, World},
 };
 use bevy_platform::prelude::{Box, Vec};
+use bevy_ptr::move_as_ptr;
 use core::{marker::PhantomData, mem};
 
 use super::OrderedRelationshipSourceCollection;
@@ -296,6 +300,136 @@ impl<'w> EntityWorldMut<'w> {
         self.add_related::<R>(&[entity])
     }
 
+    /// Relates this entity to the given `targets` with the [`ManyToManyRelationship`] `R`,
+    /// keeping any targets it is already related to.
+    ///
+    /// See [`add_related_many`](Self::add_related_many) to relate sources to this entity instead.
+    #[track_caller]
+    pub fn add_related_targets<R: ManyToManyRelationship>(
+        &mut self,
+        targets: &[Entity],
+    ) -> &mut Self {
+        self.add_related_targets_with_caller::<R>(targets, MaybeLocation::caller())
+    }
+
+    pub(crate) fn add_related_targets_with_caller<R: ManyToManyRelationship>(
+        &mut self,
+        targets: &[Entity],
+        caller: MaybeLocation,
+    ) -> &mut Self {
+        if !self.contains::<R>() {
+            let relationship = R::from_targets(targets.iter().copied());
+            move_as_ptr!(relationship);
+            return self.insert_with_caller(
+                relationship,
+                InsertMode::Replace,
+                caller,
+                RelationshipHookMode::Run,
+            );
+        }
+        let current = self.get::<R>().unwrap();
+        let mut added = Vec::with_capacity(targets.len());
+        for target in targets {
+            if !added.contains(target) && !current.iter().any(|current| current == *target) {
+                added.push(*target);
+            }
+        }
+        self.modify_many_to_many::<R>(&added, &[], caller)
+    }
+
+    /// Removes the given `targets` from this entity's [`ManyToManyRelationship`] `R`.
+    ///
+    /// If no targets remain, `R` is removed from this entity.
+    #[track_caller]
+    pub fn remove_related_targets<R: ManyToManyRelationship>(
+        &mut self,
+        targets: &[Entity],
+    ) -> &mut Self {
+        if !self.contains::<R>() {
+            return self;
+        }
+        let current = self.get::<R>().unwrap();
+        let mut removed = Vec::with_capacity(targets.len());
+        for target in targets {
+            if !removed.contains(target) && current.iter().any(|current| current == *target) {
+                removed.push(*target);
+            }
+        }
+        self.modify_many_to_many::<R>(&[], &removed, MaybeLocation::caller());
+        if self
+            .get::<R>()
+            .is_some_and(ManyToManyRelationship::is_empty)
+        {
+            self.remove::<R>();
+        }
+        self
+    }
+
+    /// Relates the given `sources` to this entity with the [`ManyToManyRelationship`] `R`,
+    /// keeping any other targets they are already related to.
+    ///
+    /// See [`add_related_targets`](Self::add_related_targets) to relate this entity to targets instead.
+    #[track_caller]
+    pub fn add_related_many<R: ManyToManyRelationship>(&mut self, sources: &[Entity]) -> &mut Self {
+        self.add_related_many_with_caller::<R>(sources, MaybeLocation::caller())
+    }
+
+    pub(crate) fn add_related_many_with_caller<R: ManyToManyRelationship>(
+        &mut self,
+        sources: &[Entity],
+        caller: MaybeLocation,
+    ) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for source in sources {
+                world
+                    .entity_mut(*source)
+                    .add_related_targets_with_caller::<R>(&[id], caller);
+            }
+        });
+        self
+    }
+
+    /// Removes the [`ManyToManyRelationship`] `R` between the given `sources` and this entity.
+    pub fn remove_related_many<R: ManyToManyRelationship>(
+        &mut self,
+        sources: &[Entity],
+    ) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for source in sources {
+                if let Ok(mut source) = world.get_entity_mut(*source) {
+                    source.remove_related_targets::<R>(&[id]);
+                }
+            }
+        });
+        self
+    }
+
+    /// Removes the [`ManyToManyRelationship`] `R` between this entity and all of its sources.
+    pub fn detach_all_related_many<R: ManyToManyRelationship>(&mut self) -> &mut Self {
+        self.remove::<R::RelationshipTarget>()
+    }
+
+    /// Despawns entities that relate to this one via the given [`ManyToManyRelationship`],
+    /// even if they are also related to other entities.
+    /// This entity will not be despawned.
+    pub fn despawn_related_many<R: ManyToManyRelationship>(&mut self) -> &mut Self {
+        if let Some(sources) = self.get::<R::RelationshipTarget>() {
+            // We have to collect here to defer removal, allowing observers and hooks to see this data
+            // before it is finally removed.
+            let sources = sources.iter().collect::<Vec<_>>();
+            self.world_scope(|world| {
+                for entity in sources {
+                    if let Ok(entity_mut) = world.get_entity_mut(entity) {
+                        entity_mut.despawn();
+                    };
+                }
+            });
+        }
+        self
+    }
+
     /// Despawns entities that relate to this one via the given [`RelationshipTarget`].
     /// This entity will not be despawned.
     pub fn despawn_related<S: RelationshipTarget>(&mut self) -> &mut Self {
@@ -405,6 +539,42 @@ impl<'w> EntityWorldMut<'w> {
 
         self.insert_with_relationship_hook_mode(R::from(entity), relationship_hook_mode);
     }
+
+    /// Adds and removes targets of this entity's [`ManyToManyRelationship`] `R`,
+    /// only updating the [`ManyToManyRelationshipTarget`]s of the `added` and `removed` targets.
+    ///
+    /// Unlike a full re-insertion, this leaves the order of the untouched targets and their sources intact.
+    fn modify_many_to_many<R: ManyToManyRelationship>(
+        &mut self,
+        added: &[Entity],
+        removed: &[Entity],
+        caller: MaybeLocation,
+    ) -> &mut Self {
+        self.assert_not_despawned();
+        let this = self.id();
+        self.world_scope(|world| {
+            let mut deferred_world = DeferredWorld::from(&mut *world);
+            deferred_world
+                .modify_component_with_relationship_hook_mode::<R, _>(
+                    this,
+                    RelationshipHookMode::Skip,
+                    |relationship| {
+                        let collection = relationship.collection_mut_risky();
+                        for target in removed {
+                            collection.remove(*target);
+                        }
+                        for target in added {
+                            add_unique(collection, *target);
+                        }
+                    },
+                )
+                .expect("entity access must be valid");
+            many_to_many::unlink_targets::<R>(deferred_world.reborrow(), this, removed);
+            many_to_many::link_targets::<R>(deferred_world, this, added, caller);
+            world.flush();
+        });
+        self
+    }
 }
 
 impl<'a> EntityCommands<'a> {
@@ -514,6 +684,73 @@ impl<'a> EntityCommands<'a> {
         })
     }
 
+    /// Relates this entity to the given `targets` with the [`ManyToManyRelationship`] `R`,
+    /// keeping any targets it is already related to.
+    #[track_caller]
+    pub fn add_related_targets<R: ManyToManyRelationship>(
+        &mut self,
+        targets: &[Entity],
+    ) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+        let caller = MaybeLocation::caller();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_related_targets_with_caller::<R>(&targets, caller);
+        })
+    }
+
+    /// Removes the given `targets` from this entity's [`ManyToManyRelationship`] `R`.
+    pub fn remove_related_targets<R: ManyToManyRelationship>(
+        &mut self,
+        targets: &[Entity],
+    ) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_related_targets::<R>(&targets);
+        })
+    }
+
+    /// Relates the given `sources` to this entity with the [`ManyToManyRelationship`] `R`,
+    /// keeping any other targets they are already related to.
+    #[track_caller]
+    pub fn add_related_many<R: ManyToManyRelationship>(&mut self, sources: &[Entity]) -> &mut Self {
+        let sources: Box<[Entity]> = sources.into();
+        let caller = MaybeLocation::caller();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_related_many_with_caller::<R>(&sources, caller);
+        })
+    }
+
+    /// Removes the [`ManyToManyRelationship`] `R` between the given `sources` and this entity.
+    pub fn remove_related_many<R: ManyToManyRelationship>(
+        &mut self,
+        sources: &[Entity],
+    ) -> &mut Self {
+        let sources: Box<[Entity]> = sources.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_related_many::<R>(&sources);
+        })
+    }
+
+    /// Removes the [`ManyToManyRelationship`] `R` between this entity and all of its sources.
+    pub fn detach_all_related_many<R: ManyToManyRelationship>(&mut self) -> &mut Self {
+        self.queue(|mut entity: EntityWorldMut| {
+            entity.detach_all_related_many::<R>();
+        })
+    }
+
+    /// Despawns entities that relate to this one via the given [`ManyToManyRelationship`],
+    /// even if they are also related to other entities.
+    /// This entity will not be despawned.
+    pub fn despawn_related_many<R: ManyToManyRelationship>(&mut self) -> &mut Self {
+        self.queue(|mut entity: EntityWorldMut| {
+            entity.despawn_related_many::<R>();
+        })
+    }
+
     /// Despawns entities that relate to this one via the given [`RelationshipTarget`].
     /// This entity will not be despawned.
     pub fn despawn_related<S: RelationshipTarget>(&mut self) -> &mut Self {
@@ -1,7 +1,9 @@
 use crate::{
-    entity::Entity,
+    entity::{Entity, EntityHashSet},
     query::{QueryData, QueryFilter},
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{
+        ManyToManyRelationship, ManyToManyRelationshipTarget, Relationship, RelationshipTarget,
+    },
     system::Query,
 };
 use alloc::collections::VecDeque;
@@ -141,6 +143,64 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     {
         AncestorIter::new(self, entity)
     }
+
+    /// If the given `entity` contains the `R` [`ManyToManyRelationship`] component, returns the
+    /// target entities of that relationship.
+    pub fn many_related<R: ManyToManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyToManyRelationship::iter)
+    }
+
+    /// If the given `entity` contains the `S` [`ManyToManyRelationshipTarget`] component, returns the
+    /// source entities stored on that component.
+    pub fn many_relationship_sources<S: ManyToManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyToManyRelationshipTarget::iter)
+    }
+
+    /// Iterates all entities that transitively relate to the given `entity` through the `S`
+    /// [`ManyToManyRelationshipTarget`], in breadth-first order.
+    ///
+    /// Each entity is returned once, even if it can be reached through several paths,
+    /// which also makes this safe to use on relationship graphs that contain loops.
+    pub fn iter_many_descendants<S: ManyToManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> ManyDescendantIter<'w, 's, D, F, S>
+    where
+        D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+    {
+        ManyDescendantIter::new(self, entity)
+    }
+
+    /// Iterates all entities that the given `entity` transitively relates to through the `R`
+    /// [`ManyToManyRelationship`], in breadth-first order.
+    ///
+    /// Each entity is returned once, even if it can be reached through several paths,
+    /// which also makes this safe to use on relationship graphs that contain loops.
+    pub fn iter_many_ancestors<R: ManyToManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> ManyAncestorIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        ManyAncestorIter::new(self, entity)
+    }
 }
 
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
@@ -270,3 +330,115 @@ where
         self.next
     }
 }
+
+/// An [`Iterator`] of [`Entity`]s over the entities that transitively relate to an [`Entity`]
+/// through a [`ManyToManyRelationshipTarget`].
+///
+/// Traverses the relationship graph breadth-first, visiting each entity once.
+pub struct ManyDescendantIter<'w, 's, D: QueryData, F: QueryFilter, S: ManyToManyRelationshipTarget>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+{
+    sources_query: &'w Query<'w, 's, D, F>,
+    visited: EntityHashSet,
+    vecdeque: VecDeque<Entity>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, S: ManyToManyRelationshipTarget>
+    ManyDescendantIter<'w, 's, D, F, S>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+{
+    /// Returns a new [`ManyDescendantIter`].
+    pub fn new(sources_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        let mut visited = EntityHashSet::new();
+        visited.insert(entity);
+        let mut iter = ManyDescendantIter {
+            sources_query,
+            visited,
+            vecdeque: VecDeque::new(),
+        };
+        iter.visit(entity);
+        iter
+    }
+
+    fn visit(&mut self, entity: Entity) {
+        if let Ok(sources) = self.sources_query.get(entity) {
+            for source in sources.iter() {
+                if self.visited.insert(source) {
+                    self.vecdeque.push_back(source);
+                }
+            }
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, S: ManyToManyRelationshipTarget> Iterator
+    for ManyDescendantIter<'w, 's, D, F, S>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+        self.visit(entity);
+        Some(entity)
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s over the entities that an [`Entity`] transitively relates to
+/// through a [`ManyToManyRelationship`].
+///
+/// Traverses the relationship graph breadth-first, visiting each entity once.
+pub struct ManyAncestorIter<'w, 's, D: QueryData, F: QueryFilter, R: ManyToManyRelationship>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    targets_query: &'w Query<'w, 's, D, F>,
+    visited: EntityHashSet,
+    vecdeque: VecDeque<Entity>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: ManyToManyRelationship>
+    ManyAncestorIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    /// Returns a new [`ManyAncestorIter`].
+    pub fn new(targets_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        let mut visited = EntityHashSet::new();
+        visited.insert(entity);
+        let mut iter = ManyAncestorIter {
+            targets_query,
+            visited,
+            vecdeque: VecDeque::new(),
+        };
+        iter.visit(entity);
+        iter
+    }
+
+    fn visit(&mut self, entity: Entity) {
+        if let Ok(targets) = self.targets_query.get(entity) {
+            for target in targets.iter() {
+                if self.visited.insert(target) {
+                    self.vecdeque.push_back(target);
+                }
+            }
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: ManyToManyRelationship> Iterator
+    for ManyAncestorIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+        self.visit(entity);
+        Some(entity)
+    }
+}
@@ -0,0 +1,323 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use bevy_platform::collections::HashMap;
+use bevy_utils::prelude::DebugName;
+
+use crate::{
+    component::{ComponentId, Components},
+    schedule::{ConflictingSystems, SystemKey},
+};
+
+/// A structured report of the system order ambiguities in a schedule: pairs of systems with
+/// conflicting data access and no ordering between them, so that they may run in either order.
+///
+/// The report is part of the [`ScheduleBuildMetadata`](super::ScheduleBuildMetadata) returned by
+/// [`Schedule::initialize`](super::Schedule::initialize), and can also be retrieved from an
+/// initialized schedule through [`Schedule::ambiguity_report`](super::Schedule::ambiguity_report).
+/// Ambiguities accepted through `ambiguous_with` or ignored through
+/// [`Schedules::allow_ambiguous_component`](super::Schedules::allow_ambiguous_component) are not
+/// part of the report.
+///
+/// Use [`AmbiguityReport::assert_no_new_ambiguities`] to keep new ambiguities from creeping in:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Resource)]
+/// # struct Score(u32);
+/// fn add_points(mut score: ResMut<Score>) {}
+/// fn reset_score(mut score: ResMut<Score>) {}
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems((add_points, reset_score));
+/// schedule.initialize(&mut world).unwrap();
+///
+/// let report = schedule.ambiguity_report(world.components()).unwrap();
+/// assert_eq!(report.len(), 1);
+/// // This ambiguity is known, so it's not reported as new.
+/// # #[cfg(feature = "debug")]
+/// report.assert_no_new_ambiguities(&[("add_points", "reset_score")]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AmbiguityReport {
+    ambiguities: Vec<SystemAmbiguity>,
+}
+
+/// A pair of systems with conflicting data access and no ordering between them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemAmbiguity {
+    /// The system of the pair that runs first in the schedule's topological order.
+    pub first: SystemKey,
+    /// The name of [`first`](Self::first).
+    pub first_name: String,
+    /// The system of the pair that runs second in the schedule's topological order.
+    pub second: SystemKey,
+    /// The name of [`second`](Self::second).
+    pub second_name: String,
+    /// The data both systems access in a conflicting way.
+    pub access: ConflictingAccess,
+}
+
+/// The data two ambiguous systems access in a conflicting way.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConflictingAccess {
+    /// The systems conflict on the **whole world**, since one of them is exclusive, or they
+    /// conflict without a particular component, such as two systems with `Query<EntityMut>`.
+    World,
+    /// The systems have conflicting access to these components or resources.
+    Components(Vec<ConflictingComponent>),
+}
+
+/// A component or resource that two ambiguous systems access in a conflicting way.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictingComponent {
+    /// The id of the component or resource.
+    pub id: ComponentId,
+    /// The name of the component or resource.
+    pub name: DebugName,
+}
+
+impl SystemAmbiguity {
+    /// Returns `true` if this ambiguity is between the systems named `a` and `b`, in either order.
+    pub fn is_between(&self, a: &str, b: &str) -> bool {
+        (self.first_name == a && self.second_name == b)
+            || (self.first_name == b && self.second_name == a)
+    }
+
+    /// Returns an ordering constraint that resolves this ambiguity while keeping the order the
+    /// systems currently run in with the
+    /// [`SingleThreadedExecutor`](super::SingleThreadedExecutor).
+    pub fn suggested_ordering(&self) -> String {
+        format!("{}.before({})", self.first_name, self.second_name)
+    }
+}
+
+impl fmt::Display for SystemAmbiguity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} and {} conflict on ",
+            self.first_name, self.second_name
+        )?;
+        match &self.access {
+            ConflictingAccess::World => f.write_str("the world")?,
+            ConflictingAccess::Components(components) => {
+                for (i, component) in components.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", component.name)?;
+                }
+            }
+        }
+        write!(f, " (suggested ordering: {})", self.suggested_ordering())
+    }
+}
+
+impl AmbiguityReport {
+    /// Builds the report from the conflicting systems found while building a schedule.
+    ///
+    /// `order` is the topological order of the systems, which decides the suggested ordering.
+    pub(super) fn new(
+        conflicting_systems: &ConflictingSystems,
+        components: &Components,
+        order: &[SystemKey],
+        system_name: impl Fn(SystemKey) -> String,
+    ) -> Self {
+        let positions: HashMap<_, _> = order
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (*key, index))
+            .collect();
+        let ambiguities = conflicting_systems
+            .iter()
+            .map(|(a, b, conflicts)| {
+                let (first, second) = if positions.get(a) <= positions.get(b) {
+                    (*a, *b)
+                } else {
+                    (*b, *a)
+                };
+                SystemAmbiguity {
+                    first,
+                    first_name: system_name(first),
+                    second,
+                    second_name: system_name(second),
+                    access: if conflicts.is_empty() {
+                        ConflictingAccess::World
+                    } else {
+                        ConflictingAccess::Components(
+                            conflicts
+                                .iter()
+                                .map(|&id| ConflictingComponent {
+                                    id,
+                                    name: components.get_name(id).unwrap(),
+                                })
+                                .collect(),
+                        )
+                    },
+                }
+            })
+            .collect();
+        Self { ambiguities }
+    }
+
+    /// Returns `true` if there are no ambiguities.
+    pub fn is_empty(&self) -> bool {
+        self.ambiguities.is_empty()
+    }
+
+    /// Returns the number of ambiguous system pairs.
+    pub fn len(&self) -> usize {
+        self.ambiguities.len()
+    }
+
+    /// Returns an iterator over the ambiguous system pairs.
+    pub fn iter(&self) -> impl Iterator<Item = &SystemAmbiguity> {
+        self.ambiguities.iter()
+    }
+
+    /// Returns the ambiguities that are not between any of the `known` pairs of system names.
+    pub fn new_ambiguities<'a>(
+        &'a self,
+        known: &'a [(&str, &str)],
+    ) -> impl Iterator<Item = &'a SystemAmbiguity> {
+        self.ambiguities
+            .iter()
+            .filter(|ambiguity| !known.iter().any(|(a, b)| ambiguity.is_between(a, b)))
+    }
+
+    /// Panics if there are ambiguities that are not between any of the `known` pairs of system
+    /// names, listing each of them along with a suggested ordering.
+    ///
+    /// System names are formatted according to
+    /// [`ScheduleBuildSettings::use_shortnames`](super::ScheduleBuildSettings::use_shortnames),
+    /// and are only available with the `debug` feature.
+    #[track_caller]
+    pub fn assert_no_new_ambiguities(&self, known: &[(&str, &str)]) {
+        let mut message = String::new();
+        for ambiguity in self.new_ambiguities(known) {
+            writeln!(message, " -- {ambiguity}").unwrap();
+        }
+        assert!(
+            message.is_empty(),
+            "found new system order ambiguities:\n{message}"
+        );
+    }
+}
+
+impl<'a> IntoIterator for &'a AmbiguityReport {
+    type Item = &'a SystemAmbiguity;
+    type IntoIter = core::slice::Iter<'a, SystemAmbiguity>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ambiguities.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString};
+
+    use crate::{prelude::*, schedule::ConflictingAccess};
+
+    #[derive(Resource)]
+    struct A;
+
+    #[derive(Resource)]
+    struct B;
+
+    fn read_a(_: Res<A>) {}
+    fn write_a(_: ResMut<A>) {}
+    fn write_b(_: ResMut<B>) {}
+    fn write_ab(_: ResMut<A>, _: ResMut<B>) {}
+    fn exclusive(_: &mut World) {}
+
+    #[test]
+    fn report_from_initialize() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((read_a, write_a, (write_b, write_ab).chain()));
+
+        let metadata = schedule.initialize(&mut world).unwrap().unwrap();
+        let report = &metadata.ambiguities;
+        assert_eq!(
+            report,
+            &schedule.ambiguity_report(world.components()).unwrap()
+        );
+        assert_eq!(report.len(), 3);
+
+        let a = world.component_id::<A>().unwrap();
+        for ambiguity in report {
+            let ConflictingAccess::Components(components) = &ambiguity.access else {
+                panic!("expected a component conflict");
+            };
+            assert_eq!(components.len(), 1);
+            assert_eq!(components[0].id, a);
+        }
+
+        // The suggestion keeps the topological order.
+        let systems: alloc::vec::Vec<_> = schedule.systems().unwrap().map(|(key, _)| key).collect();
+        for ambiguity in report {
+            let first = systems.iter().position(|&key| key == ambiguity.first);
+            let second = systems.iter().position(|&key| key == ambiguity.second);
+            assert!(first < second);
+            assert_eq!(
+                ambiguity.suggested_ordering(),
+                format!("{}.before({})", ambiguity.first_name, ambiguity.second_name)
+            );
+        }
+    }
+
+    #[test]
+    fn exclusive_systems_conflict_on_world() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((read_a, exclusive));
+        schedule.initialize(&mut world).unwrap();
+
+        let report = schedule.ambiguity_report(world.components()).unwrap();
+        assert_eq!(report.len(), 1);
+        let ambiguity = report.iter().next().unwrap();
+        assert_eq!(ambiguity.access, ConflictingAccess::World);
+        assert!(ambiguity.to_string().contains("the world"));
+    }
+
+    #[test]
+    fn uninitialized_schedule_has_no_report() {
+        let world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(read_a);
+        assert!(schedule.ambiguity_report(world.components()).is_err());
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn no_new_ambiguities() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(crate::schedule::ScheduleBuildSettings {
+            use_shortnames: true,
+            ..Default::default()
+        });
+        schedule.add_systems((read_a, write_a, write_b));
+        schedule.initialize(&mut world).unwrap();
+
+        let report = schedule.ambiguity_report(world.components()).unwrap();
+        report.assert_no_new_ambiguities(&[("write_a", "read_a")]);
+        assert_eq!(report.new_ambiguities(&[]).count(), 1);
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    #[should_panic(expected = "found new system order ambiguities")]
+    fn new_ambiguity_panics() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((read_a, write_a, write_ab));
+        schedule.initialize(&mut world).unwrap();
+
+        let report = schedule.ambiguity_report(world.components()).unwrap();
+        report.assert_no_new_ambiguities(&[("read_a", "write_a")]);
+    }
+}
@@ -0,0 +1,239 @@
+use alloc::vec::Vec;
+use bevy_platform::collections::HashMap;
+use bevy_utils::prelude::DebugName;
+use core::time::Duration;
+
+use crate::{
+    resource::Resource,
+    schedule::{InternedScheduleLabel, ScheduleLabel, SystemKey, SystemSchedule},
+};
+
+/// Per-system run time and run counts, recorded by every [`Schedule`](super::Schedule) that runs
+/// while this resource exists in the [`World`](crate::world::World).
+///
+/// The executors only measure systems when this resource is present, so inserting it is all that
+/// is needed to start profiling, and removing it stops the measurements and their overhead.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{ScheduleDiagnostics, ScheduleLabel};
+/// # #[derive(ScheduleLabel, Hash, Debug, PartialEq, Eq, Clone)]
+/// # struct Update;
+/// fn expensive_system() {}
+///
+/// let mut world = World::new();
+/// world.init_resource::<ScheduleDiagnostics>();
+///
+/// let mut schedule = Schedule::new(Update);
+/// schedule.add_systems(expensive_system);
+/// schedule.run(&mut world);
+///
+/// let diagnostics = world.resource::<ScheduleDiagnostics>();
+/// let (label, _, slowest) = diagnostics.slowest_systems(1)[0];
+/// assert_eq!(label, Update.intern());
+/// assert_eq!(slowest.run_count, 1);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct ScheduleDiagnostics {
+    schedules: HashMap<InternedScheduleLabel, HashMap<SystemKey, SystemDiagnostics>>,
+}
+
+/// Measurements of a single system, as recorded in [`ScheduleDiagnostics`].
+#[derive(Clone, Debug)]
+pub struct SystemDiagnostics {
+    /// The name of the system.
+    pub name: DebugName,
+    /// How many times the system ran.
+    pub run_count: u64,
+    /// How many times the system was skipped by its run conditions, or the run conditions of one
+    /// of its system sets.
+    pub skip_count: u64,
+    /// The time spent running the system the last time it ran.
+    pub last_duration: Duration,
+    /// The total time spent running the system.
+    pub total_duration: Duration,
+}
+
+impl SystemDiagnostics {
+    fn new(name: DebugName) -> Self {
+        Self {
+            name,
+            run_count: 0,
+            skip_count: 0,
+            last_duration: Duration::ZERO,
+            total_duration: Duration::ZERO,
+        }
+    }
+
+    /// Returns the average time spent running the system, or [`Duration::ZERO`] if it never ran.
+    pub fn average_duration(&self) -> Duration {
+        if self.run_count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.total_duration.as_secs_f64() / self.run_count as f64)
+    }
+}
+
+impl ScheduleDiagnostics {
+    /// Returns the measurements of the system with the given key in the given schedule,
+    /// if it has run or been skipped at least once.
+    pub fn get(&self, label: impl ScheduleLabel, system: SystemKey) -> Option<&SystemDiagnostics> {
+        self.schedules.get(&label.intern())?.get(&system)
+    }
+
+    /// Returns an iterator over the measurements of every system in the given schedule.
+    pub fn schedule(
+        &self,
+        label: impl ScheduleLabel,
+    ) -> impl Iterator<Item = (SystemKey, &SystemDiagnostics)> {
+        self.schedules
+            .get(&label.intern())
+            .into_iter()
+            .flat_map(|systems| systems.iter().map(|(key, system)| (*key, system)))
+    }
+
+    /// Returns an iterator over the measurements of every system in every schedule.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (InternedScheduleLabel, SystemKey, &SystemDiagnostics)> {
+        self.schedules
+            .iter()
+            .flat_map(|(label, systems)| systems.iter().map(|(key, system)| (*label, *key, system)))
+    }
+
+    /// Returns up to `count` systems with the highest
+    /// [average run time](SystemDiagnostics::average_duration), slowest first.
+    pub fn slowest_systems(
+        &self,
+        count: usize,
+    ) -> Vec<(InternedScheduleLabel, SystemKey, &SystemDiagnostics)> {
+        let mut systems: Vec<_> = self
+            .iter()
+            .filter(|(_, _, system)| system.run_count > 0)
+            .collect();
+        systems.sort_by_key(|(_, _, system)| core::cmp::Reverse(system.average_duration()));
+        systems.truncate(count);
+        systems
+    }
+
+    /// Discards all measurements.
+    pub fn clear(&mut self) {
+        self.schedules.clear();
+    }
+
+    /// Adds the statistics gathered by the executor during the last run of `schedule`.
+    pub(super) fn record(&mut self, label: InternedScheduleLabel, schedule: &SystemSchedule) {
+        let systems = self.schedules.entry(label).or_default();
+        for ((key, system), stats) in schedule
+            .system_ids
+            .iter()
+            .zip(&schedule.systems)
+            .zip(&schedule.system_run_stats)
+        {
+            if stats.runs == 0 && stats.skips == 0 {
+                continue;
+            }
+            let diagnostics = systems
+                .entry(*key)
+                .or_insert_with(|| SystemDiagnostics::new(system.system.name()));
+            diagnostics.run_count += u64::from(stats.runs);
+            diagnostics.skip_count += u64::from(stats.skips);
+            if stats.runs > 0 {
+                diagnostics.last_duration = stats.duration;
+                diagnostics.total_duration += stats.duration;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    use crate::{
+        prelude::*,
+        schedule::{
+            MultiThreadedExecutor, ScheduleDiagnostics, ScheduleLabel, SingleThreadedExecutor,
+            SystemExecutor,
+        },
+    };
+
+    #[derive(ScheduleLabel, Hash, Debug, PartialEq, Eq, Clone)]
+    struct TestSchedule;
+
+    fn fast_system() {}
+
+    fn records_systems(executor: impl SystemExecutor + 'static) {
+        let mut world = World::new();
+        world.init_resource::<ScheduleDiagnostics>();
+
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.set_executor(executor);
+        schedule.add_systems((
+            fast_system,
+            fast_system,
+            fast_system.run_if(|| false),
+            (fast_system, fast_system).run_if(|| false),
+        ));
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let diagnostics = world.resource::<ScheduleDiagnostics>();
+        assert_eq!(diagnostics.schedule(TestSchedule).count(), 5);
+
+        let (ran, skipped): (Vec<_>, Vec<_>) = diagnostics
+            .schedule(TestSchedule)
+            .partition(|(_, system)| system.run_count > 0);
+        assert_eq!(ran.len(), 2);
+        for (key, system) in ran {
+            assert_eq!(system.run_count, 2);
+            assert_eq!(system.skip_count, 0);
+            assert!(system.total_duration >= system.last_duration);
+            assert!(diagnostics.get(TestSchedule, key).is_some());
+        }
+        assert_eq!(skipped.len(), 3);
+        for (_, system) in skipped {
+            assert_eq!(system.skip_count, 2);
+            assert_eq!(system.last_duration, Duration::ZERO);
+            assert_eq!(system.total_duration, Duration::ZERO);
+        }
+
+        let slowest = diagnostics.slowest_systems(usize::MAX);
+        assert_eq!(slowest.len(), 2);
+        assert!(slowest
+            .iter()
+            .all(|(label, _, _)| *label == TestSchedule.intern()));
+        assert!(slowest[0].2.average_duration() >= slowest[1].2.average_duration());
+        assert_eq!(diagnostics.slowest_systems(1)[0].1, slowest[0].1);
+    }
+
+    #[test]
+    fn records_systems_singlethreaded() {
+        records_systems(SingleThreadedExecutor::new());
+    }
+
+    #[test]
+    fn records_systems_multithreaded() {
+        records_systems(MultiThreadedExecutor::new());
+    }
+
+    #[test]
+    fn records_only_while_resource_exists() {
+        let mut world = World::new();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems(fast_system);
+
+        schedule.run(&mut world);
+        assert!(schedule.executable().system_run_stats.is_empty());
+
+        world.init_resource::<ScheduleDiagnostics>();
+        schedule.run(&mut world);
+        let diagnostics = world.resource::<ScheduleDiagnostics>();
+        assert_eq!(diagnostics.iter().next().unwrap().2.run_count, 1);
+
+        world.remove_resource::<ScheduleDiagnostics>();
+        schedule.run(&mut world);
+        assert!(schedule.executable().system_run_stats.is_empty());
+    }
+}
@@ -3,8 +3,9 @@ mod multi_threaded;
 mod single_threaded;
 
 use alloc::{boxed::Box, vec, vec::Vec};
+use bevy_platform::time::Instant;
 use bevy_utils::prelude::DebugName;
-use core::any::TypeId;
+use core::{any::TypeId, time::Duration};
 
 pub use self::single_threaded::SingleThreadedExecutor;
 
@@ -104,6 +105,12 @@ pub struct SystemSchedule {
     ///
     /// If a set doesn't run because of its conditions, this is used to skip all systems in it.
     pub(super) systems_in_sets_with_conditions: Vec<FixedBitSet>,
+    /// Indexed by system node id.
+    /// Run statistics gathered by the executor during the current run.
+    ///
+    /// Empty unless [`ScheduleDiagnostics`](super::ScheduleDiagnostics) are being recorded, in which case
+    /// [`Schedule::run`](super::Schedule::run) resets it before every run.
+    pub(super) system_run_stats: Vec<SystemRunStats>,
 }
 
 impl SystemSchedule {
@@ -119,6 +126,7 @@ impl SystemSchedule {
             system_dependents: Vec::new(),
             sets_with_conditions_of_systems: Vec::new(),
             systems_in_sets_with_conditions: Vec::new(),
+            system_run_stats: Vec::new(),
         }
     }
 
@@ -130,6 +138,27 @@ impl SystemSchedule {
     pub unsafe fn systems_mut(&mut self) -> &mut Vec<SystemWithAccess> {
         &mut self.systems
     }
+
+    /// Records that the system at `system_index` ran, if run statistics are being gathered.
+    ///
+    /// `start` is the instant the system started running, or [`None`] if it wasn't measured.
+    fn record_run(&mut self, system_index: usize, start: Option<Instant>) {
+        if let (Some(stats), Some(start)) = (self.system_run_stats.get_mut(system_index), start) {
+            stats.runs += 1;
+            stats.duration += start.elapsed();
+        }
+    }
+}
+
+/// Statistics an executor gathers about a single system during one run of a [`SystemSchedule`].
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct SystemRunStats {
+    /// How many times the system ran.
+    pub(super) runs: u32,
+    /// How many times the system was skipped by its run conditions or those of its sets.
+    pub(super) skips: u32,
+    /// The time spent running the system.
+    pub(super) duration: Duration,
 }
 
 /// A special [`System`] that instructs the executor to call
@@ -1,9 +1,10 @@
 use alloc::{boxed::Box, vec::Vec};
 use bevy_platform::cell::SyncUnsafeCell;
 use bevy_platform::sync::Arc;
+use bevy_platform::time::Instant;
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
 use concurrent_queue::ConcurrentQueue;
-use core::{any::Any, panic::AssertUnwindSafe};
+use core::{any::Any, panic::AssertUnwindSafe, time::Duration};
 use fixedbitset::FixedBitSet;
 #[cfg(feature = "std")]
 use std::eprintln;
@@ -22,7 +23,8 @@ use crate::{
     },
     prelude::Resource,
     schedule::{
-        is_apply_deferred, ConditionWithAccess, SystemExecutor, SystemSchedule, SystemWithAccess,
+        is_apply_deferred, ConditionWithAccess, SystemExecutor, SystemRunStats, SystemSchedule,
+        SystemWithAccess,
     },
     system::{BoxedSystem, RunSystemError, ScheduleSystem},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
@@ -38,6 +40,8 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<SystemWithAccess>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    /// Whether system tasks should measure how long their system runs.
+    record_run_stats: bool,
 }
 
 struct Conditions<'a> {
@@ -52,6 +56,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
         executor: &'env MultiThreadedExecutor,
         schedule: &'sys mut SystemSchedule,
         world: &'env mut World,
+        record_run_stats: bool,
     ) -> Self {
         Environment {
             executor,
@@ -63,6 +68,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
             }),
             world_cell: world.as_unsafe_world_cell(),
+            record_run_stats,
         }
     }
 }
@@ -77,6 +83,9 @@ struct SystemTaskMetadata {
     /// a system is skipped by an earlier system set condition or system stepping,
     /// and needs access to run its conditions but not for itself.
     condition_conflicting_systems: FixedBitSet,
+    /// The set of systems earlier in topological order that must complete before this one can
+    /// start. Only populated when the executor is [deterministic](MultiThreadedExecutor::deterministic).
+    earlier_conflicting_systems: FixedBitSet,
     /// Indices of the systems that directly depend on the system.
     dependents: Vec<usize>,
     /// Is `true` if the system does not access `!Send` data.
@@ -88,6 +97,8 @@ struct SystemTaskMetadata {
 /// The result of running a system that is sent across a channel.
 struct SystemResult {
     system_index: usize,
+    /// How long the system ran, if it was measured.
+    duration: Option<Duration>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -98,6 +109,8 @@ pub struct MultiThreadedExecutor {
     system_completion: ConcurrentQueue<SystemResult>,
     /// Setting when true applies deferred system buffers after all systems have run
     apply_final_deferred: bool,
+    /// When true, conflicting systems always run in topological order.
+    deterministic: bool,
     /// When set, tells the executor that a thread has panicked.
     panic_payload: Mutex<Option<Box<dyn Any + Send>>>,
     starting_systems: FixedBitSet,
@@ -134,6 +147,8 @@ pub struct ExecutorState {
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
+    /// Run statistics of each system, moved out of the [`SystemSchedule`] while it's running.
+    system_run_stats: Vec<SystemRunStats>,
 }
 
 /// References to data required by the executor.
@@ -175,6 +190,7 @@ impl SystemExecutor for MultiThreadedExecutor {
             state.system_task_metadata.push(SystemTaskMetadata {
                 conflicting_systems: FixedBitSet::with_capacity(sys_count),
                 condition_conflicting_systems: FixedBitSet::with_capacity(sys_count),
+                earlier_conflicting_systems: FixedBitSet::with_capacity(sys_count),
                 dependents: schedule.system_dependents[index].clone(),
                 is_send: schedule.systems[index].system.is_send(),
                 is_exclusive: schedule.systems[index].system.is_exclusive(),
@@ -231,6 +247,10 @@ impl SystemExecutor for MultiThreadedExecutor {
                     .set_condition_conflicting_systems
                     .push(conflicting_systems);
             }
+
+            if self.deterministic {
+                state.calculate_earlier_conflicting_systems(schedule);
+            }
         }
 
         state.num_dependencies_remaining = Vec::with_capacity(sys_count);
@@ -270,12 +290,15 @@ impl SystemExecutor for MultiThreadedExecutor {
             }
         }
 
+        state.system_run_stats = core::mem::take(&mut schedule.system_run_stats);
+
         let thread_executor = world
             .get_resource::<MainThreadExecutor>()
             .map(|e| e.0.clone());
         let thread_executor = thread_executor.as_deref();
 
-        let environment = &Environment::new(self, schedule, world);
+        let record_run_stats = !state.system_run_stats.is_empty();
+        let environment = &Environment::new(self, schedule, world, record_run_stats);
 
         ComputeTaskPool::get_or_init(TaskPool::default).scope_with_executor(
             false,
@@ -308,6 +331,8 @@ impl SystemExecutor for MultiThreadedExecutor {
             state.unapplied_systems.clear();
         }
 
+        schedule.system_run_stats = core::mem::take(&mut state.system_run_stats);
+
         // check to see if there was a panic
         let payload = self.panic_payload.get_mut().unwrap();
         if let Some(payload) = payload.take() {
@@ -332,12 +357,16 @@ impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
         system_index: usize,
         res: Result<(), Box<dyn Any + Send>>,
         system: &ScheduleSystem,
+        start: Option<Instant>,
     ) {
         // tell the executor that the system finished
         self.environment
             .executor
             .system_completion
-            .push(SystemResult { system_index })
+            .push(SystemResult {
+                system_index,
+                duration: start.map(|start| start.elapsed()),
+            })
             .unwrap_or_else(|error| unreachable!("{}", error));
         if let Err(payload) = res {
             #[cfg(feature = "std")]
@@ -396,11 +425,44 @@ impl MultiThreadedExecutor {
             system_completion: ConcurrentQueue::unbounded(),
             starting_systems: FixedBitSet::new(),
             apply_final_deferred: true,
+            deterministic: false,
             panic_payload: Mutex::new(None),
             #[cfg(feature = "trace")]
             executor_span: info_span!("multithreaded executor"),
         }
     }
+
+    /// Creates a new deterministic `multi_threaded` executor for use with a [`Schedule`].
+    ///
+    /// Systems still run in parallel, but a system never starts before every system that comes
+    /// earlier in the schedule's topological order and conflicts with it has completed. Systems
+    /// that conflict with each other, including through their run conditions, therefore always run
+    /// in the same order as they would with a [`SingleThreadedExecutor`], and
+    /// [`ApplyDeferred`] sync points always apply the buffers of the same systems.
+    /// As a result, commands, message writes and observer triggers are committed in a stable order,
+    /// regardless of how the task pool happens to schedule the system tasks.
+    ///
+    /// Exclusive systems conflict with every other system. The extra ordering constraints reduce
+    /// parallelism somewhat, so this is meant for lockstep simulation and replay testing.
+    ///
+    /// Note that non-conflicting systems may still start in any order, so the [`Tick`]s they
+    /// receive can differ between runs.
+    ///
+    /// [`Schedule`]: crate::schedule::Schedule
+    /// [`SingleThreadedExecutor`]: crate::schedule::SingleThreadedExecutor
+    /// [`ApplyDeferred`]: crate::schedule::ApplyDeferred
+    /// [`Tick`]: crate::change_detection::Tick
+    pub fn deterministic() -> Self {
+        Self {
+            deterministic: true,
+            ..Self::new()
+        }
+    }
+
+    /// Returns `true` if this executor was created with [`MultiThreadedExecutor::deterministic`].
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
 }
 
 impl ExecutorState {
@@ -419,6 +481,41 @@ impl ExecutorState {
             skipped_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
+            system_run_stats: Vec::new(),
+        }
+    }
+
+    /// Orders conflicting systems by their topological order, for the deterministic mode.
+    ///
+    /// Two systems conflict if either is exclusive, if their accesses conflict, or if one of them
+    /// conflicts with the run conditions of the other or of the other's system sets.
+    fn calculate_earlier_conflicting_systems(&mut self, schedule: &SystemSchedule) {
+        let sys_count = schedule.system_ids.len();
+        for index1 in 0..sys_count {
+            let mut earlier = FixedBitSet::with_capacity(sys_count);
+            let meta1 = &self.system_task_metadata[index1];
+            if meta1.is_exclusive {
+                earlier.insert_range(0..index1);
+            } else {
+                for index2 in 0..index1 {
+                    let meta2 = &self.system_task_metadata[index2];
+                    let conflicts_with_set_conditions = |system: usize, other: usize| {
+                        schedule.sets_with_conditions_of_systems[other]
+                            .ones()
+                            .any(|set| self.set_condition_conflicting_systems[set].contains(system))
+                    };
+                    if meta2.is_exclusive
+                        || meta1.conflicting_systems.contains(index2)
+                        || meta1.condition_conflicting_systems.contains(index2)
+                        || meta2.condition_conflicting_systems.contains(index1)
+                        || conflicts_with_set_conditions(index2, index1)
+                        || conflicts_with_set_conditions(index1, index2)
+                    {
+                        earlier.insert(index2);
+                    }
+                }
+            }
+            self.system_task_metadata[index1].earlier_conflicting_systems = earlier;
         }
     }
 
@@ -552,6 +649,13 @@ impl ExecutorState {
             return false;
         }
 
+        if !system_meta
+            .earlier_conflicting_systems
+            .is_subset(&self.completed_systems)
+        {
+            return false;
+        }
+
         if !system_meta.is_send && self.local_thread_running {
             return false;
         }
@@ -662,6 +766,7 @@ impl ExecutorState {
         let system_meta = &self.system_task_metadata[system_index];
 
         let task = async move {
+            let start = context.environment.record_run_stats.then(Instant::now);
             let res = handle_errors(
                 |system| {
                     // SAFETY:
@@ -679,7 +784,7 @@ impl ExecutorState {
                 context.error_handler,
                 "System panicked",
             );
-            context.system_completed(system_index, res, system);
+            context.system_completed(system_index, res, system, start);
         };
 
         if system_meta.is_send {
@@ -703,6 +808,7 @@ impl ExecutorState {
             let unapplied_systems = self.unapplied_systems.clone();
             self.unapplied_systems.clear();
             let task = async move {
+                let start = context.environment.record_run_stats.then(Instant::now);
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
@@ -712,12 +818,13 @@ impl ExecutorState {
                     world,
                     context.error_handler,
                 );
-                context.system_completed(system_index, res, system);
+                context.system_completed(system_index, res, system, start);
             };
 
             context.scope.spawn_on_scope(task);
         } else {
             let task = async move {
+                let start = context.environment.record_run_stats.then(Instant::now);
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
@@ -727,7 +834,7 @@ impl ExecutorState {
                     context.error_handler,
                     "Exclusive system panicked",
                 );
-                context.system_completed(system_index, res, system);
+                context.system_completed(system_index, res, system, start);
             };
 
             context.scope.spawn_on_scope(task);
@@ -738,7 +845,10 @@ impl ExecutorState {
     }
 
     fn finish_system_and_handle_dependents(&mut self, result: SystemResult) {
-        let SystemResult { system_index, .. } = result;
+        let SystemResult {
+            system_index,
+            duration,
+        } = result;
 
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
@@ -754,11 +864,21 @@ impl ExecutorState {
         self.completed_systems.insert(system_index);
         self.unapplied_systems.insert(system_index);
 
+        if let (Some(stats), Some(duration)) =
+            (self.system_run_stats.get_mut(system_index), duration)
+        {
+            stats.runs += 1;
+            stats.duration += duration;
+        }
+
         self.signal_dependents(system_index);
     }
 
     fn skip_system_and_signal_dependents(&mut self, system_index: usize) {
         self.completed_systems.insert(system_index);
+        if let Some(stats) = self.system_run_stats.get_mut(system_index) {
+            stats.skips += 1;
+        }
         self.signal_dependents(system_index);
     }
 
@@ -925,9 +1045,11 @@ impl MainThreadExecutor {
 #[cfg(test)]
 mod tests {
     use alloc::string::String;
+    use alloc::vec::Vec;
     use core::{
         panic::AssertUnwindSafe,
         sync::atomic::{AtomicBool, Ordering::Relaxed},
+        time::Duration,
     };
     use std::panic::catch_unwind;
 
@@ -936,8 +1058,11 @@ mod tests {
             BevyError, ErrorContext, FallbackErrorHandler, PANIC_ORIGINATES_FROM_ERROR_HANDLER,
         },
         prelude::Resource,
-        schedule::{IntoScheduleConfigs, MultiThreadedExecutor, Schedule},
-        system::Commands,
+        schedule::{
+            ApplyDeferred, IntoScheduleConfigs, MultiThreadedExecutor, Schedule,
+            SingleThreadedExecutor, SystemExecutor,
+        },
+        system::{Commands, Res, ResMut},
         world::World,
     };
 
@@ -1088,4 +1213,52 @@ mod tests {
         );
         assert!(!SYSTEM_RAN.load(Relaxed));
     }
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<u32>);
+
+    fn wait() {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    fn log(value: u32) -> impl FnMut(ResMut<Log>) {
+        move |mut log| log.0.push(value)
+    }
+
+    fn log_deferred(value: u32) -> impl FnMut(Commands) {
+        move |mut commands| {
+            commands.queue(move |world: &mut World| world.resource_mut::<Log>().0.push(value));
+        }
+    }
+
+    fn run_log_schedule(executor: impl SystemExecutor + 'static) -> Vec<u32> {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut schedule = Schedule::default();
+        schedule.set_executor(executor);
+        schedule.add_systems((
+            (wait, log(0)).chain(),
+            log(1),
+            (wait, wait, log(2)).chain(),
+            log_deferred(3),
+            (wait, log_deferred(4), ApplyDeferred, log(5)).chain(),
+            (log(6), wait).run_if(|log: Res<Log>| log.0.len().is_multiple_of(2)),
+            log_deferred(7),
+        ));
+        for _ in 0..3 {
+            schedule.run(&mut world);
+        }
+        world.remove_resource::<Log>().unwrap().0
+    }
+
+    #[test]
+    fn deterministic_executor_matches_single_threaded_order() {
+        let expected = run_log_schedule(SingleThreadedExecutor::new());
+        for _ in 0..10 {
+            assert_eq!(
+                run_log_schedule(MultiThreadedExecutor::deterministic()),
+                expected
+            );
+        }
+    }
 }
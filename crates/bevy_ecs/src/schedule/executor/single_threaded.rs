@@ -3,6 +3,7 @@ use core::panic::AssertUnwindSafe;
 #[cfg(feature = "std")]
 use std::backtrace::Backtrace;
 
+use bevy_platform::time::Instant;
 use fixedbitset::FixedBitSet;
 
 #[cfg(feature = "trace")]
@@ -131,11 +132,23 @@ impl SystemExecutor for SingleThreadedExecutor {
             self.completed_systems.insert(system_index);
 
             if !should_run {
+                #[cfg(feature = "bevy_debug_stepping")]
+                let skipped_by_stepping = _skip_systems.is_some_and(|s| s.contains(system_index));
+                #[cfg(not(feature = "bevy_debug_stepping"))]
+                let skipped_by_stepping = false;
+                if !skipped_by_stepping
+                    && let Some(stats) = schedule.system_run_stats.get_mut(system_index)
+                {
+                    stats.skips += 1;
+                }
                 continue;
             }
 
+            let start = (!schedule.system_run_stats.is_empty()).then(Instant::now);
+
             if is_apply_deferred(&**system) {
                 self.apply_deferred(schedule, world, error_handler);
+                schedule.record_run(system_index, start);
                 continue;
             }
 
@@ -164,6 +177,7 @@ impl SystemExecutor for SingleThreadedExecutor {
                 (f)(system);
             }
 
+            schedule.record_run(system_index, start);
             self.unapplied_systems.insert(system_index);
         }
 
@@ -1,8 +1,10 @@
 //! Contains APIs for ordering systems and executing them on a [`World`](crate::world::World)
 
+mod ambiguity;
 mod auto_insert_apply_deferred;
 mod condition;
 mod config;
+mod diagnostics;
 mod error;
 mod executor;
 mod node;
@@ -12,7 +14,10 @@ mod set;
 mod stepping;
 
 pub use self::graph::GraphInfo;
-pub use self::{condition::*, config::*, error::*, executor::*, node::*, schedule::*, set::*};
+pub use self::{
+    ambiguity::*, condition::*, config::*, diagnostics::*, error::*, executor::*, node::*,
+    schedule::*, set::*,
+};
 pub use pass::{FlattenedDependencies, ScheduleBuildPass};
 
 /// An implementation of a graph data structure.
@@ -15,7 +15,7 @@ use bevy_platform::{
     collections::{HashMap, HashSet},
     hash::FixedHasher,
 };
-use bevy_utils::{default, TypeIdMap};
+use bevy_utils::{default, prelude::DebugName, TypeIdMap};
 use core::{
     any::{Any, TypeId},
     fmt::{Debug, Write},
@@ -572,6 +572,14 @@ impl Schedule {
 
         let error_handler = world.fallback_error_handler();
 
+        let record_diagnostics = world.contains_resource::<ScheduleDiagnostics>();
+        self.executable.system_run_stats.clear();
+        if record_diagnostics {
+            self.executable
+                .system_run_stats
+                .resize(self.executable.systems.len(), Default::default());
+        }
+
         #[cfg(not(feature = "bevy_debug_stepping"))]
         self.executor
             .run(&mut self.executable, world, None, error_handler);
@@ -590,6 +598,12 @@ impl Schedule {
                 error_handler,
             );
         }
+
+        if record_diagnostics
+            && let Some(mut diagnostics) = world.get_resource_mut::<ScheduleDiagnostics>()
+        {
+            diagnostics.record(self.label, &self.executable);
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -708,6 +722,32 @@ impl Schedule {
         Ok(iter)
     }
 
+    /// Returns the system order ambiguities of this schedule, which is only available once the
+    /// schedule has been initialized through [`Schedule::initialize`] or [`Schedule::run`].
+    ///
+    /// This doesn't depend on [`ScheduleBuildSettings::ambiguity_detection`].
+    pub fn ambiguity_report(
+        &self,
+        components: &Components,
+    ) -> Result<AmbiguityReport, ScheduleNotInitialized> {
+        if !self.executor_initialized {
+            return Err(ScheduleNotInitialized);
+        }
+
+        let systems: HashMap<_, _> = self
+            .executable
+            .system_ids
+            .iter()
+            .zip(&self.executable.systems)
+            .collect();
+        Ok(AmbiguityReport::new(
+            self.graph.conflicting_systems(),
+            components,
+            &self.executable.system_ids,
+            |key| self.graph.format_system_name(systems[&key].system.name()),
+        ))
+    }
+
     /// Returns the number of systems in this schedule.
     pub fn systems_len(&self) -> usize {
         if !self.executor_initialized {
@@ -1261,11 +1301,19 @@ impl ScheduleGraph {
         }
 
         // build the schedule
+        let schedule = self.build_schedule_inner(flat_dependency, hierarchy_analysis);
+        let ambiguities = AmbiguityReport::new(
+            &self.conflicting_systems,
+            world.components(),
+            &schedule.system_ids,
+            |key| self.format_system_name(self.systems[key].name()),
+        );
         Ok((
-            self.build_schedule_inner(flat_dependency, hierarchy_analysis),
+            schedule,
             ScheduleBuildMetadata {
                 warnings,
                 edges_added_by_build_passes: added_edges,
+                ambiguities,
             },
         ))
     }
@@ -1362,6 +1410,7 @@ impl ScheduleGraph {
             system_dependents,
             sets_with_conditions_of_systems,
             systems_in_sets_with_conditions,
+            system_run_stats: Vec::new(),
         }
     }
 
@@ -1496,16 +1545,20 @@ impl ScheduleGraph {
         self.get_node_name_inner(id, self.settings.report_sets)
     }
 
+    /// Formats the name of a system according to [`ScheduleBuildSettings::use_shortnames`].
+    pub(super) fn format_system_name(&self, name: DebugName) -> String {
+        if self.settings.use_shortnames {
+            name.shortname().to_string()
+        } else {
+            name.to_string()
+        }
+    }
+
     #[inline]
     fn get_node_name_inner(&self, id: &NodeId, report_sets: bool) -> String {
         match *id {
             NodeId::System(key) => {
-                let name = self.systems[key].name();
-                let name = if self.settings.use_shortnames {
-                    name.shortname().to_string()
-                } else {
-                    name.to_string()
-                };
+                let name = self.format_system_name(self.systems[key].name());
                 if report_sets {
                     let sets = self.names_of_sets_containing_node(id);
                     if sets.is_empty() {
@@ -1642,6 +1695,8 @@ pub struct ScheduleBuildMetadata {
     /// These edges are not stored in the [`ScheduleGraph`], and so are only available during the
     /// build process.
     pub edges_added_by_build_passes: HashSet<(SystemKey, SystemKey)>,
+    /// The system order ambiguities found in the schedule.
+    pub ambiguities: AmbiguityReport,
 }
 
 /// An event triggered when a schedule is successfully built.
@@ -0,0 +1,486 @@
+use alloc::boxed::Box;
+use core::{
+    future::Future,
+    pin::Pin,
+    ptr::NonNull,
+    task::{Context, Poll},
+};
+
+use bevy_platform::{
+    cell::SyncCell,
+    sync::{Arc, Mutex, PoisonError, TryLockError},
+};
+use bevy_utils::prelude::DebugName;
+
+use super::{
+    Adapt, AdapterSystem, ExclusiveFunctionSystem, HasExclusiveSystemInput, RunSystemError,
+};
+use crate::{
+    message::{Message, MessageCursor, Messages},
+    system::{Commands, InMut, IntoSystem, System, SystemIn, SystemInput},
+    world::World,
+};
+
+/// Creates a system that drives the future returned by `func`, for example an `async fn`.
+///
+/// Each time the system runs, the future is polled once, with access to the [`World`] through
+/// the [`AsyncWorld`] passed to `func`. Awaiting [`AsyncWorld::next_frame`],
+/// [`AsyncWorld::next_message`] or [`AsyncWorld::wait_until`] suspends the future until a later
+/// run of the system, so that a multi-frame flow can be written as straight-line code. Other
+/// futures can be awaited too, such as a [`Task`](bevy_tasks::Task) spawned on one of the task
+/// pools: they are polled again on each run until they are ready.
+///
+/// Once the future completes, `func` is called again to start a new one on the next run.
+///
+/// The system has exclusive world access, and only lends it to the future while it is being
+/// polled: [`AsyncWorld::with_world`] panics when called at any other time.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::system::{async_system, AsyncWorld};
+///
+/// #[derive(Message, Clone)]
+/// struct StartGame;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// #[derive(Resource, Default)]
+/// struct Countdown(u32);
+///
+/// async fn start_game(world: AsyncWorld) {
+///     world.next_message::<StartGame>().await;
+///     for second in (1..=3).rev() {
+///         world.with_world(|world| world.resource_mut::<Countdown>().0 = second);
+///         world.next_frame().await;
+///     }
+///     world.commands(|mut commands| {
+///         commands.spawn(Player);
+///     });
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Countdown>();
+/// world.init_resource::<Messages<StartGame>>();
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(async_system(start_game));
+///
+/// schedule.run(&mut world);
+/// world.write_message(StartGame);
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Countdown>().0, 3);
+///
+/// for _ in 0..3 {
+///     schedule.run(&mut world);
+/// }
+/// assert_eq!(world.query::<&Player>().iter(&world).count(), 1);
+/// ```
+pub fn async_system<F, Fut>(func: F) -> AsyncSystem<F, Fut>
+where
+    F: FnMut(AsyncWorld) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let poll: PollFn<F, Fut> = AsyncTask::poll;
+    let system = IntoSystem::into_system(poll);
+    AdapterSystem::new(
+        AsyncAdapter {
+            task: AsyncTask {
+                func,
+                future: None,
+                world: AsyncWorld::default(),
+            },
+        },
+        system,
+        DebugName::type_name::<F>(),
+    )
+}
+
+type PollFn<F, Fut> = fn(InMut<'_, AsyncTask<F, Fut>>, &mut World);
+
+/// A system that polls a future once per run, created by [`async_system`].
+pub type AsyncSystem<F, Fut> = AdapterSystem<
+    AsyncAdapter<F, Fut>,
+    ExclusiveFunctionSystem<
+        (
+            HasExclusiveSystemInput,
+            fn(InMut<'static, AsyncTask<F, Fut>>),
+        ),
+        (),
+        PollFn<F, Fut>,
+    >,
+>;
+
+/// The [`Adapt`] implementation of an [`AsyncSystem`].
+///
+/// It owns the future of the system, and lends it to an exclusive system that polls it with
+/// access to the [`World`].
+pub struct AsyncAdapter<F, Fut> {
+    task: AsyncTask<F, Fut>,
+}
+
+/// The future of an [`AsyncSystem`], along with the function that creates it.
+#[doc(hidden)]
+pub struct AsyncTask<F, Fut> {
+    func: F,
+    future: Option<SyncCell<Pin<Box<Fut>>>>,
+    world: AsyncWorld,
+}
+
+impl<F, Fut> AsyncTask<F, Fut>
+where
+    F: FnMut(AsyncWorld) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    fn poll(InMut(task): InMut<Self>, world: &mut World) {
+        let future = task
+            .future
+            .get_or_insert_with(|| SyncCell::new(Box::pin((task.func)(task.world.clone()))));
+        let ready = task.world.lend(world, || {
+            bevy_tasks::futures::check_ready(future.get()).is_some()
+        });
+        if ready {
+            task.future = None;
+        }
+    }
+}
+
+impl<F, Fut, S> Adapt<S> for AsyncAdapter<F, Fut>
+where
+    F: FnMut(AsyncWorld) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+    S: System<In = InMut<'static, AsyncTask<F, Fut>>>,
+{
+    type In = ();
+    type Out = S::Out;
+
+    fn adapt(
+        &mut self,
+        _input: <Self::In as SystemInput>::Inner<'_>,
+        run_system: impl FnOnce(SystemIn<'_, S>) -> Result<S::Out, RunSystemError>,
+    ) -> Result<Self::Out, RunSystemError> {
+        run_system(&mut self.task)
+    }
+}
+
+/// A pointer to the [`World`] lent to the future of an [`AsyncSystem`].
+struct WorldPtr(NonNull<World>);
+
+// SAFETY: The pointer is only dereferenced while the system lends its `&mut World`, and `World` is
+// `Send`.
+unsafe impl Send for WorldPtr {}
+
+/// A handle to the [`World`] for the future of an [`async_system`].
+///
+/// The world is only available while the future is being polled, that is, between two `await`
+/// points. Methods accessing it panic when called at any other time, for example from a task
+/// spawned on another thread.
+#[derive(Clone, Default)]
+pub struct AsyncWorld {
+    world: Arc<Mutex<Option<WorldPtr>>>,
+}
+
+impl AsyncWorld {
+    /// Runs `f` with mutable access to the [`World`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future of the system isn't being polled, or if called from within `f`.
+    #[track_caller]
+    pub fn with_world<R>(&self, f: impl FnOnce(&mut World) -> R) -> R {
+        let guard = match self.world.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(error)) => error.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                panic!("the world of an async system is already borrowed")
+            }
+        };
+        let Some(world) = guard.as_ref() else {
+            panic!("the world of an async system can only be accessed while its future is polled");
+        };
+        // SAFETY: The pointer is only set while the system lends its `&mut World`, and the system
+        // takes the lock to unset it before the borrow ends. Holding the lock ensures this is the
+        // only reference to the world.
+        f(unsafe { &mut *world.0.as_ptr() })
+    }
+
+    /// Runs `f` with the [`Commands`] of the [`World`].
+    ///
+    /// The commands are applied at the end of the current poll, when the system flushes the world.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future of the system isn't being polled.
+    #[track_caller]
+    pub fn commands<R>(&self, f: impl FnOnce(Commands) -> R) -> R {
+        self.with_world(|world| f(world.commands()))
+    }
+
+    /// Returns a future that resolves the next time the system runs, which is usually on the next
+    /// frame.
+    pub fn next_frame(&self) -> NextFrame {
+        NextFrame { yielded: false }
+    }
+
+    /// Returns a future that resolves to the next message of type `M` written after the future is
+    /// first polled.
+    ///
+    /// The future never resolves if the [`Messages<M>`] resource doesn't exist.
+    pub fn next_message<M: Message + Clone>(&self) -> NextMessage<M> {
+        NextMessage {
+            world: self.clone(),
+            cursor: None,
+        }
+    }
+
+    /// Returns a future that calls `f` each time it is polled, and resolves once it returns
+    /// `Some`.
+    ///
+    /// This can be used to wait for any condition on the world, such as an asset finishing to
+    /// load.
+    pub fn wait_until<T, F>(&self, f: F) -> WaitUntil<F>
+    where
+        F: FnMut(&mut World) -> Option<T> + Unpin,
+    {
+        WaitUntil {
+            world: self.clone(),
+            f,
+        }
+    }
+
+    /// Lends `world` to the future while running `f`.
+    fn lend<R>(&self, world: &mut World, f: impl FnOnce() -> R) -> R {
+        struct Reclaim<'a>(&'a Mutex<Option<WorldPtr>>);
+
+        impl Drop for Reclaim<'_> {
+            fn drop(&mut self) {
+                // This waits for any `with_world` call to return, so that the borrow of the world
+                // doesn't outlive `lend`.
+                *self.0.lock().unwrap_or_else(PoisonError::into_inner) = None;
+            }
+        }
+
+        *self.world.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some(WorldPtr(NonNull::from(world)));
+        let _reclaim = Reclaim(&self.world);
+        f()
+    }
+}
+
+/// A future that resolves the next time an [`AsyncSystem`] runs.
+///
+/// Created by [`AsyncWorld::next_frame`].
+pub struct NextFrame {
+    yielded: bool,
+}
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// A future that resolves to the next message of type `M`.
+///
+/// Created by [`AsyncWorld::next_message`].
+pub struct NextMessage<M: Message> {
+    world: AsyncWorld,
+    cursor: Option<MessageCursor<M>>,
+}
+
+// The cursor doesn't hold any `M`.
+impl<M: Message> Unpin for NextMessage<M> {}
+
+impl<M: Message + Clone> Future for NextMessage<M> {
+    type Output = M;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<M> {
+        let this = &mut *self;
+        let message = this.world.with_world(|world| {
+            let messages = world.get_resource::<Messages<M>>()?;
+            this.cursor
+                .get_or_insert_with(|| messages.get_cursor_current())
+                .read(messages)
+                .next()
+                .cloned()
+        });
+        match message {
+            Some(message) => Poll::Ready(message),
+            None => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A future that resolves once a function of the world returns `Some`.
+///
+/// Created by [`AsyncWorld::wait_until`].
+pub struct WaitUntil<F> {
+    world: AsyncWorld,
+    f: F,
+}
+
+impl<T, F> Future for WaitUntil<F>
+where
+    F: FnMut(&mut World) -> Option<T> + Unpin,
+{
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let this = &mut *self;
+        match this.world.with_world(&mut this.f) {
+            Some(value) => Poll::Ready(value),
+            None => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::{async_system, AsyncWorld};
+    use crate::{prelude::*, system::System};
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<u32>);
+
+    #[derive(Message, Clone)]
+    struct Ping(u32);
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[test]
+    fn polls_once_per_run() {
+        async fn count(world: AsyncWorld) {
+            for i in 0..3 {
+                world.with_world(|world| world.resource_mut::<Log>().0.push(i));
+                world.next_frame().await;
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(async_system(count));
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec![0]);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec![0, 1, 2]);
+
+        // The future completes on the fourth run, and a new one starts on the fifth.
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec![0, 1, 2]);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec![0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn commands_between_awaits() {
+        async fn spawn_twice(world: AsyncWorld) {
+            let entity = world.commands(|mut commands| commands.spawn(Marker).id());
+            world.next_frame().await;
+            world.with_world(|world| assert!(world.get::<Marker>(entity).is_some()));
+            world.commands(|mut commands| {
+                commands.entity(entity).despawn();
+            });
+            core::future::pending::<()>().await;
+        }
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(async_system(spawn_twice));
+
+        schedule.run(&mut world);
+        assert_eq!(world.query::<&Marker>().iter(&world).count(), 1);
+        schedule.run(&mut world);
+        assert_eq!(world.query::<&Marker>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn awaits_messages() {
+        async fn log_pings(world: AsyncWorld) {
+            let Ping(value) = world.next_message::<Ping>().await;
+            world.with_world(|world| world.resource_mut::<Log>().0.push(value));
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.init_resource::<Messages<Ping>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(async_system(log_pings));
+
+        // Messages written before the future first awaits are not received.
+        world.write_message(Ping(0));
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert!(world.resource::<Log>().0.is_empty());
+
+        world.write_message(Ping(1));
+        world.write_message(Ping(2));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec![1]);
+    }
+
+    #[test]
+    fn waits_until_condition() {
+        async fn wait_for_log(world: AsyncWorld) {
+            let len = world
+                .wait_until(|world| world.get_resource::<Log>().map(|log| log.0.len()))
+                .await;
+            world.with_world(|world| world.resource_mut::<Log>().0.push(len as u32));
+        }
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(async_system(wait_for_log));
+
+        schedule.run(&mut world);
+        world.insert_resource(Log(vec![7]));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec![7, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "can only be accessed while its future is polled")]
+    fn world_unavailable_between_polls() {
+        #[derive(Resource)]
+        struct Handle(AsyncWorld);
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(async_system(|world: AsyncWorld| async move {
+            let handle = world.clone();
+            world.commands(|mut commands| commands.insert_resource(Handle(handle)));
+        }));
+        schedule.run(&mut world);
+
+        world.resource::<Handle>().0.with_world(|_| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn nested_world_access_panics() {
+        let mut world = World::new();
+        let mut system = async_system(|world: AsyncWorld| async move {
+            world.with_world(|_| world.with_world(|_| {}));
+        });
+        system.initialize(&mut world);
+        system.run((), &mut world).unwrap();
+    }
+}
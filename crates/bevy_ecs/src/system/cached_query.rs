@@ -0,0 +1,497 @@
+use alloc::vec::Vec;
+
+use bevy_platform::collections::HashMap;
+
+use crate::{
+    archetype::{ArchetypeId, MoveLogGuard},
+    change_detection::Tick,
+    entity::{unique_slice, Entity, EntityHashMap, UniqueEntityVec},
+    query::{
+        FilteredAccessSet, IterQueryData, QueryData, QueryFilter, QueryManyUniqueIter, QueryState,
+        ROQueryItem, ReadOnlyQueryData,
+    },
+    system::{Query, ReadOnlySystemParam, SystemMeta, SystemParam, SystemParamValidationError},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+/// A [`SystemParam`] that keeps a dense list of the entities matching a query,
+/// and only iterates over those.
+///
+/// A regular [`Query`] visits every entity of every matched archetype, and checks the
+/// non-archetypal filters such as [`Changed`](crate::query::Changed) and
+/// [`Added`](crate::query::Added) for each of them. When only a few of these entities
+/// actually match, most of that work is wasted.
+///
+/// `CachedQuery` remembers which entities passed the filter, and keeps that set up to date
+/// incrementally: when the system runs, only the entities that were added to or removed from
+/// a matched archetype since its last run are re-checked, as reported by
+/// [`Archetype::moved_entities_since`]. Archetypes that start matching the query are picked up
+/// when the system runs. Iterating over the matches then costs `O(matches)` rather than
+/// `O(archetype entities)`.
+///
+/// This only applies to [archetypal](QueryFilter::IS_ARCHETYPAL) queries, such as those
+/// filtered with [`With`](crate::query::With) and [`Without`](crate::query::Without).
+/// Change detection filters depend on the change ticks of each entity, which can be updated
+/// without moving the entity, so when the query isn't archetypal every entity of the matched
+/// archetypes is checked again on every run, at the same cost as a [`Query`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::CachedQuery;
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct Invulnerable;
+///
+/// fn report_wounded(query: CachedQuery<(Entity, &Health), (Changed<Health>, Without<Invulnerable>)>) {
+///     for (entity, health) in query.iter() {
+///         println!("{entity} now has {} health", health.0);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(report_wounded);
+/// ```
+///
+/// [`Archetype::moved_entities_since`]: crate::archetype::Archetype::moved_entities_since
+pub struct CachedQuery<'w, 's, D: QueryData, F: QueryFilter = ()> {
+    query: Query<'w, 's, D, F>,
+    entities: &'s UniqueEntityVec,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter> CachedQuery<'w, 's, D, F> {
+    /// Returns an iterator over the read-only query items of the matching entities.
+    pub fn iter(
+        &self,
+    ) -> QueryManyUniqueIter<'_, 's, D::ReadOnly, F, unique_slice::Iter<'s, Entity>> {
+        self.query.iter_many_unique(self.entities)
+    }
+
+    /// Returns an iterator over the query items of the matching entities.
+    pub fn iter_mut(&mut self) -> QueryManyUniqueIter<'_, 's, D, F, unique_slice::Iter<'s, Entity>>
+    where
+        D: IterQueryData,
+    {
+        self.query.iter_many_unique_mut(self.entities)
+    }
+
+    /// Returns the matching entities, in no particular order.
+    #[inline]
+    pub fn entities(&self) -> &'s [Entity] {
+        self.entities.as_vec()
+    }
+
+    /// Returns the number of matching entities.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity matches the query.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns the underlying [`Query`], which visits every entity of the matched archetypes.
+    pub fn query(&self) -> &Query<'w, 's, D, F> {
+        &self.query
+    }
+
+    /// Returns the underlying [`Query`] mutably, which visits every entity of the matched archetypes.
+    pub fn query_mut(&mut self) -> &mut Query<'w, 's, D, F> {
+        &mut self.query
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter> IntoIterator for &'w CachedQuery<'_, 's, D, F> {
+    type Item = ROQueryItem<'w, 's, D>;
+    type IntoIter = QueryManyUniqueIter<'w, 's, D::ReadOnly, F, unique_slice::Iter<'s, Entity>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The [`SystemParam::State`] of a [`CachedQuery`].
+pub struct CachedQueryState<D: QueryData, F: QueryFilter> {
+    query_state: QueryState<D, F>,
+    /// The [`Archetype::version`] of each matched archetype when the matches were last updated,
+    /// along with the guard keeping its moves recorded. Empty if the query isn't archetypal.
+    ///
+    /// [`Archetype::version`]: crate::archetype::Archetype::version
+    versions: HashMap<ArchetypeId, (u64, MoveLogGuard)>,
+    matches: MatchSet,
+}
+
+/// The matching entities, with the index of each of them in the list.
+#[derive(Default)]
+struct MatchSet {
+    entities: Vec<Entity>,
+    indices: EntityHashMap<usize>,
+}
+
+impl MatchSet {
+    /// Adds or removes `entity`, depending on whether it `matches`.
+    fn update(&mut self, entity: Entity, matches: bool) {
+        if matches {
+            self.indices.entry(entity).or_insert_with(|| {
+                self.entities.push(entity);
+                self.entities.len() - 1
+            });
+        } else if let Some(index) = self.indices.remove(&entity) {
+            self.entities.swap_remove(index);
+            if let Some(&swapped) = self.entities.get(index) {
+                self.indices.insert(swapped, index);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entities.clear();
+        self.indices.clear();
+    }
+}
+
+impl<D: QueryData, F: QueryFilter> CachedQueryState<D, F> {
+    /// Whether an entity matches the query only depends on its archetype.
+    const IS_ARCHETYPAL: bool = D::IS_ARCHETYPAL && F::IS_ARCHETYPAL;
+
+    /// Brings the cached matches up to date with `query`, which must have been created from `query_state`.
+    fn refresh(
+        versions: &mut HashMap<ArchetypeId, (u64, MoveLogGuard)>,
+        matches: &mut MatchSet,
+        query_state: &QueryState<D, F>,
+        query: &Query<D, F>,
+        world: UnsafeWorldCell,
+    ) {
+        if !Self::IS_ARCHETYPAL {
+            matches.clear();
+        }
+        let mut check = |entity: Entity| matches.update(entity, query.contains(entity));
+
+        for archetype_id in query_state.matched_archetypes() {
+            let Some(archetype) = world.archetypes().get(archetype_id) else {
+                continue;
+            };
+            // Archetypal queries only start or stop matching an entity when it moves between
+            // archetypes.
+            let moved = if Self::IS_ARCHETYPAL {
+                match versions.get_mut(&archetype_id) {
+                    Some((version, _)) => {
+                        let moved = archetype.moved_entities_since(*version);
+                        *version = archetype.version();
+                        moved
+                    }
+                    None => {
+                        let guard = archetype.log_moves();
+                        versions.insert(archetype_id, (archetype.version(), guard));
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            match moved {
+                Some(moved) => moved.for_each(&mut check),
+                None => archetype
+                    .entities()
+                    .iter()
+                    .for_each(|entity| check(entity.id())),
+            }
+        }
+    }
+}
+
+// SAFETY: Access is registered by delegating to the `Query` implementation,
+// which panics on conflicting access.
+unsafe impl<D: QueryData + 'static, F: QueryFilter + 'static> SystemParam
+    for CachedQuery<'_, '_, D, F>
+{
+    type State = CachedQueryState<D, F>;
+    type Item<'w, 's> = CachedQuery<'w, 's, D, F>;
+
+    fn init_state(world: &mut World) -> Self::State {
+        CachedQueryState {
+            query_state: Query::<D, F>::init_state(world),
+            versions: HashMap::default(),
+            matches: MatchSet::default(),
+        }
+    }
+
+    fn init_access(
+        state: &Self::State,
+        system_meta: &mut SystemMeta,
+        component_access_set: &mut FilteredAccessSet,
+        world: &mut World,
+    ) {
+        Query::init_access(&state.query_state, system_meta, component_access_set, world);
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Result<Self::Item<'w, 's>, SystemParamValidationError> {
+        let CachedQueryState {
+            query_state,
+            versions,
+            matches,
+        } = state;
+        query_state.update_archetypes_unsafe_world_cell(world);
+        // SAFETY: We have registered all of the query's world accesses,
+        // so the caller ensures that `world` has permission to access any
+        // world data that the query needs.
+        // We called `update_archetypes_unsafe_world_cell`, which validates the world.
+        let query = unsafe {
+            query_state.query_unchecked_manual_with_ticks(world, system_meta.last_run, change_tick)
+        };
+        CachedQueryState::refresh(versions, matches, query_state, &query, world);
+        // SAFETY: `MatchSet` stores each entity at most once.
+        let entities = unsafe { UniqueEntityVec::from_vec_ref_unchecked(&matches.entities) };
+        Ok(CachedQuery { query, entities })
+    }
+}
+
+// SAFETY: The query is constrained to read-only fetches.
+unsafe impl<D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> ReadOnlySystemParam
+    for CachedQuery<'_, '_, D, F>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, system::RunSystemOnce};
+
+    #[derive(Component, PartialEq, Debug)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    struct C;
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct S(u32);
+
+    #[derive(Resource, Default)]
+    struct Seen(Vec<u32>);
+
+    #[test]
+    fn cached_query_tracks_structural_changes() {
+        fn system(query: CachedQuery<&A, Without<B>>, mut seen: ResMut<Seen>) {
+            assert_eq!(query.len(), query.entities().len());
+            seen.0 = query.iter().map(|a| a.0).collect();
+            seen.0.sort();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+
+        let a = world.spawn(A(1)).id();
+        world.spawn((A(2), B));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [1]);
+
+        let c = world.spawn((A(3), C)).id();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [1, 3]);
+
+        world.entity_mut(a).insert(B);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [3]);
+
+        world.despawn(c);
+        schedule.run(&mut world);
+        assert!(world.resource::<Seen>().0.is_empty());
+
+        world.entity_mut(a).remove::<B>();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [1]);
+    }
+
+    #[test]
+    fn cached_query_tracks_change_ticks() {
+        fn system(query: CachedQuery<&A, Or<(Changed<A>, Added<B>)>>, mut seen: ResMut<Seen>) {
+            seen.0 = query.iter().map(|a| a.0).collect();
+            seen.0.sort();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+
+        let a = world.spawn(A(1)).id();
+        let b = world.spawn(A(2)).id();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [1, 2]);
+
+        schedule.run(&mut world);
+        assert!(world.resource::<Seen>().0.is_empty());
+
+        world.get_mut::<A>(a).unwrap().0 = 10;
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [10]);
+
+        world.entity_mut(b).insert(B);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [2]);
+    }
+
+    #[test]
+    fn cached_query_tracks_moves_between_matched_archetypes() {
+        fn system(query: CachedQuery<&A>, mut seen: ResMut<Seen>) {
+            seen.0 = query.iter().map(|a| a.0).collect();
+            seen.0.sort();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+
+        let entities: Vec<_> = (0..4).map(|i| world.spawn(A(i)).id()).collect();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [0, 1, 2, 3]);
+
+        // Moving back and forth between matched archetypes never duplicates an entity.
+        world.entity_mut(entities[0]).insert(B);
+        world.entity_mut(entities[1]).insert(B).remove::<B>();
+        world.entity_mut(entities[2]).insert(C).remove::<A>();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [0, 1, 3]);
+
+        // Moves that are no longer recorded are caught up with by scanning the archetype.
+        for i in 0..200 {
+            world.entity_mut(entities[3]).insert(B);
+            world.entity_mut(entities[3]).remove::<B>();
+            world.spawn((A(10 + i), C)).despawn();
+        }
+        world.entity_mut(entities[2]).insert(A(2));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn cached_query_tracks_sparse_set_change_ticks() {
+        fn system(query: CachedQuery<&S, Changed<S>>, mut seen: ResMut<Seen>) {
+            seen.0 = query.iter().map(|s| s.0).collect();
+            seen.0.sort();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+
+        let a = world.spawn(S(1)).id();
+        world.spawn((S(2), C));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [1, 2]);
+
+        schedule.run(&mut world);
+        assert!(world.resource::<Seen>().0.is_empty());
+
+        world.get_mut::<S>(a).unwrap().0 = 10;
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [10]);
+    }
+
+    #[test]
+    fn archetype_records_moved_entities() {
+        let mut world = World::new();
+        let a = world.spawn(A(0)).id();
+        let archetype_id = world.entity(a).archetype().id();
+
+        // Moves are only recorded while a guard is alive.
+        world.spawn(A(1));
+        let version = world.archetypes()[archetype_id].version();
+        assert!(world.archetypes()[archetype_id]
+            .moved_entities_since(version - 1)
+            .is_none());
+
+        let guard = world.archetypes()[archetype_id].log_moves();
+        let version = world.archetypes()[archetype_id].version();
+        let b = world.spawn(A(1)).id();
+        world.entity_mut(a).insert(B);
+        let archetype = &world.archetypes()[archetype_id];
+        assert_eq!(
+            archetype
+                .moved_entities_since(version)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            [b, a]
+        );
+        assert_eq!(
+            archetype
+                .moved_entities_since(archetype.version())
+                .unwrap()
+                .count(),
+            0
+        );
+
+        // Only a bounded number of moves are kept.
+        for i in 0..100 {
+            world.spawn(A(i)).despawn();
+        }
+        assert!(world.archetypes()[archetype_id]
+            .moved_entities_since(version)
+            .is_none());
+
+        drop(guard);
+        let version = world.archetypes()[archetype_id].version();
+        world.spawn(A(0));
+        assert!(world.archetypes()[archetype_id]
+            .moved_entities_since(version)
+            .is_none());
+    }
+
+    #[test]
+    fn cached_query_iter_mut() {
+        let mut world = World::new();
+        world.spawn(A(1));
+        world.spawn((A(2), C));
+        world.spawn((A(3), B));
+
+        world
+            .run_system_once(|mut query: CachedQuery<&mut A, Without<B>>| {
+                for mut a in query.iter_mut() {
+                    a.0 *= 10;
+                }
+            })
+            .unwrap();
+
+        let mut values = world
+            .query::<&A>()
+            .iter(&world)
+            .map(|a| a.0)
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, [3, 10, 20]);
+    }
+
+    #[test]
+    fn archetype_version_changes_on_moves() {
+        let mut world = World::new();
+        let entity = world.spawn(A(0)).id();
+        let archetype_id = world.entity(entity).archetype().id();
+        let version = world.archetypes()[archetype_id].version();
+
+        world.spawn(A(1));
+        assert_ne!(world.archetypes()[archetype_id].version(), version);
+
+        let version = world.archetypes()[archetype_id].version();
+        world.get_mut::<A>(entity).unwrap().0 = 2;
+        assert_eq!(world.archetypes()[archetype_id].version(), version);
+
+        world.entity_mut(entity).insert(B);
+        assert_ne!(world.archetypes()[archetype_id].version(), version);
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     change_detection::MaybeLocation,
     entity::Entity,
     error::{CommandOutput, ErrorContext, ErrorHandler, Result},
-    event::Event,
+    event::{EntityEvent, Event},
     message::{Message, Messages},
     resource::Resource,
     schedule::ScheduleLabel,
@@ -304,6 +304,20 @@ pub fn trigger_with<E: Event<Trigger<'static>: Send + Sync>>(
     }
 }
 
+/// Triggers the given [`EntityEvent`] and retains it on its target, to replay it to observers
+/// added later on. See [`World::trigger_sticky`].
+#[track_caller]
+pub fn trigger_sticky<E>(event: E) -> impl Command
+where
+    E: EntityEvent + Clone,
+    for<'a> E::Trigger<'a>: Default,
+{
+    let caller = MaybeLocation::caller();
+    move |world: &mut World| {
+        world.trigger_sticky_with_caller(event, caller);
+    }
+}
+
 /// A [`Command`] that writes an arbitrary [`Message`].
 #[track_caller]
 pub fn write_message<M: Message>(message: M) -> impl Command {
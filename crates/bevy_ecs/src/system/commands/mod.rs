@@ -1241,6 +1241,17 @@ impl<'w, 's> Commands<'w, 's> {
         self.queue(command::trigger(event));
     }
 
+    /// Triggers the given [`EntityEvent`] and retains it on its target, so that it is replayed to
+    /// observers added later on. See [`World::trigger_sticky`].
+    #[track_caller]
+    pub fn trigger_sticky<E>(&mut self, event: E)
+    where
+        E: EntityEvent + Clone,
+        for<'a> E::Trigger<'a>: Default,
+    {
+        self.queue(command::trigger_sticky(event));
+    }
+
     /// Triggers the given [`Event`] using the given [`Trigger`], which will run any [`Observer`]s watching for it.
     ///
     /// [`Trigger`]: crate::event::Trigger
@@ -110,34 +110,40 @@ where
     ) -> Result<Self::Out, RunSystemError> {
         // SAFETY: The safety is upheld by the caller.
         let world = unsafe { world.world_mut() };
-        world.last_change_tick_scope(self.system_meta.last_run, |world| {
-            #[cfg(feature = "trace")]
-            let _span_guard = self.system_meta.system_span.enter();
-
-            let params = F::Param::get_param(
-                self.param_state.as_mut().expect(PARAM_MESSAGE),
-                &self.system_meta,
-            )?;
-
-            #[cfg(feature = "hotpatching")]
-            let out = {
-                let mut hot_fn =
-                    subsecond::HotFn::current(<F as ExclusiveSystemParamFunction<Marker>>::run);
-                // SAFETY:
-                // - pointer used to call is from the current jump table
-                unsafe {
-                    hot_fn
-                        .try_call_with_ptr(self.current_ptr, (&mut self.func, world, input, params))
-                        .expect("Error calling hotpatched system. Run a full rebuild")
-                }
-            };
-            #[cfg(not(feature = "hotpatching"))]
-            let out = self.func.run(world, input, params);
-
-            world.flush();
-            self.system_meta.last_run = world.increment_change_tick();
-
-            IntoResult::into_result(out)
+        let source = self.system_meta.name.clone();
+        world.change_source_scope(&source, |world| {
+            world.last_change_tick_scope(self.system_meta.last_run, |world| {
+                #[cfg(feature = "trace")]
+                let _span_guard = self.system_meta.system_span.enter();
+
+                let params = F::Param::get_param(
+                    self.param_state.as_mut().expect(PARAM_MESSAGE),
+                    &self.system_meta,
+                )?;
+
+                #[cfg(feature = "hotpatching")]
+                let out = {
+                    let mut hot_fn =
+                        subsecond::HotFn::current(<F as ExclusiveSystemParamFunction<Marker>>::run);
+                    // SAFETY:
+                    // - pointer used to call is from the current jump table
+                    unsafe {
+                        hot_fn
+                            .try_call_with_ptr(
+                                self.current_ptr,
+                                (&mut self.func, world, input, params),
+                            )
+                            .expect("Error calling hotpatched system. Run a full rebuild")
+                    }
+                };
+                #[cfg(not(feature = "hotpatching"))]
+                let out = self.func.run(world, input, params);
+
+                world.flush();
+                self.system_meta.last_run = world.increment_change_tick();
+
+                IntoResult::into_result(out)
+            })
         })
     }
 
@@ -120,7 +120,9 @@
 //! [`Vec<P>`]: alloc::vec::Vec
 
 mod adapter_system;
+mod async_system;
 mod builder;
+mod cached_query;
 mod combinator;
 mod commands;
 mod exclusive_function_system;
@@ -138,7 +140,9 @@ mod system_registry;
 use core::any::TypeId;
 
 pub use adapter_system::*;
+pub use async_system::*;
 pub use builder::*;
+pub use cached_query::*;
 pub use combinator::*;
 pub use commands::*;
 pub use exclusive_function_system::*;
@@ -0,0 +1,801 @@
+//! An opt-in audit log of the structural changes made to the entities of a [`World`].
+//!
+//! See [`ChangeJournal`] for more information.
+
+use crate::{
+    archetype::Archetype,
+    change_detection::MaybeLocation,
+    component::ComponentId,
+    entity::Entity,
+    resource::IS_RESOURCE,
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+use alloc::{
+    boxed::Box,
+    collections::VecDeque,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_utils::prelude::DebugName;
+use core::fmt;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_platform::sync::Arc,
+    bevy_reflect::{diff::ReflectDiff, PartialReflect, Reflect},
+};
+
+/// A ring buffer recording the structural changes made to the entities of a [`World`]: spawns,
+/// despawns, and component insertions and removals, each tagged with the system that caused it.
+///
+/// This is meant for debugging, for example to find out where two worlds that should be identical
+/// started to diverge. Recording has a cost, so the journal is disabled by default: enable it with
+/// [`World::enable_change_journal`], and read it back with [`World::change_journal`].
+///
+/// Once the journal holds [`capacity`](Self::capacity) entries, each new entry evicts the oldest
+/// one. Every entry gets a [`sequence`](JournalEntry::sequence) number, which keeps increasing
+/// across evictions and [`clear`](Self::clear)s, so that [`since`](Self::since) can be used to
+/// read only the entries recorded after a previous read.
+///
+/// Changes made to resources are not recorded. With the `bevy_reflect` feature,
+/// [`with_values`](Self::with_values) also records the [`Debug`](core::fmt::Debug) representation
+/// of the inserted and removed component values, and the [`ComponentDiff`] of the replaced ones,
+/// for components registered in the [`AppTypeRegistry`](crate::reflect::AppTypeRegistry) with
+/// [`ReflectFromPtr`](bevy_reflect::ReflectFromPtr). Component and system names are only available
+/// with the `debug` feature.
+///
+/// With the `serialize` feature, the journal and its entries can be serialized with `serde`, to
+/// formats such as RON or JSON.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, world::{ChangeJournal, EntityChange}};
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.enable_change_journal(ChangeJournal::new(1024));
+///
+/// let entity = world.spawn(Health(10)).id();
+/// world.entity_mut(entity).remove::<Health>();
+/// world.despawn(entity);
+///
+/// let journal = world.change_journal().unwrap();
+/// let changes: Vec<_> = journal.entries_for(entity).map(|entry| &entry.change).collect();
+/// assert!(matches!(changes[0], EntityChange::Spawned));
+/// assert!(matches!(changes[1], EntityChange::Inserted { .. }));
+/// assert!(matches!(changes[2], EntityChange::Removed { .. }));
+/// assert!(matches!(changes[3], EntityChange::Despawned));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ChangeJournal {
+    capacity: usize,
+    record_values: bool,
+    next_sequence: u64,
+    entries: VecDeque<JournalEntry>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    source: Option<String>,
+}
+
+/// A change recorded in a [`ChangeJournal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct JournalEntry {
+    /// The position of this entry among all the entries recorded by the journal.
+    pub sequence: u64,
+    /// The change tick of the world when the change was made.
+    pub tick: u32,
+    /// The entity that changed.
+    pub entity: Entity,
+    /// What changed.
+    pub change: EntityChange,
+    /// The name of the system whose commands, or exclusive access to the world, made the change,
+    /// if it was made by a system.
+    pub source: Option<String>,
+    /// The location of the code that made the change, with the `track_location` feature.
+    pub caller: Option<String>,
+}
+
+/// A structural change to an entity, recorded in a [`ChangeJournal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum EntityChange {
+    /// The entity was spawned. Its initial components are recorded as [`Inserted`](Self::Inserted).
+    Spawned,
+    /// The entity was despawned. Its last components are recorded as [`Removed`](Self::Removed)
+    /// right before.
+    Despawned,
+    /// A component was added to the entity.
+    Inserted {
+        /// The name of the component.
+        component: String,
+        /// The inserted value, if values are recorded.
+        value: Option<String>,
+    },
+    /// A component already on the entity was replaced by a new value.
+    Replaced {
+        /// The name of the component.
+        component: String,
+        /// The changes made to the previous value, if values are recorded.
+        diff: Option<ComponentDiff>,
+        /// The new value, if values are recorded but the previous value couldn't be cloned
+        /// to compute the `diff`.
+        value: Option<String>,
+        /// The previous value, if values are recorded but it couldn't be cloned to compute
+        /// the `diff`.
+        previous: Option<String>,
+    },
+    /// A component was removed from the entity.
+    Removed {
+        /// The name of the component.
+        component: String,
+        /// The removed value, if values are recorded.
+        value: Option<String>,
+    },
+}
+
+/// The changes made to a component when it was replaced, recorded in [`EntityChange::Replaced`].
+///
+/// With the `bevy_reflect` feature, this holds the [`ReflectDiff`] from the previous value to the
+/// new one, which can be applied to a copy of the previous value. Diffs are compared, formatted and
+/// serialized through the [`Debug`](core::fmt::Debug) representation of the [`ReflectDiff`], since
+/// serializing the diff itself requires a [`TypeRegistry`](bevy_reflect::TypeRegistry).
+/// Deserialized diffs only keep that representation.
+#[derive(Clone)]
+pub struct ComponentDiff {
+    #[cfg(feature = "bevy_reflect")]
+    diff: Option<Arc<ReflectDiff>>,
+    text: String,
+}
+
+impl ComponentDiff {
+    /// Creates a component diff holding `diff`.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn new(diff: ReflectDiff) -> Self {
+        Self {
+            text: alloc::format!("{diff:?}"),
+            diff: Some(Arc::new(diff)),
+        }
+    }
+
+    /// Returns the [`ReflectDiff`], unless this diff was deserialized.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn diff(&self) -> Option<&ReflectDiff> {
+        self.diff.as_deref()
+    }
+
+    /// Returns the [`Debug`](core::fmt::Debug) representation of the diff.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl PartialEq for ComponentDiff {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Eq for ComponentDiff {}
+
+impl fmt::Debug for ComponentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl fmt::Display for ComponentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl Serialize for ComponentDiff {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> Deserialize<'de> for ComponentDiff {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            #[cfg(feature = "bevy_reflect")]
+            diff: None,
+            text: String::deserialize(deserializer)?,
+        })
+    }
+}
+
+/// A component value captured by [`ChangeJournal::replaced_values`] before it is replaced.
+pub(crate) enum PreviousValue {
+    /// A copy of the value, to compute the diff with the new value.
+    #[cfg(feature = "bevy_reflect")]
+    Reflected(Box<dyn PartialReflect>),
+    /// The [`Debug`](core::fmt::Debug) representation of the value, if it couldn't be copied.
+    Debug(String),
+}
+
+impl ChangeJournal {
+    /// Creates a journal keeping up to the `capacity` most recent entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            record_values: false,
+            next_sequence: 0,
+            entries: VecDeque::new(),
+            source: None,
+        }
+    }
+
+    /// Sets whether the values of the components are recorded along with the changes.
+    ///
+    /// This requires the `bevy_reflect` feature, and only applies to components registered in the
+    /// [`AppTypeRegistry`](crate::reflect::AppTypeRegistry) with
+    /// [`ReflectFromPtr`](bevy_reflect::ReflectFromPtr).
+    pub fn with_values(mut self, record_values: bool) -> Self {
+        self.record_values = record_values;
+        self
+    }
+
+    /// Returns the maximum number of entries kept by the journal.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of entries kept by the journal, evicting the oldest entries if
+    /// there are more.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.entries.len().saturating_sub(capacity);
+        self.entries.drain(..excess);
+    }
+
+    /// Returns `true` if the values of the components are recorded along with the changes.
+    pub fn records_values(&self) -> bool {
+        self.record_values
+    }
+
+    /// Returns the number of entries in the journal.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the journal has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the [`sequence`](JournalEntry::sequence) number the next entry will get.
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    /// Returns an iterator over the entries, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &JournalEntry> {
+        self.entries.iter()
+    }
+
+    /// Returns an iterator over the entries whose [`sequence`](JournalEntry::sequence) number is
+    /// at least `sequence`, oldest first.
+    pub fn since(&self, sequence: u64) -> impl DoubleEndedIterator<Item = &JournalEntry> {
+        let start = self
+            .entries
+            .partition_point(|entry| entry.sequence < sequence);
+        self.entries.range(start..)
+    }
+
+    /// Returns an iterator over the entries recorded for `entity`, oldest first.
+    pub fn entries_for(&self, entity: Entity) -> impl DoubleEndedIterator<Item = &JournalEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.entity == entity)
+    }
+
+    /// Removes all the entries. The [`sequence`](JournalEntry::sequence) numbers keep increasing.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn push(
+        &mut self,
+        entity: Entity,
+        tick: u32,
+        changes: impl IntoIterator<Item = EntityChange>,
+        caller: MaybeLocation,
+    ) {
+        let caller = caller.into_option().map(ToString::to_string);
+        for change in changes {
+            if self.entries.len() >= self.capacity {
+                self.entries.pop_front();
+            }
+            if self.capacity > 0 {
+                self.entries.push_back(JournalEntry {
+                    sequence: self.next_sequence,
+                    tick,
+                    entity,
+                    change,
+                    source: self.source.clone(),
+                    caller: caller.clone(),
+                });
+            }
+            self.next_sequence += 1;
+        }
+    }
+
+    /// Returns whether values are recorded if the changes made to `archetype` should be recorded.
+    ///
+    /// # Safety
+    /// There must be no outstanding mutable references to the journal.
+    #[inline]
+    unsafe fn recording(world: UnsafeWorldCell<'_>, archetype: &Archetype) -> Option<bool> {
+        // SAFETY: Upheld by the caller.
+        let journal = unsafe { world.change_journal() }?;
+        (!archetype.contains(IS_RESOURCE)).then_some(journal.record_values)
+    }
+
+    /// # Safety
+    /// There must be no outstanding references to the journal.
+    unsafe fn record(
+        world: UnsafeWorldCell<'_>,
+        entity: Entity,
+        changes: Vec<EntityChange>,
+        caller: MaybeLocation,
+    ) {
+        let tick = world.change_tick().get();
+        // SAFETY: Upheld by the caller.
+        if let Some(journal) = unsafe { world.change_journal_mut() } {
+            journal.push(entity, tick, changes, caller);
+        }
+    }
+
+    /// Records the spawn of `entity`, with the given `components`.
+    ///
+    /// # Safety
+    /// - `entity` must be spawned in `archetype`, which must contain the `components`.
+    /// - There must be no outstanding references to the journal, nor mutable references to the
+    ///   components of `entity`.
+    pub(crate) unsafe fn record_spawn(
+        world: UnsafeWorldCell<'_>,
+        entity: Entity,
+        archetype: &Archetype,
+        components: impl Iterator<Item = ComponentId>,
+        caller: MaybeLocation,
+    ) {
+        // SAFETY: Upheld by the caller.
+        let Some(record_values) = (unsafe { Self::recording(world, archetype) }) else {
+            return;
+        };
+        let mut changes = Vec::from([EntityChange::Spawned]);
+        changes.extend(components.map(|id| {
+            EntityChange::Inserted {
+                component: component_name(world, id),
+                // SAFETY: Upheld by the caller.
+                value: record_values
+                    .then(|| unsafe { component_value(world, entity, id) })
+                    .flatten(),
+            }
+        }));
+        // SAFETY: Upheld by the caller.
+        unsafe { Self::record(world, entity, changes, caller) };
+    }
+
+    /// Returns the values of the `components` of `entity` that are about to be replaced, if values
+    /// are recorded.
+    ///
+    /// # Safety
+    /// - `entity` must be spawned in `archetype`, which must contain the `components`.
+    /// - There must be no outstanding mutable references to the journal, nor to the components of
+    ///   `entity`.
+    #[inline]
+    pub(crate) unsafe fn replaced_values(
+        world: UnsafeWorldCell<'_>,
+        entity: Entity,
+        archetype: &Archetype,
+        components: &[ComponentId],
+    ) -> Vec<Option<PreviousValue>> {
+        // SAFETY: Upheld by the caller.
+        if unsafe { Self::recording(world, archetype) } != Some(true) {
+            return Vec::new();
+        }
+        components
+            .iter()
+            // SAFETY: Upheld by the caller.
+            .map(|&id| unsafe { previous_value(world, entity, id) })
+            .collect()
+    }
+
+    /// Records the insertion of the `added` components to `entity`, and the replacement of the
+    /// `replaced` ones, whose previous values were returned by [`Self::replaced_values`].
+    ///
+    /// # Safety
+    /// - `entity` must be spawned in `archetype`, which must contain the `added` and `replaced`
+    ///   components.
+    /// - There must be no outstanding references to the journal, nor mutable references to the
+    ///   components of `entity`.
+    pub(crate) unsafe fn record_insert(
+        world: UnsafeWorldCell<'_>,
+        entity: Entity,
+        archetype: &Archetype,
+        added: &[ComponentId],
+        replaced: &[ComponentId],
+        previous: Vec<Option<PreviousValue>>,
+        caller: MaybeLocation,
+    ) {
+        // SAFETY: Upheld by the caller.
+        let Some(record_values) = (unsafe { Self::recording(world, archetype) }) else {
+            return;
+        };
+        let value = |id| {
+            record_values
+                // SAFETY: Upheld by the caller.
+                .then(|| unsafe { component_value(world, entity, id) })
+                .flatten()
+        };
+        let replaced = replaced
+            .iter()
+            .zip(previous.into_iter().chain(core::iter::repeat_with(|| None)));
+        let changes = added
+            .iter()
+            .map(|&id| EntityChange::Inserted {
+                component: component_name(world, id),
+                value: value(id),
+            })
+            .chain(replaced.map(|(&id, previous)| {
+                let (diff, previous) = match previous {
+                    #[cfg(feature = "bevy_reflect")]
+                    Some(PreviousValue::Reflected(previous)) => {
+                        // SAFETY: Upheld by the caller.
+                        let diff = unsafe {
+                            reflect_component(world, entity, id, |value| {
+                                ComponentDiff::new(previous.diff(value.as_partial_reflect()))
+                            })
+                        };
+                        (diff, None)
+                    }
+                    Some(PreviousValue::Debug(previous)) => (None, Some(previous)),
+                    None => (None, None),
+                };
+                EntityChange::Replaced {
+                    component: component_name(world, id),
+                    value: diff.is_none().then(|| value(id)).flatten(),
+                    diff,
+                    previous,
+                }
+            }))
+            .collect();
+        // SAFETY: Upheld by the caller.
+        unsafe { Self::record(world, entity, changes, caller) };
+    }
+
+    /// Records the removal of the `components` of `entity`, followed by its despawn if `despawned`
+    /// is `true`.
+    ///
+    /// # Safety
+    /// - `entity` must be spawned in `archetype`, which must contain the `components`.
+    /// - There must be no outstanding references to the journal, nor mutable references to the
+    ///   components of `entity`.
+    pub(crate) unsafe fn record_remove(
+        world: UnsafeWorldCell<'_>,
+        entity: Entity,
+        archetype: &Archetype,
+        components: impl Iterator<Item = ComponentId>,
+        despawned: bool,
+        caller: MaybeLocation,
+    ) {
+        // SAFETY: Upheld by the caller.
+        let Some(record_values) = (unsafe { Self::recording(world, archetype) }) else {
+            return;
+        };
+        let mut changes: Vec<_> = components
+            .map(|id| EntityChange::Removed {
+                component: component_name(world, id),
+                // SAFETY: Upheld by the caller.
+                value: record_values
+                    .then(|| unsafe { component_value(world, entity, id) })
+                    .flatten(),
+            })
+            .collect();
+        if despawned {
+            changes.push(EntityChange::Despawned);
+        }
+        // SAFETY: Upheld by the caller.
+        unsafe { Self::record(world, entity, changes, caller) };
+    }
+}
+
+fn component_name(world: UnsafeWorldCell<'_>, id: ComponentId) -> String {
+    world
+        .components()
+        .get_name(id)
+        .map(|name| name.to_string())
+        .unwrap_or_default()
+}
+
+/// Calls `f` with the component `id` of `entity`, obtained through reflection.
+///
+/// # Safety
+/// There must be no outstanding mutable references to the component, nor to the
+/// [`AppTypeRegistry`](crate::reflect::AppTypeRegistry).
+#[cfg(feature = "bevy_reflect")]
+unsafe fn reflect_component<R>(
+    world: UnsafeWorldCell<'_>,
+    entity: Entity,
+    id: ComponentId,
+    f: impl FnOnce(&dyn Reflect) -> R,
+) -> Option<R> {
+    use bevy_reflect::ReflectFromPtr;
+
+    let type_id = world.components().get_info(id)?.type_id()?;
+    // SAFETY: Upheld by the caller.
+    let registry = unsafe { world.get_resource::<crate::reflect::AppTypeRegistry>() }?;
+    let registry = registry.read();
+    let from_ptr = registry.get_type_data::<ReflectFromPtr>(type_id)?;
+    // SAFETY: Upheld by the caller.
+    let ptr = unsafe { world.get_entity(entity).ok()?.get_by_id(id) }?;
+    // SAFETY: `from_ptr` was registered for the type of the component `ptr` points to.
+    Some(f(unsafe { from_ptr.as_reflect(ptr) }))
+}
+
+/// Returns the [`Debug`](core::fmt::Debug) representation of the component `id` of `entity`,
+/// obtained through reflection.
+///
+/// # Safety
+/// There must be no outstanding mutable references to the component, nor to the
+/// [`AppTypeRegistry`](crate::reflect::AppTypeRegistry).
+unsafe fn component_value(
+    world: UnsafeWorldCell<'_>,
+    entity: Entity,
+    id: ComponentId,
+) -> Option<String> {
+    #[cfg(feature = "bevy_reflect")]
+    {
+        // SAFETY: Upheld by the caller.
+        unsafe { reflect_component(world, entity, id, |value| alloc::format!("{value:?}")) }
+    }
+    #[cfg(not(feature = "bevy_reflect"))]
+    {
+        let _ = (world, entity, id);
+        None
+    }
+}
+
+/// Captures the component `id` of `entity` before it is replaced, cloning it through reflection
+/// if possible.
+///
+/// # Safety
+/// There must be no outstanding mutable references to the component, nor to the
+/// [`AppTypeRegistry`](crate::reflect::AppTypeRegistry).
+unsafe fn previous_value(
+    world: UnsafeWorldCell<'_>,
+    entity: Entity,
+    id: ComponentId,
+) -> Option<PreviousValue> {
+    #[cfg(feature = "bevy_reflect")]
+    {
+        // SAFETY: Upheld by the caller.
+        unsafe {
+            reflect_component(world, entity, id, |value| match value.reflect_clone() {
+                Ok(value) => PreviousValue::Reflected(value.into_partial_reflect()),
+                Err(_) => PreviousValue::Debug(alloc::format!("{value:?}")),
+            })
+        }
+    }
+    #[cfg(not(feature = "bevy_reflect"))]
+    {
+        // SAFETY: Upheld by the caller.
+        unsafe { component_value(world, entity, id) }.map(PreviousValue::Debug)
+    }
+}
+
+impl World {
+    /// Starts recording the structural changes made to the entities of this world in `journal`,
+    /// replacing the current journal if there is one.
+    ///
+    /// See [`ChangeJournal`] for more information.
+    pub fn enable_change_journal(&mut self, journal: ChangeJournal) {
+        self.change_journal = Some(Box::new(journal));
+    }
+
+    /// Stops recording the structural changes made to the entities of this world, returning the
+    /// journal if there was one.
+    pub fn disable_change_journal(&mut self) -> Option<ChangeJournal> {
+        self.change_journal.take().map(|journal| *journal)
+    }
+
+    /// Returns the [`ChangeJournal`] of this world, if it is enabled.
+    pub fn change_journal(&self) -> Option<&ChangeJournal> {
+        self.change_journal.as_deref()
+    }
+
+    /// Returns the [`ChangeJournal`] of this world mutably, if it is enabled.
+    pub fn change_journal_mut(&mut self) -> Option<&mut ChangeJournal> {
+        self.change_journal.as_deref_mut()
+    }
+
+    /// Runs `f`, attributing the changes recorded in the [`ChangeJournal`] to the system named
+    /// `source`.
+    #[inline]
+    pub(crate) fn change_source_scope<R>(
+        &mut self,
+        source: &DebugName,
+        f: impl FnOnce(&mut World) -> R,
+    ) -> R {
+        let Some(journal) = self.change_journal.as_deref_mut() else {
+            return f(self);
+        };
+        let previous = journal.source.replace(source.to_string());
+        let out = f(self);
+        if let Some(journal) = self.change_journal.as_deref_mut() {
+            journal.source = previous;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec, vec::Vec};
+
+    use super::{ChangeJournal, EntityChange};
+    use crate::prelude::*;
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Resource)]
+    struct R;
+
+    fn kinds(world: &World, entity: Entity) -> Vec<&'static str> {
+        world
+            .change_journal()
+            .unwrap()
+            .entries_for(entity)
+            .map(|entry| match entry.change {
+                EntityChange::Spawned => "spawned",
+                EntityChange::Despawned => "despawned",
+                EntityChange::Inserted { .. } => "inserted",
+                EntityChange::Replaced { .. } => "replaced",
+                EntityChange::Removed { .. } => "removed",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn records_structural_changes() {
+        let mut world = World::new();
+        world.spawn(A(0));
+        world.enable_change_journal(ChangeJournal::new(64));
+
+        let entity = world.spawn(A(1)).id();
+        world.entity_mut(entity).insert((A(2), B));
+        world.entity_mut(entity).insert_if_new(A(3));
+        world.entity_mut(entity).remove::<B>();
+        world.insert_resource(R);
+        world.despawn(entity);
+        let empty = world.spawn_empty().id();
+
+        assert_eq!(
+            kinds(&world, entity),
+            vec![
+                "spawned",
+                "inserted",
+                "inserted",
+                "replaced",
+                "removed",
+                "removed",
+                "despawned"
+            ]
+        );
+        assert_eq!(kinds(&world, empty), vec!["spawned"]);
+
+        let journal = world.change_journal().unwrap();
+        assert_eq!(journal.len(), 8);
+        let sequences: Vec<_> = journal.iter().map(|entry| entry.sequence).collect();
+        assert_eq!(sequences, (0..8).collect::<Vec<_>>());
+        assert_eq!(journal.since(6).count(), 2);
+
+        world.disable_change_journal();
+        world.spawn(A(4));
+        assert!(world.change_journal().is_none());
+    }
+
+    #[test]
+    fn ring_buffer_evicts_oldest_entries() {
+        let mut world = World::new();
+        world.enable_change_journal(ChangeJournal::new(3));
+        for _ in 0..3 {
+            world.spawn(B);
+        }
+
+        let journal = world.change_journal_mut().unwrap();
+        assert_eq!(journal.len(), 3);
+        assert_eq!(journal.next_sequence(), 6);
+        assert_eq!(journal.iter().next().unwrap().sequence, 3);
+        assert_eq!(journal.since(0).count(), 3);
+
+        journal.set_capacity(1);
+        assert_eq!(journal.iter().next().unwrap().sequence, 5);
+        journal.clear();
+        assert!(journal.is_empty());
+        assert_eq!(journal.next_sequence(), 6);
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn tags_changes_with_their_system() {
+        fn spawn_a(mut commands: Commands) {
+            commands.spawn(A(0));
+        }
+
+        fn spawn_b(world: &mut World) {
+            world.spawn(B);
+        }
+
+        let mut world = World::new();
+        world.enable_change_journal(ChangeJournal::new(64));
+        let mut schedule = Schedule::default();
+        schedule.add_systems((spawn_a, spawn_b).chain());
+        schedule.run(&mut world);
+        world.spawn(B);
+
+        let journal = world.change_journal().unwrap();
+        let sources: Vec<_> = journal
+            .iter()
+            .filter(|entry| entry.change == EntityChange::Spawned)
+            .map(|entry| entry.source.as_deref())
+            .collect();
+        assert_eq!(sources.len(), 3);
+        assert!(sources[0].unwrap().ends_with("spawn_a"));
+        assert!(sources[1].unwrap().ends_with("spawn_b"));
+        assert_eq!(sources[2], None);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn records_reflected_values() {
+        use bevy_reflect::PartialReflect;
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.resource::<AppTypeRegistry>().write().register::<A>();
+        world.enable_change_journal(ChangeJournal::new(64).with_values(true));
+
+        let entity = world.spawn((A(1), B)).id();
+        world.entity_mut(entity).insert(A(2));
+        world.entity_mut(entity).remove::<A>();
+
+        let journal = world.change_journal().unwrap();
+        let values: Vec<_> = journal
+            .iter()
+            .filter_map(|entry| match &entry.change {
+                EntityChange::Inserted { value, .. } | EntityChange::Removed { value, .. } => {
+                    Some(value.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        let a = |value: u32| Some(format!("{}::A({value})", module_path!()));
+        assert_eq!(values, vec![a(1), None, a(2)]);
+
+        let Some(EntityChange::Replaced {
+            diff: Some(diff),
+            value: None,
+            previous: None,
+            ..
+        }) = journal.iter().map(|entry| &entry.change).nth(3)
+        else {
+            panic!("the replaced value should be recorded as a diff");
+        };
+        let mut value = A(1);
+        value.apply_diff(diff.diff().unwrap()).unwrap();
+        assert_eq!(value, A(2));
+    }
+}
@@ -370,10 +370,10 @@ impl Drop for CommandQueue {
 
 impl SystemBuffer for CommandQueue {
     #[inline]
-    fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+    fn apply(&mut self, system_meta: &SystemMeta, world: &mut World) {
         #[cfg(feature = "trace")]
-        let _span_guard = _system_meta.commands_span.enter();
-        self.apply(world);
+        let _span_guard = system_meta.commands_span.enter();
+        world.change_source_scope(system_meta.name(), |world| self.apply(world));
     }
 
     #[inline]
@@ -19,9 +19,9 @@ use crate::{
     system::EntityCommands,
     template::{SceneEntityReferences, Template, TemplateContext},
     world::{
-        error::EntityComponentError, unsafe_world_cell::UnsafeEntityCell, ComponentEntry,
-        DynamicComponentFetch, EntityMut, EntityRef, FilteredEntityMut, FilteredEntityRef, Mut,
-        OccupiedComponentEntry, Ref, VacantComponentEntry, World,
+        error::EntityComponentError, unsafe_world_cell::UnsafeEntityCell, ChangeJournal,
+        ComponentEntry, DynamicComponentFetch, EntityMut, EntityRef, FilteredEntityMut,
+        FilteredEntityRef, Mut, OccupiedComponentEntry, Ref, VacantComponentEntry, World,
     },
 };
 
@@ -1666,6 +1666,20 @@ impl<'w> EntityWorldMut<'w> {
             // If there is no location, we are already despawned
             return;
         };
+        let world = self.world.as_unsafe_world_cell();
+        // SAFETY: The entity is spawned in this archetype, and there are no outstanding
+        // references to the world.
+        unsafe {
+            let archetype = &world.archetypes()[location.archetype_id];
+            ChangeJournal::record_remove(
+                world,
+                self.entity,
+                archetype,
+                archetype.iter_components(),
+                true,
+                caller,
+            );
+        }
         let archetype = &self.world.archetypes[location.archetype_id];
 
         // SAFETY: Archetype cannot be mutably aliased by DeferredWorld
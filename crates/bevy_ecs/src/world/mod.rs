@@ -1,11 +1,13 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+mod change_journal;
 pub(crate) mod command_queue;
 mod deferred_world;
 mod entity_access;
 mod entity_fetch;
 mod filtered_resource;
 mod identifier;
+mod snapshot;
 mod spawn_batch;
 
 pub mod error;
@@ -18,6 +20,7 @@ pub use crate::{
     world::command_queue::CommandQueue,
 };
 pub use bevy_ecs_macros::FromWorld;
+pub use change_journal::*;
 pub use deferred_world::DeferredWorld;
 pub use entity_access::{
     ComponentEntry, DynamicComponentFetch, EntityMut, EntityMutExcept, EntityRef, EntityRefExcept,
@@ -27,10 +30,11 @@ pub use entity_access::{
 pub use entity_fetch::{EntityFetcher, WorldEntityFetch};
 pub use filtered_resource::*;
 pub use identifier::WorldId;
+pub use snapshot::*;
 pub use spawn_batch::*;
 
 use crate::{
-    archetype::{ArchetypeId, Archetypes},
+    archetype::{ArchetypeFlags, ArchetypeId, Archetypes},
     bundle::{
         Bundle, BundleId, BundleInfo, BundleInserter, BundleSpawner, Bundles, DynamicBundle,
         InsertMode, NoBundleEffect,
@@ -39,9 +43,9 @@ use crate::{
         CheckChangeTicks, ComponentTicks, ComponentTicksMut, MaybeLocation, MutUntyped, Tick,
     },
     component::{
-        Component, ComponentDescriptor, ComponentId, ComponentIds, ComponentInfo, Components,
-        ComponentsQueuedRegistrator, ComponentsRegistrator, Mutable, RequiredComponents,
-        RequiredComponentsError,
+        ArchetypeInvariant, Component, ComponentDescriptor, ComponentId, ComponentIds,
+        ComponentInfo, Components, ComponentsQueuedRegistrator, ComponentsRegistrator,
+        InvariantViolationBehavior, Mutable, RequiredComponents, RequiredComponentsError,
     },
     entity::{Entities, Entity, EntityAllocator, EntityNotSpawnedError, SpawnError},
     entity_disabling::DefaultQueryFilters,
@@ -107,6 +111,7 @@ pub struct World {
     pub(crate) last_check_tick: Tick,
     pub(crate) last_trigger_id: u32,
     pub(crate) command_queue: RawCommandQueue,
+    pub(crate) change_journal: Option<Box<ChangeJournal>>,
 }
 
 impl Default for World {
@@ -129,6 +134,7 @@ impl Default for World {
             last_check_tick: Tick::new(0),
             last_trigger_id: 0,
             command_queue: RawCommandQueue::new(),
+            change_journal: None,
             component_ids: ComponentIds::default(),
         };
         world.bootstrap();
@@ -593,6 +599,93 @@ impl World {
         Some(component_info.required_components())
     }
 
+    /// Registers an [`ArchetypeInvariant`], which is checked whenever components are inserted into
+    /// or removed from an entity with any of the invariant's components.
+    ///
+    /// See [`World::register_exclusive_components`] and [`World::register_exactly_one_component`]
+    /// for typed shorthands.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the components of the invariant have not been registered.
+    pub fn register_archetype_invariant(&mut self, invariant: ArchetypeInvariant) {
+        for id in self.components.register_archetype_invariant(invariant) {
+            self.archetypes
+                .update_flags(id, ArchetypeFlags::HAS_INVARIANTS, true);
+        }
+    }
+
+    /// Registers the components of the bundle `B` as mutually exclusive:
+    /// no entity may have more than one of them.
+    ///
+    /// See [`ArchetypeInvariant`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::component::InvariantViolationBehavior;
+    /// #[derive(Component)]
+    /// struct Grounded;
+    ///
+    /// #[derive(Component)]
+    /// struct Airborne;
+    ///
+    /// # let mut world = World::default();
+    /// world.register_exclusive_components::<(Grounded, Airborne)>(InvariantViolationBehavior::Panic);
+    ///
+    /// let player = world.spawn(Grounded).id();
+    /// // This would panic, since the player can't be both grounded and airborne.
+    /// // world.entity_mut(player).insert(Airborne);
+    /// ```
+    pub fn register_exclusive_components<B: Bundle>(
+        &mut self,
+        behavior: InvariantViolationBehavior,
+    ) {
+        let components = B::component_ids(&mut self.components_registrator()).collect::<Vec<_>>();
+        self.register_archetype_invariant(
+            ArchetypeInvariant::at_most_one(components).with_behavior(behavior),
+        );
+    }
+
+    /// Requires entities with the component `S` to have exactly one of the components of the bundle `B`.
+    ///
+    /// See [`ArchetypeInvariant`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::component::InvariantViolationBehavior;
+    /// #[derive(Component)]
+    /// struct Character;
+    ///
+    /// #[derive(Component)]
+    /// struct Grounded;
+    ///
+    /// #[derive(Component)]
+    /// struct Airborne;
+    ///
+    /// # let mut world = World::default();
+    /// world.register_exactly_one_component::<Character, (Grounded, Airborne)>(
+    ///     InvariantViolationBehavior::AutoRemove,
+    /// );
+    ///
+    /// let player = world.spawn((Character, Grounded)).id();
+    /// world.entity_mut(player).insert(Airborne);
+    /// assert!(!world.entity(player).contains::<Grounded>());
+    /// ```
+    pub fn register_exactly_one_component<S: Component, B: Bundle>(
+        &mut self,
+        behavior: InvariantViolationBehavior,
+    ) {
+        let scope = self.register_component::<S>();
+        let components = B::component_ids(&mut self.components_registrator()).collect::<Vec<_>>();
+        self.register_archetype_invariant(
+            ArchetypeInvariant::exactly_one(scope, components).with_behavior(behavior),
+        );
+    }
+
     /// Registers a new [`Component`] type and returns the [`ComponentId`] created for it.
     ///
     /// This method differs from [`World::register_component`] in that it uses a [`ComponentDescriptor`]
@@ -1142,7 +1235,33 @@ impl World {
         caller: MaybeLocation,
     ) -> Result<EntityWorldMut<'_>, SpawnError> {
         self.entities.check_can_spawn_at(entity)?;
-        Ok(self.spawn_empty_at_unchecked(entity, caller))
+        Ok(self.spawn_empty_at_recorded(entity, caller))
+    }
+
+    /// Like [`spawn_empty_at_unchecked`](Self::spawn_empty_at_unchecked), but records the spawn in
+    /// the [`ChangeJournal`].
+    fn spawn_empty_at_recorded(
+        &mut self,
+        entity: Entity,
+        caller: MaybeLocation,
+    ) -> EntityWorldMut<'_> {
+        if self.change_journal.is_none() {
+            return self.spawn_empty_at_unchecked(entity, caller);
+        }
+        self.spawn_empty_at_unchecked(entity, caller);
+        let world_cell = self.as_unsafe_world_cell();
+        // SAFETY: The entity was just spawned in the empty archetype, and there are no outstanding
+        // references to the world.
+        unsafe {
+            ChangeJournal::record_spawn(
+                world_cell,
+                entity,
+                world_cell.archetypes().empty(),
+                core::iter::empty(),
+                caller,
+            );
+        }
+        self.entity_mut(entity)
     }
 
     /// A faster version of [`spawn_at_unchecked`](Self::spawn_at_unchecked) for the empty bundle.
@@ -1285,7 +1404,7 @@ impl World {
     pub(crate) fn spawn_empty_with_caller(&mut self, caller: MaybeLocation) -> EntityWorldMut<'_> {
         let entity = self.entity_allocator.alloc();
         // This was just spawned from null, so it shouldn't panic.
-        self.spawn_empty_at_unchecked(entity, caller)
+        self.spawn_empty_at_recorded(entity, caller)
     }
 
     /// Spawns a batch of entities with the same component [`Bundle`] type. Takes a given
@@ -2991,7 +3110,9 @@ impl World {
             self.get_entity_mut(entity)
                 .expect("ResourceCache is in sync")
         } else {
-            self.spawn_empty()
+            // Resource entities are not recorded in the change journal.
+            let entity = self.entity_allocator.alloc();
+            self.spawn_empty_at_unchecked(entity, caller)
         };
         // SAFETY: pointer valid for this component id per precondition
         unsafe {
@@ -0,0 +1,1165 @@
+//! Capturing and restoring the values of selected components and resources.
+//!
+//! See [`WorldSnapshot`] for more information.
+
+use crate::{
+    archetype::ArchetypeEntity,
+    bundle::Bundle,
+    change_detection::{CheckChangeTicks, ComponentTicks, MaybeLocation, Tick},
+    component::{ComponentCloneBehavior, ComponentId, ComponentInfo},
+    entity::{ComponentValueCloner, Entity, EntityIndexSet, EntityMapper},
+    query::DebugCheckedUnwrap,
+    resource::{Resource, IS_RESOURCE},
+    storage::{Column, TableRow},
+    world::{
+        unsafe_world_cell::{get_component_and_ticks, UnsafeWorldCell},
+        World, WorldId,
+    },
+};
+use alloc::vec::Vec;
+use bevy_ptr::{OwningPtr, PtrMut};
+use bumpalo::Bump;
+use fixedbitset::FixedBitSet;
+use nonmax::NonMaxU32;
+use thiserror::Error;
+
+/// Selects the components and resources captured by [`World::capture_snapshot`].
+///
+/// Build it using [`SnapshotConfig::build`], which returns a [`SnapshotConfigBuilder`].
+#[derive(Clone, Debug)]
+pub struct SnapshotConfig {
+    world_id: WorldId,
+    components: Vec<ComponentId>,
+    resources: Vec<ComponentId>,
+    despawn_untracked: bool,
+}
+
+impl SnapshotConfig {
+    /// Returns a new [`SnapshotConfigBuilder`] for the given `world`.
+    ///
+    /// A [`SnapshotConfig`] can only be used with the world it was built for.
+    pub fn build(world: &mut World) -> SnapshotConfigBuilder<'_> {
+        SnapshotConfigBuilder {
+            config: SnapshotConfig {
+                world_id: world.id(),
+                components: Vec::new(),
+                resources: Vec::new(),
+                despawn_untracked: false,
+            },
+            world,
+        }
+    }
+
+    /// Returns the components captured on entities.
+    pub fn components(&self) -> &[ComponentId] {
+        &self.components
+    }
+
+    /// Returns the captured resources.
+    pub fn resources(&self) -> &[ComponentId] {
+        &self.resources
+    }
+
+    /// Returns `true` if restoring a snapshot despawns entities that were not captured in it.
+    ///
+    /// See [`SnapshotConfigBuilder::despawn_untracked`].
+    pub fn despawns_untracked(&self) -> bool {
+        self.despawn_untracked
+    }
+}
+
+/// A builder for configuring [`SnapshotConfig`]. See [`WorldSnapshot`] for more information.
+pub struct SnapshotConfigBuilder<'w> {
+    world: &'w mut World,
+    config: SnapshotConfig,
+}
+
+impl<'w> SnapshotConfigBuilder<'w> {
+    /// Captures every component in the bundle `B` on any entity that has it.
+    pub fn allow<B: Bundle>(&mut self) -> &mut Self {
+        let ids = B::component_ids(&mut self.world.components_registrator());
+        self.allow_by_ids(ids)
+    }
+
+    /// Captures the components with the given `ids` on any entity that has them.
+    ///
+    /// # Panics
+    /// Panics if any of the `ids` is not registered in the world.
+    pub fn allow_by_ids(&mut self, ids: impl IntoIterator<Item = ComponentId>) -> &mut Self {
+        for id in ids {
+            assert!(
+                self.world.components().get_info(id).is_some(),
+                "Component {id:?} is not registered in the world."
+            );
+            if !self.config.components.contains(&id) {
+                self.config.components.push(id);
+            }
+        }
+        self
+    }
+
+    /// Captures the resource `R`.
+    pub fn allow_resource<R: Resource>(&mut self) -> &mut Self {
+        let id = self
+            .world
+            .components_registrator()
+            .register_component::<R>();
+        self.allow_resource_by_id(id)
+    }
+
+    /// Captures the resource with the given `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` is not registered in the world.
+    pub fn allow_resource_by_id(&mut self, id: ComponentId) -> &mut Self {
+        assert!(
+            self.world.components().get_info(id).is_some(),
+            "Resource {id:?} is not registered in the world."
+        );
+        if !self.config.resources.contains(&id) {
+            self.config.resources.push(id);
+        }
+        self
+    }
+
+    /// Sets whether restoring a snapshot despawns entities that hold a captured component but were not
+    /// captured in the snapshot, such as entities spawned after it was taken.
+    ///
+    /// By default such entities only lose their captured components.
+    pub fn despawn_untracked(&mut self, despawn_untracked: bool) -> &mut Self {
+        self.config.despawn_untracked = despawn_untracked;
+        self
+    }
+
+    /// Returns the configured [`SnapshotConfig`].
+    pub fn finish(self) -> SnapshotConfig {
+        self.config
+    }
+}
+
+/// Controls how [`World::restore_snapshot`] updates the change detection [`Tick`]s of restored values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SnapshotTickMode {
+    /// Restored values are marked as changed at the current change tick, as if they had been written by a system.
+    ///
+    /// Components that have to be re-inserted are also marked as added.
+    #[default]
+    MarkChanged,
+    /// Restored values get the ticks and change locations they had when the snapshot was captured.
+    ///
+    /// Systems that ran after the capture will not see the restored values as changed.
+    Preserve,
+    /// Restored values keep the ticks of the values they overwrite, like writes through
+    /// [`bypass_change_detection`](crate::change_detection::DetectChangesMut::bypass_change_detection).
+    ///
+    /// Components that have to be re-inserted are marked as added at the current change tick.
+    Bypass,
+}
+
+/// A compact copy of the components and resources selected by a [`SnapshotConfig`], captured
+/// with [`World::capture_snapshot`] and written back with [`World::restore_snapshot`].
+///
+/// Component values are copied using their [`ComponentCloneBehavior`], the same way the
+/// [`EntityCloner`](crate::entity::EntityCloner) copies them, and are stored contiguously per component
+/// together with their change detection ticks. A snapshot can be restored any number of times.
+///
+/// Restoring puts the world back into the captured state for the selected components and resources:
+/// - Captured values are written back, in place where the entity still has the component.
+/// - Captured components are re-inserted on entities that lost them, and removed from entities that gained them.
+/// - Entities that hold a captured component but were not captured lose it, or are despawned if
+///   [`SnapshotConfigBuilder::despawn_untracked`] is set.
+///
+/// Entities keep their [`Entity`] ids. Despawning an entity retires its id, so restoring fails with
+/// [`RestoreSnapshotError::EntitiesDespawned`] if captured entities were despawned since the capture.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::{SnapshotConfig, SnapshotTickMode};
+/// #[derive(Component, Clone, PartialEq, Debug)]
+/// struct Position(f32);
+///
+/// #[derive(Resource, Clone, PartialEq, Debug)]
+/// struct Frame(u32);
+///
+/// let mut world = World::new();
+/// let entity = world.spawn(Position(0.0)).id();
+/// world.insert_resource(Frame(0));
+///
+/// let mut builder = SnapshotConfig::build(&mut world);
+/// builder.allow::<Position>().allow_resource::<Frame>();
+/// let config = builder.finish();
+///
+/// let snapshot = world.capture_snapshot(&config);
+///
+/// world.get_mut::<Position>(entity).unwrap().0 = 10.0;
+/// world.resource_mut::<Frame>().0 = 1;
+///
+/// world.restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged).unwrap();
+/// assert_eq!(world.get::<Position>(entity), Some(&Position(0.0)));
+/// assert_eq!(world.resource::<Frame>(), &Frame(0));
+/// ```
+///
+/// # Hooks and observers
+///
+/// Restoring a [mutable](crate::component::Mutable) component the entity still has overwrites it in place
+/// like a mutation does, without running hooks or observers. Immutable components are always re-inserted,
+/// so their hooks and observers run. This keeps relationships in sync: capture the relationship component
+/// and let its hooks rebuild the [`RelationshipTarget`](crate::relationship::RelationshipTarget) rather
+/// than capturing both.
+#[derive(Debug)]
+pub struct WorldSnapshot {
+    world_id: WorldId,
+    change_tick: Tick,
+    despawn_untracked: bool,
+    entities: EntityIndexSet,
+    components: Vec<SnapshotColumn>,
+    resources: Vec<SnapshotColumn>,
+}
+
+// SAFETY: Snapshot columns only hold component and resource values, which are `Send + Sync`,
+// and they are only mutated through `&mut WorldSnapshot`.
+unsafe impl Send for WorldSnapshot {}
+
+// SAFETY: See the `Send` implementation above.
+unsafe impl Sync for WorldSnapshot {}
+
+impl WorldSnapshot {
+    /// Returns the [`WorldId`] of the world this snapshot was captured from.
+    pub fn world_id(&self) -> WorldId {
+        self.world_id
+    }
+
+    /// Returns the change tick of the world when this snapshot was captured.
+    pub fn change_tick(&self) -> Tick {
+        self.change_tick
+    }
+
+    /// Returns the captured entities.
+    pub fn entities(&self) -> &EntityIndexSet {
+        &self.entities
+    }
+
+    /// Returns `true` if `entity` was captured in this snapshot.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    /// Returns the number of captured entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entities were captured.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Calls [`Tick::check_tick`] on all captured ticks, so that snapshots kept around for a long time
+    /// stay valid. Pass the value returned by [`World::check_change_ticks`].
+    pub fn check_change_ticks(&mut self, check: CheckChangeTicks) {
+        for column in self.components.iter_mut().chain(&mut self.resources) {
+            // SAFETY: `len` is the length of the column.
+            unsafe { column.data.check_change_ticks(column.len as usize, check) };
+        }
+    }
+}
+
+/// The captured values of a single component or resource.
+#[derive(Debug)]
+struct SnapshotColumn {
+    component_id: ComponentId,
+    data: Column,
+    /// The index in [`WorldSnapshot::entities`] of the entity each value was captured from.
+    /// Empty for resources.
+    rows: Vec<u32>,
+    /// The indices in [`WorldSnapshot::entities`] of the entities that have this component.
+    present: FixedBitSet,
+    len: u32,
+    capacity: usize,
+}
+
+impl SnapshotColumn {
+    fn new(info: &ComponentInfo, capacity: usize) -> Self {
+        Self {
+            component_id: info.id(),
+            data: Column::with_capacity(info, capacity),
+            rows: Vec::with_capacity(capacity),
+            present: FixedBitSet::new(),
+            len: 0,
+            capacity,
+        }
+    }
+
+    /// Moves `value` into the next row.
+    ///
+    /// # Safety
+    /// - There must be spare capacity for the value.
+    /// - `value` must be a valid value of this column's component.
+    unsafe fn push(
+        &mut self,
+        value: OwningPtr<'_>,
+        ticks: ComponentTicks,
+        changed_by: MaybeLocation,
+    ) {
+        debug_assert!((self.len as usize) < self.capacity);
+        let row = table_row(self.len);
+        // SAFETY: `row` is within capacity and uninitialized, `value` matches the component per the caller.
+        unsafe {
+            self.data.initialize(row, value, ticks.changed, changed_by);
+            *self.data.get_added_tick_unchecked(row).get() = ticks.added;
+        }
+        self.len += 1;
+    }
+
+    /// Returns the change detection ticks and change location of `row`.
+    ///
+    /// # Safety
+    /// `row` must be less than `len`.
+    unsafe fn ticks(&self, row: TableRow) -> (ComponentTicks, MaybeLocation) {
+        // SAFETY: `row` is in bounds per the caller.
+        unsafe {
+            (
+                self.data.get_ticks_unchecked(row),
+                self.data
+                    .get_changed_by_unchecked(row)
+                    .map(|changed_by| *changed_by.get()),
+            )
+        }
+    }
+}
+
+impl Drop for SnapshotColumn {
+    fn drop(&mut self) {
+        // SAFETY: `len` and `capacity` are the length and capacity of the column, which is never used again.
+        unsafe { self.data.drop(self.capacity, self.len as usize) };
+    }
+}
+
+#[inline]
+fn table_row(index: u32) -> TableRow {
+    TableRow::new(NonMaxU32::new(index).expect("snapshot columns hold less than u32::MAX values"))
+}
+
+#[cold]
+#[inline(never)]
+fn clone_failed(info: &ComponentInfo) -> ! {
+    panic!(
+        "Component `{}` could not be cloned into or out of a `WorldSnapshot`. Implement `Clone` for it, \
+        or register it for reflection, and make sure its clone behavior is not `Ignore`.",
+        info.name()
+    )
+}
+
+/// An error returned by [`World::restore_snapshot`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RestoreSnapshotError {
+    /// These captured entities were despawned since the capture, so they can't be spawned again
+    /// with the same id.
+    #[error("the captured entities {0:?} were despawned, so their ids can't be restored")]
+    EntitiesDespawned(Vec<Entity>),
+}
+
+/// A restored value that could not be written in place.
+struct PendingInsert<'b> {
+    entity: Entity,
+    component_id: ComponentId,
+    value: PtrMut<'b>,
+    ticks: ComponentTicks,
+    changed_by: MaybeLocation,
+}
+
+/// Writes captured values back into a world. See [`World::restore_snapshot`].
+struct SnapshotRestorer<'a, 'b> {
+    world: UnsafeWorldCell<'a>,
+    cloner: ComponentValueCloner<'a, 'b>,
+    bump: &'b Bump,
+    tick_mode: SnapshotTickMode,
+    change_tick: Tick,
+    caller: MaybeLocation,
+}
+
+impl<'a, 'b> SnapshotRestorer<'a, 'b> {
+    /// Clones `row` of `column` and writes it to `target` in place, or returns it if it has to be inserted instead.
+    ///
+    /// # Safety
+    /// - `column` must come from a snapshot captured from this restorer's world, and `row` must be less than its length.
+    /// - This restorer must have exclusive access to the world's component values.
+    unsafe fn restore(
+        &mut self,
+        column: &SnapshotColumn,
+        row: u32,
+        target: Option<Entity>,
+        mapper: &mut dyn EntityMapper,
+    ) -> Option<PendingInsert<'b>> {
+        let world = self.world;
+        // SAFETY: the snapshot was captured from this world, so its component ids are valid.
+        let info = unsafe {
+            world
+                .components()
+                .get_info(column.component_id)
+                .debug_checked_unwrap()
+        };
+        let clone_fn = info
+            .clone_behavior()
+            .resolve(ComponentCloneBehavior::global_default_fn());
+        let row = table_row(row);
+        // SAFETY: `row` is less than the column's length.
+        let (ticks, changed_by) = unsafe { column.ticks(row) };
+        let entity = target.unwrap_or(Entity::PLACEHOLDER);
+        // SAFETY:
+        // - `row` is less than the column's length, and the column holds values of `info`'s component.
+        // - `info` is from the world of the cloner.
+        let Some(value) = (unsafe {
+            self.cloner.clone_value(
+                clone_fn,
+                info,
+                column.data.get_data_unchecked(row),
+                entity,
+                mapper,
+            )
+        }) else {
+            clone_failed(info);
+        };
+
+        // Immutable components are re-inserted, so that their hooks and observers run.
+        let existing = target.filter(|_| info.mutable()).and_then(|target| {
+            let location = world.entities().get_spawned(target).ok()?;
+            // SAFETY:
+            // - `location` is the location of `target`.
+            // - we have exclusive access to the world's component values.
+            unsafe {
+                get_component_and_ticks(
+                    world,
+                    column.component_id,
+                    info.storage_type(),
+                    target,
+                    location,
+                )
+            }
+        });
+        let Some((existing, cells)) = existing else {
+            return Some(PendingInsert {
+                entity,
+                component_id: column.component_id,
+                value,
+                ticks,
+                changed_by,
+            });
+        };
+
+        let layout = info.layout();
+        let old = self.bump.alloc_layout(layout);
+        // SAFETY:
+        // - `existing` and `value` are valid values of this component, and `old` has its layout.
+        // - the old value is moved out before the restored value is moved in, so that it can't be
+        //   dropped twice if its `Drop` implementation panics.
+        // - we have exclusive access to the value and its ticks.
+        unsafe {
+            let existing = existing.assert_unique();
+            core::ptr::copy_nonoverlapping(existing.as_ptr(), old.as_ptr(), layout.size());
+            core::ptr::copy_nonoverlapping(value.as_ptr(), existing.as_ptr(), layout.size());
+            match self.tick_mode {
+                SnapshotTickMode::MarkChanged => {
+                    *cells.changed.get() = self.change_tick;
+                    cells
+                        .changed_by
+                        .zip(self.caller)
+                        .map(|(cell, caller)| *cell.get() = caller);
+                }
+                SnapshotTickMode::Preserve => {
+                    *cells.added.get() = ticks.added;
+                    *cells.changed.get() = ticks.changed;
+                    cells
+                        .changed_by
+                        .zip(changed_by)
+                        .map(|(cell, changed_by)| *cell.get() = changed_by);
+                }
+                SnapshotTickMode::Bypass => {}
+            }
+            if let Some(drop) = info.drop() {
+                drop(OwningPtr::new(old));
+            }
+        }
+        None
+    }
+}
+
+impl World {
+    /// Captures the components and resources selected by `config` into a [`WorldSnapshot`].
+    ///
+    /// # Panics
+    /// - Panics if `config` was built for a different world.
+    /// - Panics if a selected component or resource cannot be cloned. See [`WorldSnapshot`] for details.
+    pub fn capture_snapshot(&self, config: &SnapshotConfig) -> WorldSnapshot {
+        assert_eq!(
+            self.id(),
+            config.world_id,
+            "Attempted to capture a snapshot with a `SnapshotConfig` built for a different world."
+        );
+        let world = self.as_unsafe_world_cell_readonly();
+
+        #[cfg(feature = "bevy_reflect")]
+        let app_registry = self.get_resource::<crate::reflect::AppTypeRegistry>();
+
+        let archetypes: Vec<_> = self
+            .archetypes
+            .iter()
+            .filter(|archetype| {
+                !archetype.contains(IS_RESOURCE)
+                    && config.components.iter().any(|&id| archetype.contains(id))
+            })
+            .collect();
+
+        let mut entities = EntityIndexSet::with_capacity(
+            archetypes
+                .iter()
+                .map(|archetype| archetype.len() as usize)
+                .sum(),
+        );
+        for archetype in &archetypes {
+            entities.extend(archetype.entities().iter().map(ArchetypeEntity::id));
+        }
+
+        let mut snapshot = WorldSnapshot {
+            world_id: self.id(),
+            change_tick: self.read_change_tick(),
+            despawn_untracked: config.despawn_untracked,
+            components: Vec::with_capacity(config.components.len()),
+            resources: Vec::with_capacity(config.resources.len()),
+            entities,
+        };
+
+        let mut bump = Bump::new();
+        for &component_id in &config.components {
+            // SAFETY: the config was built for this world, which checked that the component is registered.
+            let info = unsafe {
+                self.components
+                    .get_info(component_id)
+                    .debug_checked_unwrap()
+            };
+            let clone_fn = info
+                .clone_behavior()
+                .resolve(ComponentCloneBehavior::global_default_fn());
+            let capacity = archetypes
+                .iter()
+                .filter(|archetype| archetype.contains(component_id))
+                .map(|archetype| archetype.len() as usize)
+                .sum();
+
+            // Push the column before filling it, so that values are dropped if a clone handler panics.
+            snapshot
+                .components
+                .push(SnapshotColumn::new(info, capacity));
+            // SAFETY: the column was just pushed.
+            let column = unsafe { snapshot.components.last_mut().debug_checked_unwrap() };
+            column.present.grow(snapshot.entities.len());
+
+            let mut cloner = ComponentValueCloner::new(
+                &bump,
+                self.entity_allocator(),
+                #[cfg(feature = "bevy_reflect")]
+                app_registry,
+            );
+            for archetype in archetypes
+                .iter()
+                .filter(|archetype| archetype.contains(component_id))
+            {
+                for (entity, location) in archetype.entities_with_location() {
+                    // SAFETY:
+                    // - `location` is the location of `entity`, whose archetype contains the component.
+                    // - We only read from the world.
+                    let (source, ticks) = unsafe {
+                        get_component_and_ticks(
+                            world,
+                            component_id,
+                            info.storage_type(),
+                            entity,
+                            location,
+                        )
+                        .debug_checked_unwrap()
+                    };
+                    // SAFETY: `source` points to a value of the component described by `info`, which is from this world.
+                    let Some(value) =
+                        (unsafe { cloner.clone_value(clone_fn, info, source, entity, &mut ()) })
+                    else {
+                        clone_failed(info);
+                    };
+                    // SAFETY: the entity was added to `entities` above.
+                    let index = unsafe {
+                        snapshot
+                            .entities
+                            .get_index_of(&entity)
+                            .debug_checked_unwrap()
+                    };
+                    column.rows.push(index as u32);
+                    column.present.insert(index);
+                    // SAFETY:
+                    // - the column has capacity for every entity in these archetypes.
+                    // - `value` was cloned from a value of this component, and is owned by us.
+                    // - we only read the ticks.
+                    unsafe {
+                        column.push(
+                            value.promote(),
+                            ComponentTicks {
+                                added: *ticks.added.get(),
+                                changed: *ticks.changed.get(),
+                            },
+                            ticks.changed_by.map(|changed_by| *changed_by.get()),
+                        );
+                    }
+                }
+            }
+            drop(cloner);
+            // The column owns the moved values now.
+            bump.reset();
+        }
+
+        for &component_id in &config.resources {
+            // SAFETY: the config was built for this world, which checked that the resource is registered.
+            let info = unsafe {
+                self.components
+                    .get_info(component_id)
+                    .debug_checked_unwrap()
+            };
+            let clone_fn = info
+                .clone_behavior()
+                .resolve(ComponentCloneBehavior::global_default_fn());
+            let source = self.resource_entities.get(component_id).and_then(|entity| {
+                let location = self.entities.get_spawned(entity).ok()?;
+                // SAFETY: `location` is the location of `entity`, and we only read from the world.
+                unsafe {
+                    get_component_and_ticks(
+                        world,
+                        component_id,
+                        info.storage_type(),
+                        entity,
+                        location,
+                    )
+                }
+                .map(|source| (entity, source))
+            });
+
+            snapshot
+                .resources
+                .push(SnapshotColumn::new(info, source.is_some() as usize));
+            let Some((entity, (source, ticks))) = source else {
+                continue;
+            };
+            // SAFETY: the column was just pushed.
+            let column = unsafe { snapshot.resources.last_mut().debug_checked_unwrap() };
+            let mut cloner = ComponentValueCloner::new(
+                &bump,
+                self.entity_allocator(),
+                #[cfg(feature = "bevy_reflect")]
+                app_registry,
+            );
+            // SAFETY: `source` points to a value of the resource described by `info`, which is from this world.
+            let Some(value) =
+                (unsafe { cloner.clone_value(clone_fn, info, source, entity, &mut ()) })
+            else {
+                clone_failed(info);
+            };
+            // SAFETY:
+            // - the column was created with a capacity of one.
+            // - `value` was cloned from a value of this resource, and is owned by us.
+            // - we only read the ticks.
+            unsafe {
+                column.push(
+                    value.promote(),
+                    ComponentTicks {
+                        added: *ticks.added.get(),
+                        changed: *ticks.changed.get(),
+                    },
+                    ticks.changed_by.map(|changed_by| *changed_by.get()),
+                );
+            }
+            drop(cloner);
+            bump.reset();
+        }
+
+        snapshot
+    }
+
+    /// Restores the components and resources captured in `snapshot`, updating their change detection
+    /// ticks according to `tick_mode`. See [`WorldSnapshot`] for what restoring does.
+    ///
+    /// Returns an error without changing the world if a captured entity was despawned since the capture.
+    ///
+    /// # Panics
+    /// Panics if `snapshot` was captured from a different world.
+    #[track_caller]
+    pub fn restore_snapshot(
+        &mut self,
+        snapshot: &WorldSnapshot,
+        tick_mode: SnapshotTickMode,
+    ) -> Result<(), RestoreSnapshotError> {
+        assert_eq!(
+            self.id(),
+            snapshot.world_id,
+            "Attempted to restore a `WorldSnapshot` captured from a different world."
+        );
+        let caller = MaybeLocation::caller();
+        self.flush();
+
+        let despawned: Vec<_> = snapshot
+            .entities
+            .iter()
+            .copied()
+            .filter(|&entity| !self.entities.contains_spawned(entity))
+            .collect();
+        let retired: Vec<_> = despawned
+            .iter()
+            .copied()
+            .filter(|&entity| self.entities.check_can_spawn_at(entity).is_err())
+            .collect();
+        if !retired.is_empty() {
+            return Err(RestoreSnapshotError::EntitiesDespawned(retired));
+        }
+
+        self.remove_untracked(snapshot);
+
+        // Spawn captured entities whose id was allocated but not spawned since.
+        for entity in despawned {
+            self.spawn_empty_at_with_caller(entity, caller)
+                .expect("entity can be spawned");
+        }
+
+        let resource_targets: Vec<_> = snapshot
+            .resources
+            .iter()
+            .map(|column| self.resource_entities.get(column.component_id))
+            .collect();
+        let change_tick = self.change_tick();
+        let bump = Bump::new();
+        let mut pending_components = Vec::new();
+        let mut pending_resources = Vec::new();
+        {
+            let world = self.as_unsafe_world_cell();
+
+            #[cfg(feature = "bevy_reflect")]
+            // SAFETY: we have exclusive world access, and the registry is cloned so it doesn't alias it.
+            let app_registry =
+                unsafe { world.get_resource::<crate::reflect::AppTypeRegistry>() }.cloned();
+
+            let mut restorer = SnapshotRestorer {
+                world,
+                cloner: ComponentValueCloner::new(
+                    &bump,
+                    world.entity_allocator(),
+                    #[cfg(feature = "bevy_reflect")]
+                    app_registry.as_ref(),
+                ),
+                bump: &bump,
+                tick_mode,
+                change_tick,
+                caller,
+            };
+
+            for column in &snapshot.components {
+                for (row, &index) in column.rows.iter().enumerate() {
+                    let target = snapshot.entities[index as usize];
+                    // SAFETY:
+                    // - the snapshot was captured from this world, and `row` is less than the column's length.
+                    // - we have exclusive world access.
+                    let pending =
+                        unsafe { restorer.restore(column, row as u32, Some(target), &mut ()) };
+                    pending_components.extend(pending);
+                }
+            }
+            for (column, &target) in snapshot.resources.iter().zip(&resource_targets) {
+                if column.len == 0 {
+                    continue;
+                }
+                // SAFETY:
+                // - the snapshot was captured from this world, and the column holds one value.
+                // - we have exclusive world access.
+                let pending = unsafe { restorer.restore(column, 0, target, &mut ()) };
+                pending_resources.extend(pending);
+            }
+        }
+
+        // Insert the values that could not be restored in place, one bundle per entity.
+        pending_components.sort_by_key(|pending| pending.entity);
+        let mut component_ids = Vec::new();
+        for pending in pending_components.chunk_by_mut(|a, b| a.entity == b.entity) {
+            component_ids.clear();
+            component_ids.extend(pending.iter().map(|pending| pending.component_id));
+            // SAFETY: every value is a valid value of its component, and is moved out exactly once.
+            unsafe {
+                self.entity_mut(pending[0].entity).insert_by_ids(
+                    &component_ids,
+                    pending
+                        .iter_mut()
+                        .map(|pending| pending.value.reborrow().promote()),
+                );
+            }
+        }
+        for pending in &mut pending_resources {
+            // SAFETY: the value is a valid value of the resource, and is moved out exactly once.
+            unsafe {
+                self.insert_resource_by_id(
+                    pending.component_id,
+                    pending.value.reborrow().promote(),
+                    caller,
+                );
+            }
+            pending.entity = self
+                .resource_entities
+                .get(pending.component_id)
+                .unwrap_or(Entity::PLACEHOLDER);
+        }
+
+        if tick_mode == SnapshotTickMode::Preserve {
+            let world = self.as_unsafe_world_cell();
+            for pending in pending_components.iter().chain(&pending_resources) {
+                let Ok(location) = world.entities().get_spawned(pending.entity) else {
+                    continue;
+                };
+                // SAFETY: the component is from this world.
+                let info = unsafe {
+                    world
+                        .components()
+                        .get_info(pending.component_id)
+                        .debug_checked_unwrap()
+                };
+                // SAFETY:
+                // - `location` is the location of `pending.entity`.
+                // - we have exclusive world access.
+                if let Some((_, cells)) = unsafe {
+                    get_component_and_ticks(
+                        world,
+                        pending.component_id,
+                        info.storage_type(),
+                        pending.entity,
+                        location,
+                    )
+                } {
+                    // SAFETY: we have exclusive access to the ticks.
+                    unsafe {
+                        *cells.added.get() = pending.ticks.added;
+                        *cells.changed.get() = pending.ticks.changed;
+                    }
+                    cells
+                        .changed_by
+                        .zip(pending.changed_by)
+                        // SAFETY: we have exclusive access to the change location.
+                        .map(|(cell, changed_by)| unsafe { *cell.get() = changed_by });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes captured components and resources that were not present when `snapshot` was captured.
+    fn remove_untracked(&mut self, snapshot: &WorldSnapshot) {
+        let mut removals: Vec<(Entity, Vec<ComponentId>)> = Vec::new();
+        let mut despawns = Vec::new();
+        for archetype in self.archetypes.iter() {
+            if archetype.contains(IS_RESOURCE) {
+                continue;
+            }
+            let columns: Vec<_> = snapshot
+                .components
+                .iter()
+                .filter(|column| archetype.contains(column.component_id))
+                .collect();
+            if columns.is_empty() {
+                continue;
+            }
+            for archetype_entity in archetype.entities() {
+                let entity = archetype_entity.id();
+                let Some(index) = snapshot.entities.get_index_of(&entity) else {
+                    if snapshot.despawn_untracked {
+                        despawns.push(entity);
+                    } else {
+                        removals.push((
+                            entity,
+                            columns.iter().map(|column| column.component_id).collect(),
+                        ));
+                    }
+                    continue;
+                };
+                let missing: Vec<_> = columns
+                    .iter()
+                    .filter(|column| !column.present.contains(index))
+                    .map(|column| column.component_id)
+                    .collect();
+                if !missing.is_empty() {
+                    removals.push((entity, missing));
+                }
+            }
+        }
+
+        for entity in despawns {
+            // Despawning an entity can despawn others that were queued as well.
+            if let Ok(entity) = self.get_entity_mut(entity) {
+                entity.despawn();
+            }
+        }
+        for (entity, component_ids) in removals {
+            if let Ok(mut entity) = self.get_entity_mut(entity) {
+                entity.remove_by_ids(&component_ids);
+            }
+        }
+        for column in &snapshot.resources {
+            if column.len == 0 {
+                self.remove_resource_by_id(column.component_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{component::Component, hierarchy::Children, prelude::ChildOf};
+    use alloc::{sync::Arc, vec};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Position(i32);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Velocity(i32);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Untracked;
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Target(#[entities] Entity);
+
+    #[derive(Resource, Clone, Debug, PartialEq)]
+    struct Score(u32);
+
+    fn config<B: Bundle>(world: &mut World) -> SnapshotConfig {
+        let mut builder = SnapshotConfig::build(world);
+        builder.allow::<B>().allow_resource::<Score>();
+        builder.finish()
+    }
+
+    #[test]
+    fn restore_values() {
+        let mut world = World::new();
+        let a = world.spawn((Position(1), Velocity(2))).id();
+        let b = world.spawn(Position(3)).id();
+        world.insert_resource(Score(4));
+        let config = config::<(Position, Velocity)>(&mut world);
+
+        let snapshot = world.capture_snapshot(&config);
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.contains(a) && snapshot.contains(b));
+
+        for _ in 0..2 {
+            world.get_mut::<Position>(a).unwrap().0 = 10;
+            world.get_mut::<Velocity>(a).unwrap().0 = 20;
+            world.get_mut::<Position>(b).unwrap().0 = 30;
+            world.resource_mut::<Score>().0 = 40;
+
+            world
+                .restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged)
+                .unwrap();
+            assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+            assert_eq!(world.get::<Velocity>(a), Some(&Velocity(2)));
+            assert_eq!(world.get::<Position>(b), Some(&Position(3)));
+            assert_eq!(world.resource::<Score>(), &Score(4));
+        }
+    }
+
+    #[test]
+    fn restore_inserts_and_removes_components() {
+        let mut world = World::new();
+        let a = world.spawn((Position(1), Velocity(2), Untracked)).id();
+        let b = world.spawn((Position(3), Untracked)).id();
+        let config = config::<(Position, Velocity)>(&mut world);
+
+        let snapshot = world.capture_snapshot(&config);
+
+        world.entity_mut(a).remove::<Velocity>();
+        world.entity_mut(b).insert(Velocity(5));
+        world.insert_resource(Score(6));
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged)
+            .unwrap();
+
+        assert_eq!(world.get::<Velocity>(a), Some(&Velocity(2)));
+        assert!(world.get::<Velocity>(b).is_none());
+        assert!(world.get::<Untracked>(a).is_some());
+        assert!(world.get::<Untracked>(b).is_some());
+        assert!(!world.contains_resource::<Score>());
+    }
+
+    #[test]
+    fn restore_untracked_entities() {
+        let mut world = World::new();
+        world.spawn(Position(1));
+        let config = config::<Position>(&mut world);
+        let snapshot = world.capture_snapshot(&config);
+
+        let spawned = world.spawn((Position(2), Untracked)).id();
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged)
+            .unwrap();
+        assert!(world.get::<Position>(spawned).is_none());
+        assert!(world.get::<Untracked>(spawned).is_some());
+
+        let mut builder = SnapshotConfig::build(&mut world);
+        builder.allow::<Position>().despawn_untracked(true);
+        let config = builder.finish();
+        let snapshot = world.capture_snapshot(&config);
+
+        let spawned = world.spawn((Position(2), Untracked)).id();
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged)
+            .unwrap();
+        assert!(!world.entities().contains_spawned(spawned));
+    }
+
+    #[test]
+    fn restore_despawned_entities_fails() {
+        let mut world = World::new();
+        let a = world.spawn(Position(1)).id();
+        let b = world.spawn((Position(2), Target(a))).id();
+        let config = config::<(Position, Target)>(&mut world);
+        let snapshot = world.capture_snapshot(&config);
+
+        world.entity_mut(b).insert(Position(3));
+        world.despawn(a);
+        let reused = world.spawn(Position(4)).id();
+        assert_eq!(
+            world.restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged),
+            Err(RestoreSnapshotError::EntitiesDespawned(vec![a]))
+        );
+        assert_eq!(world.get::<Position>(b), Some(&Position(3)));
+        assert_eq!(world.get::<Target>(b), Some(&Target(a)));
+        assert_eq!(world.get::<Position>(reused), Some(&Position(4)));
+    }
+
+    #[test]
+    fn restore_tick_modes() {
+        let mut world = World::new();
+        let entity = world.spawn(Position(1)).id();
+        let config = config::<Position>(&mut world);
+        let captured = world.entity(entity).get_change_ticks::<Position>().unwrap();
+        let snapshot = world.capture_snapshot(&config);
+
+        world.increment_change_tick();
+        world.get_mut::<Position>(entity).unwrap().0 = 2;
+        let mutated = world.entity(entity).get_change_ticks::<Position>().unwrap();
+        assert_ne!(mutated.changed, captured.changed);
+
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::Bypass)
+            .unwrap();
+        let ticks = world.entity(entity).get_change_ticks::<Position>().unwrap();
+        assert_eq!(ticks.changed, mutated.changed);
+
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::Preserve)
+            .unwrap();
+        let ticks = world.entity(entity).get_change_ticks::<Position>().unwrap();
+        assert_eq!(ticks.added, captured.added);
+        assert_eq!(ticks.changed, captured.changed);
+
+        world.increment_change_tick();
+        let change_tick = world.change_tick();
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged)
+            .unwrap();
+        let ticks = world.entity(entity).get_change_ticks::<Position>().unwrap();
+        assert_eq!(ticks.added, captured.added);
+        assert_eq!(ticks.changed, change_tick);
+
+        // Re-inserted components get their captured ticks back as well.
+        world.entity_mut(entity).remove::<Position>();
+        world.increment_change_tick();
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::Preserve)
+            .unwrap();
+        let ticks = world.entity(entity).get_change_ticks::<Position>().unwrap();
+        assert_eq!(ticks.added, captured.added);
+        assert_eq!(ticks.changed, captured.changed);
+        assert_eq!(world.get::<Position>(entity), Some(&Position(1)));
+    }
+
+    #[test]
+    fn restore_immutable_components() {
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+        let child = world.spawn(ChildOf(parent)).id();
+        let mut builder = SnapshotConfig::build(&mut world);
+        builder.allow::<ChildOf>();
+        let config = builder.finish();
+        let snapshot = world.capture_snapshot(&config);
+
+        world.entity_mut(child).insert(ChildOf(other));
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged)
+            .unwrap();
+
+        assert_eq!(world.get::<ChildOf>(child), Some(&ChildOf(parent)));
+        assert_eq!(&**world.entity(parent).get::<Children>().unwrap(), &[child]);
+        assert!(world.entity(other).get::<Children>().is_none());
+    }
+
+    #[test]
+    fn snapshot_drops_values() {
+        #[derive(Component)]
+        #[component(clone_behavior = clone::<Self>())]
+        struct Counted(Arc<AtomicUsize>);
+
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Self(self.0.clone())
+            }
+        }
+
+        let live = Arc::new(AtomicUsize::new(0));
+        let mut world = World::new();
+        let entity = world.spawn(Counted(live.clone())).id();
+        let mut builder = SnapshotConfig::build(&mut world);
+        builder.allow::<Counted>();
+        let config = builder.finish();
+
+        let snapshot = world.capture_snapshot(&config);
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged)
+            .unwrap();
+        world
+            .restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged)
+            .unwrap();
+        assert_eq!(live.load(Ordering::Relaxed), 3);
+        // The world's value, the snapshot's value and the `live` handle itself.
+        assert_eq!(Arc::strong_count(&live), 3);
+
+        drop(snapshot);
+        assert_eq!(Arc::strong_count(&live), 2);
+        world.despawn(entity);
+        assert_eq!(Arc::strong_count(&live), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "could not be cloned")]
+    fn uncloneable_components_panic() {
+        #[derive(Component)]
+        struct NotClone;
+
+        let mut world = World::new();
+        world.spawn(NotClone);
+        let mut builder = SnapshotConfig::build(&mut world);
+        builder.allow::<NotClone>();
+        let config = builder.finish();
+        world.capture_snapshot(&config);
+    }
+
+    #[test]
+    #[should_panic(expected = "different world")]
+    fn restore_into_other_world_panics() {
+        let mut world = World::new();
+        let config = config::<Position>(&mut world);
+        let snapshot = world.capture_snapshot(&config);
+        let _ = World::new().restore_snapshot(&snapshot, SnapshotTickMode::MarkChanged);
+    }
+}
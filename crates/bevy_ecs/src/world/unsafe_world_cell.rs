@@ -19,7 +19,7 @@ use crate::{
     query::{DebugCheckedUnwrap, QueryAccessError, ReleaseStateQueryData, SingleEntityQueryData},
     resource::{Resource, ResourceEntities},
     storage::{ComponentSparseSet, Storages, Table},
-    world::RawCommandQueue,
+    world::{ChangeJournal, RawCommandQueue},
 };
 use bevy_platform::sync::atomic::Ordering;
 use bevy_ptr::{Ptr, UnsafeCellDeref};
@@ -699,6 +699,29 @@ impl<'w> UnsafeWorldCell<'w> {
         unsafe { (*self.ptr).command_queue.clone() }
     }
 
+    /// Returns the world's [`ChangeJournal`], if it is enabled.
+    ///
+    /// # Safety
+    /// It is the caller's responsibility to ensure that there are no outstanding
+    /// mutable references to the journal.
+    #[inline]
+    pub(crate) unsafe fn change_journal(self) -> Option<&'w ChangeJournal> {
+        // SAFETY: Caller ensures there are no outstanding mutable references
+        unsafe { (*self.ptr).change_journal.as_deref() }
+    }
+
+    /// Returns the world's [`ChangeJournal`] mutably, if it is enabled.
+    ///
+    /// # Safety
+    /// It is the caller's responsibility to ensure that there are no outstanding
+    /// references to the journal.
+    #[inline]
+    pub(crate) unsafe fn change_journal_mut(self) -> Option<&'w mut ChangeJournal> {
+        self.assert_allows_mutable_access();
+        // SAFETY: Caller ensures there are no outstanding references
+        unsafe { (*self.ptr).change_journal.as_deref_mut() }
+    }
+
     /// # Safety
     /// It is the caller's responsibility to ensure that there are no outstanding
     /// references to `last_trigger_id`.
@@ -1281,7 +1304,7 @@ unsafe fn get_component(
 /// - `storage_type` must accurately reflect where the components for `component_id` are stored.
 /// - the caller must ensure that no aliasing rules are violated
 #[inline]
-unsafe fn get_component_and_ticks(
+pub(crate) unsafe fn get_component_and_ticks(
     world: UnsafeWorldCell<'_>,
     component_id: ComponentId,
     storage_type: StorageType,
@@ -0,0 +1,936 @@
+//! A generic inspector, which builds a tree of feathers widgets for editing any reflected value.
+//!
+//! The inspector is spawned with the [`inspector`] scene function. Every field of the inspected
+//! value gets an editor widget based on its type:
+//! * `bool` fields are edited with a [`FeathersCheckbox`].
+//! * Numbers are edited with a [`FeathersNumberInput`]. A
+//!   [`Range`](bevy_reflect::validation::Range) attribute on the field becomes a [`HardLimit`].
+//! * [`String`] fields are edited with a [`FeathersTextInput`]. A
+//!   [`MaxLen`](bevy_reflect::validation::MaxLen) attribute limits the number of characters.
+//! * [`Color`] fields are edited as a hex string, next to a [`FeathersColorSwatch`].
+//! * Enums are edited with a [`FeathersMenu`] listing their variants. The fields of the current
+//!   variant are shown below the menu.
+//! * Structs, tuples, lists and arrays are shown as collapsible groups, with a
+//!   [`FeathersDisclosureToggle`] in the header.
+//!
+//! Other values, such as maps and sets, are displayed but can't be edited.
+//!
+//! Each edit triggers an [`InspectorEdit`] on the widget, which propagates to the root of the
+//! inspector. There it is written back to the [`InspectorTarget`] through reflection.
+use core::{any::TypeId, ops::RangeInclusive};
+
+use bevy_asset::{ReflectAsset, UntypedAssetId};
+use bevy_color::{Color, Srgba};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    error::BevyError,
+    event::EntityEvent,
+    hierarchy::{ChildOf, Children},
+    lifecycle::Add,
+    observer::On,
+    query::With,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    resource::Resource,
+    system::{Commands, Query},
+    world::World,
+};
+use bevy_log::warn;
+use bevy_reflect::{
+    attributes::CustomAttributes,
+    enums::{DynamicEnum, DynamicVariant, Enum, EnumInfo, VariantInfo},
+    std_traits::ReflectDefault,
+    validation::Constraints,
+    Access, NamedField, ParsedPath, PartialReflect, Reflect, ReflectPath, ReflectRef, UnnamedField,
+};
+use bevy_scene::prelude::*;
+use bevy_text::{EditableText, TextEdit, TextEditChange};
+use bevy_ui::{
+    px, widget::Text, AlignItems, Checked, Display, FlexDirection, InteractionDisabled, Node,
+    UiRect,
+};
+use bevy_ui_widgets::{Activate, ValueChange};
+
+use crate::{
+    containers::{flex_spacer, group, group_body, group_header},
+    controls::{
+        ColorSwatchValue, FeathersCheckbox, FeathersColorSwatch, FeathersDisclosureToggle,
+        FeathersMenu, FeathersMenuButton, FeathersMenuItem, FeathersMenuPopup, FeathersNumberInput,
+        FeathersTextInput, FeathersTextInputContainer, HardLimit, NumberInputRange,
+        NumberInputValue,
+    },
+    display::{label, label_dim},
+    theme::ThemedText,
+};
+
+/// Scene function to spawn an inspector for `value`.
+///
+/// The widgets are built from `value` as it is when this is called: the inspector doesn't
+/// react to changes made to the inspected value by other means. Edits are written back to
+/// `target`.
+///
+/// ```ignore
+/// let target = InspectorTarget::resource::<GameSettings>();
+/// let scene = inspector(target.reflect(world).unwrap().as_partial_reflect(), target);
+/// world.spawn_scene(bsn! { :scene });
+/// ```
+pub fn inspector(value: &dyn PartialReflect, target: InspectorTarget) -> impl Scene + use<> {
+    let root = ParsedPath::from(Vec::<Access<'static>>::new());
+    let rows = fields(value, &root).unwrap_or_else(|| {
+        vec![property(
+            value.reflect_short_type_path().to_owned(),
+            value,
+            root,
+            None,
+        )]
+    });
+
+    bsn! {
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Stretch,
+            row_gap: px(4),
+        }
+        template_value(target)
+        on(inspector_on_edit)
+        Children [
+            {rows}
+        ]
+    }
+}
+
+/// The value edited by an inspector, which edits are written back to.
+#[derive(Component, Default, Clone, Debug)]
+pub enum InspectorTarget {
+    /// Edits aren't written back anywhere. They can still be observed as [`InspectorEdit`]
+    /// events on the root of the inspector.
+    #[default]
+    Detached,
+    /// A component on an entity.
+    Component {
+        /// The entity which has the component.
+        entity: Entity,
+        /// The [`TypeId`] of the component.
+        type_id: TypeId,
+    },
+    /// A resource, identified by its [`TypeId`].
+    Resource(TypeId),
+    /// An asset.
+    Asset(UntypedAssetId),
+}
+
+impl InspectorTarget {
+    /// Targets the component `C` on `entity`.
+    pub fn component<C: Component>(entity: Entity) -> Self {
+        Self::Component {
+            entity,
+            type_id: TypeId::of::<C>(),
+        }
+    }
+
+    /// Targets the resource `R`.
+    pub fn resource<R: Resource>() -> Self {
+        Self::Resource(TypeId::of::<R>())
+    }
+
+    /// Targets the asset with the given `id`.
+    pub fn asset(id: impl Into<UntypedAssetId>) -> Self {
+        Self::Asset(id.into())
+    }
+
+    /// Returns the current value of the target, if it exists.
+    ///
+    /// The type of the target must be registered, with [`ReflectComponent`] type data for
+    /// components and resources, or [`ReflectAsset`] type data for assets.
+    pub fn reflect<'w>(&self, world: &'w World) -> Option<&'w dyn Reflect> {
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        match *self {
+            Self::Detached => None,
+            Self::Component { entity, type_id } => type_registry
+                .get_type_data::<ReflectComponent>(type_id)?
+                .reflect(world.get_entity(entity).ok()?),
+            Self::Resource(type_id) => type_registry
+                .get_type_data::<ReflectComponent>(type_id)?
+                .reflect(world.get_entity(resource_entity(world, type_id)?).ok()?),
+            Self::Asset(id) => type_registry
+                .get_type_data::<ReflectAsset>(id.type_id())?
+                .get(world, id),
+        }
+    }
+
+    /// Replaces the part of the target at `path` with `value`.
+    pub fn apply(
+        &self,
+        world: &mut World,
+        path: &ParsedPath,
+        value: &dyn PartialReflect,
+    ) -> Result<(), BevyError> {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let (entity, type_id) = match *self {
+            Self::Detached => return Ok(()),
+            Self::Component { entity, type_id } => (entity, type_id),
+            Self::Resource(type_id) => (
+                resource_entity(world, type_id).ok_or("the resource does not exist")?,
+                type_id,
+            ),
+            Self::Asset(id) => {
+                let asset = type_registry
+                    .get_type_data::<ReflectAsset>(id.type_id())
+                    .ok_or("the asset type is not registered")?
+                    .get_mut(world, id)
+                    .ok_or("the asset does not exist")?;
+                return apply_at(asset.as_partial_reflect_mut(), path, value);
+            }
+        };
+        let mut component = type_registry
+            .get_type_data::<ReflectComponent>(type_id)
+            .ok_or("the component type is not registered")?
+            .reflect_mut(world.get_entity_mut(entity)?)
+            .ok_or("the entity does not have the component")?;
+        apply_at(component.as_partial_reflect_mut(), path, value)
+    }
+}
+
+fn resource_entity(world: &World, type_id: TypeId) -> Option<Entity> {
+    let component_id = world.components().get_id(type_id)?;
+    world.resource_entities().get(component_id)
+}
+
+fn apply_at(
+    target: &mut dyn PartialReflect,
+    path: &ParsedPath,
+    value: &dyn PartialReflect,
+) -> Result<(), BevyError> {
+    path.reflect_element_mut(target)
+        .map_err(|err| err.to_string())?
+        .try_apply(value)?;
+    Ok(())
+}
+
+/// Event triggered when a value is edited in an inspector.
+///
+/// This is triggered on the editor widget, and propagates up to the root of the inspector,
+/// which writes the value to its [`InspectorTarget`].
+#[derive(EntityEvent, Debug)]
+#[entity_event(propagate, auto_propagate)]
+pub struct InspectorEdit {
+    /// The entity receiving this event.
+    #[event_target]
+    pub entity: Entity,
+    /// The path of the edited value, relative to the inspected value.
+    pub path: ParsedPath,
+    /// The new value.
+    pub value: Box<dyn PartialReflect>,
+    /// False while the value is being dragged, true once the edit is complete.
+    pub is_final: bool,
+}
+
+impl InspectorEdit {
+    /// Replaces the part of `target` at [`path`](Self::path) with the new value.
+    pub fn apply(&self, target: &mut dyn PartialReflect) -> Result<(), BevyError> {
+        apply_at(target, &self.path, self.value.as_ref())
+    }
+}
+
+fn inspector_on_edit(
+    edit: On<InspectorEdit>,
+    q_target: Query<&InspectorTarget>,
+    mut commands: Commands,
+) {
+    let Ok(target) = q_target.get(edit.event_target()) else {
+        return;
+    };
+    if matches!(target, InspectorTarget::Detached) {
+        return;
+    }
+
+    let target = target.clone();
+    let path = edit.path.clone();
+    let value = edit.value.to_dynamic();
+    commands.queue(move |world: &mut World| {
+        if let Err(err) = target.apply(world, &path, value.as_ref()) {
+            warn!("Failed to apply inspector edit to `{path}`: {err}");
+        }
+    });
+}
+
+/// The path of the value edited by an inspector widget, relative to the inspected value.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Clone, Default)]
+pub struct InspectorField(pub ParsedPath);
+
+impl Default for InspectorField {
+    fn default() -> Self {
+        Self(ParsedPath::from(Vec::<Access<'static>>::new()))
+    }
+}
+
+/// The current text of an inspector text input, used to ignore cursor motions.
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct InspectorText(String);
+
+/// The name of the enum variant selected by a menu item.
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct InspectorVariant(&'static str);
+
+/// Marker for the caption of an enum menu, which shows the current variant.
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct InspectorVariantCaption;
+
+/// Marker for the container of the fields of the current enum variant.
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct InspectorVariantFields;
+
+/// Marker for the body of a collapsible group.
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct InspectorGroupBody;
+
+/// Builds a row for each field of a struct, tuple, list or array.
+///
+/// Returns `None` for other kinds of values.
+fn fields(value: &dyn PartialReflect, path: &ParsedPath) -> Option<Vec<Box<dyn Scene>>> {
+    let info = value.get_represented_type_info();
+    let rows = match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            let info = info.and_then(|info| info.as_struct().ok());
+            value
+                .iter_fields()
+                .enumerate()
+                .map(|(index, (name, field))| {
+                    let mut field_path = path.clone();
+                    field_path.push_field(name.to_owned());
+                    let attributes = info
+                        .and_then(|info| info.field_at(index))
+                        .map(NamedField::custom_attributes);
+                    property(name.to_owned(), field, field_path, attributes)
+                })
+                .collect()
+        }
+        ReflectRef::TupleStruct(value) => {
+            let info = info.and_then(|info| info.as_tuple_struct().ok());
+            value
+                .iter_fields()
+                .enumerate()
+                .map(|(index, field)| {
+                    let mut field_path = path.clone();
+                    field_path.push_tuple_index(index);
+                    let attributes = info
+                        .and_then(|info| info.field_at(index))
+                        .map(UnnamedField::custom_attributes);
+                    property(index.to_string(), field, field_path, attributes)
+                })
+                .collect()
+        }
+        ReflectRef::Tuple(value) => value
+            .iter_fields()
+            .enumerate()
+            .map(|(index, field)| {
+                let mut field_path = path.clone();
+                field_path.push_tuple_index(index);
+                property(index.to_string(), field, field_path, None)
+            })
+            .collect(),
+        ReflectRef::List(value) => items(value.iter(), path),
+        ReflectRef::Array(value) => items(value.iter(), path),
+        _ => return None,
+    };
+    Some(rows)
+}
+
+fn items<'a>(
+    items: impl Iterator<Item = &'a dyn PartialReflect>,
+    path: &ParsedPath,
+) -> Vec<Box<dyn Scene>> {
+    items
+        .enumerate()
+        .map(|(index, item)| {
+            let mut item_path = path.clone();
+            item_path.push_list_index(index);
+            property(index.to_string(), item, item_path, None)
+        })
+        .collect()
+}
+
+/// Builds a row for each field of the current variant of an enum.
+fn variant_fields(value: &dyn Enum, info: &EnumInfo, path: &ParsedPath) -> Vec<Box<dyn Scene>> {
+    let variant = info.variant(value.variant_name());
+    value
+        .iter_fields()
+        .enumerate()
+        .map(|(index, field)| {
+            let mut field_path = path.clone();
+            let (name, attributes) = match field.name() {
+                Some(name) => {
+                    field_path.push_field(name.to_owned());
+                    let attributes = match variant {
+                        Some(VariantInfo::Struct(variant)) => variant.field_at(index),
+                        _ => None,
+                    };
+                    (
+                        name.to_owned(),
+                        attributes.map(NamedField::custom_attributes),
+                    )
+                }
+                None => {
+                    field_path.push_tuple_index(index);
+                    let attributes = match variant {
+                        Some(VariantInfo::Tuple(variant)) => variant.field_at(index),
+                        _ => None,
+                    };
+                    (
+                        index.to_string(),
+                        attributes.map(UnnamedField::custom_attributes),
+                    )
+                }
+            };
+            property(name, field.value(), field_path, attributes)
+        })
+        .collect()
+}
+
+/// Builds the widgets for a single named value.
+fn property(
+    name: String,
+    value: &dyn PartialReflect,
+    path: ParsedPath,
+    attributes: Option<&CustomAttributes>,
+) -> Box<dyn Scene> {
+    let constraints = attributes
+        .map(Constraints::from_attributes)
+        .unwrap_or_default();
+    if let Some(editor) = editor(value, &path, &constraints) {
+        return row(name, editor);
+    }
+
+    if let ReflectRef::Enum(value) = value.reflect_ref()
+        && let Some(info) = value
+            .get_represented_type_info()
+            .and_then(|info| info.as_enum().ok())
+    {
+        return enum_property(name, value, info, path);
+    }
+
+    match fields(value, &path) {
+        Some(rows) => collapsible_group(name, rows),
+        None => row(name, Box::new(label_dim(format!("{value:?}")))),
+    }
+}
+
+/// A label, followed by the widget used to edit the value.
+fn row(name: String, editor: Box<dyn Scene>) -> Box<dyn Scene> {
+    Box::new(bsn! {
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: px(8),
+        }
+        Children [
+            (label(name) Node { min_width: px(80) }),
+            editor,
+        ]
+    })
+}
+
+/// A group with a disclosure toggle in its header, which shows or hides the group body.
+fn collapsible_group(name: String, rows: Vec<Box<dyn Scene>>) -> Box<dyn Scene> {
+    Box::new(bsn! {
+        group()
+        Children [
+            (
+                group_header()
+                Children [
+                    (@FeathersDisclosureToggle Checked on(group_on_toggle)),
+                    label(name),
+                    flex_spacer(),
+                ]
+            ),
+            (
+                group_body()
+                InspectorGroupBody
+                Children [
+                    {rows}
+                ]
+            ),
+        ]
+    })
+}
+
+fn group_on_toggle(
+    change: On<ValueChange<bool>>,
+    q_parent: Query<&ChildOf>,
+    q_children: Query<&Children>,
+    mut q_body: Query<&mut Node, With<InspectorGroupBody>>,
+    mut commands: Commands,
+) {
+    if change.value {
+        commands.entity(change.source).insert(Checked);
+    } else {
+        commands.entity(change.source).remove::<Checked>();
+    }
+
+    // The toggle is in the group header, which is a sibling of the body.
+    let Some(group) = q_parent.iter_ancestors(change.source).nth(1) else {
+        return;
+    };
+    let Ok(children) = q_children.get(group) else {
+        return;
+    };
+    for child in children.iter() {
+        if let Ok(mut node) = q_body.get_mut(*child) {
+            node.display = if change.value {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+}
+
+/// Returns the widget used to edit a value directly, if there is one for its type.
+fn editor(
+    value: &dyn PartialReflect,
+    path: &ParsedPath,
+    constraints: &Constraints,
+) -> Option<Box<dyn Scene>> {
+    macro_rules! number_editors {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = value.try_downcast_ref::<$ty>() {
+                    return Some(number_editor(*value, path.clone(), constraints));
+                }
+            )*
+        };
+    }
+
+    number_editors!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    if let Some(value) = value.try_downcast_ref::<bool>() {
+        return Some(checkbox_editor(*value, path.clone()));
+    }
+    if let Some(value) = value.try_downcast_ref::<String>() {
+        return Some(text_editor(value.clone(), path.clone(), constraints));
+    }
+    if let Some(value) = value.try_downcast_ref::<Color>() {
+        return Some(color_editor(*value, path.clone()));
+    }
+    None
+}
+
+fn checkbox_editor(checked: bool, path: ParsedPath) -> Box<dyn Scene> {
+    Box::new(bsn! {
+        @FeathersCheckbox
+        template_value(InspectorField(path))
+        {checked.then(|| template_value(Checked))}
+        on(checkbox_on_change)
+    })
+}
+
+fn checkbox_on_change(
+    change: On<ValueChange<bool>>,
+    q_field: Query<&InspectorField>,
+    mut commands: Commands,
+) {
+    let Ok(InspectorField(path)) = q_field.get(change.source) else {
+        return;
+    };
+    if change.value {
+        commands.entity(change.source).insert(Checked);
+    } else {
+        commands.entity(change.source).remove::<Checked>();
+    }
+    commands.trigger(InspectorEdit {
+        entity: change.source,
+        path: path.clone(),
+        value: Box::new(change.value),
+        is_final: change.is_final,
+    });
+}
+
+/// A number type which can be edited with a [`FeathersNumberInput`].
+trait InspectorNumber: Reflect + Copy {
+    /// The type of the [`ValueChange`] events emitted by the number input.
+    type Input: Copy + Send + Sync + 'static;
+
+    /// The value displayed by the number input.
+    fn input_value(self) -> NumberInputValue;
+
+    /// Converts the value emitted by the number input, if it fits in this type.
+    fn from_input(value: Self::Input) -> Option<Self>;
+
+    /// The limit of the number input, from the range the value is constrained to.
+    fn hard_limit(range: Option<&RangeInclusive<f64>>) -> Option<HardLimit>;
+}
+
+macro_rules! impl_inspector_float {
+    ($($ty:ident => $variant:ident),*) => {
+        $(
+            impl InspectorNumber for $ty {
+                type Input = $ty;
+
+                fn input_value(self) -> NumberInputValue {
+                    NumberInputValue::$variant(self)
+                }
+
+                fn from_input(value: $ty) -> Option<Self> {
+                    Some(value)
+                }
+
+                fn hard_limit(range: Option<&RangeInclusive<f64>>) -> Option<HardLimit> {
+                    range.map(|range| {
+                        HardLimit(NumberInputRange::$variant(
+                            *range.start() as $ty..*range.end() as $ty,
+                        ))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_inspector_int {
+    ($($ty:ident => $variant:ident($input:ident)),*) => {
+        $(
+            impl InspectorNumber for $ty {
+                type Input = $input;
+
+                fn input_value(self) -> NumberInputValue {
+                    NumberInputValue::$variant($input::try_from(self).unwrap_or($input::MAX))
+                }
+
+                fn from_input(value: $input) -> Option<Self> {
+                    $ty::try_from(value).ok()
+                }
+
+                fn hard_limit(range: Option<&RangeInclusive<f64>>) -> Option<HardLimit> {
+                    // Always limit to the bounds of the type, so that values which don't fit
+                    // can't be entered.
+                    let (mut min, mut max) = ($ty::MIN as f64, $ty::MAX as f64);
+                    if let Some(range) = range {
+                        min = min.max(range.start().ceil());
+                        max = max.min(range.end().floor());
+                    }
+                    Some(HardLimit(NumberInputRange::$variant(
+                        min as $input..max as $input,
+                    )))
+                }
+            }
+        )*
+    };
+}
+
+impl_inspector_float!(f32 => F32, f64 => F64);
+impl_inspector_int!(
+    i8 => I32(i32),
+    i16 => I32(i32),
+    i32 => I32(i32),
+    u8 => I32(i32),
+    u16 => I32(i32),
+    i64 => I64(i64),
+    isize => I64(i64),
+    u32 => I64(i64),
+    u64 => I64(i64),
+    usize => I64(i64)
+);
+
+fn number_editor<N: InspectorNumber>(
+    value: N,
+    path: ParsedPath,
+    constraints: &Constraints,
+) -> Box<dyn Scene> {
+    let hard_limit = N::hard_limit(constraints.range.as_ref());
+    Box::new(bsn! {
+        @FeathersNumberInput
+        template_value(value.input_value())
+        template_value(InspectorField(path))
+        {hard_limit.map(template_value)}
+        on(number_input_on_change::<N>)
+    })
+}
+
+fn number_input_on_change<N: InspectorNumber>(
+    change: On<ValueChange<N::Input>>,
+    q_field: Query<&InspectorField>,
+    mut commands: Commands,
+) {
+    let Ok(InspectorField(path)) = q_field.get(change.source) else {
+        return;
+    };
+    let Some(value) = N::from_input(change.value) else {
+        return;
+    };
+    commands.entity(change.source).insert(value.input_value());
+    commands.trigger(InspectorEdit {
+        entity: change.source,
+        path: path.clone(),
+        value: Box::new(value),
+        is_final: change.is_final,
+    });
+}
+
+fn text_editor(text: String, path: ParsedPath, constraints: &Constraints) -> Box<dyn Scene> {
+    let max_characters = constraints.max_len;
+    Box::new(bsn! {
+        @FeathersTextInputContainer
+        Children [
+            (
+                @FeathersTextInput {
+                    @max_characters: {max_characters},
+                }
+                template_value(InspectorField(path))
+                template_value(InspectorText(text))
+                on(text_input_init)
+                on(text_input_on_change)
+            )
+        ]
+    })
+}
+
+/// Observer which sets the initial text of an inspector text input.
+fn text_input_init(
+    insert: On<Add, EditableText>,
+    mut q_text_input: Query<(&mut EditableText, &InspectorText)>,
+) {
+    if let Ok((mut editable_text, InspectorText(text))) =
+        q_text_input.get_mut(insert.event_target())
+    {
+        editable_text.queue_edit(TextEdit::SelectAll);
+        editable_text.queue_edit(TextEdit::Insert(text.clone().into()));
+    }
+}
+
+fn text_input_on_change(
+    change: On<TextEditChange>,
+    mut q_text_input: Query<(&EditableText, &InspectorField, &mut InspectorText)>,
+    mut commands: Commands,
+) {
+    let Ok((editable_text, InspectorField(path), mut text)) =
+        q_text_input.get_mut(change.event_target())
+    else {
+        return;
+    };
+    let value = editable_text.value().to_string();
+    if value == text.0 {
+        return;
+    }
+    text.0 = value.clone();
+    commands.trigger(InspectorEdit {
+        entity: change.event_target(),
+        path: path.clone(),
+        value: Box::new(value),
+        is_final: true,
+    });
+}
+
+fn color_editor(color: Color, path: ParsedPath) -> Box<dyn Scene> {
+    let hex = Srgba::from(color).to_hex();
+    Box::new(bsn! {
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: px(4),
+            flex_grow: 1.0,
+        }
+        template_value(InspectorField(path))
+        Children [
+            (
+                @FeathersTextInputContainer
+                Node {
+                    padding: { px(4).left() },
+                }
+                Children [
+                    (
+                        @FeathersTextInput {
+                            @visible_width: 10f32,
+                            @max_characters: 9usize,
+                        }
+                        template_value(InspectorText(hex))
+                        on(text_input_init)
+                        on(color_input_on_change)
+                    )
+                ]
+            ),
+            (
+                @FeathersColorSwatch
+                ColorSwatchValue({color})
+            ),
+        ]
+    })
+}
+
+fn color_input_on_change(
+    change: On<TextEditChange>,
+    mut q_text_input: Query<(&EditableText, &mut InspectorText)>,
+    q_parent: Query<&ChildOf>,
+    q_children: Query<&Children>,
+    q_field: Query<&InspectorField>,
+    q_swatch: Query<(), With<ColorSwatchValue>>,
+    mut commands: Commands,
+) {
+    let Ok((editable_text, mut text)) = q_text_input.get_mut(change.event_target()) else {
+        return;
+    };
+    let value = editable_text.value().to_string();
+    if value == text.0 {
+        return;
+    }
+    text.0 = value;
+    let Ok(color) = Srgba::hex(&text.0) else {
+        return;
+    };
+
+    let Some((field_id, InspectorField(path))) = q_parent
+        .iter_ancestors(change.event_target())
+        .find_map(|ancestor| q_field.get(ancestor).ok().map(|field| (ancestor, field)))
+    else {
+        return;
+    };
+    if let Some(swatch_id) = q_children
+        .iter_descendants(field_id)
+        .find(|descendant| q_swatch.contains(*descendant))
+    {
+        commands
+            .entity(swatch_id)
+            .insert(ColorSwatchValue(color.into()));
+    }
+    commands.trigger(InspectorEdit {
+        entity: field_id,
+        path: path.clone(),
+        value: Box::new(Color::from(color)),
+        is_final: true,
+    });
+}
+
+/// A menu to pick the variant of an enum, followed by the fields of the current variant.
+///
+/// Only unit variants can be picked, since there are no values to fill in the fields of
+/// other variants.
+fn enum_property(
+    name: String,
+    value: &dyn Enum,
+    info: &EnumInfo,
+    path: ParsedPath,
+) -> Box<dyn Scene> {
+    let current = value.variant_name().to_owned();
+    let items: Vec<Box<dyn Scene>> = info
+        .iter()
+        .map(|variant| {
+            let is_unit = matches!(variant, VariantInfo::Unit(_));
+            let variant_name = variant.name();
+            Box::new(bsn! {
+                @FeathersMenuItem {
+                    @caption: bsn! { Text(variant_name) ThemedText },
+                }
+                template_value(InspectorVariant(variant_name))
+                {(!is_unit).then(|| template_value(InteractionDisabled))}
+                on(variant_on_activate)
+            }) as Box<dyn Scene>
+        })
+        .collect();
+    let rows = variant_fields(value, info, &path);
+
+    let menu: Box<dyn Scene> = Box::new(bsn! {
+        @FeathersMenu
+        Node {
+            flex_grow: 1.0,
+        }
+        Children [
+            (
+                @FeathersMenuButton {
+                    @caption: bsn! { Text(current) ThemedText InspectorVariantCaption },
+                }
+                Node {
+                    flex_grow: 1.0,
+                }
+            ),
+            (
+                @FeathersMenuPopup
+                Children [
+                    {items}
+                ]
+            ),
+        ]
+    });
+    let header = row(name, menu);
+
+    Box::new(bsn! {
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Stretch,
+            row_gap: px(4),
+        }
+        template_value(InspectorField(path))
+        Children [
+            header,
+            (
+                Node {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Stretch,
+                    row_gap: px(4),
+                    padding: UiRect::left(px(12)),
+                }
+                InspectorVariantFields
+                Children [
+                    {rows}
+                ]
+            ),
+        ]
+    })
+}
+
+fn variant_on_activate(
+    activate: On<Activate>,
+    q_variant: Query<&InspectorVariant>,
+    q_parent: Query<&ChildOf>,
+    q_children: Query<&Children>,
+    q_field: Query<&InspectorField>,
+    q_menu: Query<(), With<FeathersMenu>>,
+    q_variant_fields: Query<(), With<InspectorVariantFields>>,
+    mut q_caption: Query<&mut Text, With<InspectorVariantCaption>>,
+    mut commands: Commands,
+) {
+    let Ok(&InspectorVariant(variant)) = q_variant.get(activate.entity) else {
+        return;
+    };
+
+    if let Some(menu_id) = q_parent
+        .iter_ancestors(activate.entity)
+        .find(|ancestor| q_menu.contains(*ancestor))
+    {
+        for descendant in q_children.iter_descendants(menu_id) {
+            if let Ok(mut caption) = q_caption.get_mut(descendant) {
+                caption.0 = variant.to_owned();
+            }
+        }
+    }
+
+    let Some((field_id, InspectorField(path))) = q_parent
+        .iter_ancestors(activate.entity)
+        .find_map(|ancestor| q_field.get(ancestor).ok().map(|field| (ancestor, field)))
+    else {
+        return;
+    };
+    // Unit variants have no fields to show.
+    if let Ok(children) = q_children.get(field_id) {
+        for child in children.iter() {
+            if q_variant_fields.contains(*child) {
+                commands.entity(*child).despawn_children();
+            }
+        }
+    }
+    commands.trigger(InspectorEdit {
+        entity: field_id,
+        path: path.clone(),
+        value: Box::new(DynamicEnum::new(variant, DynamicVariant::Unit)),
+        is_final: true,
+    });
+}
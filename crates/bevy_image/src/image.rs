@@ -14,7 +14,9 @@ use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 #[cfg(feature = "serialize")]
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
-use bevy_asset::{uuid_handle, Asset, AssetApp, Assets, Handle, RenderAssetUsages};
+use bevy_asset::{
+    uuid_handle, Asset, AssetApp, Assets, Handle, RenderAssetUsages, VisitAssetDependencies,
+};
 use bevy_color::{Color, ColorToComponents, Gray, LinearRgba, Srgba, Xyza};
 use bevy_ecs::resource::Resource;
 use bevy_math::{AspectRatio, UVec2, UVec3, Vec2};
@@ -23,8 +25,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use wgpu_types::{
     AddressMode, CompareFunction, Extent3d, Features, FilterMode, MipmapFilterMode,
-    SamplerBorderColor, SamplerDescriptor, TextureDataOrder, TextureDescriptor, TextureDimension,
-    TextureFormat, TextureUsages, TextureViewDescriptor,
+    SamplerBorderColor, SamplerDescriptor, TextureAspect, TextureDataOrder, TextureDescriptor,
+    TextureDimension, TextureFormat, TextureUsages, TextureViewDescriptor,
 };
 
 /// Trait used to provide default values for Bevy-external types that
@@ -602,7 +604,7 @@ impl ToExtents for UVec3 {
 ///
 /// To transmit an [`Image`] between two running Bevy apps, e.g. through BRP, use [`SerializedImage`](crate::SerializedImage).
 /// This type is only meant for short-term transmission between same versions and should not be stored anywhere.
-#[derive(Asset, Debug, Clone, PartialEq)]
+#[derive(VisitAssetDependencies, Debug, Clone, PartialEq)]
 #[cfg_attr(
     feature = "bevy_reflect",
     derive(Reflect),
@@ -645,6 +647,50 @@ pub struct Image {
     pub copy_on_resize: bool,
 }
 
+impl Asset for Image {
+    fn memory_size(&self) -> usize {
+        let gpu_size = if self.asset_usage.contains(RenderAssetUsages::RENDER_WORLD) {
+            self.texture_size()
+        } else {
+            0
+        };
+        size_of::<Self>() + self.data.as_ref().map_or(0, Vec::len) + gpu_size
+    }
+}
+
+impl Image {
+    /// Estimates the size in bytes of the GPU texture described by the
+    /// [`texture_descriptor`](Self::texture_descriptor), including all mip levels, layers and samples.
+    fn texture_size(&self) -> usize {
+        let descriptor = &self.texture_descriptor;
+        let format = descriptor.format;
+        // Combined depth-stencil formats only have a block size per aspect.
+        let block_size = format.block_copy_size(None).unwrap_or_else(|| {
+            format
+                .block_copy_size(Some(TextureAspect::DepthOnly))
+                .unwrap_or(4)
+                + format
+                    .block_copy_size(Some(TextureAspect::StencilOnly))
+                    .unwrap_or(0)
+        }) as u64;
+        let (block_width, block_height) = format.block_dimensions();
+
+        let bytes: u64 = (0..descriptor.mip_level_count)
+            .map(|level| {
+                let size = descriptor
+                    .size
+                    .mip_level_size(level, descriptor.dimension)
+                    .physical_size(format);
+                (size.width / block_width) as u64
+                    * (size.height / block_height) as u64
+                    * size.depth_or_array_layers as u64
+                    * block_size
+            })
+            .sum();
+        (bytes * descriptor.sample_count as u64) as usize
+    }
+}
+
 #[cfg(feature = "serialize")]
 mod image_serde {
     use super::*;
@@ -2470,6 +2516,31 @@ mod test {
         assert_eq!(Vec2::ONE, image.size_f32());
     }
 
+    #[test]
+    fn image_memory_size() {
+        let size = Extent3d {
+            width: 64,
+            height: 32,
+            depth_or_array_layers: 1,
+        };
+        let mut image = Image::new_uninit(
+            size,
+            TextureDimension::D2,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        assert_eq!(image.memory_size(), size_of::<Image>() + 64 * 32 * 4);
+
+        image.texture_descriptor.mip_level_count = 3;
+        assert_eq!(
+            image.memory_size(),
+            size_of::<Image>() + (64 * 32 + 32 * 16 + 16 * 8) * 4
+        );
+
+        image.asset_usage = RenderAssetUsages::MAIN_WORLD;
+        assert_eq!(image.memory_size(), size_of::<Image>());
+    }
+
     #[test]
     fn on_edge_pixel_is_invalid() {
         let image = Image::new_fill(
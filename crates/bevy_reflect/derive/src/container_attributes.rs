@@ -15,7 +15,7 @@ use proc_macro2::{Ident, Span};
 use quote::quote_spanned;
 use syn::{
     ext::IdentExt, parenthesized, parse::ParseStream, spanned::Spanned, token, Expr, LitBool,
-    MetaList, MetaNameValue, Path, Token, WhereClause,
+    LitInt, MetaList, MetaNameValue, Path, Token, WhereClause,
 };
 
 mod kw {
@@ -29,6 +29,7 @@ mod kw {
     syn::custom_keyword!(no_field_bounds);
     syn::custom_keyword!(no_auto_register);
     syn::custom_keyword!(opaque);
+    syn::custom_keyword!(version);
 }
 
 // The traits listed below are not considered "special" (i.e. they use the `ReflectMyTrait` syntax)
@@ -185,6 +186,7 @@ pub(crate) struct ContainerAttributes {
     no_auto_register: bool,
     custom_attributes: CustomAttributes,
     is_opaque: bool,
+    version: Option<LitInt>,
     type_data: Vec<TypeDataRegistration>,
 }
 
@@ -237,6 +239,8 @@ impl ContainerAttributes {
             self.parse_opaque(input)
         } else if lookahead.peek(kw::no_field_bounds) {
             self.parse_no_field_bounds(input)
+        } else if lookahead.peek(kw::version) {
+            self.parse_version(input)
         } else if lookahead.peek(kw::Clone) {
             self.parse_clone(input)
         } else if lookahead.peek(kw::no_auto_register) {
@@ -401,6 +405,24 @@ impl ContainerAttributes {
         Ok(())
     }
 
+    /// Parse `version` attribute.
+    ///
+    /// Examples:
+    /// - `#[reflect(version = 3)]`
+    fn parse_version(&mut self, input: ParseStream) -> syn::Result<()> {
+        input.parse::<kw::version>()?;
+        input.parse::<Token![=]>()?;
+        let version = input.parse::<LitInt>()?;
+        version.base10_parse::<u32>()?;
+
+        if self.version.is_some() {
+            return Err(syn::Error::new(version.span(), "`version` already set"));
+        }
+
+        self.version = Some(version);
+        Ok(())
+    }
+
     /// Parse `no_auto_register` attribute.
     ///
     /// Examples:
@@ -498,6 +520,11 @@ impl ContainerAttributes {
             .any(|data| data.reflect_path().is_ident(name))
     }
 
+    /// The serialization version of the type, if set with `#[reflect(version = N)]`.
+    pub fn version(&self) -> Option<&LitInt> {
+        self.version.as_ref()
+    }
+
     /// The list of type data registrations.
     pub fn type_data(&self) -> &[TypeDataRegistration] {
         &self.type_data
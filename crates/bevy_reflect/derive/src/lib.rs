@@ -276,6 +276,24 @@ fn match_reflect_impls(ast: DeriveInput, source: ReflectImplSource) -> TokenStre
 /// //   Self: Any + Send + Sync,
 /// ```
 ///
+/// ## `#[reflect(version = N)]`
+///
+/// This attribute sets the serialization version of the type, and registers
+/// `ReflectVersion` type data for it.
+///
+/// The reflection serializers will record the version alongside the serialized data,
+/// and the reflection deserializers will upgrade data with an older version
+/// by running the migrations registered with `TypeRegistry::register_migration`.
+///
+/// ```ignore (bevy_reflect is not accessible from this crate)
+/// #[derive(Reflect)]
+/// #[reflect(version = 2)]
+/// struct Player {
+///   // Was called `hp` in version 1.
+///   health: u32,
+/// }
+/// ```
+///
 /// ## `#[reflect(where T: Trait, U::Assoc: Trait, ...)]`
 ///
 /// This attribute can be used to add additional bounds to the generated reflection trait impls.
@@ -44,6 +44,12 @@ pub(crate) fn impl_get_type_registration<'a>(
         }
     });
 
+    let version_data = meta.attrs().version().map(|version| {
+        quote! {
+            registration.insert(#bevy_reflect_path::serde::ReflectVersion::new(#version));
+        }
+    });
+
     let type_data = meta.attrs().type_data().iter().map(|data| {
         let reflect_path = data.reflect_path();
         let args = data.args();
@@ -70,6 +76,7 @@ pub(crate) fn impl_get_type_registration<'a>(
                 );
                 #from_reflect_data
                 #serialization_data
+                #version_data
                 #(#type_data)*
                 registration
             }
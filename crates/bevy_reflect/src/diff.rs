@@ -0,0 +1,785 @@
+//! Computing and applying the differences between two reflected values.
+//!
+//! A [`ReflectDiff`] describes how to turn one value into another.
+//! It is obtained with [`PartialReflect::diff`], and applied with [`PartialReflect::apply_diff`].
+//!
+//! Diffs only contain what changed: the fields of a struct that are equal in both values
+//! are left out, lists are patched with insertions and removals rather than rewritten,
+//! and maps and sets only record the entries that were added, removed or modified.
+//! This makes them suitable for undo stacks and for delta compression of network updates.
+//!
+//! With a [`TypeRegistry`], diffs can be serialized with [`ReflectDiffSerializer`]
+//! and deserialized with [`ReflectDiffDeserializer`].
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy_reflect::{diff::ReflectDiff, PartialReflect, Reflect};
+//! #[derive(Reflect, PartialEq, Debug)]
+//! struct Player {
+//!     name: String,
+//!     health: u32,
+//!     inventory: Vec<String>,
+//! }
+//!
+//! let before = Player {
+//!     name: "Alice".to_string(),
+//!     health: 100,
+//!     inventory: vec!["sword".to_string()],
+//! };
+//! let after = Player {
+//!     name: "Alice".to_string(),
+//!     health: 80,
+//!     inventory: vec!["sword".to_string(), "shield".to_string()],
+//! };
+//!
+//! let diff = before.diff(&after);
+//! assert!(matches!(&diff, ReflectDiff::Struct(fields) if fields.len() == 2));
+//!
+//! let mut value = before;
+//! value.apply_diff(&diff).unwrap();
+//! assert_eq!(value, after);
+//! ```
+//!
+//! [`TypeRegistry`]: crate::TypeRegistry
+//! [`ReflectDiffSerializer`]: crate::serde::ReflectDiffSerializer
+//! [`ReflectDiffDeserializer`]: crate::serde::ReflectDiffDeserializer
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{Debug, Formatter};
+
+use thiserror::Error;
+
+use crate::{
+    list::List, map::Map, set::Set, ApplyError, PartialReflect, ReflectKind, ReflectMut, ReflectRef,
+};
+
+/// The difference between two reflected values, as returned by [`PartialReflect::diff`].
+///
+/// See the [module-level documentation](crate::diff) for more information.
+pub enum ReflectDiff {
+    /// Both values are equal.
+    Unchanged,
+    /// The value must be replaced as a whole.
+    ///
+    /// This is used for opaque values, values of different types,
+    /// and enums that changed variant.
+    Replace(Box<dyn PartialReflect>),
+    /// The changed fields of a [struct](crate::structs::Struct), by name.
+    Struct(Vec<(String, ReflectDiff)>),
+    /// The changed fields of a [tuple struct](crate::tuple_struct::TupleStruct), by index.
+    TupleStruct(Vec<(usize, ReflectDiff)>),
+    /// The changed fields of a [tuple](crate::tuple::Tuple), by index.
+    Tuple(Vec<(usize, ReflectDiff)>),
+    /// The changed elements of an [array](crate::array::Array), by index.
+    Array(Vec<(usize, ReflectDiff)>),
+    /// The changed fields of an [enum](crate::enums::Enum) whose variant is the same in both values.
+    Enum {
+        /// The name of the variant.
+        variant: String,
+        /// The changed fields of the variant, by index.
+        fields: Vec<(usize, ReflectDiff)>,
+    },
+    /// The operations turning one [list](crate::list::List) into the other, applied in order.
+    List(Vec<ListDiffOp>),
+    /// The changed entries of a [map](crate::map::Map).
+    Map(Vec<MapDiffOp>),
+    /// The changed values of a [set](crate::set::Set).
+    Set(Vec<SetDiffOp>),
+}
+
+/// An operation of a [`ReflectDiff::List`].
+///
+/// Indices refer to the list as it is when the operation is applied,
+/// that is, after all the previous operations of the diff.
+pub enum ListDiffOp {
+    /// Inserts `value` at `index`, shifting all the following elements.
+    Insert {
+        /// The index of the inserted element.
+        index: usize,
+        /// The inserted element.
+        value: Box<dyn PartialReflect>,
+    },
+    /// Removes the element at `index`, shifting all the following elements.
+    Remove {
+        /// The index of the removed element.
+        index: usize,
+    },
+    /// Applies `diff` to the element at `index`.
+    Modify {
+        /// The index of the modified element.
+        index: usize,
+        /// The changes made to the element.
+        diff: ReflectDiff,
+    },
+}
+
+/// An operation of a [`ReflectDiff::Map`].
+pub enum MapDiffOp {
+    /// Inserts a new entry.
+    Insert {
+        /// The key of the inserted entry.
+        key: Box<dyn PartialReflect>,
+        /// The value of the inserted entry.
+        value: Box<dyn PartialReflect>,
+    },
+    /// Removes the entry with the given key.
+    Remove {
+        /// The key of the removed entry.
+        key: Box<dyn PartialReflect>,
+    },
+    /// Applies `diff` to the value of the entry with the given key.
+    Modify {
+        /// The key of the modified entry.
+        key: Box<dyn PartialReflect>,
+        /// The changes made to the value.
+        diff: ReflectDiff,
+    },
+}
+
+/// An operation of a [`ReflectDiff::Set`].
+pub enum SetDiffOp {
+    /// Inserts a value.
+    Insert(Box<dyn PartialReflect>),
+    /// Removes a value.
+    Remove(Box<dyn PartialReflect>),
+}
+
+/// An error returned by [`PartialReflect::apply_diff`].
+#[derive(Error, Debug)]
+pub enum ApplyDiffError {
+    /// A [`ReflectDiff::Replace`] could not be applied.
+    #[error(transparent)]
+    Apply(#[from] ApplyError),
+    /// The diff does not match the kind of the value it is applied to.
+    #[error("attempted to apply a `{diff_kind}` diff to `{value_kind}`")]
+    MismatchedKinds {
+        /// The kind of value the diff was computed for.
+        diff_kind: ReflectKind,
+        /// The kind of the value the diff was applied to.
+        value_kind: ReflectKind,
+    },
+    /// The diff was computed for another enum variant than the current one.
+    #[error("attempted to apply a diff for variant `{expected}` to variant `{found}`")]
+    MismatchedVariants {
+        /// The variant the diff was computed for.
+        expected: Box<str>,
+        /// The current variant of the value.
+        found: Box<str>,
+    },
+    /// The diff refers to a struct field that does not exist.
+    #[error("no field named `{0}`")]
+    MissingField(Box<str>),
+    /// The diff refers to a field or element index that does not exist.
+    #[error("no field or element at index {0}")]
+    MissingIndex(usize),
+    /// The diff refers to a map key that does not exist.
+    #[error("no entry with the key `{0}`")]
+    MissingKey(Box<str>),
+}
+
+impl ReflectDiff {
+    /// Returns `true` if this diff is [`ReflectDiff::Unchanged`].
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, ReflectDiff::Unchanged)
+    }
+
+    /// Returns the [`ReflectKind`] of the values this diff applies to,
+    /// or `None` for [`Unchanged`](ReflectDiff::Unchanged) and [`Replace`](ReflectDiff::Replace)
+    /// diffs, which apply to any value.
+    pub fn kind(&self) -> Option<ReflectKind> {
+        match self {
+            ReflectDiff::Unchanged | ReflectDiff::Replace(_) => None,
+            ReflectDiff::Struct(_) => Some(ReflectKind::Struct),
+            ReflectDiff::TupleStruct(_) => Some(ReflectKind::TupleStruct),
+            ReflectDiff::Tuple(_) => Some(ReflectKind::Tuple),
+            ReflectDiff::Array(_) => Some(ReflectKind::Array),
+            ReflectDiff::Enum { .. } => Some(ReflectKind::Enum),
+            ReflectDiff::List(_) => Some(ReflectKind::List),
+            ReflectDiff::Map(_) => Some(ReflectKind::Map),
+            ReflectDiff::Set(_) => Some(ReflectKind::Set),
+        }
+    }
+}
+
+impl Debug for ReflectDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ReflectDiff::Unchanged => f.write_str("Unchanged"),
+            ReflectDiff::Replace(value) => f.debug_tuple("Replace").field(value).finish(),
+            ReflectDiff::Struct(fields) => f.debug_tuple("Struct").field(fields).finish(),
+            ReflectDiff::TupleStruct(fields) => f.debug_tuple("TupleStruct").field(fields).finish(),
+            ReflectDiff::Tuple(fields) => f.debug_tuple("Tuple").field(fields).finish(),
+            ReflectDiff::Array(elements) => f.debug_tuple("Array").field(elements).finish(),
+            ReflectDiff::Enum { variant, fields } => f
+                .debug_struct("Enum")
+                .field("variant", variant)
+                .field("fields", fields)
+                .finish(),
+            ReflectDiff::List(ops) => f.debug_tuple("List").field(ops).finish(),
+            ReflectDiff::Map(ops) => f.debug_tuple("Map").field(ops).finish(),
+            ReflectDiff::Set(ops) => f.debug_tuple("Set").field(ops).finish(),
+        }
+    }
+}
+
+impl Debug for ListDiffOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ListDiffOp::Insert { index, value } => f
+                .debug_struct("Insert")
+                .field("index", index)
+                .field("value", value)
+                .finish(),
+            ListDiffOp::Remove { index } => f.debug_struct("Remove").field("index", index).finish(),
+            ListDiffOp::Modify { index, diff } => f
+                .debug_struct("Modify")
+                .field("index", index)
+                .field("diff", diff)
+                .finish(),
+        }
+    }
+}
+
+impl Debug for MapDiffOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MapDiffOp::Insert { key, value } => f
+                .debug_struct("Insert")
+                .field("key", key)
+                .field("value", value)
+                .finish(),
+            MapDiffOp::Remove { key } => f.debug_struct("Remove").field("key", key).finish(),
+            MapDiffOp::Modify { key, diff } => f
+                .debug_struct("Modify")
+                .field("key", key)
+                .field("diff", diff)
+                .finish(),
+        }
+    }
+}
+
+impl Debug for SetDiffOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SetDiffOp::Insert(value) => f.debug_tuple("Insert").field(value).finish(),
+            SetDiffOp::Remove(value) => f.debug_tuple("Remove").field(value).finish(),
+        }
+    }
+}
+
+/// Returns `true` if `a` and `b` can be diffed field by field.
+fn is_same_type(a: &dyn PartialReflect, b: &dyn PartialReflect) -> bool {
+    match (a.get_represented_type_info(), b.get_represented_type_info()) {
+        (Some(a), Some(b)) => a.type_id() == b.type_id(),
+        _ => a.reflect_kind() == b.reflect_kind(),
+    }
+}
+
+/// Returns `true` if `a` and `b` are known to be equal.
+fn is_equal(a: &dyn PartialReflect, b: &dyn PartialReflect) -> bool {
+    a.reflect_partial_eq(b).unwrap_or(false)
+}
+
+/// Collects the diffs of the fields produced by `fields` that are not [`ReflectDiff::Unchanged`].
+fn changed_fields<K>(fields: impl Iterator<Item = (K, ReflectDiff)>) -> Vec<(K, ReflectDiff)> {
+    fields.filter(|(_, diff)| !diff.is_unchanged()).collect()
+}
+
+/// Wraps the changed fields in a diff with `wrap`, or returns [`ReflectDiff::Unchanged`] if there are none.
+fn wrap_fields<K>(
+    fields: Vec<(K, ReflectDiff)>,
+    wrap: impl FnOnce(Vec<(K, ReflectDiff)>) -> ReflectDiff,
+) -> ReflectDiff {
+    if fields.is_empty() {
+        ReflectDiff::Unchanged
+    } else {
+        wrap(fields)
+    }
+}
+
+/// Computes the diff turning `from` into `to`.
+///
+/// See [`PartialReflect::diff`].
+pub(crate) fn diff(from: &dyn PartialReflect, to: &dyn PartialReflect) -> ReflectDiff {
+    let replace = || ReflectDiff::Replace(to.to_dynamic());
+    if !is_same_type(from, to) {
+        return replace();
+    }
+
+    match (from.reflect_ref(), to.reflect_ref()) {
+        (ReflectRef::Struct(from), ReflectRef::Struct(to)) => {
+            if from.field_len() != to.field_len() {
+                return replace();
+            }
+            let mut fields = Vec::new();
+            for (name, to_field) in to.iter_fields() {
+                let Some(from_field) = from.field(name) else {
+                    return replace();
+                };
+                fields.push((name.to_string(), diff(from_field, to_field)));
+            }
+            wrap_fields(changed_fields(fields.into_iter()), ReflectDiff::Struct)
+        }
+        (ReflectRef::TupleStruct(from), ReflectRef::TupleStruct(to)) => {
+            if from.field_len() != to.field_len() {
+                return replace();
+            }
+            let fields = from
+                .iter_fields()
+                .zip(to.iter_fields())
+                .map(|(from, to)| diff(from, to))
+                .enumerate();
+            wrap_fields(changed_fields(fields), ReflectDiff::TupleStruct)
+        }
+        (ReflectRef::Tuple(from), ReflectRef::Tuple(to)) => {
+            if from.field_len() != to.field_len() {
+                return replace();
+            }
+            let fields = from
+                .iter_fields()
+                .zip(to.iter_fields())
+                .map(|(from, to)| diff(from, to))
+                .enumerate();
+            wrap_fields(changed_fields(fields), ReflectDiff::Tuple)
+        }
+        (ReflectRef::Array(from), ReflectRef::Array(to)) => {
+            if from.len() != to.len() {
+                return replace();
+            }
+            let elements = from
+                .iter()
+                .zip(to.iter())
+                .map(|(from, to)| diff(from, to))
+                .enumerate();
+            wrap_fields(changed_fields(elements), ReflectDiff::Array)
+        }
+        (ReflectRef::Enum(from), ReflectRef::Enum(to)) => {
+            if from.variant_name() != to.variant_name() || from.field_len() != to.field_len() {
+                return replace();
+            }
+            let fields = from
+                .iter_fields()
+                .zip(to.iter_fields())
+                .map(|(from, to)| diff(from.value(), to.value()))
+                .enumerate();
+            wrap_fields(changed_fields(fields), |fields| ReflectDiff::Enum {
+                variant: to.variant_name().to_string(),
+                fields,
+            })
+        }
+        (ReflectRef::List(from), ReflectRef::List(to)) => diff_lists(from, to),
+        (ReflectRef::Map(from), ReflectRef::Map(to)) => diff_maps(from, to),
+        (ReflectRef::Set(from), ReflectRef::Set(to)) => diff_sets(from, to),
+        _ => {
+            if is_equal(from, to) {
+                ReflectDiff::Unchanged
+            } else {
+                replace()
+            }
+        }
+    }
+}
+
+/// Diffs two lists by skipping their common prefix and suffix, modifying the elements
+/// in between pairwise, and inserting or removing the remaining ones.
+fn diff_lists(from: &dyn List, to: &dyn List) -> ReflectDiff {
+    let (from_len, to_len) = (from.len(), to.len());
+    let prefix = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(from, to)| is_equal(*from, *to))
+        .count();
+    let suffix = (0..(from_len.min(to_len) - prefix))
+        .take_while(
+            |offset| match (from.get(from_len - 1 - offset), to.get(to_len - 1 - offset)) {
+                (Some(from), Some(to)) => is_equal(from, to),
+                _ => false,
+            },
+        )
+        .count();
+
+    let from_middle = from_len - prefix - suffix;
+    let to_middle = to_len - prefix - suffix;
+    let common = from_middle.min(to_middle);
+
+    let mut ops = Vec::new();
+    for index in prefix..prefix + common {
+        if let (Some(from), Some(to)) = (from.get(index), to.get(index)) {
+            let diff = diff(from, to);
+            if !diff.is_unchanged() {
+                ops.push(ListDiffOp::Modify { index, diff });
+            }
+        }
+    }
+    for _ in common..from_middle {
+        ops.push(ListDiffOp::Remove {
+            index: prefix + common,
+        });
+    }
+    for index in prefix + common..prefix + to_middle {
+        if let Some(value) = to.get(index) {
+            ops.push(ListDiffOp::Insert {
+                index,
+                value: value.to_dynamic(),
+            });
+        }
+    }
+
+    if ops.is_empty() {
+        ReflectDiff::Unchanged
+    } else {
+        ReflectDiff::List(ops)
+    }
+}
+
+fn diff_maps(from: &dyn Map, to: &dyn Map) -> ReflectDiff {
+    let mut ops = Vec::new();
+    for (key, from_value) in from.iter() {
+        match to.get(key) {
+            None => ops.push(MapDiffOp::Remove {
+                key: key.to_dynamic(),
+            }),
+            Some(to_value) => {
+                let diff = diff(from_value, to_value);
+                if !diff.is_unchanged() {
+                    ops.push(MapDiffOp::Modify {
+                        key: key.to_dynamic(),
+                        diff,
+                    });
+                }
+            }
+        }
+    }
+    for (key, value) in to.iter() {
+        if from.get(key).is_none() {
+            ops.push(MapDiffOp::Insert {
+                key: key.to_dynamic(),
+                value: value.to_dynamic(),
+            });
+        }
+    }
+
+    if ops.is_empty() {
+        ReflectDiff::Unchanged
+    } else {
+        ReflectDiff::Map(ops)
+    }
+}
+
+fn diff_sets(from: &dyn Set, to: &dyn Set) -> ReflectDiff {
+    let removed = from
+        .iter()
+        .filter(|value| !to.contains(*value))
+        .map(|value| SetDiffOp::Remove(value.to_dynamic()));
+    let inserted = to
+        .iter()
+        .filter(|value| !from.contains(*value))
+        .map(|value| SetDiffOp::Insert(value.to_dynamic()));
+    let ops = removed.chain(inserted).collect::<Vec<_>>();
+
+    if ops.is_empty() {
+        ReflectDiff::Unchanged
+    } else {
+        ReflectDiff::Set(ops)
+    }
+}
+
+/// Applies `diff` to `value`.
+///
+/// See [`PartialReflect::apply_diff`].
+pub(crate) fn apply_diff(
+    value: &mut dyn PartialReflect,
+    diff: &ReflectDiff,
+) -> Result<(), ApplyDiffError> {
+    let value_kind = value.reflect_kind();
+    match diff {
+        ReflectDiff::Unchanged => return Ok(()),
+        ReflectDiff::Replace(new_value) => return Ok(value.try_apply(new_value.as_ref())?),
+        _ => {}
+    }
+
+    match (diff, value.reflect_mut()) {
+        (ReflectDiff::Struct(fields), ReflectMut::Struct(value)) => {
+            for (name, diff) in fields {
+                let field = value
+                    .field_mut(name)
+                    .ok_or_else(|| ApplyDiffError::MissingField(name.as_str().into()))?;
+                apply_diff(field, diff)?;
+            }
+            Ok(())
+        }
+        (ReflectDiff::TupleStruct(fields), ReflectMut::TupleStruct(value)) => {
+            for (index, diff) in fields {
+                let field = value
+                    .field_mut(*index)
+                    .ok_or(ApplyDiffError::MissingIndex(*index))?;
+                apply_diff(field, diff)?;
+            }
+            Ok(())
+        }
+        (ReflectDiff::Tuple(fields), ReflectMut::Tuple(value)) => {
+            for (index, diff) in fields {
+                let field = value
+                    .field_mut(*index)
+                    .ok_or(ApplyDiffError::MissingIndex(*index))?;
+                apply_diff(field, diff)?;
+            }
+            Ok(())
+        }
+        (ReflectDiff::Array(elements), ReflectMut::Array(value)) => {
+            for (index, diff) in elements {
+                let element = value
+                    .get_mut(*index)
+                    .ok_or(ApplyDiffError::MissingIndex(*index))?;
+                apply_diff(element, diff)?;
+            }
+            Ok(())
+        }
+        (ReflectDiff::Enum { variant, fields }, ReflectMut::Enum(value)) => {
+            if value.variant_name() != variant {
+                return Err(ApplyDiffError::MismatchedVariants {
+                    expected: variant.as_str().into(),
+                    found: value.variant_name().into(),
+                });
+            }
+            for (index, diff) in fields {
+                let field = value
+                    .field_at_mut(*index)
+                    .ok_or(ApplyDiffError::MissingIndex(*index))?;
+                apply_diff(field, diff)?;
+            }
+            Ok(())
+        }
+        (ReflectDiff::List(ops), ReflectMut::List(value)) => {
+            for op in ops {
+                match op {
+                    ListDiffOp::Insert {
+                        index,
+                        value: element,
+                    } => {
+                        if *index > value.len() {
+                            return Err(ApplyDiffError::MissingIndex(*index));
+                        }
+                        value.insert(*index, element.to_dynamic());
+                    }
+                    ListDiffOp::Remove { index } => {
+                        if *index >= value.len() {
+                            return Err(ApplyDiffError::MissingIndex(*index));
+                        }
+                        value.remove(*index);
+                    }
+                    ListDiffOp::Modify { index, diff } => {
+                        let element = value
+                            .get_mut(*index)
+                            .ok_or(ApplyDiffError::MissingIndex(*index))?;
+                        apply_diff(element, diff)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        (ReflectDiff::Map(ops), ReflectMut::Map(value)) => {
+            for op in ops {
+                match op {
+                    MapDiffOp::Insert { key, value: entry } => {
+                        value.insert_boxed(key.to_dynamic(), entry.to_dynamic());
+                    }
+                    MapDiffOp::Remove { key } => {
+                        value.remove(key.as_ref());
+                    }
+                    MapDiffOp::Modify { key, diff } => {
+                        let entry = value
+                            .get_mut(key.as_ref())
+                            .ok_or_else(|| ApplyDiffError::MissingKey(key_name(key.as_ref())))?;
+                        apply_diff(entry, diff)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        (ReflectDiff::Set(ops), ReflectMut::Set(value)) => {
+            for op in ops {
+                match op {
+                    SetDiffOp::Insert(element) => {
+                        value.insert_boxed(element.to_dynamic());
+                    }
+                    SetDiffOp::Remove(element) => {
+                        value.remove(element.as_ref());
+                    }
+                }
+            }
+            Ok(())
+        }
+        _ => Err(ApplyDiffError::MismatchedKinds {
+            diff_kind: diff.kind().unwrap_or(value_kind),
+            value_kind,
+        }),
+    }
+}
+
+/// Formats a map key for [`ApplyDiffError::MissingKey`].
+fn key_name(key: &dyn PartialReflect) -> Box<str> {
+    alloc::format!("{key:?}").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reflect;
+    use alloc::{string::ToString, vec};
+    use bevy_platform::collections::{HashMap, HashSet};
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Foo {
+        a: u32,
+        b: String,
+        list: Vec<u32>,
+        map: HashMap<u32, String>,
+        set: HashSet<u32>,
+        state: State,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    enum State {
+        Idle,
+        Moving { speed: f32, target: (i32, i32) },
+    }
+
+    fn foo() -> Foo {
+        Foo {
+            a: 1,
+            b: "hello".to_string(),
+            list: vec![1, 2, 3, 4],
+            map: HashMap::from_iter([(1, "one".to_string()), (2, "two".to_string())]),
+            set: HashSet::from_iter([1, 2, 3]),
+            state: State::Moving {
+                speed: 1.0,
+                target: (0, 0),
+            },
+        }
+    }
+
+    fn assert_roundtrip(from: &Foo, to: &Foo) {
+        let diff = from.diff(to);
+        let mut value = from.clone();
+        value.apply_diff(&diff).unwrap();
+        assert_eq!(&value, to);
+    }
+
+    #[test]
+    fn equal_values_are_unchanged() {
+        assert!(foo().diff(&foo()).is_unchanged());
+    }
+
+    #[test]
+    fn should_only_diff_changed_fields() {
+        let from = foo();
+        let mut to = foo();
+        to.a = 2;
+
+        let diff = from.diff(&to);
+        let ReflectDiff::Struct(fields) = &diff else {
+            panic!("expected a struct diff, found {diff:?}");
+        };
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].0, "a");
+        assert!(matches!(fields[0].1, ReflectDiff::Replace(_)));
+
+        assert_roundtrip(&from, &to);
+    }
+
+    #[test]
+    fn should_diff_lists_with_insertions_and_removals() {
+        let from = foo();
+        let mut to = foo();
+        to.list = vec![1, 5, 2, 4, 6];
+
+        let diff = from.list.diff(&to.list);
+        let ReflectDiff::List(ops) = &diff else {
+            panic!("expected a list diff, found {diff:?}");
+        };
+        assert!(ops.len() < to.list.len());
+
+        assert_roundtrip(&from, &to);
+
+        to.list.clear();
+        assert_roundtrip(&from, &to);
+        assert_roundtrip(&to, &from);
+    }
+
+    #[test]
+    fn should_diff_maps_and_sets() {
+        let from = foo();
+        let mut to = foo();
+        to.map.remove(&1);
+        to.map.insert(2, "deux".to_string());
+        to.map.insert(3, "three".to_string());
+        to.set.remove(&2);
+        to.set.insert(10);
+
+        let ReflectDiff::Map(ops) = from.map.diff(&to.map) else {
+            panic!("expected a map diff");
+        };
+        assert_eq!(ops.len(), 3);
+        let ReflectDiff::Set(ops) = from.set.diff(&to.set) else {
+            panic!("expected a set diff");
+        };
+        assert_eq!(ops.len(), 2);
+
+        assert_roundtrip(&from, &to);
+    }
+
+    #[test]
+    fn should_diff_enums() {
+        let from = foo();
+        let mut to = foo();
+        to.state = State::Moving {
+            speed: 1.0,
+            target: (3, 0),
+        };
+        assert!(matches!(
+            from.state.diff(&to.state),
+            ReflectDiff::Enum { ref variant, ref fields } if variant == "Moving" && fields.len() == 1
+        ));
+        assert_roundtrip(&from, &to);
+
+        to.state = State::Idle;
+        assert!(matches!(
+            from.state.diff(&to.state),
+            ReflectDiff::Replace(_)
+        ));
+        assert_roundtrip(&from, &to);
+        assert_roundtrip(&to, &from);
+    }
+
+    #[test]
+    fn should_error_on_mismatched_diffs() {
+        let from = foo();
+        let mut to = foo();
+        to.state = State::Moving {
+            speed: 2.0,
+            target: (0, 0),
+        };
+        let diff = from.state.diff(&to.state);
+
+        let mut idle = State::Idle;
+        assert!(matches!(
+            idle.apply_diff(&diff),
+            Err(ApplyDiffError::MismatchedVariants { .. })
+        ));
+
+        let mut list = vec![1_u32];
+        assert!(matches!(
+            list.apply_diff(&diff),
+            Err(ApplyDiffError::MismatchedKinds {
+                diff_kind: ReflectKind::Enum,
+                value_kind: ReflectKind::List,
+            })
+        ));
+    }
+}
@@ -596,6 +596,7 @@ extern crate alloc;
 extern crate self as bevy_reflect;
 
 pub mod array;
+pub mod diff;
 mod error;
 mod fields;
 mod from_reflect;
@@ -616,6 +617,7 @@ pub mod tuple_struct;
 mod type_data;
 mod type_path;
 mod type_registry;
+pub mod validation;
 
 mod impls {
     mod alloc;
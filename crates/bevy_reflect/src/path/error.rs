@@ -1,7 +1,8 @@
-use core::fmt;
+use core::{fmt, num::ParseIntError};
 
 use super::Access;
 use crate::{enums::VariantType, ReflectKind};
+use thiserror::Error;
 
 /// The kind of [`AccessError`], along with some kind-specific information.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -129,3 +130,57 @@ impl fmt::Display for AccessError<'_> {
 }
 
 impl core::error::Error for AccessError<'_> {}
+
+/// The kind of [`PathQueryError`], along with some kind-specific information.
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum PathQueryErrorKind<'a> {
+    /// A field name or index was expected, but none was found.
+    #[error("Expected an identifier, but reached the end of the path")]
+    ExpectedIdent,
+
+    /// An index could not be parsed as an integer.
+    #[error("Failed to parse index as integer")]
+    InvalidIndex(ParseIntError),
+
+    /// A specific character was expected, but a different one or none was found.
+    #[error("Expected `{0}`")]
+    Expected(char),
+
+    /// A character was found where it isn't allowed.
+    #[error("Unexpected `{0}`")]
+    Unexpected(char),
+
+    /// A quoted string was never closed.
+    #[error("Unterminated string")]
+    UnterminatedString,
+
+    /// A filter didn't start with `@` or a path.
+    #[error("Expected `@` or a path at the start of the filter")]
+    ExpectedFilterPath,
+
+    /// A filter comparison is missing the value to compare with.
+    #[error("Expected a value to compare with")]
+    ExpectedValue,
+
+    /// The value of a filter comparison is not a string, number or boolean.
+    #[error("`{0}` is not a string, number or boolean")]
+    InvalidValue(&'a str),
+}
+
+/// An error returned when parsing a [`PathQuery`](super::PathQuery).
+///
+/// A sample message:
+///
+/// ```text
+/// Encountered an error at offset 17 while parsing query `.items[?(.count >)]`: Expected a value to compare with
+/// ```
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+#[error("Encountered an error at offset {offset} while parsing query `{query}`: {kind}")]
+pub struct PathQueryError<'a> {
+    /// The kind of error.
+    pub kind: PathQueryErrorKind<'a>,
+    /// Position in `query`.
+    pub offset: usize,
+    /// The query that the error occurred in.
+    pub query: &'a str,
+}
@@ -8,6 +8,9 @@ mod parse;
 pub use parse::ParseError;
 use parse::PathParser;
 
+mod query;
+pub use query::{PathQuery, PathQueryIter, PathQueryIterMut};
+
 use crate::{PartialReflect, Reflect};
 use alloc::borrow::Cow;
 use alloc::vec::Vec;
@@ -107,6 +110,8 @@ impl<'a> ReflectPath<'a> for &'a str {
 /// Using these functions repeatedly with the same string requires parsing the string every time.
 /// To avoid this cost, it's recommended to construct a [`ParsedPath`] instead.
 ///
+/// To select any number of elements at once, using wildcards and filters, see [`PathQuery`].
+///
 /// # Syntax
 ///
 /// ## Structs
@@ -0,0 +1,966 @@
+//! Queries selecting any number of elements within a type.
+
+use alloc::{borrow::Cow, boxed::Box, string::String, vec, vec::Vec};
+use core::{cmp::Ordering, fmt};
+
+use super::{Access, PathQueryError, PathQueryErrorKind};
+use crate::{enums::VariantType, PartialReflect, ReflectRef};
+
+/// A pre-parsed query selecting any number of elements within a type.
+///
+/// Queries extend the [path syntax](crate::GetPath) with accesses that can select
+/// more than one element:
+/// - Wildcards: `[*]` selects every element of a list or array and every value of a map,
+///   while `.*` selects every field of a struct, tuple struct, tuple or enum variant.
+/// - Key patterns: `["key*"]` selects the values of a map whose string key matches the pattern,
+///   or the fields of a struct whose name does. `*` matches any sequence of characters.
+/// - Filters: `[?(.count > 0)]` selects the elements of a list or array and the values of a map
+///   for which the filter holds.
+///
+/// A filter starts with a path relative to the element, which may be `@` to refer to the
+/// element itself. It is followed by one of `==`, `!=`, `<`, `<=`, `>` or `>=` and a quoted string,
+/// a number or a boolean to compare with. Strings are compared to string values and to the variant
+/// name of enums, and `\` escapes the following character within quotes.
+/// A filter without a comparison, like `[?(.enabled)]`, selects the elements for which the path
+/// exists and isn't `false`.
+///
+/// Elements that don't have the value targeted by an access are skipped rather than causing an
+/// error, so `.enemies[*].shield` selects the shields of the enemies that have one.
+///
+/// # Example
+/// ```
+/// # use bevy_reflect::{PathQuery, Reflect};
+/// #[derive(Reflect)]
+/// struct Level {
+///     enemies: Vec<Enemy>,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Enemy {
+///     health: u32,
+/// }
+///
+/// let mut level = Level {
+///     enemies: vec![Enemy { health: 0 }, Enemy { health: 50 }, Enemy { health: 80 }],
+/// };
+///
+/// let alive = PathQuery::parse(".enemies[?(.health > 0)].health").unwrap();
+/// let health: Vec<u32> = alive
+///     .iter(&level)
+///     .filter_map(|health| health.try_downcast_ref::<u32>().copied())
+///     .collect();
+/// assert_eq!(health, vec![50, 80]);
+///
+/// let mut healths = alive.iter_mut(&mut level);
+/// while let Some(health) = healths.fetch_next() {
+///     health.apply(&100_u32);
+/// }
+/// assert_eq!(level.enemies[0].health, 0);
+/// assert_eq!(level.enemies[2].health, 100);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathQuery {
+    segments: Vec<QuerySegment>,
+}
+
+impl PathQuery {
+    /// Parses a [`PathQuery`] from a string.
+    ///
+    /// Returns an error pointing at the offending part of the string if it isn't a valid query.
+    /// Any valid path string is also a valid query.
+    pub fn parse(query: &str) -> Result<Self, PathQueryError<'_>> {
+        let mut parser = QueryParser {
+            query,
+            remaining: query,
+        };
+        let segments = parser.segments(false)?;
+        Ok(Self { segments })
+    }
+
+    /// Returns an iterator over the elements of `root` selected by this query.
+    pub fn iter<'r>(&self, root: &'r dyn PartialReflect) -> PathQueryIter<'r> {
+        let mut elements = Vec::new();
+        visit(&self.segments, root, &mut Vec::new(), &mut |_, element| {
+            elements.push(element);
+        });
+        PathQueryIter(elements.into_iter())
+    }
+
+    /// Returns an iterator over mutable references to the elements of `root`
+    /// selected by this query.
+    ///
+    /// The elements are selected when this is called,
+    /// so filters are not affected by changes made while iterating.
+    pub fn iter_mut<'q, 'r>(
+        &'q self,
+        root: &'r mut dyn PartialReflect,
+    ) -> PathQueryIterMut<'q, 'r> {
+        let mut paths = Vec::new();
+        visit(&self.segments, root, &mut Vec::new(), &mut |steps, _| {
+            paths.push(steps.iter().map(Step::to_owned_key).collect());
+        });
+        PathQueryIterMut {
+            root,
+            paths: paths.into_iter(),
+        }
+    }
+}
+
+impl fmt::Display for PathQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            match segment {
+                QuerySegment::Access(access) => write!(f, "{access}")?,
+                QuerySegment::Fields => f.write_str(".*")?,
+                QuerySegment::Elements => f.write_str("[*]")?,
+                QuerySegment::Keys(pattern) => {
+                    f.write_str("[")?;
+                    write_quoted(f, pattern)?;
+                    f.write_str("]")?;
+                }
+                QuerySegment::Filter(filter) => {
+                    write!(f, "[?(@{}", filter.query)?;
+                    if let Some((comparison, value)) = &filter.comparison {
+                        write!(f, " {comparison} {value}")?;
+                    }
+                    f.write_str(")]")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PathQuery {
+    type Error = PathQueryError<'a>;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        PathQuery::parse(value)
+    }
+}
+
+/// An iterator over the elements selected by a [`PathQuery`].
+///
+/// Created with [`PathQuery::iter`].
+pub struct PathQueryIter<'r>(vec::IntoIter<&'r dyn PartialReflect>);
+
+impl<'r> Iterator for PathQueryIter<'r> {
+    type Item = &'r dyn PartialReflect;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for PathQueryIter<'_> {}
+
+/// An iterator over mutable references to the elements selected by a [`PathQuery`].
+///
+/// Created with [`PathQuery::iter_mut`].
+///
+/// Since the elements are all borrowed from the same root value,
+/// only one of them can be accessed at a time,
+/// which is why this uses [`fetch_next`](Self::fetch_next) rather than [`Iterator`].
+pub struct PathQueryIterMut<'q, 'r> {
+    root: &'r mut dyn PartialReflect,
+    paths: vec::IntoIter<Vec<Step<'q, Box<dyn PartialReflect>>>>,
+}
+
+impl PathQueryIterMut<'_, '_> {
+    /// Returns the next selected element, or `None` once all of them have been returned.
+    ///
+    /// Elements that no longer exist, because the value was changed through a previously
+    /// returned element, are skipped.
+    pub fn fetch_next(&mut self) -> Option<&mut dyn PartialReflect> {
+        let root: &dyn PartialReflect = &*self.root;
+        let path = self.paths.by_ref().find(|path| {
+            path.iter()
+                .try_fold(root, |value, step| step.element(value))
+                .is_some()
+        })?;
+        let root: &mut dyn PartialReflect = &mut *self.root;
+        path.iter()
+            .try_fold(root, |value, step| step.element_mut(value))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum QuerySegment {
+    Access(Access<'static>),
+    Fields,
+    Elements,
+    Keys(String),
+    Filter(Box<QueryFilter>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct QueryFilter {
+    query: PathQuery,
+    comparison: Option<(Comparison, QueryValue)>,
+}
+
+impl QueryFilter {
+    fn matches(&self, element: &dyn PartialReflect) -> bool {
+        let mut matches = false;
+        visit(
+            &self.query.segments,
+            element,
+            &mut Vec::new(),
+            &mut |_, value| {
+                matches |= match &self.comparison {
+                    Some((comparison, expected)) => expected
+                        .compare_with(value)
+                        .is_some_and(|ordering| comparison.holds(ordering)),
+                    None => value.try_downcast_ref::<bool>() != Some(&false),
+                };
+            },
+        );
+        matches
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    // NOTE: Longer tokens come first, so that `<=` isn't parsed as `<`.
+    const TOKENS: [(&'static str, Self); 6] = [
+        ("==", Self::Eq),
+        ("!=", Self::Ne),
+        ("<=", Self::Le),
+        (">=", Self::Ge),
+        ("<", Self::Lt),
+        (">", Self::Gt),
+    ];
+
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Eq => ordering.is_eq(),
+            Self::Ne => ordering.is_ne(),
+            Self::Lt => ordering.is_lt(),
+            Self::Le => ordering.is_le(),
+            Self::Gt => ordering.is_gt(),
+            Self::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        })
+    }
+}
+
+/// A value that filters compare elements with.
+#[derive(Clone, Debug, PartialEq)]
+enum QueryValue {
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    String(String),
+}
+
+impl QueryValue {
+    /// Returns how `value` compares to this value, or `None` if they can't be compared.
+    fn compare_with(&self, value: &dyn PartialReflect) -> Option<Ordering> {
+        match self {
+            Self::Bool(expected) => value.try_downcast_ref::<bool>()?.partial_cmp(expected),
+            Self::String(expected) => {
+                let value = as_str(value).or_else(|| match value.reflect_ref() {
+                    ReflectRef::Enum(enum_ref) => Some(enum_ref.variant_name()),
+                    _ => None,
+                })?;
+                Some(value.cmp(expected.as_str()))
+            }
+            Self::Int(_) | Self::Float(_) => match (as_number(value)?, self) {
+                (Self::Int(value), Self::Int(expected)) => Some(value.cmp(expected)),
+                (value, expected) => value.as_f64()?.partial_cmp(&expected.as_f64()?),
+            },
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Self::Int(value) => Some(value as f64),
+            Self::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for QueryValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value:?}"),
+            Self::String(value) => write_quoted(f, value),
+        }
+    }
+}
+
+fn write_quoted(f: &mut fmt::Formatter<'_>, string: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in string.chars() {
+        if matches!(c, '"' | '\\') {
+            f.write_str("\\")?;
+        }
+        write!(f, "{c}")?;
+    }
+    f.write_str("\"")
+}
+
+fn as_str(value: &dyn PartialReflect) -> Option<&str> {
+    value
+        .try_downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| value.try_downcast_ref::<&'static str>().copied())
+        .or_else(|| {
+            value
+                .try_downcast_ref::<Cow<'static, str>>()
+                .map(|value| &**value)
+        })
+}
+
+fn as_number(value: &dyn PartialReflect) -> Option<QueryValue> {
+    macro_rules! integers {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = value.try_downcast_ref::<$ty>() {
+                    return Some(QueryValue::Int(*value as i128));
+                }
+            )*
+        };
+    }
+
+    integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+    if let Some(value) = value.try_downcast_ref::<i128>() {
+        return Some(QueryValue::Int(*value));
+    }
+    if let Some(value) = value.try_downcast_ref::<u128>() {
+        return Some(
+            i128::try_from(*value).map_or(QueryValue::Float(*value as f64), QueryValue::Int),
+        );
+    }
+    if let Some(value) = value.try_downcast_ref::<f32>() {
+        return Some(QueryValue::Float(*value as f64));
+    }
+    value
+        .try_downcast_ref::<f64>()
+        .map(|value| QueryValue::Float(*value))
+}
+
+/// Returns `true` if `text` matches `pattern`, in which `*` matches any sequence of characters.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let (middle, suffix) = rest.rsplit_once('*').unwrap_or(("", rest));
+    let Some(mut text) = text
+        .strip_prefix(prefix)
+        .and_then(|text| text.strip_suffix(suffix))
+    else {
+        return false;
+    };
+    for part in middle.split('*') {
+        match text.find(part) {
+            Some(index) => text = &text[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// A step from a value to one of its elements.
+///
+/// Steps into maps hold the key of the element, which is `K`.
+enum Step<'q, K> {
+    Access(Access<'q>),
+    Key(K),
+}
+
+impl<'q> Step<'q, &dyn PartialReflect> {
+    fn to_owned_key(&self) -> Step<'q, Box<dyn PartialReflect>> {
+        match self {
+            Step::Access(access) => Step::Access(access.clone()),
+            Step::Key(key) => Step::Key(
+                key.reflect_clone()
+                    .map(PartialReflect::into_partial_reflect)
+                    .unwrap_or_else(|_| key.to_dynamic()),
+            ),
+        }
+    }
+}
+
+impl Step<'_, Box<dyn PartialReflect>> {
+    fn element<'r>(&self, value: &'r dyn PartialReflect) -> Option<&'r dyn PartialReflect> {
+        match self {
+            Step::Access(access) => access.element(value, None).ok(),
+            Step::Key(key) => value.reflect_ref().as_map().ok()?.get(&**key),
+        }
+    }
+
+    fn element_mut<'r>(
+        &self,
+        value: &'r mut dyn PartialReflect,
+    ) -> Option<&'r mut dyn PartialReflect> {
+        match self {
+            Step::Access(access) => access.element_mut(value, None).ok(),
+            Step::Key(key) => value.reflect_mut().as_map().ok()?.get_mut(&**key),
+        }
+    }
+}
+
+type Found<'f, 'q, 'r> =
+    &'f mut dyn FnMut(&[Step<'q, &'r dyn PartialReflect>], &'r dyn PartialReflect);
+
+/// Calls `found` with each element of `value` selected by `segments`,
+/// along with the steps leading to it.
+fn visit<'q, 'r>(
+    segments: &'q [QuerySegment],
+    value: &'r dyn PartialReflect,
+    steps: &mut Vec<Step<'q, &'r dyn PartialReflect>>,
+    found: Found<'_, 'q, 'r>,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        found(steps, value);
+        return;
+    };
+
+    let mut next = |step: Step<'q, &'r dyn PartialReflect>, element: &'r dyn PartialReflect| {
+        steps.push(step);
+        visit(rest, element, steps, found);
+        steps.pop();
+    };
+
+    match segment {
+        QuerySegment::Access(access) => {
+            if let Ok(element) = access.element(value, None) {
+                let access = match access {
+                    Access::Field(field) => Access::Field(Cow::Borrowed(field.as_ref())),
+                    access => access.clone(),
+                };
+                next(Step::Access(access), element);
+            }
+        }
+        QuerySegment::Fields => for_each_field(value, next),
+        QuerySegment::Elements => for_each_element(value, next),
+        QuerySegment::Keys(pattern) => for_each_key(value, pattern, next),
+        QuerySegment::Filter(filter) => for_each_element(value, |step, element| {
+            if filter.matches(element) {
+                next(step, element);
+            }
+        }),
+    }
+}
+
+fn for_each_field<'q, 'r>(
+    value: &'r dyn PartialReflect,
+    mut f: impl FnMut(Step<'q, &'r dyn PartialReflect>, &'r dyn PartialReflect),
+) {
+    match value.reflect_ref() {
+        ReflectRef::Struct(struct_ref) => {
+            for (index, (_, field)) in struct_ref.iter_fields().enumerate() {
+                f(Step::Access(Access::FieldIndex(index)), field);
+            }
+        }
+        ReflectRef::TupleStruct(tuple) => {
+            for (index, field) in tuple.iter_fields().enumerate() {
+                f(Step::Access(Access::TupleIndex(index)), field);
+            }
+        }
+        ReflectRef::Tuple(tuple) => {
+            for (index, field) in tuple.iter_fields().enumerate() {
+                f(Step::Access(Access::TupleIndex(index)), field);
+            }
+        }
+        ReflectRef::Enum(enum_ref) => {
+            let access = match enum_ref.variant_type() {
+                VariantType::Struct => Access::FieldIndex,
+                VariantType::Tuple => Access::TupleIndex,
+                VariantType::Unit => return,
+            };
+            for (index, field) in enum_ref.iter_fields().enumerate() {
+                f(Step::Access(access(index)), field.value());
+            }
+        }
+        _ => {}
+    }
+}
+
+fn for_each_element<'q, 'r>(
+    value: &'r dyn PartialReflect,
+    mut f: impl FnMut(Step<'q, &'r dyn PartialReflect>, &'r dyn PartialReflect),
+) {
+    match value.reflect_ref() {
+        ReflectRef::List(list) => {
+            for (index, element) in list.iter().enumerate() {
+                f(Step::Access(Access::ListIndex(index)), element);
+            }
+        }
+        ReflectRef::Array(array) => {
+            for (index, element) in array.iter().enumerate() {
+                f(Step::Access(Access::ListIndex(index)), element);
+            }
+        }
+        ReflectRef::Map(map) => {
+            for (key, element) in map.iter() {
+                f(Step::Key(key), element);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn for_each_key<'q, 'r>(
+    value: &'r dyn PartialReflect,
+    pattern: &str,
+    mut f: impl FnMut(Step<'q, &'r dyn PartialReflect>, &'r dyn PartialReflect),
+) {
+    match value.reflect_ref() {
+        ReflectRef::Map(map) => {
+            for (key, element) in map.iter() {
+                if as_str(key).is_some_and(|key| glob_matches(pattern, key)) {
+                    f(Step::Key(key), element);
+                }
+            }
+        }
+        ReflectRef::Struct(struct_ref) => {
+            for (index, (name, field)) in struct_ref.iter_fields().enumerate() {
+                if glob_matches(pattern, name) {
+                    f(Step::Access(Access::FieldIndex(index)), field);
+                }
+            }
+        }
+        ReflectRef::Enum(enum_ref) if enum_ref.variant_type() == VariantType::Struct => {
+            for (index, field) in enum_ref.iter_fields().enumerate() {
+                if field.name().is_some_and(|name| glob_matches(pattern, name)) {
+                    f(Step::Access(Access::FieldIndex(index)), field.value());
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+struct QueryParser<'a> {
+    query: &'a str,
+    remaining: &'a str,
+}
+
+impl<'a> QueryParser<'a> {
+    fn offset(&self) -> usize {
+        self.query.len() - self.remaining.len()
+    }
+
+    fn error_at(&self, offset: usize, kind: PathQueryErrorKind<'a>) -> PathQueryError<'a> {
+        PathQueryError {
+            kind,
+            offset,
+            query: self.query,
+        }
+    }
+
+    fn error(&self, kind: PathQueryErrorKind<'a>) -> PathQueryError<'a> {
+        self.error_at(self.offset(), kind)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.remaining.chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.remaining = &self.remaining[c.len_utf8()..];
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        let matches = self.peek() == Some(expected);
+        if matches {
+            self.bump();
+        }
+        matches
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), PathQueryError<'a>> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(PathQueryErrorKind::Expected(expected)))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.remaining = self.remaining.trim_start();
+    }
+
+    /// Parses segments until the end of the query or, within a filter, the end of its path.
+    fn segments(&mut self, in_filter: bool) -> Result<Vec<QuerySegment>, PathQueryError<'a>> {
+        let mut segments = Vec::new();
+        while let Some(c) = self.peek() {
+            let segment = match c {
+                '.' => {
+                    self.bump();
+                    if self.eat('*') {
+                        QuerySegment::Fields
+                    } else {
+                        QuerySegment::Access(field(self.ident(in_filter)?))
+                    }
+                }
+                '#' => {
+                    self.bump();
+                    let offset = self.offset();
+                    let ident = self.ident(in_filter)?;
+                    QuerySegment::Access(Access::FieldIndex(self.index(ident, offset)?))
+                }
+                '[' => {
+                    self.bump();
+                    self.bracket(in_filter)?
+                }
+                _ if in_filter => break,
+                ']' => return Err(self.error(PathQueryErrorKind::Unexpected(c))),
+                // As in paths, a leading dot is implied for the first field.
+                _ if segments.is_empty() => QuerySegment::Access(field(self.ident(false)?)),
+                _ => return Err(self.error(PathQueryErrorKind::Unexpected(c))),
+            };
+            segments.push(segment);
+        }
+        Ok(segments)
+    }
+
+    /// Parses the rest of a segment following its opening bracket.
+    fn bracket(&mut self, in_filter: bool) -> Result<QuerySegment, PathQueryError<'a>> {
+        let segment = match self.peek() {
+            Some('*') => {
+                self.bump();
+                QuerySegment::Elements
+            }
+            Some('"') => QuerySegment::Keys(self.string()?),
+            Some('?') => {
+                self.bump();
+                self.expect('(')?;
+                let filter = self.filter()?;
+                self.expect(')')?;
+                QuerySegment::Filter(Box::new(filter))
+            }
+            _ => {
+                let offset = self.offset();
+                let ident = self.ident(in_filter)?;
+                QuerySegment::Access(Access::ListIndex(self.index(ident, offset)?))
+            }
+        };
+        self.expect(']')?;
+        Ok(segment)
+    }
+
+    fn filter(&mut self) -> Result<QueryFilter, PathQueryError<'a>> {
+        self.skip_whitespace();
+        if !self.eat('@') && !matches!(self.peek(), Some('.' | '#' | '[')) {
+            return Err(self.error(PathQueryErrorKind::ExpectedFilterPath));
+        }
+        let query = PathQuery {
+            segments: self.segments(true)?,
+        };
+        self.skip_whitespace();
+
+        let token = Comparison::TOKENS
+            .into_iter()
+            .find(|(token, _)| self.remaining.starts_with(token));
+        let comparison = match token {
+            Some((token, comparison)) => {
+                self.remaining = &self.remaining[token.len()..];
+                self.skip_whitespace();
+                let value = self.value()?;
+                self.skip_whitespace();
+                Some((comparison, value))
+            }
+            None => None,
+        };
+        Ok(QueryFilter { query, comparison })
+    }
+
+    fn value(&mut self) -> Result<QueryValue, PathQueryError<'a>> {
+        if self.peek() == Some('"') {
+            return self.string().map(QueryValue::String);
+        }
+
+        let offset = self.offset();
+        let len = self
+            .remaining
+            .find(|c: char| c.is_whitespace() || c == ')')
+            .unwrap_or(self.remaining.len());
+        let (token, remaining) = self.remaining.split_at(len);
+        let value = match token {
+            "" => return Err(self.error(PathQueryErrorKind::ExpectedValue)),
+            "true" => QueryValue::Bool(true),
+            "false" => QueryValue::Bool(false),
+            _ => token
+                .parse()
+                .map(QueryValue::Int)
+                .or_else(|_| token.parse().map(QueryValue::Float))
+                .map_err(|_| self.error_at(offset, PathQueryErrorKind::InvalidValue(token)))?,
+        };
+        self.remaining = remaining;
+        Ok(value)
+    }
+
+    /// Parses a string in double quotes, in which `\` escapes the next character.
+    fn string(&mut self) -> Result<String, PathQueryError<'a>> {
+        let offset = self.offset();
+        self.bump();
+        let mut string = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.bump() {
+                    Some(c) => string.push(c),
+                    None => break,
+                },
+                Some(c) => string.push(c),
+                None => break,
+            }
+        }
+        Err(self.error_at(offset, PathQueryErrorKind::UnterminatedString))
+    }
+
+    fn ident(&mut self, in_filter: bool) -> Result<&'a str, PathQueryError<'a>> {
+        let is_end = |c: char| {
+            matches!(c, '.' | '#' | '[' | ']')
+                || (in_filter
+                    && (c.is_whitespace() || matches!(c, '=' | '!' | '<' | '>' | '(' | ')')))
+        };
+        let len = self.remaining.find(is_end).unwrap_or(self.remaining.len());
+        let (ident, remaining) = self.remaining.split_at(len);
+        if ident.is_empty() {
+            return Err(self.error(match self.peek() {
+                Some(c) => PathQueryErrorKind::Unexpected(c),
+                None => PathQueryErrorKind::ExpectedIdent,
+            }));
+        }
+        self.remaining = remaining;
+        Ok(ident)
+    }
+
+    fn index(&self, ident: &str, offset: usize) -> Result<usize, PathQueryError<'a>> {
+        ident
+            .parse()
+            .map_err(|error| self.error_at(offset, PathQueryErrorKind::InvalidIndex(error)))
+    }
+}
+
+fn field(ident: &str) -> Access<'static> {
+    ident
+        .parse()
+        .map(Access::TupleIndex)
+        .unwrap_or_else(|_| Access::Field(String::from(ident).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reflect;
+    use alloc::{string::ToString, vec};
+    use bevy_platform::collections::HashMap;
+
+    #[derive(Reflect)]
+    struct Level {
+        enemies: Vec<Enemy>,
+        spawns: HashMap<String, u32>,
+    }
+
+    #[derive(Reflect)]
+    struct Enemy {
+        health: u32,
+        speed: f32,
+        state: State,
+    }
+
+    #[derive(Reflect)]
+    enum State {
+        Idle,
+        Chasing { target: usize },
+    }
+
+    fn level() -> Level {
+        Level {
+            enemies: vec![
+                Enemy {
+                    health: 0,
+                    speed: 1.5,
+                    state: State::Idle,
+                },
+                Enemy {
+                    health: 50,
+                    speed: 2.0,
+                    state: State::Chasing { target: 3 },
+                },
+                Enemy {
+                    health: 80,
+                    speed: 0.5,
+                    state: State::Idle,
+                },
+            ],
+            spawns: HashMap::from_iter([
+                ("north_gate".to_string(), 4),
+                ("north_tower".to_string(), 2),
+                ("south_gate".to_string(), 1),
+            ]),
+        }
+    }
+
+    fn select<T: Reflect + Copy + PartialOrd>(query: &str, root: &dyn PartialReflect) -> Vec<T> {
+        let mut values: Vec<T> = PathQuery::parse(query)
+            .unwrap()
+            .iter(root)
+            .map(|value| *value.try_downcast_ref::<T>().unwrap())
+            .collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values
+    }
+
+    #[test]
+    fn query_wildcards_and_keys() {
+        let level = level();
+
+        assert_eq!(select::<u32>(".enemies[*].health", &level), [0, 50, 80]);
+        assert_eq!(select::<u32>("enemies[1].health", &level), [50]);
+        assert_eq!(select::<usize>(".enemies[*].state.target", &level), [3]);
+        assert_eq!(select::<usize>(".enemies[*].state.*", &level), [3]);
+        assert_eq!(select::<u32>(".spawns[*]", &level), [1, 2, 4]);
+        assert_eq!(select::<u32>(r#".spawns["north*"]"#, &level), [2, 4]);
+        assert_eq!(select::<u32>(r#".spawns["*_gate"]"#, &level), [1, 4]);
+        assert_eq!(select::<f32>(r#".enemies[0]["sp*d"]"#, &level), [1.5]);
+        assert!(select::<u32>(".enemies[5].health", &level).is_empty());
+    }
+
+    #[test]
+    fn query_filters() {
+        let level = level();
+
+        assert_eq!(
+            select::<u32>(".enemies[?(.health > 0)].health", &level),
+            [50, 80]
+        );
+        assert_eq!(
+            select::<u32>(".enemies[?(.speed >= 1.5)].health", &level),
+            [0, 50]
+        );
+        assert_eq!(
+            select::<u32>(r#".enemies[?(.state == "Idle")].health"#, &level),
+            [0, 80]
+        );
+        assert_eq!(
+            select::<u32>(".enemies[?(.state.target)].health", &level),
+            [50]
+        );
+        assert_eq!(select::<u32>(".spawns[?(@ != 2)]", &level), [1, 4]);
+    }
+
+    #[test]
+    fn query_iter_mut() {
+        let mut level = level();
+
+        let chasing = PathQuery::parse(r#".enemies[?(.state != "Idle")].speed"#).unwrap();
+        let mut speeds = chasing.iter_mut(&mut level);
+        while let Some(speed) = speeds.fetch_next() {
+            speed.apply(&10.0_f32);
+        }
+
+        let north = PathQuery::parse(r#".spawns["north*"]"#).unwrap();
+        let mut spawns = north.iter_mut(&mut level);
+        while let Some(spawn) = spawns.fetch_next() {
+            spawn.apply(&0_u32);
+        }
+
+        assert_eq!(select::<f32>(".enemies[*].speed", &level), [0.5, 1.5, 10.0]);
+        assert_eq!(select::<u32>(".spawns[*]", &level), [0, 0, 1]);
+    }
+
+    #[test]
+    fn query_display() {
+        for query in [
+            ".enemies[*].health",
+            ".enemies[0].*#1",
+            r#".spawns["north\"*"]"#,
+            r#".enemies[?(@.state == "Idle")][?(@.health <= -2)][?(@ > 1.5)][?(@.active)]"#,
+        ] {
+            assert_eq!(PathQuery::parse(query).unwrap().to_string(), query);
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let error = |query, offset, kind| PathQueryError {
+            kind,
+            offset,
+            query,
+        };
+
+        assert_eq!(
+            PathQuery::parse(".enemies[*"),
+            Err(error(".enemies[*", 10, PathQueryErrorKind::Expected(']')))
+        );
+        assert_eq!(
+            PathQuery::parse(r#".spawns["north]"#),
+            Err(error(
+                r#".spawns["north]"#,
+                8,
+                PathQueryErrorKind::UnterminatedString
+            ))
+        );
+        assert_eq!(
+            PathQuery::parse(".items[?(count > 0)]"),
+            Err(error(
+                ".items[?(count > 0)]",
+                9,
+                PathQueryErrorKind::ExpectedFilterPath
+            ))
+        );
+        assert_eq!(
+            PathQuery::parse(".items[?(.count >)]"),
+            Err(error(
+                ".items[?(.count >)]",
+                17,
+                PathQueryErrorKind::ExpectedValue
+            ))
+        );
+        assert_eq!(
+            PathQuery::parse(".items[?(.count > zero)]"),
+            Err(error(
+                ".items[?(.count > zero)]",
+                18,
+                PathQueryErrorKind::InvalidValue("zero")
+            ))
+        );
+        assert_eq!(
+            PathQuery::parse(".items[*]x"),
+            Err(error(".items[*]x", 9, PathQueryErrorKind::Unexpected('x')))
+        );
+        assert!(matches!(
+            PathQuery::parse(".items[x]"),
+            Err(PathQueryError {
+                kind: PathQueryErrorKind::InvalidIndex(_),
+                offset: 7,
+                ..
+            })
+        ));
+    }
+}
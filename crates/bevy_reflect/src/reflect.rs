@@ -1,8 +1,15 @@
 use crate::{
-    array::array_debug, enums::enum_debug, list::list_debug, map::map_debug, set::set_debug,
-    structs::struct_debug, tuple::tuple_debug, tuple_struct::tuple_struct_debug, DynamicTypePath,
-    DynamicTyped, OpaqueInfo, ReflectCloneError, ReflectKind, ReflectKindMismatchError, ReflectMut,
-    ReflectOwned, ReflectRef, TypeInfo, TypePath, Typed,
+    array::array_debug,
+    diff::{ApplyDiffError, ReflectDiff},
+    enums::enum_debug,
+    list::list_debug,
+    map::map_debug,
+    set::set_debug,
+    structs::struct_debug,
+    tuple::tuple_debug,
+    tuple_struct::tuple_struct_debug,
+    DynamicTypePath, DynamicTyped, OpaqueInfo, ReflectCloneError, ReflectKind,
+    ReflectKindMismatchError, ReflectMut, ReflectOwned, ReflectRef, TypeInfo, TypePath, Typed,
 };
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
@@ -292,6 +299,46 @@ where
         }
     }
 
+    /// Computes the changes that turn this value into `other`.
+    ///
+    /// Only the parts of `other` that differ from this value are recorded,
+    /// so that the result can be stored or sent cheaply, and later applied
+    /// to a copy of this value with [`apply_diff`].
+    ///
+    /// See the [`diff`](crate::diff) module for more information.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if a changed value is [opaque] and cannot be cloned with [`reflect_clone`],
+    /// as described in [`to_dynamic`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_reflect::PartialReflect;
+    /// let mut value = vec![1, 2, 3];
+    /// let diff = value.diff(&vec![1, 3, 4]);
+    ///
+    /// value.apply_diff(&diff).unwrap();
+    /// assert_eq!(value, vec![1, 3, 4]);
+    /// ```
+    ///
+    /// [`apply_diff`]: PartialReflect::apply_diff
+    /// [opaque]: crate::ReflectKind::Opaque
+    /// [`reflect_clone`]: PartialReflect::reflect_clone
+    /// [`to_dynamic`]: PartialReflect::to_dynamic
+    fn diff(&self, other: &dyn PartialReflect) -> ReflectDiff {
+        crate::diff::diff(self.as_partial_reflect(), other)
+    }
+
+    /// Applies a [`ReflectDiff`] computed by [`diff`](PartialReflect::diff) to this value.
+    ///
+    /// Fields, elements and entries not mentioned by the diff are left untouched.
+    /// If an error occurs, the changes made before it are not undone.
+    fn apply_diff(&mut self, diff: &ReflectDiff) -> Result<(), ApplyDiffError> {
+        crate::diff::apply_diff(self.as_partial_reflect_mut(), diff)
+    }
+
     /// Attempts to clone `Self` using reflection.
     ///
     /// Unlike [`to_dynamic`], which generally returns a dynamic representation of `Self`,
@@ -0,0 +1,454 @@
+use super::BinaryError;
+use alloc::vec::Vec;
+use serde::de::{
+    value::{BorrowedStrDeserializer, U32Deserializer},
+    DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+/// Reads the string table at the start of `input`, returning the strings and the remaining input.
+pub(super) fn read_string_table(input: &[u8]) -> Result<(Vec<&str>, &[u8]), BinaryError> {
+    let mut reader = Reader {
+        input,
+        truncated: false,
+    };
+    let len = reader.length()?;
+    let mut strings = Vec::with_capacity(len.min(reader.input.len()));
+    for _ in 0..len {
+        let len = reader.length()?;
+        let bytes = reader.bytes(len)?;
+        strings.push(core::str::from_utf8(bytes).map_err(|_| BinaryError::InvalidUtf8)?);
+    }
+    Ok((strings, reader.input))
+}
+
+struct Reader<'de> {
+    input: &'de [u8],
+    /// Whether a read went past the end of the input.
+    truncated: bool,
+}
+
+impl<'de> Reader<'de> {
+    fn bytes(&mut self, len: usize) -> Result<&'de [u8], BinaryError> {
+        if self.input.len() < len {
+            self.truncated = true;
+            return Err(BinaryError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, BinaryError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u128, BinaryError> {
+        let mut value = 0_u128;
+        for shift in (0..128).step_by(7) {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as u128;
+            if shift > 0 && bits >> (128 - shift) != 0 {
+                return Err(BinaryError::InvalidVarint);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(BinaryError::InvalidVarint)
+    }
+
+    fn length(&mut self) -> Result<usize, BinaryError> {
+        usize::try_from(self.varint()?).map_err(|_| BinaryError::InvalidVarint)
+    }
+}
+
+/// A [`Deserializer`] reading the body of the binary format.
+pub(super) struct BinaryDeserializer<'a, 'de> {
+    reader: Reader<'de>,
+    strings: &'a [&'de str],
+    field_names: bool,
+}
+
+impl<'a, 'de> BinaryDeserializer<'a, 'de> {
+    pub(super) fn new(input: &'de [u8], strings: &'a [&'de str], field_names: bool) -> Self {
+        Self {
+            reader: Reader {
+                input,
+                truncated: false,
+            },
+            strings,
+            field_names,
+        }
+    }
+
+    pub(super) fn remaining(&self) -> usize {
+        self.reader.input.len()
+    }
+
+    /// Returns `true` if deserialization ran past the end of the input.
+    pub(super) fn truncated(&self) -> bool {
+        self.reader.truncated
+    }
+
+    fn unsigned<T: TryFrom<u128>>(&mut self) -> Result<T, BinaryError> {
+        T::try_from(self.reader.varint()?).map_err(|_| BinaryError::OutOfRange)
+    }
+
+    fn signed<T: TryFrom<i128>>(&mut self) -> Result<T, BinaryError> {
+        let value = self.reader.varint()?;
+        let value = ((value >> 1) as i128) ^ -((value & 1) as i128);
+        T::try_from(value).map_err(|_| BinaryError::OutOfRange)
+    }
+
+    fn string(&mut self) -> Result<&'de str, BinaryError> {
+        let index = self.reader.varint()?;
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.strings.get(index).copied())
+            .ok_or(BinaryError::InvalidStringIndex(index))
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], BinaryError> {
+        Ok(self.reader.bytes(N)?.try_into().unwrap())
+    }
+}
+
+impl<'de> Deserializer<'de> for &mut BinaryDeserializer<'_, 'de> {
+    type Error = BinaryError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, BinaryError> {
+        Err(BinaryError::NotSelfDescribing)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        _visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        Err(BinaryError::NotSelfDescribing)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        match self.reader.byte()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            tag => Err(BinaryError::InvalidTag(tag)),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_i8(self.reader.byte()? as i8)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_i16(self.signed()?)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_i32(self.signed()?)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_i64(self.signed()?)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_i128(self.signed()?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_u8(self.reader.byte()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_u16(self.unsigned()?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_u32(self.unsigned()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_u64(self.unsigned()?)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_u128(self.unsigned()?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_f32(f32::from_le_bytes(self.array()?))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_f64(f64::from_le_bytes(self.array()?))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        let value = char::from_u32(self.unsigned()?).ok_or(BinaryError::OutOfRange)?;
+        visitor.visit_char(value)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_borrowed_str(self.string()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        let len = self.reader.length()?;
+        visitor.visit_borrowed_bytes(self.reader.bytes(len)?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        match self.reader.byte()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            tag => Err(BinaryError::InvalidTag(tag)),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        let len = self.reader.length()?;
+        visitor.visit_seq(Elements {
+            deserializer: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        visitor.visit_seq(Elements {
+            deserializer: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        let len = self.reader.length()?;
+        visitor.visit_map(Elements {
+            deserializer: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        if !self.field_names {
+            return self.deserialize_tuple(fields.len(), visitor);
+        }
+
+        let len = self.reader.length()?;
+        visitor.visit_map(NamedFields {
+            deserializer: self,
+            fields,
+            remaining: len,
+            value: None,
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        visitor.visit_enum(self)
+    }
+}
+
+/// The elements of sequences, tuples and maps, and the fields of structs without field names.
+struct Elements<'b, 'a, 'de> {
+    deserializer: &'b mut BinaryDeserializer<'a, 'de>,
+    remaining: usize,
+}
+
+impl<'de> SeqAccess<'de> for Elements<'_, '_, 'de> {
+    type Error = BinaryError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, BinaryError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> MapAccess<'de> for Elements<'_, '_, 'de> {
+    type Error = BinaryError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, BinaryError> {
+        self.next_element_seed(seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, BinaryError> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// The fields of structs written with field names.
+///
+/// Fields that are not part of the expected struct are skipped using their length prefix.
+struct NamedFields<'b, 'a, 'de> {
+    deserializer: &'b mut BinaryDeserializer<'a, 'de>,
+    fields: &'static [&'static str],
+    remaining: usize,
+    value: Option<&'de [u8]>,
+}
+
+impl<'de> MapAccess<'de> for NamedFields<'_, '_, 'de> {
+    type Error = BinaryError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, BinaryError> {
+        while self.remaining > 0 {
+            self.remaining -= 1;
+            let name = self.deserializer.string()?;
+            let len = self.deserializer.reader.length()?;
+            let value = self.deserializer.reader.bytes(len)?;
+            if self.fields.contains(&name) {
+                self.value = Some(value);
+                return seed
+                    .deserialize(BorrowedStrDeserializer::<BinaryError>::new(name))
+                    .map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, BinaryError> {
+        let input = self.value.take().ok_or(BinaryError::UnexpectedEnd)?;
+        seed.deserialize(&mut BinaryDeserializer::new(
+            input,
+            self.deserializer.strings,
+            true,
+        ))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> EnumAccess<'de> for &mut BinaryDeserializer<'_, 'de> {
+    type Error = BinaryError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), BinaryError> {
+        let variant = if self.field_names {
+            seed.deserialize(BorrowedStrDeserializer::<BinaryError>::new(self.string()?))?
+        } else {
+            let index: U32Deserializer<BinaryError> = self.unsigned::<u32>()?.into_deserializer();
+            seed.deserialize(index)?
+        };
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for &mut BinaryDeserializer<'_, 'de> {
+    type Error = BinaryError;
+
+    fn unit_variant(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, BinaryError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        self.deserialize_struct("", fields, visitor)
+    }
+}
@@ -0,0 +1,375 @@
+//! A compact binary format for reflected values.
+
+mod de;
+mod ser;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+use de::{read_string_table, BinaryDeserializer};
+use ser::{BinarySerializer, StringTable};
+use serde::{de::DeserializeSeed, Serialize};
+use thiserror::Error;
+
+/// The bytes at the start of data written with [`to_binary`].
+pub const BINARY_MAGIC: [u8; 4] = *b"BRFL";
+
+/// The current version of the format written by [`to_binary`].
+pub const BINARY_FORMAT_VERSION: u8 = 1;
+
+/// Header flag set when struct fields are written with their names.
+const FIELD_NAMES_FLAG: u8 = 1;
+
+/// Options controlling how values are written by [`to_binary`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BinaryOptions {
+    /// Whether to write the names of struct fields and enum variants.
+    ///
+    /// By default, struct fields are written in order without their names,
+    /// and enum variants are written as their index,
+    /// so the data can only be read back by the same version of the types.
+    ///
+    /// With field names, each field is written along with its name and length,
+    /// which allows reading the data after fields have been added, removed or reordered.
+    /// Fields missing from the data then need a default value, as with other formats.
+    pub field_names: bool,
+}
+
+/// Serializes a value to the compact binary format.
+///
+/// This is meant to be used with the [`TypedReflectSerializer`] and [`ReflectSerializer`],
+/// for data that doesn't need to be human-readable, such as save games and network snapshots,
+/// but it works with any [`Serialize`] type.
+///
+/// The data starts with a header containing [`BINARY_MAGIC`], the [format version] and a
+/// table of all the strings in the value, such as type paths, map keys and field names.
+/// Each string is stored once, and the value itself refers to strings by their index.
+/// Integers are written as [LEB128] varints, using [zigzag encoding] for signed integers,
+/// and floats as their little-endian bytes.
+///
+/// The format is not self-describing:
+/// it can't be read by [`Deserializer::deserialize_any`] and, as a result,
+/// data of [versioned] types can only be read if it has the current version.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypeRegistry, serde::*};
+/// #[derive(Reflect, PartialEq, Debug)]
+/// struct Player {
+///     name: String,
+///     health: u32,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Player>();
+///
+/// let player = Player { name: String::from("Bevy"), health: 10 };
+/// let serializer = ReflectSerializer::new(&player, &registry);
+/// let bytes = to_binary(&serializer, BinaryOptions::default()).unwrap();
+///
+/// let value = from_binary_seed(&bytes, ReflectDeserializer::new(&registry)).unwrap();
+/// assert!(value.reflect_partial_eq(&player).unwrap());
+/// ```
+///
+/// [`TypedReflectSerializer`]: crate::serde::TypedReflectSerializer
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+/// [format version]: BINARY_FORMAT_VERSION
+/// [LEB128]: https://en.wikipedia.org/wiki/LEB128
+/// [zigzag encoding]: https://en.wikipedia.org/wiki/Variable-length_quantity#Zigzag_encoding
+/// [`Deserializer::deserialize_any`]: serde::Deserializer::deserialize_any
+/// [versioned]: crate::serde::ReflectVersion
+pub fn to_binary<T>(value: &T, options: BinaryOptions) -> Result<Vec<u8>, BinaryError>
+where
+    T: Serialize + ?Sized,
+{
+    let mut strings = StringTable::default();
+    let mut body = Vec::new();
+    value.serialize(&mut BinarySerializer {
+        output: &mut body,
+        strings: &mut strings,
+        field_names: options.field_names,
+    })?;
+
+    let mut output = Vec::with_capacity(body.len() + 16);
+    output.extend_from_slice(&BINARY_MAGIC);
+    output.push(BINARY_FORMAT_VERSION);
+    output.push(if options.field_names {
+        FIELD_NAMES_FLAG
+    } else {
+        0
+    });
+    strings.write(&mut output);
+    output.extend_from_slice(&body);
+    Ok(output)
+}
+
+/// Deserializes a value written by [`to_binary`] using the given seed,
+/// such as a [`TypedReflectDeserializer`] or a [`ReflectDeserializer`].
+///
+/// Whether the data was written with [field names] is read from its header.
+///
+/// [`TypedReflectDeserializer`]: crate::serde::TypedReflectDeserializer
+/// [`ReflectDeserializer`]: crate::serde::ReflectDeserializer
+/// [field names]: BinaryOptions::field_names
+pub fn from_binary_seed<'de, T>(bytes: &'de [u8], seed: T) -> Result<T::Value, BinaryError>
+where
+    T: DeserializeSeed<'de>,
+{
+    let [magic @ .., version, flags] = bytes
+        .get(..BINARY_MAGIC.len() + 2)
+        .and_then(|header| <[u8; 6]>::try_from(header).ok())
+        .ok_or(BinaryError::InvalidHeader)?;
+    if magic != BINARY_MAGIC {
+        return Err(BinaryError::InvalidHeader);
+    }
+    if version != BINARY_FORMAT_VERSION {
+        return Err(BinaryError::UnsupportedVersion(version));
+    }
+
+    let (strings, body) = read_string_table(&bytes[BINARY_MAGIC.len() + 2..])?;
+    let mut deserializer = BinaryDeserializer::new(body, &strings, flags & FIELD_NAMES_FLAG != 0);
+    let value = seed.deserialize(&mut deserializer).map_err(|error| {
+        // Errors passing through `ReflectDeserialize` lose their variant,
+        // so running out of input is tracked by the deserializer instead.
+        if deserializer.truncated() {
+            BinaryError::UnexpectedEnd
+        } else {
+            error
+        }
+    })?;
+    match deserializer.remaining() {
+        0 => Ok(value),
+        remaining => Err(BinaryError::TrailingBytes(remaining)),
+    }
+}
+
+/// An error returned when reading or writing the binary format.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    /// The data doesn't start with [`BINARY_MAGIC`].
+    #[error("the data is missing the binary reflect header")]
+    InvalidHeader,
+    /// The data was written by an unknown version of the format.
+    #[error("unsupported binary format version {0}")]
+    UnsupportedVersion(u8),
+    /// The data ended in the middle of a value.
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    /// The data contains more bytes after the value.
+    #[error("{0} unexpected bytes after the value")]
+    TrailingBytes(usize),
+    /// A varint was too large for the value it encodes.
+    #[error("invalid varint")]
+    InvalidVarint,
+    /// An integer or character was out of range for its type.
+    #[error("value out of range for its type")]
+    OutOfRange,
+    /// A boolean or option tag was neither 0 nor 1.
+    #[error("invalid tag {0}, expected 0 or 1")]
+    InvalidTag(u8),
+    /// A string index was not in the string table.
+    #[error("invalid string index {0}")]
+    InvalidStringIndex(u128),
+    /// The string table contained invalid UTF-8.
+    #[error("invalid UTF-8 in the string table")]
+    InvalidUtf8,
+    /// A sequence or map was serialized without a known length.
+    #[error("sequences and maps must have a known length")]
+    UnknownLength,
+    /// A value required a self-describing format, using [`Deserializer::deserialize_any`].
+    ///
+    /// [`Deserializer::deserialize_any`]: serde::Deserializer::deserialize_any
+    #[error("the binary format is not self-describing")]
+    NotSelfDescribing,
+    /// An error returned by a type's serialization or deserialization.
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl serde::ser::Error for BinaryError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl serde::de::Error for BinaryError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        serde::{
+            ReflectDeserializer, ReflectSerializer, TypedReflectDeserializer,
+            TypedReflectSerializer,
+        },
+        structs::GetField,
+        FromReflect, Reflect, TypeRegistry,
+    };
+    use alloc::vec;
+    use bevy_platform::collections::HashMap;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Player {
+        name: String,
+        position: (f32, f32),
+        health: i32,
+        inventory: Vec<Item>,
+        stats: HashMap<String, u64>,
+        target: Option<String>,
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    enum Item {
+        Empty,
+        Potion(u8),
+        Sword { damage: u16, name: String },
+    }
+
+    fn get_registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Player>();
+        registry
+    }
+
+    fn get_player() -> Player {
+        Player {
+            name: String::from("Bevy"),
+            position: (1.5, -2.0),
+            health: -3,
+            inventory: vec![
+                Item::Empty,
+                Item::Potion(4),
+                Item::Sword {
+                    damage: 300,
+                    name: String::from("Bevy"),
+                },
+            ],
+            stats: HashMap::from_iter([(String::from("score"), 1_000_000)]),
+            target: Some(String::from("Ferris")),
+        }
+    }
+
+    fn roundtrip(options: BinaryOptions) -> Vec<u8> {
+        let registry = get_registry();
+        let player = get_player();
+
+        let bytes = to_binary(&ReflectSerializer::new(&player, &registry), options).unwrap();
+        let value = from_binary_seed(&bytes, ReflectDeserializer::new(&registry)).unwrap();
+        assert_eq!(
+            Player::from_reflect(value.as_partial_reflect()).unwrap(),
+            player
+        );
+        bytes
+    }
+
+    fn count(bytes: &[u8], needle: &str) -> usize {
+        bytes
+            .windows(needle.len())
+            .filter(|window| *window == needle.as_bytes())
+            .count()
+    }
+
+    #[test]
+    fn should_roundtrip() {
+        let bytes = roundtrip(BinaryOptions::default());
+        assert!(bytes.starts_with(&BINARY_MAGIC));
+
+        // Strings are stored once, and field names are not stored at all.
+        assert_eq!(
+            count(&bytes, "bevy_reflect::serde::binary::tests::Player"),
+            1
+        );
+        assert_eq!(count(&bytes, "Bevy"), 1);
+        assert_eq!(count(&bytes, "health"), 0);
+
+        let ron = ron::to_string(&ReflectSerializer::new(&get_player(), &get_registry())).unwrap();
+        assert!(bytes.len() < ron.len());
+    }
+
+    #[test]
+    fn should_roundtrip_with_field_names() {
+        let bytes = roundtrip(BinaryOptions { field_names: true });
+        assert_eq!(count(&bytes, "health"), 1);
+        assert_eq!(count(&bytes, "Sword"), 1);
+    }
+
+    #[test]
+    fn should_skip_unknown_fields_with_field_names() {
+        #[derive(Reflect)]
+        struct Old {
+            removed: Vec<String>,
+            health: u32,
+            name: String,
+        }
+
+        #[derive(Reflect, Debug, PartialEq)]
+        struct New {
+            name: String,
+            health: u32,
+        }
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Old>();
+        registry.register::<New>();
+
+        let old = Old {
+            removed: vec![String::from("a"), String::from("b")],
+            health: 10,
+            name: String::from("Bevy"),
+        };
+        let bytes = to_binary(
+            &TypedReflectSerializer::new(&old, &registry),
+            BinaryOptions { field_names: true },
+        )
+        .unwrap();
+
+        let value =
+            from_binary_seed(&bytes, TypedReflectDeserializer::of::<New>(&registry)).unwrap();
+        let value = value.reflect_ref().as_struct().unwrap();
+        assert_eq!(value.get_field::<u32>("health"), Some(&10));
+        assert_eq!(
+            value.get_field::<String>("name").map(String::as_str),
+            Some("Bevy")
+        );
+    }
+
+    #[test]
+    fn should_encode_integers_as_varints() {
+        let bytes = to_binary(&(1_u64, -1_i32, 300_u32), BinaryOptions::default()).unwrap();
+        // Header, empty string table, then the values.
+        assert_eq!(bytes, [b'B', b'R', b'F', b'L', 1, 0, 0, 1, 1, 172, 2]);
+    }
+
+    #[test]
+    fn should_error_on_invalid_data() {
+        let registry = get_registry();
+        let bytes = to_binary(
+            &ReflectSerializer::new(&get_player(), &registry),
+            BinaryOptions::default(),
+        )
+        .unwrap();
+
+        let error = from_binary_seed(
+            &bytes[..bytes.len() - 1],
+            ReflectDeserializer::new(&registry),
+        )
+        .unwrap_err();
+        assert_eq!(error, BinaryError::UnexpectedEnd);
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        let error = from_binary_seed(&trailing, ReflectDeserializer::new(&registry)).unwrap_err();
+        assert_eq!(error, BinaryError::TrailingBytes(1));
+
+        let error =
+            from_binary_seed(b"(name: \"Bevy\")", ReflectDeserializer::new(&registry)).unwrap_err();
+        assert_eq!(error, BinaryError::InvalidHeader);
+    }
+}
@@ -0,0 +1,408 @@
+use super::BinaryError;
+use alloc::{string::String, vec::Vec};
+use bevy_platform::collections::HashMap;
+use serde::{
+    ser::{
+        SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Serialize, Serializer,
+};
+
+/// The strings written by a [`BinarySerializer`], each stored once.
+#[derive(Default)]
+pub(super) struct StringTable {
+    strings: Vec<String>,
+    indices: HashMap<String, u64>,
+}
+
+impl StringTable {
+    fn intern(&mut self, value: &str) -> u64 {
+        if let Some(index) = self.indices.get(value) {
+            return *index;
+        }
+        let index = self.strings.len() as u64;
+        self.strings.push(String::from(value));
+        self.indices.insert(String::from(value), index);
+        index
+    }
+
+    pub(super) fn write(&self, output: &mut Vec<u8>) {
+        write_varint(output, self.strings.len() as u128);
+        for string in &self.strings {
+            write_varint(output, string.len() as u128);
+            output.extend_from_slice(string.as_bytes());
+        }
+    }
+}
+
+pub(super) fn write_varint(output: &mut Vec<u8>, mut value: u128) {
+    while value >= 0x80 {
+        output.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+fn zigzag(value: i128) -> u128 {
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+/// A [`Serializer`] writing the body of the binary format.
+///
+/// Strings are replaced by their index in the shared [`StringTable`].
+pub(super) struct BinarySerializer<'a> {
+    pub(super) output: &'a mut Vec<u8>,
+    pub(super) strings: &'a mut StringTable,
+    pub(super) field_names: bool,
+}
+
+impl BinarySerializer<'_> {
+    fn varint(&mut self, value: u128) {
+        write_varint(self.output, value);
+    }
+
+    fn string(&mut self, value: &str) {
+        let index = self.strings.intern(value);
+        self.varint(index as u128);
+    }
+
+    fn variant(&mut self, variant_index: u32, variant: &str) {
+        if self.field_names {
+            self.string(variant);
+        } else {
+            self.varint(variant_index as u128);
+        }
+    }
+
+    fn length(&mut self, len: Option<usize>) -> Result<(), BinaryError> {
+        let len = len.ok_or(BinaryError::UnknownLength)?;
+        self.varint(len as u128);
+        Ok(())
+    }
+
+    /// Serializes `value` into its own buffer, and writes it prefixed by its length.
+    fn length_prefixed<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BinaryError> {
+        let mut buffer = Vec::new();
+        value.serialize(&mut BinarySerializer {
+            output: &mut buffer,
+            strings: &mut *self.strings,
+            field_names: self.field_names,
+        })?;
+        self.varint(buffer.len() as u128);
+        self.output.extend_from_slice(&buffer);
+        Ok(())
+    }
+}
+
+impl<'a, 'b> Serializer for &'b mut BinarySerializer<'a> {
+    type Ok = ();
+    type Error = BinaryError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = StructSerializer<'b, 'a>;
+    type SerializeStructVariant = StructSerializer<'b, 'a>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), BinaryError> {
+        self.output.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), BinaryError> {
+        self.output.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), BinaryError> {
+        self.serialize_i128(v as i128)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), BinaryError> {
+        self.serialize_i128(v as i128)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), BinaryError> {
+        self.serialize_i128(v as i128)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), BinaryError> {
+        self.varint(zigzag(v));
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), BinaryError> {
+        self.output.push(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), BinaryError> {
+        self.serialize_u128(v as u128)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), BinaryError> {
+        self.serialize_u128(v as u128)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), BinaryError> {
+        self.serialize_u128(v as u128)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), BinaryError> {
+        self.varint(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), BinaryError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), BinaryError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), BinaryError> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), BinaryError> {
+        self.string(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), BinaryError> {
+        self.varint(v.len() as u128);
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), BinaryError> {
+        self.output.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), BinaryError> {
+        self.output.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), BinaryError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), BinaryError> {
+        self.variant(variant_index, variant);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        self.variant(variant_index, variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, BinaryError> {
+        self.length(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, BinaryError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, BinaryError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, BinaryError> {
+        self.variant(variant_index, variant);
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, BinaryError> {
+        self.length(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<StructSerializer<'b, 'a>, BinaryError> {
+        Ok(StructSerializer::new(self))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<StructSerializer<'b, 'a>, BinaryError> {
+        self.variant(variant_index, variant);
+        Ok(StructSerializer::new(self))
+    }
+}
+
+macro_rules! impl_serialize_elements {
+    ($($trait:ident :: $method:ident),* $(,)?) => {
+        $(
+            impl $trait for &mut BinarySerializer<'_> {
+                type Ok = ();
+                type Error = BinaryError;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BinaryError> {
+                    value.serialize(&mut **self)
+                }
+
+                fn end(self) -> Result<(), BinaryError> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_serialize_elements!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field,
+);
+
+impl SerializeMap for &mut BinarySerializer<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), BinaryError> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BinaryError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        Ok(())
+    }
+}
+
+/// Serializes the fields of structs and struct variants.
+///
+/// Without field names, fields are written one after the other.
+/// With field names, the struct is written as the number of fields,
+/// followed by the name of each field and its length-prefixed value,
+/// which allows skipping fields that no longer exist when deserializing.
+pub(super) struct StructSerializer<'b, 'a> {
+    serializer: &'b mut BinarySerializer<'a>,
+    fields: Vec<u8>,
+    len: usize,
+}
+
+impl<'b, 'a> StructSerializer<'b, 'a> {
+    fn new(serializer: &'b mut BinarySerializer<'a>) -> Self {
+        Self {
+            serializer,
+            fields: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        if !self.serializer.field_names {
+            return value.serialize(&mut *self.serializer);
+        }
+
+        let mut serializer = BinarySerializer {
+            output: &mut self.fields,
+            strings: &mut *self.serializer.strings,
+            field_names: true,
+        };
+        serializer.string(key);
+        serializer.length_prefixed(value)?;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), BinaryError> {
+        if self.serializer.field_names {
+            self.serializer.varint(self.len as u128);
+            self.serializer.output.extend_from_slice(&self.fields);
+        }
+        Ok(())
+    }
+}
+
+impl SerializeStruct for StructSerializer<'_, '_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        self.finish()
+    }
+}
+
+impl SerializeStructVariant for StructSerializer<'_, '_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        self.finish()
+    }
+}
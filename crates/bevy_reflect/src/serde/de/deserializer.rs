@@ -5,17 +5,27 @@ use crate::ReflectFromReflect;
 use crate::{
     serde::{
         de::{
-            arrays::ArrayVisitor, enums::EnumVisitor, error_utils::make_custom_error,
-            lists::ListVisitor, maps::MapVisitor, options::OptionVisitor, sets::SetVisitor,
-            structs::StructVisitor, tuple_structs::TupleStructVisitor, tuples::TupleVisitor,
+            arrays::ArrayVisitor,
+            enums::EnumVisitor,
+            error_utils::make_custom_error,
+            lists::ListVisitor,
+            maps::MapVisitor,
+            options::OptionVisitor,
+            schemaless::{schemaless_map, SchemalessValueDeserializer, SchemalessVisitor},
+            sets::SetVisitor,
+            structs::StructVisitor,
+            tuple_structs::TupleStructVisitor,
+            tuples::TupleVisitor,
         },
+        migration::{VERSIONED_FIELDS, VERSIONED_STRUCT},
+        MigrationError, ReflectVersion, SchemalessReflectDeserializer,
         TypeRegistrationDeserializer,
     },
     PartialReflect, ReflectDeserialize, TypeInfo, TypePath, TypeRegistration, TypeRegistry,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String, vec::Vec};
 use core::{fmt, fmt::Formatter};
-use serde::de::{DeserializeSeed, Error, IgnoredAny, MapAccess, Visitor};
+use serde::de::{DeserializeSeed, Error, IgnoredAny, MapAccess, SeqAccess, Visitor};
 
 use super::ReflectDeserializerProcessor;
 
@@ -138,6 +148,16 @@ impl<'a, P: ReflectDeserializerProcessor> ReflectDeserializer<'a, P> {
     }
 }
 
+impl<'a, P: ReflectDeserializerProcessor> ReflectDeserializer<'a, P> {
+    /// An internal constructor for creating a deserializer with an optional processor.
+    pub(super) fn new_internal(registry: &'a TypeRegistry, processor: Option<&'a mut P>) -> Self {
+        Self {
+            registry,
+            processor,
+        }
+    }
+}
+
 impl<'de, P: ReflectDeserializerProcessor> DeserializeSeed<'de> for ReflectDeserializer<'_, P> {
     type Value = Box<dyn PartialReflect>;
 
@@ -358,6 +378,9 @@ impl<'de, P: ReflectDeserializerProcessor> DeserializeSeed<'de>
     where
         D: serde::Deserializer<'de>,
     {
+        #[cfg(feature = "debug_stack")]
+        TYPE_INFO_STACK.with_borrow_mut(|stack| stack.push(self.registration.type_info()));
+
         let deserialize_internal = || -> Result<Self::Value, D::Error> {
             // First, check if our processor wants to deserialize this type
             // This takes priority over any other deserialization operations
@@ -375,167 +398,384 @@ impl<'de, P: ReflectDeserializerProcessor> DeserializeSeed<'de>
                 deserializer
             };
 
-            let type_path = self.registration.type_info().type_path();
-
-            // Handle both Value case and types that have a custom `ReflectDeserialize`
-            if let Some(deserialize_reflect) = self.registration.data::<ReflectDeserialize>() {
-                let value = deserialize_reflect.deserialize(deserializer)?;
-                return Ok(value.into_partial_reflect());
+            let registration = self.registration;
+            if let Some(version) = registration.data::<ReflectVersion>() {
+                // Self-describing formats may hold data without the version, such as data
+                // written before the type was versioned.
+                let self_describing = deserializer.is_human_readable();
+                let visitor = VersionedVisitor {
+                    version,
+                    deserializer: self,
+                    self_describing,
+                };
+                return if self_describing {
+                    deserializer.deserialize_any(visitor)
+                } else {
+                    deserializer.deserialize_struct(VERSIONED_STRUCT, VERSIONED_FIELDS, visitor)
+                };
             }
 
-            if let Some(deserialize_reflect) =
-                self.registration.data::<ReflectDeserializeWithRegistry>()
-            {
-                let value = deserialize_reflect.deserialize(deserializer, self.registry)?;
-                return Ok(value);
-            }
+            self.deserialize_unversioned(deserializer)
+        };
+
+        let output = deserialize_internal();
+
+        #[cfg(feature = "debug_stack")]
+        TYPE_INFO_STACK.with_borrow_mut(crate::type_info_stack::TypeInfoStack::pop);
+
+        output
+    }
+}
+
+impl<P: ReflectDeserializerProcessor> TypedReflectDeserializer<'_, P> {
+    /// Deserializes the value itself, without the version of [versioned] types.
+    ///
+    /// [versioned]: ReflectVersion
+    fn deserialize_unversioned<'de, D>(
+        self,
+        deserializer: D,
+    ) -> Result<Box<dyn PartialReflect>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let type_path = self.registration.type_info().type_path();
 
-            let dynamic_value: Box<dyn PartialReflect> = match self.registration.type_info() {
-                TypeInfo::Struct(struct_info) => {
-                    let mut dynamic_struct = deserializer.deserialize_struct(
-                        struct_info.type_path_table().ident().unwrap(),
-                        struct_info.field_names(),
-                        StructVisitor {
-                            struct_info,
+        // Handle both Value case and types that have a custom `ReflectDeserialize`
+        if let Some(deserialize_reflect) = self.registration.data::<ReflectDeserialize>() {
+            let value = deserialize_reflect.deserialize(deserializer)?;
+            return Ok(value.into_partial_reflect());
+        }
+
+        if let Some(deserialize_reflect) =
+            self.registration.data::<ReflectDeserializeWithRegistry>()
+        {
+            let value = deserialize_reflect.deserialize(deserializer, self.registry)?;
+            return Ok(value);
+        }
+
+        let dynamic_value: Box<dyn PartialReflect> = match self.registration.type_info() {
+            TypeInfo::Struct(struct_info) => {
+                let mut dynamic_struct = deserializer.deserialize_struct(
+                    struct_info.type_path_table().ident().unwrap(),
+                    struct_info.field_names(),
+                    StructVisitor {
+                        struct_info,
+                        registration: self.registration,
+                        registry: self.registry,
+                        processor: self.processor,
+                    },
+                )?;
+                dynamic_struct.set_represented_type(Some(self.registration.type_info()));
+                Box::new(dynamic_struct)
+            }
+            TypeInfo::TupleStruct(tuple_struct_info) => {
+                let mut dynamic_tuple_struct = if tuple_struct_info.field_len() == 1
+                    && self.registration.data::<SerializationData>().is_none()
+                {
+                    deserializer.deserialize_newtype_struct(
+                        tuple_struct_info.type_path_table().ident().unwrap(),
+                        TupleStructVisitor {
+                            tuple_struct_info,
                             registration: self.registration,
                             registry: self.registry,
                             processor: self.processor,
                         },
-                    )?;
-                    dynamic_struct.set_represented_type(Some(self.registration.type_info()));
-                    Box::new(dynamic_struct)
-                }
-                TypeInfo::TupleStruct(tuple_struct_info) => {
-                    let mut dynamic_tuple_struct = if tuple_struct_info.field_len() == 1
-                        && self.registration.data::<SerializationData>().is_none()
-                    {
-                        deserializer.deserialize_newtype_struct(
-                            tuple_struct_info.type_path_table().ident().unwrap(),
-                            TupleStructVisitor {
-                                tuple_struct_info,
-                                registration: self.registration,
-                                registry: self.registry,
-                                processor: self.processor,
-                            },
-                        )?
-                    } else {
-                        deserializer.deserialize_tuple_struct(
-                            tuple_struct_info.type_path_table().ident().unwrap(),
-                            tuple_struct_info.field_len(),
-                            TupleStructVisitor {
-                                tuple_struct_info,
-                                registration: self.registration,
-                                registry: self.registry,
-                                processor: self.processor,
-                            },
-                        )?
-                    };
-                    dynamic_tuple_struct.set_represented_type(Some(self.registration.type_info()));
-                    Box::new(dynamic_tuple_struct)
-                }
-                TypeInfo::List(list_info) => {
-                    let mut dynamic_list = deserializer.deserialize_seq(ListVisitor {
-                        list_info,
-                        registry: self.registry,
-                        processor: self.processor,
-                    })?;
-                    dynamic_list.set_represented_type(Some(self.registration.type_info()));
-                    Box::new(dynamic_list)
-                }
-                TypeInfo::Array(array_info) => {
-                    let mut dynamic_array = deserializer.deserialize_tuple(
-                        array_info.capacity(),
-                        ArrayVisitor {
-                            array_info,
+                    )?
+                } else {
+                    deserializer.deserialize_tuple_struct(
+                        tuple_struct_info.type_path_table().ident().unwrap(),
+                        tuple_struct_info.field_len(),
+                        TupleStructVisitor {
+                            tuple_struct_info,
+                            registration: self.registration,
                             registry: self.registry,
                             processor: self.processor,
                         },
-                    )?;
-                    dynamic_array.set_represented_type(Some(self.registration.type_info()));
-                    Box::new(dynamic_array)
-                }
-                TypeInfo::Map(map_info) => {
-                    let mut dynamic_map = deserializer.deserialize_map(MapVisitor {
-                        map_info,
+                    )?
+                };
+                dynamic_tuple_struct.set_represented_type(Some(self.registration.type_info()));
+                Box::new(dynamic_tuple_struct)
+            }
+            TypeInfo::List(list_info) => {
+                let mut dynamic_list = deserializer.deserialize_seq(ListVisitor {
+                    list_info,
+                    registry: self.registry,
+                    processor: self.processor,
+                })?;
+                dynamic_list.set_represented_type(Some(self.registration.type_info()));
+                Box::new(dynamic_list)
+            }
+            TypeInfo::Array(array_info) => {
+                let mut dynamic_array = deserializer.deserialize_tuple(
+                    array_info.capacity(),
+                    ArrayVisitor {
+                        array_info,
                         registry: self.registry,
                         processor: self.processor,
-                    })?;
-                    dynamic_map.set_represented_type(Some(self.registration.type_info()));
-                    Box::new(dynamic_map)
-                }
-                TypeInfo::Set(set_info) => {
-                    let mut dynamic_set = deserializer.deserialize_seq(SetVisitor {
-                        set_info,
+                    },
+                )?;
+                dynamic_array.set_represented_type(Some(self.registration.type_info()));
+                Box::new(dynamic_array)
+            }
+            TypeInfo::Map(map_info) => {
+                let mut dynamic_map = deserializer.deserialize_map(MapVisitor {
+                    map_info,
+                    registry: self.registry,
+                    processor: self.processor,
+                })?;
+                dynamic_map.set_represented_type(Some(self.registration.type_info()));
+                Box::new(dynamic_map)
+            }
+            TypeInfo::Set(set_info) => {
+                let mut dynamic_set = deserializer.deserialize_seq(SetVisitor {
+                    set_info,
+                    registry: self.registry,
+                    processor: self.processor,
+                })?;
+                dynamic_set.set_represented_type(Some(self.registration.type_info()));
+                Box::new(dynamic_set)
+            }
+            TypeInfo::Tuple(tuple_info) => {
+                let mut dynamic_tuple = deserializer.deserialize_tuple(
+                    tuple_info.field_len(),
+                    TupleVisitor {
+                        tuple_info,
+                        registration: self.registration,
                         registry: self.registry,
                         processor: self.processor,
-                    })?;
-                    dynamic_set.set_represented_type(Some(self.registration.type_info()));
-                    Box::new(dynamic_set)
-                }
-                TypeInfo::Tuple(tuple_info) => {
-                    let mut dynamic_tuple = deserializer.deserialize_tuple(
-                        tuple_info.field_len(),
-                        TupleVisitor {
-                            tuple_info,
+                    },
+                )?;
+                dynamic_tuple.set_represented_type(Some(self.registration.type_info()));
+                Box::new(dynamic_tuple)
+            }
+            TypeInfo::Enum(enum_info) => {
+                let mut dynamic_enum = if enum_info.type_path_table().module_path()
+                    == Some("core::option")
+                    && enum_info.type_path_table().ident() == Some("Option")
+                {
+                    deserializer.deserialize_option(OptionVisitor {
+                        enum_info,
+                        registry: self.registry,
+                        processor: self.processor,
+                    })?
+                } else {
+                    deserializer.deserialize_enum(
+                        enum_info.type_path_table().ident().unwrap(),
+                        enum_info.variant_names(),
+                        EnumVisitor {
+                            enum_info,
                             registration: self.registration,
                             registry: self.registry,
                             processor: self.processor,
                         },
-                    )?;
-                    dynamic_tuple.set_represented_type(Some(self.registration.type_info()));
-                    Box::new(dynamic_tuple)
-                }
-                TypeInfo::Enum(enum_info) => {
-                    let mut dynamic_enum = if enum_info.type_path_table().module_path()
-                        == Some("core::option")
-                        && enum_info.type_path_table().ident() == Some("Option")
-                    {
-                        deserializer.deserialize_option(OptionVisitor {
-                            enum_info,
-                            registry: self.registry,
-                            processor: self.processor,
-                        })?
+                    )?
+                };
+                dynamic_enum.set_represented_type(Some(self.registration.type_info()));
+                Box::new(dynamic_enum)
+            }
+            TypeInfo::Opaque(_) => {
+                // This case should already be handled
+                return Err(make_custom_error(format_args!(
+                    "type `{type_path}` did not register the `ReflectDeserialize` type data. For certain types, this may need to be registered manually using `register_type_data`",
+                )));
+            }
+        };
+
+        // Try to produce a concrete instance of the type to deserialize by using the reflected `FromReflect`.
+        if let Some(from_reflect) = self.registration.data::<ReflectFromReflect>()
+            && let Some(value) = from_reflect.from_reflect(&*dynamic_value)
+        {
+            return Ok(value);
+        }
+
+        Ok(dynamic_value)
+    }
+}
+
+/// A [`Visitor`] for deserializing the data of [versioned] types,
+/// migrating it to the current version of the type if needed.
+///
+/// In [human-readable] formats, which are expected to be self-describing and to write the version
+/// wrapper as a map, any other data is read as version 0 of the type and migrated.
+///
+/// [versioned]: ReflectVersion
+/// [human-readable]: serde::Deserializer::is_human_readable
+struct VersionedVisitor<'a, P: ReflectDeserializerProcessor> {
+    version: &'a ReflectVersion,
+    deserializer: TypedReflectDeserializer<'a, P>,
+    self_describing: bool,
+}
+
+impl<P: ReflectDeserializerProcessor> VersionedVisitor<'_, P> {
+    /// Returns `true` if `version` is the current version of the type,
+    /// or an error if it is newer.
+    fn is_current<E: Error>(&self, version: u32) -> Result<bool, E> {
+        if version > self.version.version() {
+            return Err(self.migration_error(MigrationError::UnsupportedVersion {
+                version,
+                current: self.version.version(),
+            }));
+        }
+        Ok(version == self.version.version())
+    }
+
+    fn migrate<E: Error>(
+        self,
+        value: Box<dyn PartialReflect>,
+        version: u32,
+    ) -> Result<Box<dyn PartialReflect>, E> {
+        let value = self
+            .version
+            .migrate(value, version)
+            .map_err(|err| self.migration_error(err))?;
+        self.deserializer
+            .deserialize_unversioned(SchemalessValueDeserializer(&*value))
+            .map_err(make_custom_error)
+    }
+
+    /// Migrates data that was written without its version, as version 0 of the type.
+    fn migrate_unversioned<E: Error>(
+        self,
+        value: Result<Box<dyn PartialReflect>, E>,
+    ) -> Result<Box<dyn PartialReflect>, E> {
+        self.migrate(value?, 0)
+    }
+
+    fn migration_error<E: Error>(&self, error: MigrationError) -> E {
+        make_custom_error(format_args!(
+            "cannot migrate data of type `{}`: {error}",
+            self.deserializer.registration.type_info().type_path()
+        ))
+    }
+}
+
+impl<'de, P: ReflectDeserializerProcessor> Visitor<'de> for VersionedVisitor<'_, P> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("versioned reflected value")
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+        self.migrate_unversioned(SchemalessVisitor.visit_bool(v))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        self.migrate_unversioned(SchemalessVisitor.visit_i64(v))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        self.migrate_unversioned(SchemalessVisitor.visit_u64(v))
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        self.migrate_unversioned(SchemalessVisitor.visit_f64(v))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.migrate_unversioned(SchemalessVisitor.visit_str(v))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        self.migrate_unversioned(SchemalessVisitor.visit_string(v))
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        self.migrate_unversioned(SchemalessVisitor.visit_unit())
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        self.migrate_unversioned(SchemalessVisitor.visit_none())
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        self.migrate_unversioned(SchemalessVisitor.visit_some(deserializer))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        if self.self_describing {
+            return self.migrate_unversioned(SchemalessVisitor.visit_seq(seq));
+        }
+
+        let version = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+
+        if self.is_current(version)? {
+            seq.next_element_seed(UnversionedDeserializer(self.deserializer))?
+                .ok_or_else(|| Error::invalid_length(1, &"versioned reflected value"))
+        } else {
+            let value = seq
+                .next_element_seed(SchemalessReflectDeserializer)?
+                .ok_or_else(|| Error::invalid_length(1, &self))?;
+            self.migrate(value, version)
+        }
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        // The version must come first, since it decides how the value is deserialized.
+        // Maps starting with anything else hold data written without its version.
+        let is_field = |key: &dyn PartialReflect, field: &str| {
+            key.try_downcast_ref::<String>()
+                .is_some_and(|key| key == field)
+        };
+        let mut entries = Vec::new();
+        if let Some(key) = map.next_key_seed(SchemalessReflectDeserializer)? {
+            let value = map.next_value_seed(SchemalessReflectDeserializer)?;
+            let version = value
+                .try_downcast_ref::<u64>()
+                .filter(|_| is_field(&*key, VERSIONED_FIELDS[0]))
+                // Versions that don't fit are reported as unsupported.
+                .map(|&version| u32::try_from(version).unwrap_or(u32::MAX));
+            entries.push((key, value));
+
+            if let Some(version) = version
+                && let Some(key) = map.next_key_seed(SchemalessReflectDeserializer)?
+            {
+                if is_field(&*key, VERSIONED_FIELDS[1]) {
+                    let value = if self.is_current(version)? {
+                        map.next_value_seed(UnversionedDeserializer(self.deserializer))?
                     } else {
-                        deserializer.deserialize_enum(
-                            enum_info.type_path_table().ident().unwrap(),
-                            enum_info.variant_names(),
-                            EnumVisitor {
-                                enum_info,
-                                registration: self.registration,
-                                registry: self.registry,
-                                processor: self.processor,
-                            },
-                        )?
+                        let value = map.next_value_seed(SchemalessReflectDeserializer)?;
+                        self.migrate(value, version)?
                     };
-                    dynamic_enum.set_represented_type(Some(self.registration.type_info()));
-                    Box::new(dynamic_enum)
-                }
-                TypeInfo::Opaque(_) => {
-                    // This case should already be handled
-                    return Err(make_custom_error(format_args!(
-                        "type `{type_path}` did not register the `ReflectDeserialize` type data. For certain types, this may need to be registered manually using `register_type_data`",
-                    )));
+                    while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+                    return Ok(value);
                 }
-            };
-
-            // Try to produce a concrete instance of the type to deserialize by using the reflected `FromReflect`.
-            if let Some(from_reflect) = self.registration.data::<ReflectFromReflect>()
-                && let Some(value) = from_reflect.from_reflect(&*dynamic_value)
-            {
-                return Ok(value);
+                entries.push((key, map.next_value_seed(SchemalessReflectDeserializer)?));
             }
+        }
 
-            Ok(dynamic_value)
-        };
-
-        #[cfg(feature = "debug_stack")]
-        TYPE_INFO_STACK.with_borrow_mut(|stack| stack.push(self.registration.type_info()));
+        while let Some(entry) =
+            map.next_entry_seed(SchemalessReflectDeserializer, SchemalessReflectDeserializer)?
+        {
+            entries.push(entry);
+        }
+        self.migrate_unversioned(Ok(schemaless_map(entries)))
+    }
+}
 
-        let output = deserialize_internal();
+/// Deserializes the value of a [`TypedReflectDeserializer`] without its version.
+struct UnversionedDeserializer<'a, P: ReflectDeserializerProcessor>(
+    TypedReflectDeserializer<'a, P>,
+);
 
-        #[cfg(feature = "debug_stack")]
-        TYPE_INFO_STACK.with_borrow_mut(crate::type_info_stack::TypeInfoStack::pop);
+impl<'de, P: ReflectDeserializerProcessor> DeserializeSeed<'de> for UnversionedDeserializer<'_, P> {
+    type Value = Box<dyn PartialReflect>;
 
-        output
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        self.0.deserialize_unversioned(deserializer)
     }
 }
@@ -0,0 +1,509 @@
+use crate::{
+    diff::{ListDiffOp, MapDiffOp, ReflectDiff, SetDiffOp},
+    serde::ReflectDeserializer,
+    PartialReflect, TypeRegistry,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{fmt, fmt::Formatter, marker::PhantomData};
+use serde::de::{
+    DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, Error, SeqAccess, VariantAccess,
+    Visitor,
+};
+
+use super::ReflectDeserializerProcessor;
+
+/// A deserializer for [`ReflectDiff`] values.
+///
+/// This is the deserializer counterpart to [`ReflectDiffSerializer`].
+///
+/// # Output
+///
+/// The values held by the returned diff, such as the new value of a [`ReflectDiff::Replace`],
+/// are deserialized with a [`ReflectDeserializer`], and so will generally be dynamic values.
+/// They are converted to the concrete types when the diff is applied with
+/// [`PartialReflect::apply_diff`].
+///
+/// If you want to override deserialization for a specific [`TypeRegistration`],
+/// you can pass in a reference to a [`ReflectDeserializerProcessor`], which will
+/// be used for every value held by the diff - see [`with_processor`].
+///
+/// # Example
+///
+/// ```
+/// # use serde::de::DeserializeSeed;
+/// # use bevy_reflect::prelude::*;
+/// # use bevy_reflect::{TypeRegistry, serde::ReflectDiffDeserializer};
+/// #[derive(Reflect, PartialEq, Debug)]
+/// struct MyStruct {
+///   a: i32,
+///   b: i32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<MyStruct>();
+///
+/// let input = r#"Struct([("b", Replace({"i32": 3}))])"#;
+///
+/// let mut deserializer = ron::Deserializer::from_str(input).unwrap();
+/// let diff = ReflectDiffDeserializer::new(&registry)
+///     .deserialize(&mut deserializer)
+///     .unwrap();
+///
+/// let mut value = MyStruct { a: 1, b: 2 };
+/// value.apply_diff(&diff).unwrap();
+/// assert_eq!(value, MyStruct { a: 1, b: 3 });
+/// ```
+///
+/// [`ReflectDiffSerializer`]: crate::serde::ReflectDiffSerializer
+/// [`PartialReflect::apply_diff`]: crate::PartialReflect::apply_diff
+/// [`TypeRegistration`]: crate::TypeRegistration
+/// [`with_processor`]: Self::with_processor
+pub struct ReflectDiffDeserializer<'a, P: ReflectDeserializerProcessor = ()> {
+    registry: &'a TypeRegistry,
+    processor: Option<&'a mut P>,
+}
+
+impl<'a> ReflectDiffDeserializer<'a, ()> {
+    /// Creates a deserializer with no processor.
+    ///
+    /// If you want to add custom logic for deserializing certain values, use
+    /// [`with_processor`].
+    ///
+    /// [`with_processor`]: Self::with_processor
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self {
+            registry,
+            processor: None,
+        }
+    }
+}
+
+impl<'a, P: ReflectDeserializerProcessor> ReflectDiffDeserializer<'a, P> {
+    /// Creates a deserializer with a processor.
+    ///
+    /// If you do not need any custom logic for handling certain values, use
+    /// [`new`].
+    ///
+    /// [`new`]: Self::new
+    pub fn with_processor(registry: &'a TypeRegistry, processor: &'a mut P) -> Self {
+        Self {
+            registry,
+            processor: Some(processor),
+        }
+    }
+}
+
+impl<'de, P: ReflectDeserializerProcessor> DeserializeSeed<'de> for ReflectDiffDeserializer<'_, P> {
+    type Value = ReflectDiff;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ReflectDiff::deserialize_part(self.registry, self.processor, deserializer)
+    }
+}
+
+/// A piece of a [`ReflectDiff`], deserialized using a [`TypeRegistry`] and an optional processor.
+trait DiffPart {
+    type Value;
+
+    fn deserialize_part<'de, D, P>(
+        registry: &TypeRegistry,
+        processor: Option<&mut P>,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        P: ReflectDeserializerProcessor;
+}
+
+/// A [`DeserializeSeed`] for the [`DiffPart`] `T`.
+struct PartSeed<'a, T, P> {
+    registry: &'a TypeRegistry,
+    processor: Option<&'a mut P>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, P> PartSeed<'a, T, P> {
+    fn new(registry: &'a TypeRegistry, processor: Option<&'a mut P>) -> Self {
+        Self {
+            registry,
+            processor,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: DiffPart, P: ReflectDeserializerProcessor> DeserializeSeed<'de>
+    for PartSeed<'_, T, P>
+{
+    type Value = T::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_part(self.registry, self.processor, deserializer)
+    }
+}
+
+/// A sequence of [`DiffPart`]s.
+struct Seq<T>(PhantomData<fn() -> T>);
+
+impl<T: DiffPart> DiffPart for Seq<T> {
+    type Value = Vec<T::Value>;
+
+    fn deserialize_part<'de, D, P>(
+        registry: &TypeRegistry,
+        processor: Option<&mut P>,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        P: ReflectDeserializerProcessor,
+    {
+        struct SeqVisitor<'a, T, P> {
+            registry: &'a TypeRegistry,
+            processor: Option<&'a mut P>,
+            _marker: PhantomData<fn() -> T>,
+        }
+
+        impl<'de, T: DiffPart, P: ReflectDeserializerProcessor> Visitor<'de> for SeqVisitor<'_, T, P> {
+            type Value = Vec<T::Value>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("a sequence of diff entries")
+            }
+
+            fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(value) = seq.next_element_seed(PartSeed::<T, P>::new(
+                    self.registry,
+                    self.processor.as_deref_mut(),
+                ))? {
+                    values.push(value);
+                }
+                Ok(values)
+            }
+        }
+
+        deserializer.deserialize_seq(SeqVisitor::<T, P> {
+            registry,
+            processor,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// A value deserialized with [`serde::Deserialize`], without using the registry.
+struct Plain<T>(PhantomData<fn() -> T>);
+
+impl<T: DeserializeOwned> DiffPart for Plain<T> {
+    type Value = T;
+
+    fn deserialize_part<'de, D, P>(
+        _registry: &TypeRegistry,
+        _processor: Option<&mut P>,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        P: ReflectDeserializerProcessor,
+    {
+        <T as serde::Deserialize>::deserialize(deserializer)
+    }
+}
+
+/// A reflected value, deserialized with a [`ReflectDeserializer`].
+struct Reflected;
+
+impl DiffPart for Reflected {
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize_part<'de, D, P>(
+        registry: &TypeRegistry,
+        processor: Option<&mut P>,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        P: ReflectDeserializerProcessor,
+    {
+        ReflectDeserializer::new_internal(registry, processor).deserialize(deserializer)
+    }
+}
+
+/// A tuple of two [`DiffPart`]s.
+struct Pair<A, B>(PhantomData<fn() -> (A, B)>);
+
+impl<A: DiffPart, B: DiffPart> DiffPart for Pair<A, B> {
+    type Value = (A::Value, B::Value);
+
+    fn deserialize_part<'de, D, P>(
+        registry: &TypeRegistry,
+        processor: Option<&mut P>,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        P: ReflectDeserializerProcessor,
+    {
+        deserializer.deserialize_tuple(2, PairVisitor::<A, B, P>::new(registry, processor))
+    }
+}
+
+/// A [`Visitor`] for tuples and tuple variants of two [`DiffPart`]s.
+struct PairVisitor<'a, A, B, P> {
+    registry: &'a TypeRegistry,
+    processor: Option<&'a mut P>,
+    _marker: PhantomData<fn() -> (A, B)>,
+}
+
+impl<'a, A, B, P> PairVisitor<'a, A, B, P> {
+    fn new(registry: &'a TypeRegistry, processor: Option<&'a mut P>) -> Self {
+        Self {
+            registry,
+            processor,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, A: DiffPart, B: DiffPart, P: ReflectDeserializerProcessor> Visitor<'de>
+    for PairVisitor<'_, A, B, P>
+{
+    type Value = (A::Value, B::Value);
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a tuple of two elements")
+    }
+
+    fn visit_seq<S>(mut self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        let first = seq
+            .next_element_seed(PartSeed::<A, P>::new(
+                self.registry,
+                self.processor.as_deref_mut(),
+            ))?
+            .ok_or_else(|| Error::invalid_length(0, &"a tuple of two elements"))?;
+        let second = seq
+            .next_element_seed(PartSeed::<B, P>::new(
+                self.registry,
+                self.processor.as_deref_mut(),
+            ))?
+            .ok_or_else(|| Error::invalid_length(1, &"a tuple of two elements"))?;
+        Ok((first, second))
+    }
+}
+
+/// A `(key, diff)` pair, as found in the diffs of structs, tuples, arrays and enums.
+type Field<K> = Pair<Plain<K>, ReflectDiff>;
+
+/// Deserializes the index of an enum variant from its name or its index.
+struct VariantIndex(&'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for VariantIndex {
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for VariantIndex {
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "one of the variants {:?}", self.0)
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+        usize::try_from(value)
+            .ok()
+            .filter(|index| *index < self.0.len())
+            .ok_or_else(|| Error::invalid_value(serde::de::Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        self.0
+            .iter()
+            .position(|name| *name == value)
+            .ok_or_else(|| Error::unknown_variant(value, self.0))
+    }
+}
+
+/// Implements [`DiffPart`] for an enum, by deserializing its variant index
+/// and passing the variant to the given closure-like block.
+macro_rules! impl_enum_diff_part {
+    ($ty:ty, $name:literal, $variants:expr, |$index:ident, $variant:ident, $registry:ident, $processor:ident| $body:block) => {
+        impl DiffPart for $ty {
+            type Value = $ty;
+
+            fn deserialize_part<'de, D, P>(
+                registry: &TypeRegistry,
+                processor: Option<&mut P>,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+                P: ReflectDeserializerProcessor,
+            {
+                struct EnumVisitor<'a, P> {
+                    registry: &'a TypeRegistry,
+                    processor: Option<&'a mut P>,
+                }
+
+                impl<'de, P: ReflectDeserializerProcessor> Visitor<'de> for EnumVisitor<'_, P> {
+                    type Value = $ty;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        formatter.write_str(concat!("a `", $name, "`"))
+                    }
+
+                    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: EnumAccess<'de>,
+                    {
+                        let ($index, $variant) = data.variant_seed(VariantIndex($variants))?;
+                        let $registry = self.registry;
+                        let $processor = self.processor;
+                        $body
+                    }
+                }
+
+                deserializer.deserialize_enum(
+                    $name,
+                    $variants,
+                    EnumVisitor {
+                        registry,
+                        processor,
+                    },
+                )
+            }
+        }
+    };
+}
+
+impl_enum_diff_part!(
+    ReflectDiff,
+    "ReflectDiff",
+    &[
+        "Unchanged",
+        "Replace",
+        "Struct",
+        "TupleStruct",
+        "Tuple",
+        "Array",
+        "Enum",
+        "List",
+        "Map",
+        "Set",
+    ],
+    |index, variant, registry, processor| {
+        match index {
+            0 => variant.unit_variant().map(|()| ReflectDiff::Unchanged),
+            1 => variant
+                .newtype_variant_seed(PartSeed::<Reflected, _>::new(registry, processor))
+                .map(ReflectDiff::Replace),
+            2 => variant
+                .newtype_variant_seed(PartSeed::<Seq<Field<String>>, _>::new(registry, processor))
+                .map(ReflectDiff::Struct),
+            3 => variant
+                .newtype_variant_seed(PartSeed::<Seq<Field<usize>>, _>::new(registry, processor))
+                .map(ReflectDiff::TupleStruct),
+            4 => variant
+                .newtype_variant_seed(PartSeed::<Seq<Field<usize>>, _>::new(registry, processor))
+                .map(ReflectDiff::Tuple),
+            5 => variant
+                .newtype_variant_seed(PartSeed::<Seq<Field<usize>>, _>::new(registry, processor))
+                .map(ReflectDiff::Array),
+            6 => variant
+                .tuple_variant(
+                    2,
+                    PairVisitor::<Plain<String>, Seq<Field<usize>>, _>::new(registry, processor),
+                )
+                .map(|(variant, fields)| ReflectDiff::Enum { variant, fields }),
+            7 => variant
+                .newtype_variant_seed(PartSeed::<Seq<ListDiffOp>, _>::new(registry, processor))
+                .map(ReflectDiff::List),
+            8 => variant
+                .newtype_variant_seed(PartSeed::<Seq<MapDiffOp>, _>::new(registry, processor))
+                .map(ReflectDiff::Map),
+            _ => variant
+                .newtype_variant_seed(PartSeed::<Seq<SetDiffOp>, _>::new(registry, processor))
+                .map(ReflectDiff::Set),
+        }
+    }
+);
+
+impl_enum_diff_part!(
+    ListDiffOp,
+    "ListDiffOp",
+    &["Insert", "Remove", "Modify"],
+    |index, variant, registry, processor| {
+        match index {
+            0 => variant
+                .tuple_variant(
+                    2,
+                    PairVisitor::<Plain<usize>, Reflected, _>::new(registry, processor),
+                )
+                .map(|(index, value)| ListDiffOp::Insert { index, value }),
+            1 => variant
+                .newtype_variant::<usize>()
+                .map(|index| ListDiffOp::Remove { index }),
+            _ => variant
+                .tuple_variant(
+                    2,
+                    PairVisitor::<Plain<usize>, ReflectDiff, _>::new(registry, processor),
+                )
+                .map(|(index, diff)| ListDiffOp::Modify { index, diff }),
+        }
+    }
+);
+
+impl_enum_diff_part!(
+    MapDiffOp,
+    "MapDiffOp",
+    &["Insert", "Remove", "Modify"],
+    |index, variant, registry, processor| {
+        match index {
+            0 => variant
+                .tuple_variant(
+                    2,
+                    PairVisitor::<Reflected, Reflected, _>::new(registry, processor),
+                )
+                .map(|(key, value)| MapDiffOp::Insert { key, value }),
+            1 => variant
+                .newtype_variant_seed(PartSeed::<Reflected, _>::new(registry, processor))
+                .map(|key| MapDiffOp::Remove { key }),
+            _ => variant
+                .tuple_variant(
+                    2,
+                    PairVisitor::<Reflected, ReflectDiff, _>::new(registry, processor),
+                )
+                .map(|(key, diff)| MapDiffOp::Modify { key, diff }),
+        }
+    }
+);
+
+impl_enum_diff_part!(
+    SetDiffOp,
+    "SetDiffOp",
+    &["Insert", "Remove"],
+    |index, variant, registry, processor| {
+        let seed = PartSeed::<Reflected, _>::new(registry, processor);
+        match index {
+            0 => variant.newtype_variant_seed(seed).map(SetDiffOp::Insert),
+            _ => variant.newtype_variant_seed(seed).map(SetDiffOp::Remove),
+        }
+    }
+);
@@ -1,3 +1,4 @@
+use crate::{attributes::CustomAttributes, validation::Constraints, PartialReflect};
 use core::fmt::Display;
 use serde::de::Error;
 
@@ -27,3 +28,20 @@ pub(super) fn make_custom_error<E: Error>(msg: impl Display) -> E {
     #[cfg(not(feature = "debug_stack"))]
     return E::custom(msg);
 }
+
+/// Checks that a deserialized field satisfies the [validation constraints] in its attributes.
+///
+/// [validation constraints]: crate::validation
+pub(super) fn check_constraints<E: Error>(
+    name: impl Display,
+    attributes: &CustomAttributes,
+    value: &dyn PartialReflect,
+) -> Result<(), E> {
+    Constraints::from_attributes(attributes)
+        .check(value)
+        .map_err(|violation| {
+            make_custom_error(format_args!(
+                "invalid value for field `{name}`: {violation}"
+            ))
+        })
+}
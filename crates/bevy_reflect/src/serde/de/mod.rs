@@ -1,11 +1,14 @@
 pub use deserialize_with_registry::*;
 pub use deserializer::*;
+pub use diffs::*;
 pub use processor::*;
 pub use registrations::*;
+pub use schemaless::*;
 
 mod arrays;
 mod deserialize_with_registry;
 mod deserializer;
+mod diffs;
 mod enums;
 mod error_utils;
 mod helpers;
@@ -15,6 +18,7 @@ mod options;
 mod processor;
 mod registration_utils;
 mod registrations;
+mod schemaless;
 mod sets;
 mod struct_utils;
 mod structs;
@@ -0,0 +1,463 @@
+use crate::{
+    enums::{DynamicEnum, DynamicVariant, Enum, VariantType},
+    list::DynamicList,
+    map::{DynamicMap, Map},
+    structs::DynamicStruct,
+    tuple::DynamicTuple,
+    PartialReflect, ReflectRef,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{fmt, fmt::Formatter};
+use serde::de::{
+    value::{Error, MapDeserializer, SeqDeserializer},
+    DeserializeSeed, Deserializer, EnumAccess, Error as _, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+/// A deserializer for reflected values that does not require any type information.
+///
+/// Rather than being guided by a [`TypeRegistration`], this deserializer relies on
+/// the format being self-describing, and builds dynamic values mirroring the shape of the data:
+/// - maps with string keys become [`DynamicStruct`]s, and other maps become [`DynamicMap`]s,
+/// - sequences become [`DynamicList`]s,
+/// - optional values become `None` or `Some` [`DynamicEnum`]s,
+/// - non-negative integers become `u64`s and negative integers become `i64`s,
+/// - and all other primitives become `bool`, `f64`, `String` or `()`.
+///
+/// This is used to load data written by an older version of a [versioned] type,
+/// which may no longer match its current type information.
+///
+/// The resulting value can be turned back into serialized data with the
+/// [`SchemalessReflectSerializer`].
+///
+/// # Example
+///
+/// ```
+/// # use serde::de::DeserializeSeed;
+/// # use bevy_reflect::{structs::GetField, serde::SchemalessReflectDeserializer};
+/// let input = r#"{ "name": "Bevy", "legs": 4 }"#;
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(input);
+/// let output = SchemalessReflectDeserializer.deserialize(&mut deserializer).unwrap();
+///
+/// let output = output.reflect_ref().as_struct().unwrap();
+/// assert_eq!(output.get_field::<String>("name").unwrap(), "Bevy");
+/// assert_eq!(output.get_field::<u64>("legs"), Some(&4));
+/// ```
+///
+/// [`TypeRegistration`]: crate::TypeRegistration
+/// [versioned]: crate::serde::ReflectVersion
+/// [`SchemalessReflectSerializer`]: crate::serde::SchemalessReflectSerializer
+pub struct SchemalessReflectDeserializer;
+
+impl<'de> DeserializeSeed<'de> for SchemalessReflectDeserializer {
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SchemalessVisitor)
+    }
+}
+
+/// The [`Visitor`] of the [`SchemalessReflectDeserializer`].
+pub(super) struct SchemalessVisitor;
+
+impl<'de> Visitor<'de> for SchemalessVisitor {
+    type Value = Box<dyn PartialReflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("any self-describing value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        match u64::try_from(v) {
+            Ok(v) => Ok(Box::new(v)),
+            Err(_) => Ok(Box::new(v)),
+        }
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Box::new(String::from(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Box::new(v.to_vec()))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Box::new(()))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Box::new(DynamicEnum::new("None", DynamicVariant::Unit)))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut tuple = DynamicTuple::default();
+        tuple.insert_boxed(SchemalessReflectDeserializer.deserialize(deserializer)?);
+        Ok(Box::new(DynamicEnum::new(
+            "Some",
+            DynamicVariant::Tuple(tuple),
+        )))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        SchemalessReflectDeserializer.deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut list = DynamicList::default();
+        while let Some(value) = seq.next_element_seed(SchemalessReflectDeserializer)? {
+            list.push_box(value);
+        }
+        Ok(Box::new(list))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::new();
+        while let Some(entry) =
+            map.next_entry_seed(SchemalessReflectDeserializer, SchemalessReflectDeserializer)?
+        {
+            entries.push(entry);
+        }
+        Ok(schemaless_map(entries))
+    }
+}
+
+/// Builds the schemaless representation of a map with the given entries:
+/// a [`DynamicStruct`] if all the keys are strings, or a [`DynamicMap`] otherwise.
+pub(super) fn schemaless_map(
+    entries: Vec<(Box<dyn PartialReflect>, Box<dyn PartialReflect>)>,
+) -> Box<dyn PartialReflect> {
+    if entries
+        .iter()
+        .all(|(key, _)| key.try_downcast_ref::<String>().is_some())
+    {
+        let mut dynamic_struct = DynamicStruct::default();
+        for (key, value) in entries {
+            // The keys were checked to be strings above.
+            let name = key.try_take::<String>().ok().unwrap();
+            dynamic_struct.insert_boxed(name, value);
+        }
+        Box::new(dynamic_struct)
+    } else {
+        let mut dynamic_map = DynamicMap::default();
+        for (key, value) in entries {
+            dynamic_map.insert_boxed(key, value);
+        }
+        Box::new(dynamic_map)
+    }
+}
+
+/// A [`Deserializer`] reading from a reflected value, without any type information.
+///
+/// This is the counterpart of [`SchemalessReflectDeserializer`], used to feed
+/// the migrated value of a [versioned] type to the typed deserializers.
+///
+/// [versioned]: crate::serde::ReflectVersion
+pub(super) struct SchemalessValueDeserializer<'a>(pub &'a dyn PartialReflect);
+
+macro_rules! visit_primitives {
+    ($value:expr, $visitor:ident, $($ty:ty => $method:ident),* $(,)?) => {
+        $(
+            if let Some(value) = $value.try_downcast_ref::<$ty>() {
+                return $visitor.$method(*value);
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for SchemalessValueDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visit_primitives!(self.0, visitor,
+            bool => visit_bool,
+            u8 => visit_u8,
+            u16 => visit_u16,
+            u32 => visit_u32,
+            u64 => visit_u64,
+            u128 => visit_u128,
+            i8 => visit_i8,
+            i16 => visit_i16,
+            i32 => visit_i32,
+            i64 => visit_i64,
+            i128 => visit_i128,
+            f32 => visit_f32,
+            f64 => visit_f64,
+            char => visit_char,
+        );
+        if let Some(value) = self.0.try_downcast_ref::<String>() {
+            return visitor.visit_str(value);
+        }
+        if self.0.try_downcast_ref::<()>().is_some() {
+            return visitor.visit_unit();
+        }
+
+        match self.0.reflect_ref() {
+            ReflectRef::Struct(value) => visit_fields(
+                (0..value.field_len())
+                    .filter_map(|index| Some((value.name_at(index)?, value.field_at(index)?))),
+                visitor,
+            ),
+            ReflectRef::TupleStruct(value) => visit_elements(value.iter_fields(), visitor),
+            ReflectRef::Tuple(value) => visit_elements(value.iter_fields(), visitor),
+            ReflectRef::List(value) => visit_elements(value.iter(), visitor),
+            ReflectRef::Array(value) => visit_elements(value.iter(), visitor),
+            ReflectRef::Set(value) => visit_elements(value.iter(), visitor),
+            ReflectRef::Map(value) => {
+                let mut map = MapDeserializer::<_, Error>::new(
+                    value
+                        .iter()
+                        .map(|(key, value)| (Self(key), SchemalessValueDeserializer(value))),
+                );
+                let output = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(output)
+            }
+            ReflectRef::Enum(value) => match option_content(value) {
+                Some(None) => visitor.visit_none(),
+                Some(Some(value)) => visitor.visit_some(SchemalessValueDeserializer(value)),
+                None => visitor.visit_enum(EnumDeserializer {
+                    variant: value.variant_name(),
+                    content: VariantContent::Enum(value),
+                }),
+            },
+            _ => Err(Error::custom(format_args!(
+                "cannot deserialize a value of type `{}` without type information",
+                self.0.reflect_type_path()
+            ))),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let ReflectRef::Enum(value) = self.0.reflect_ref() {
+            match option_content(value) {
+                Some(None) => return visitor.visit_none(),
+                Some(Some(value)) => return visitor.visit_some(SchemalessValueDeserializer(value)),
+                None => {}
+            }
+        }
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(variant) = self.0.try_downcast_ref::<String>() {
+            // Unit variants are stored as just their name.
+            return visitor.visit_enum(EnumDeserializer {
+                variant,
+                content: VariantContent::Unit,
+            });
+        }
+
+        match self.0.reflect_ref() {
+            // Other variants are stored as a single-entry map from their name to their fields.
+            ReflectRef::Struct(value) if value.field_len() == 1 => {
+                visitor.visit_enum(EnumDeserializer {
+                    variant: value.name_at(0).unwrap(),
+                    content: VariantContent::Value(value.field_at(0).unwrap()),
+                })
+            }
+            ReflectRef::Enum(value) => visitor.visit_enum(EnumDeserializer {
+                variant: value.variant_name(),
+                content: VariantContent::Enum(value),
+            }),
+            _ => Err(Error::custom(format_args!(
+                "expected an enum variant, found a value of type `{}`",
+                self.0.reflect_type_path()
+            ))),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for SchemalessValueDeserializer<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// Returns the content of `value` if it is an option.
+fn option_content(value: &dyn Enum) -> Option<Option<&dyn PartialReflect>> {
+    match (value.variant_name(), value.variant_type()) {
+        ("None", VariantType::Unit) => Some(None),
+        ("Some", VariantType::Tuple) if value.field_len() == 1 => Some(value.field_at(0)),
+        _ => None,
+    }
+}
+
+fn visit_elements<'a, 'de, V>(
+    elements: impl Iterator<Item = &'a dyn PartialReflect>,
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::<_, Error>::new(elements.map(SchemalessValueDeserializer));
+    let output = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(output)
+}
+
+fn visit_fields<'a, 'de, V>(
+    fields: impl Iterator<Item = (&'a str, &'a dyn PartialReflect)>,
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let mut map = MapDeserializer::<_, Error>::new(
+        fields.map(|(name, value)| (name, SchemalessValueDeserializer(value))),
+    );
+    let output = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(output)
+}
+
+enum VariantContent<'a> {
+    Unit,
+    Value(&'a dyn PartialReflect),
+    Enum(&'a dyn Enum),
+}
+
+struct EnumDeserializer<'a> {
+    variant: &'a str,
+    content: VariantContent<'a>,
+}
+
+impl<'a, 'de> EnumAccess<'de> for EnumDeserializer<'a> {
+    type Error = Error;
+    type Variant = VariantContent<'a>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant =
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+        Ok((variant, self.content))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantContent<'_> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self {
+            VariantContent::Unit => Err(Error::custom("expected a newtype variant")),
+            VariantContent::Value(value) => seed.deserialize(SchemalessValueDeserializer(value)),
+            VariantContent::Enum(value) => match value.field_at(0) {
+                Some(field) if value.field_len() == 1 => {
+                    seed.deserialize(SchemalessValueDeserializer(field))
+                }
+                _ => Err(Error::custom("expected a newtype variant")),
+            },
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            VariantContent::Unit => Err(Error::custom("expected a tuple variant")),
+            VariantContent::Value(value) => {
+                SchemalessValueDeserializer(value).deserialize_any(visitor)
+            }
+            VariantContent::Enum(value) => visit_elements(
+                (0..value.field_len()).filter_map(|index| value.field_at(index)),
+                visitor,
+            ),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            VariantContent::Unit => Err(Error::custom("expected a struct variant")),
+            VariantContent::Value(value) => {
+                SchemalessValueDeserializer(value).deserialize_any(visitor)
+            }
+            VariantContent::Enum(value) => visit_fields(
+                (0..value.field_len())
+                    .filter_map(|index| Some((value.name_at(index)?, value.field_at(index)?))),
+                visitor,
+            ),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     enums::StructVariantInfo,
     serde::{
         de::{
-            error_utils::make_custom_error,
+            error_utils::{check_constraints, make_custom_error},
             helpers::{ExpectedValues, Ident},
             registration_utils::try_get_registration,
         },
@@ -116,6 +116,7 @@ where
             registry,
             processor.as_deref_mut(),
         ))?;
+        check_constraints(&key, field.custom_attributes(), &*value)?;
         dynamic_struct.insert_boxed(&key, value);
     }
 
@@ -161,7 +162,8 @@ where
     let serialization_data = registration.data::<SerializationData>();
 
     for index in 0..len {
-        let name = info.field_at::<V::Error>(index)?.name();
+        let field = info.field_at::<V::Error>(index)?;
+        let name = field.name();
 
         if serialization_data
             .map(|data| data.is_field_skipped(index))
@@ -175,11 +177,12 @@ where
 
         let value = seq
             .next_element_seed(TypedReflectDeserializer::new_internal(
-                try_get_registration(*info.field_at(index)?.ty(), registry)?,
+                try_get_registration(*field.ty(), registry)?,
                 registry,
                 processor.as_deref_mut(),
             ))?
             .ok_or_else(|| Error::invalid_length(index, &len.to_string().as_str()))?;
+        check_constraints(name, field.custom_attributes(), &*value)?;
         dynamic_struct.insert_boxed(name, value);
     }
 
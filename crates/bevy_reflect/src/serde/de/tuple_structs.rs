@@ -6,7 +6,10 @@ use crate::{
 use core::{fmt, fmt::Formatter};
 use serde::de::{DeserializeSeed, SeqAccess, Visitor};
 
-use super::{registration_utils::try_get_registration, TypedReflectDeserializer};
+use super::{
+    error_utils::check_constraints, registration_utils::try_get_registration,
+    TypedReflectDeserializer,
+};
 
 use super::ReflectDeserializerProcessor;
 
@@ -53,17 +56,15 @@ impl<'de, P: ReflectDeserializerProcessor> Visitor<'de> for TupleStructVisitor<'
             return Ok(tuple);
         }
 
-        let registration = try_get_registration(
-            *self
-                .tuple_struct_info
-                .field_at(0)
-                .ok_or(serde::de::Error::custom("Field at index 0 not found"))?
-                .ty(),
-            self.registry,
-        )?;
+        let field = self
+            .tuple_struct_info
+            .field_at(0)
+            .ok_or(serde::de::Error::custom("Field at index 0 not found"))?;
+        let registration = try_get_registration(*field.ty(), self.registry)?;
         let reflect_deserializer =
             TypedReflectDeserializer::new_internal(registration, self.registry, self.processor);
         let value = reflect_deserializer.deserialize(deserializer)?;
+        check_constraints(0, field.custom_attributes(), &*value)?;
 
         tuple.insert_boxed(value.into_partial_reflect());
 
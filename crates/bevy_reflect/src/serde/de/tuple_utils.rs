@@ -1,7 +1,10 @@
 use crate::{
     enums::TupleVariantInfo,
     serde::{
-        de::{error_utils::make_custom_error, registration_utils::try_get_registration},
+        de::{
+            error_utils::{check_constraints, make_custom_error},
+            registration_utils::try_get_registration,
+        },
         SerializationData, TypedReflectDeserializer,
     },
     tuple::{DynamicTuple, TupleInfo},
@@ -98,13 +101,15 @@ where
             continue;
         }
 
+        let field = info.field_at::<V::Error>(index)?;
         let value = seq
             .next_element_seed(TypedReflectDeserializer::new_internal(
-                try_get_registration(*info.field_at(index)?.ty(), registry)?,
+                try_get_registration(*field.ty(), registry)?,
                 registry,
                 processor.as_deref_mut(),
             ))?
             .ok_or_else(|| Error::invalid_length(index, &len.to_string().as_str()))?;
+        check_constraints(index, field.custom_attributes(), &*value)?;
         tuple.insert_boxed(value);
     }
 
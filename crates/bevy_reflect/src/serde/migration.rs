@@ -0,0 +1,361 @@
+use crate::PartialReflect;
+use alloc::{boxed::Box, collections::BTreeMap, string::String};
+use bevy_platform::sync::Arc;
+use core::fmt::{Debug, Formatter};
+use thiserror::Error;
+
+/// The name of the struct wrapping the data of [versioned](ReflectVersion) types.
+pub(super) const VERSIONED_STRUCT: &str = "Versioned";
+
+/// The fields of the struct wrapping the data of [versioned](ReflectVersion) types.
+pub(super) const VERSIONED_FIELDS: &[&str] = &["version", "value"];
+
+/// A migration step, upgrading data from one version of a type to the next.
+///
+/// The value passed to the migration is the [schemaless] representation of the
+/// serialized data: structs and string-keyed maps are [`DynamicStruct`]s,
+/// sequences are [`DynamicList`]s, options are [`DynamicEnum`]s,
+/// and primitives are stored as `bool`, `u64`, `i64`, `f64` or `String`.
+///
+/// [schemaless]: crate::serde::SchemalessReflectDeserializer
+/// [`DynamicStruct`]: crate::structs::DynamicStruct
+/// [`DynamicList`]: crate::list::DynamicList
+/// [`DynamicEnum`]: crate::enums::DynamicEnum
+pub type MigrationFn =
+    Arc<dyn Fn(Box<dyn PartialReflect>) -> Result<Box<dyn PartialReflect>, String> + Send + Sync>;
+
+/// Type data recording the current serialization version of a type,
+/// along with the migrations that upgrade data from older versions.
+///
+/// This is registered automatically for types using `#[reflect(version = N)]`:
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypeRegistry, serde::ReflectVersion};
+/// #[derive(Reflect)]
+/// #[reflect(version = 2)]
+/// struct Player {
+///     health: u32,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Player>();
+///
+/// let version = registry.get_type_data::<ReflectVersion>(core::any::TypeId::of::<Player>());
+/// assert_eq!(version.unwrap().version(), 2);
+/// ```
+///
+/// Versioned types are serialized by the [`TypedReflectSerializer`] as a struct
+/// containing the `version` and the `value` itself.
+/// When the [`TypedReflectDeserializer`] encounters data with an older version,
+/// it deserializes it without a schema, using the [`SchemalessReflectDeserializer`],
+/// runs the registered migrations one version at a time,
+/// and then deserializes the migrated value as the current version of the type.
+/// This requires a self-describing format, such as RON or JSON.
+///
+/// Migrations are added with [`TypeRegistry::register_migration`].
+///
+/// In [human-readable] formats, data that isn't wrapped with its version, such as data written
+/// before the type was versioned, is read as version 0 and migrated from there.
+/// Types that used to be serialized without a version should therefore register a migration
+/// from version 0, which can simply be `Ok` if the data didn't change.
+///
+/// [`TypedReflectSerializer`]: crate::serde::TypedReflectSerializer
+/// [`TypedReflectDeserializer`]: crate::serde::TypedReflectDeserializer
+/// [`SchemalessReflectDeserializer`]: crate::serde::SchemalessReflectDeserializer
+/// [`TypeRegistry::register_migration`]: crate::TypeRegistry::register_migration
+/// [human-readable]: serde::Deserializer::is_human_readable
+#[derive(Clone)]
+pub struct ReflectVersion {
+    version: u32,
+    migrations: BTreeMap<u32, MigrationFn>,
+}
+
+impl ReflectVersion {
+    /// Creates type data for the given current version, with no migrations.
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            migrations: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the current version of the type.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Adds a migration upgrading data from `from_version` to `from_version + 1`.
+    ///
+    /// Replaces any migration previously registered for `from_version`.
+    pub fn add_migration<F>(&mut self, from_version: u32, migration: F)
+    where
+        F: Fn(Box<dyn PartialReflect>) -> Result<Box<dyn PartialReflect>, String>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.migrations.insert(from_version, Arc::new(migration));
+    }
+
+    /// Returns `true` if there is a migration from `from_version`.
+    pub fn has_migration(&self, from_version: u32) -> bool {
+        self.migrations.contains_key(&from_version)
+    }
+
+    /// Upgrades `value` from `from_version` to the current version,
+    /// running each intermediate migration in order.
+    pub fn migrate(
+        &self,
+        mut value: Box<dyn PartialReflect>,
+        from_version: u32,
+    ) -> Result<Box<dyn PartialReflect>, MigrationError> {
+        if from_version > self.version {
+            return Err(MigrationError::UnsupportedVersion {
+                version: from_version,
+                current: self.version,
+            });
+        }
+
+        for version in from_version..self.version {
+            let migration = self
+                .migrations
+                .get(&version)
+                .ok_or(MigrationError::MissingMigration { from: version })?;
+            value = migration(value).map_err(|reason| MigrationError::Failed {
+                from: version,
+                reason,
+            })?;
+        }
+
+        Ok(value)
+    }
+}
+
+impl Debug for ReflectVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReflectVersion")
+            .field("version", &self.version)
+            .field("migrations", &self.migrations.keys())
+            .finish()
+    }
+}
+
+/// An error returned when migrating data with [`ReflectVersion::migrate`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The data is newer than the current version of the type.
+    #[error("data has version {version}, but the newest supported version is {current}")]
+    UnsupportedVersion {
+        /// The version of the data.
+        version: u32,
+        /// The current version of the type.
+        current: u32,
+    },
+    /// No migration was registered for one of the versions between the data and the type.
+    #[error("no migration is registered from version {from}")]
+    MissingMigration {
+        /// The version with no migration.
+        from: u32,
+    },
+    /// A migration returned an error.
+    #[error("migration from version {from} failed: {reason}")]
+    Failed {
+        /// The version the failing migration upgrades from.
+        from: u32,
+        /// The error returned by the migration.
+        reason: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        serde::{ReflectDeserializer, TypedReflectDeserializer, TypedReflectSerializer},
+        structs::GetField,
+        FromReflect, Reflect, TypeRegistry,
+    };
+    use alloc::string::ToString;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    #[reflect(version = 3)]
+    struct Player {
+        name: String,
+        health: u32,
+        lives: Option<u8>,
+    }
+
+    /// Version 0 was not versioned, version 1 called `health` `hp`,
+    /// and version 2 did not have `lives`.
+    fn get_registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register_migration::<Player, _>(0, Ok);
+        registry.register_migration::<Player, _>(1, |value| {
+            let mut player = value
+                .reflect_ref()
+                .as_struct()
+                .map_err(|err| err.to_string())?
+                .to_dynamic_struct();
+            let (_, hp) = player.remove_by_name("hp").ok_or("missing `hp`")?;
+            player.insert_boxed("health", hp);
+            Ok(Box::new(player))
+        });
+        registry.register_migration::<Player, _>(2, |value| {
+            let mut player = value
+                .reflect_ref()
+                .as_struct()
+                .map_err(|err| err.to_string())?
+                .to_dynamic_struct();
+            player.insert("lives", Some(3_u64));
+            Ok(Box::new(player))
+        });
+        registry
+    }
+
+    fn deserialize_player(input: &str, registry: &TypeRegistry) -> Result<Player, ron::Error> {
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let value =
+            TypedReflectDeserializer::of::<Player>(registry).deserialize(&mut deserializer)?;
+        Ok(Player::from_reflect(value.as_partial_reflect()).unwrap())
+    }
+
+    #[test]
+    fn should_serialize_version() {
+        let registry = get_registry();
+        let player = Player {
+            name: "Bevy".to_string(),
+            health: 10,
+            lives: None,
+        };
+
+        let output = ron::to_string(&TypedReflectSerializer::new(&player, &registry)).unwrap();
+        assert_eq!(
+            output,
+            r#"(version:3,value:(name:"Bevy",health:10,lives:None))"#
+        );
+
+        let output = deserialize_player(&output, &registry).unwrap();
+        assert_eq!(output, player);
+    }
+
+    #[test]
+    fn should_migrate_older_versions() {
+        let registry = get_registry();
+
+        let output =
+            deserialize_player(r#"(version: 1, value: (name: "Bevy", hp: 10))"#, &registry)
+                .unwrap();
+        assert_eq!(
+            output,
+            Player {
+                name: "Bevy".to_string(),
+                health: 10,
+                lives: Some(3),
+            }
+        );
+
+        let output = deserialize_player(
+            r#"(version: 2, value: (name: "Bevy", health: 20))"#,
+            &registry,
+        )
+        .unwrap();
+        assert_eq!(output.health, 20);
+        assert_eq!(output.lives, Some(3));
+    }
+
+    #[test]
+    fn should_migrate_unversioned_data() {
+        let registry = get_registry();
+
+        let output = deserialize_player(r#"(name: "Bevy", hp: 10)"#, &registry).unwrap();
+        assert_eq!(
+            output,
+            Player {
+                name: "Bevy".to_string(),
+                health: 10,
+                lives: Some(3),
+            }
+        );
+
+        // A `version` field alone doesn't make the data versioned.
+        let error = deserialize_player(r#"(version: 3, hp: 10)"#, &registry).unwrap_err();
+        assert!(error.to_string().contains("unknown field `version`"));
+
+        let mut deserializer = serde_json::Deserializer::from_str(r#"{ "name": "Bevy", "hp": 5 }"#);
+        let value = TypedReflectDeserializer::of::<Player>(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let output = Player::from_reflect(value.as_partial_reflect()).unwrap();
+        assert_eq!(output.health, 5);
+    }
+
+    #[test]
+    fn should_roundtrip_with_non_self_describing_formats() {
+        let registry = get_registry();
+        let player = Player {
+            name: "Bevy".to_string(),
+            health: 10,
+            lives: Some(1),
+        };
+
+        let bytes =
+            postcard::to_allocvec(&TypedReflectSerializer::new(&player, &registry)).unwrap();
+        let mut deserializer = postcard::Deserializer::from_bytes(&bytes);
+        let value = TypedReflectDeserializer::of::<Player>(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(
+            Player::from_reflect(value.as_partial_reflect()),
+            Some(player)
+        );
+    }
+
+    #[test]
+    fn should_migrate_with_untyped_deserializer() {
+        let registry = get_registry();
+
+        let input = r#"{
+            "bevy_reflect::serde::migration::tests::Player": {
+                "version": 1,
+                "value": { "name": "Bevy", "hp": 10 }
+            }
+        }"#;
+        let mut deserializer = serde_json::Deserializer::from_str(input);
+        let value = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let output = value.reflect_ref().as_struct().unwrap();
+        assert_eq!(output.get_field::<u32>("health"), Some(&10));
+        assert_eq!(output.get_field::<Option<u8>>("lives"), Some(&Some(3)));
+    }
+
+    #[test]
+    fn should_error_on_unsupported_versions() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register_migration::<Player, _>(2, Ok);
+
+        let error = deserialize_player(r#"(name: "Bevy", hp: 10)"#, &registry).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("no migration is registered from version 0"));
+
+        let error = deserialize_player(r#"(version: 1, value: (name: "Bevy", hp: 10))"#, &registry)
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("no migration is registered from version 1"));
+
+        let error = deserialize_player(
+            r#"(version: 4, value: (name: "Bevy", health: 10, lives: None))"#,
+            &registry,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("data has version 4, but the newest supported version is 3"));
+    }
+}
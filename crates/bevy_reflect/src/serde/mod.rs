@@ -1,10 +1,14 @@
 //! Serde integration for reflected types.
 
+mod binary;
 mod de;
+mod migration;
 mod ser;
 mod type_data;
 
+pub use binary::*;
 pub use de::*;
+pub use migration::*;
 pub use ser::*;
 pub use type_data::*;
 
@@ -187,6 +191,72 @@ mod tests {
             .unwrap());
     }
 
+    #[test]
+    fn should_roundtrip_diff() {
+        use alloc::{string::String, vec, vec::Vec};
+        use bevy_platform::collections::{HashMap, HashSet};
+
+        #[derive(Reflect, Clone, Debug, PartialEq)]
+        enum TestEnum {
+            A(u32),
+            B { value: String },
+        }
+
+        #[derive(Reflect, Clone, Debug, PartialEq)]
+        struct TestStruct {
+            a: i32,
+            list: Vec<u32>,
+            map: HashMap<u32, String>,
+            set: HashSet<u32>,
+            enums: (TestEnum, TestEnum),
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<TestStruct>();
+        registry.register::<TestEnum>();
+
+        let from = TestStruct {
+            a: 1,
+            list: vec![1, 2, 3],
+            map: HashMap::from_iter([(1, "one".into()), (2, "two".into())]),
+            set: HashSet::from_iter([1, 2]),
+            enums: (TestEnum::A(1), TestEnum::B { value: "b".into() }),
+        };
+        let to = TestStruct {
+            a: 2,
+            list: vec![1, 4, 2, 3, 5],
+            map: HashMap::from_iter([(2, "deux".into()), (3, "three".into())]),
+            set: HashSet::from_iter([2, 3]),
+            enums: (
+                TestEnum::B { value: "a".into() },
+                TestEnum::B { value: "c".into() },
+            ),
+        };
+        let diff = from.diff(&to);
+
+        let serializer = ReflectDiffSerializer::new(&diff, &registry);
+        let serialized = ron::ser::to_string(&serializer).unwrap();
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = ReflectDiffDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let mut value = from.clone();
+        value.apply_diff(&deserialized).unwrap();
+        assert_eq!(value, to);
+
+        let serialized = serde_json::to_string(&serializer).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&serialized);
+        let deserialized = ReflectDiffDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let mut value = from;
+        value.apply_diff(&deserialized).unwrap();
+        assert_eq!(value, to);
+    }
+
     mod type_data {
         use super::*;
         use crate::from_reflect::FromReflect;
@@ -1,6 +1,4 @@
 use crate::serde::ser::error_utils::make_custom_error;
-#[cfg(feature = "debug_stack")]
-use crate::serde::ser::error_utils::TYPE_INFO_STACK;
 use crate::serde::ReflectSerializeWithRegistry;
 use crate::{PartialReflect, ReflectSerialize, TypeRegistry};
 use serde::Serializer;
@@ -38,16 +36,10 @@ pub(super) fn try_custom_serialize<S: Serializer>(
     };
 
     if let Some(reflect_serialize) = registration.data::<ReflectSerialize>() {
-        #[cfg(feature = "debug_stack")]
-        TYPE_INFO_STACK.with_borrow_mut(crate::type_info_stack::TypeInfoStack::pop);
-
         Ok(reflect_serialize.serialize(value, serializer))
     } else if let Some(reflect_serialize_with_registry) =
         registration.data::<ReflectSerializeWithRegistry>()
     {
-        #[cfg(feature = "debug_stack")]
-        TYPE_INFO_STACK.with_borrow_mut(crate::type_info_stack::TypeInfoStack::pop);
-
         Ok(reflect_serialize_with_registry.serialize(value, serializer, type_registry))
     } else {
         Err((serializer, make_custom_error(format_args!(
@@ -0,0 +1,304 @@
+use crate::{
+    diff::{ListDiffOp, MapDiffOp, ReflectDiff, SetDiffOp},
+    serde::ReflectSerializer,
+    PartialReflect, TypeRegistry,
+};
+use serde::{
+    ser::{SerializeTupleVariant, Serializer},
+    Serialize,
+};
+
+use super::ReflectSerializerProcessor;
+
+/// A serializer for [`ReflectDiff`] values.
+///
+/// This is the serializer counterpart to [`ReflectDiffDeserializer`].
+///
+/// # Output
+///
+/// Diffs are serialized as enums mirroring the variants of [`ReflectDiff`],
+/// [`ListDiffOp`], [`MapDiffOp`] and [`SetDiffOp`], with fields serialized as tuples.
+/// The values held by the diff, such as the new value of a [`ReflectDiff::Replace`] or
+/// the key of a [`MapDiffOp`], are serialized with a [`ReflectSerializer`], and so are
+/// prefixed with their [type path].
+///
+/// If you want to override serialization for specific values, you can pass in
+/// a reference to a [`ReflectSerializerProcessor`], which will be used for
+/// every value held by the diff - see [`with_processor`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::prelude::*;
+/// # use bevy_reflect::{TypeRegistry, serde::ReflectDiffSerializer};
+/// #[derive(Reflect)]
+/// struct MyStruct {
+///   a: i32,
+///   b: i32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<MyStruct>();
+///
+/// let diff = MyStruct { a: 1, b: 2 }.diff(&MyStruct { a: 1, b: 3 });
+///
+/// let serializer = ReflectDiffSerializer::new(&diff, &registry);
+/// let output = ron::to_string(&serializer).unwrap();
+///
+/// assert_eq!(output, r#"Struct([("b",Replace({"i32":3}))])"#);
+/// ```
+///
+/// [`ReflectDiffDeserializer`]: crate::serde::ReflectDiffDeserializer
+/// [type path]: crate::TypePath::type_path
+/// [`with_processor`]: Self::with_processor
+pub struct ReflectDiffSerializer<'a, P = ()> {
+    diff: &'a ReflectDiff,
+    registry: &'a TypeRegistry,
+    processor: Option<&'a P>,
+}
+
+impl<'a> ReflectDiffSerializer<'a, ()> {
+    /// Creates a serializer with no processor.
+    ///
+    /// If you want to add custom logic for serializing certain values, use
+    /// [`with_processor`].
+    ///
+    /// [`with_processor`]: Self::with_processor
+    pub fn new(diff: &'a ReflectDiff, registry: &'a TypeRegistry) -> Self {
+        Self {
+            diff,
+            registry,
+            processor: None,
+        }
+    }
+}
+
+impl<'a, P: ReflectSerializerProcessor> ReflectDiffSerializer<'a, P> {
+    /// Creates a serializer with a processor.
+    ///
+    /// If you do not need any custom logic for handling certain values, use
+    /// [`new`].
+    ///
+    /// [`new`]: Self::new
+    pub fn with_processor(
+        diff: &'a ReflectDiff,
+        registry: &'a TypeRegistry,
+        processor: &'a P,
+    ) -> Self {
+        Self {
+            diff,
+            registry,
+            processor: Some(processor),
+        }
+    }
+}
+
+impl<'a, P> ReflectDiffSerializer<'a, P> {
+    fn nested(&self, diff: &'a ReflectDiff) -> Self {
+        Self {
+            diff,
+            registry: self.registry,
+            processor: self.processor,
+        }
+    }
+
+    fn value(&self, value: &'a dyn PartialReflect) -> ReflectSerializer<'a, P> {
+        ReflectSerializer::new_internal(value, self.registry, self.processor)
+    }
+
+    fn op<T>(&self, op: &'a T) -> DiffOpSerializer<'a, T, P> {
+        DiffOpSerializer {
+            op,
+            registry: self.registry,
+            processor: self.processor,
+        }
+    }
+}
+
+impl<P: ReflectSerializerProcessor> Serialize for ReflectDiffSerializer<'_, P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        const NAME: &str = "ReflectDiff";
+        match self.diff {
+            ReflectDiff::Unchanged => serializer.serialize_unit_variant(NAME, 0, "Unchanged"),
+            ReflectDiff::Replace(value) => serializer.serialize_newtype_variant(
+                NAME,
+                1,
+                "Replace",
+                &self.value(value.as_ref()),
+            ),
+            ReflectDiff::Struct(fields) => serializer.serialize_newtype_variant(
+                NAME,
+                2,
+                "Struct",
+                &SerializeIter(|| fields.iter().map(|(name, diff)| (name, self.nested(diff)))),
+            ),
+            ReflectDiff::TupleStruct(fields) => serializer.serialize_newtype_variant(
+                NAME,
+                3,
+                "TupleStruct",
+                &SerializeIter(|| {
+                    fields
+                        .iter()
+                        .map(|(index, diff)| (index, self.nested(diff)))
+                }),
+            ),
+            ReflectDiff::Tuple(fields) => serializer.serialize_newtype_variant(
+                NAME,
+                4,
+                "Tuple",
+                &SerializeIter(|| {
+                    fields
+                        .iter()
+                        .map(|(index, diff)| (index, self.nested(diff)))
+                }),
+            ),
+            ReflectDiff::Array(elements) => serializer.serialize_newtype_variant(
+                NAME,
+                5,
+                "Array",
+                &SerializeIter(|| {
+                    elements
+                        .iter()
+                        .map(|(index, diff)| (index, self.nested(diff)))
+                }),
+            ),
+            ReflectDiff::Enum { variant, fields } => {
+                let mut state = serializer.serialize_tuple_variant(NAME, 6, "Enum", 2)?;
+                state.serialize_field(variant)?;
+                state.serialize_field(&SerializeIter(|| {
+                    fields
+                        .iter()
+                        .map(|(index, diff)| (index, self.nested(diff)))
+                }))?;
+                state.end()
+            }
+            ReflectDiff::List(ops) => serializer.serialize_newtype_variant(
+                NAME,
+                7,
+                "List",
+                &SerializeIter(|| ops.iter().map(|op| self.op(op))),
+            ),
+            ReflectDiff::Map(ops) => serializer.serialize_newtype_variant(
+                NAME,
+                8,
+                "Map",
+                &SerializeIter(|| ops.iter().map(|op| self.op(op))),
+            ),
+            ReflectDiff::Set(ops) => serializer.serialize_newtype_variant(
+                NAME,
+                9,
+                "Set",
+                &SerializeIter(|| ops.iter().map(|op| self.op(op))),
+            ),
+        }
+    }
+}
+
+/// Serializes the items returned by the iterator factory as a sequence.
+struct SerializeIter<F>(F);
+
+impl<F, I> Serialize for SerializeIter<F>
+where
+    F: Fn() -> I,
+    I: IntoIterator<Item: Serialize>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq((self.0)())
+    }
+}
+
+/// A serializer for the operations of list, map and set diffs.
+struct DiffOpSerializer<'a, T, P> {
+    op: &'a T,
+    registry: &'a TypeRegistry,
+    processor: Option<&'a P>,
+}
+
+impl<'a, T, P> DiffOpSerializer<'a, T, P> {
+    fn diff(&self, diff: &'a ReflectDiff) -> ReflectDiffSerializer<'a, P> {
+        ReflectDiffSerializer {
+            diff,
+            registry: self.registry,
+            processor: self.processor,
+        }
+    }
+
+    fn value(&self, value: &'a dyn PartialReflect) -> ReflectSerializer<'a, P> {
+        ReflectSerializer::new_internal(value, self.registry, self.processor)
+    }
+}
+
+impl<P: ReflectSerializerProcessor> Serialize for DiffOpSerializer<'_, ListDiffOp, P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        const NAME: &str = "ListDiffOp";
+        match self.op {
+            ListDiffOp::Insert { index, value } => {
+                let mut state = serializer.serialize_tuple_variant(NAME, 0, "Insert", 2)?;
+                state.serialize_field(index)?;
+                state.serialize_field(&self.value(value.as_ref()))?;
+                state.end()
+            }
+            ListDiffOp::Remove { index } => {
+                serializer.serialize_newtype_variant(NAME, 1, "Remove", index)
+            }
+            ListDiffOp::Modify { index, diff } => {
+                let mut state = serializer.serialize_tuple_variant(NAME, 2, "Modify", 2)?;
+                state.serialize_field(index)?;
+                state.serialize_field(&self.diff(diff))?;
+                state.end()
+            }
+        }
+    }
+}
+
+impl<P: ReflectSerializerProcessor> Serialize for DiffOpSerializer<'_, MapDiffOp, P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        const NAME: &str = "MapDiffOp";
+        match self.op {
+            MapDiffOp::Insert { key, value } => {
+                let mut state = serializer.serialize_tuple_variant(NAME, 0, "Insert", 2)?;
+                state.serialize_field(&self.value(key.as_ref()))?;
+                state.serialize_field(&self.value(value.as_ref()))?;
+                state.end()
+            }
+            MapDiffOp::Remove { key } => {
+                serializer.serialize_newtype_variant(NAME, 1, "Remove", &self.value(key.as_ref()))
+            }
+            MapDiffOp::Modify { key, diff } => {
+                let mut state = serializer.serialize_tuple_variant(NAME, 2, "Modify", 2)?;
+                state.serialize_field(&self.value(key.as_ref()))?;
+                state.serialize_field(&self.diff(diff))?;
+                state.end()
+            }
+        }
+    }
+}
+
+impl<P: ReflectSerializerProcessor> Serialize for DiffOpSerializer<'_, SetDiffOp, P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        const NAME: &str = "SetDiffOp";
+        match self.op {
+            SetDiffOp::Insert(value) => {
+                serializer.serialize_newtype_variant(NAME, 0, "Insert", &self.value(value.as_ref()))
+            }
+            SetDiffOp::Remove(value) => {
+                serializer.serialize_newtype_variant(NAME, 1, "Remove", &self.value(value.as_ref()))
+            }
+        }
+    }
+}
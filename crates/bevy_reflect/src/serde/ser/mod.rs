@@ -1,15 +1,19 @@
+pub use diffs::*;
 pub use processor::*;
+pub use schemaless::*;
 pub use serializable::*;
 pub use serialize_with_registry::*;
 pub use serializer::*;
 
 mod arrays;
 mod custom_serialization;
+mod diffs;
 mod enums;
 mod error_utils;
 mod lists;
 mod maps;
 mod processor;
+mod schemaless;
 mod serializable;
 mod serialize_with_registry;
 mod serializer;
@@ -0,0 +1,175 @@
+use crate::{enums::Enum, serde::ser::error_utils::make_custom_error, PartialReflect, ReflectRef};
+use alloc::string::String;
+use serde::{
+    ser::{SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+
+/// A serializer for reflected values that does not output any type information.
+///
+/// This is the serializer counterpart to [`SchemalessReflectDeserializer`].
+///
+/// Values are serialized according to their [kind] alone:
+/// structs and maps are serialized as maps, and lists, arrays, sets, tuples and
+/// tuple structs as sequences.
+/// Options are serialized as optional values, and other enums are externally tagged:
+/// unit variants are serialized as their name,
+/// and other variants as a single-entry map from their name to their fields.
+/// Opaque values are only supported for primitive types and `String`.
+///
+/// This makes it possible to write back the values produced by the
+/// [`SchemalessReflectDeserializer`], such as the result of the migrations
+/// of a [versioned] type.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{structs::DynamicStruct, serde::SchemalessReflectSerializer};
+/// let mut value = DynamicStruct::default();
+/// value.insert("name", String::from("Bevy"));
+/// value.insert("legs", 4_u64);
+///
+/// let output = serde_json::to_string(&SchemalessReflectSerializer::new(&value)).unwrap();
+/// assert_eq!(output, r#"{"name":"Bevy","legs":4}"#);
+/// ```
+///
+/// [`SchemalessReflectDeserializer`]: crate::serde::SchemalessReflectDeserializer
+/// [kind]: crate::ReflectKind
+/// [versioned]: crate::serde::ReflectVersion
+pub struct SchemalessReflectSerializer<'a> {
+    value: &'a dyn PartialReflect,
+}
+
+impl<'a> SchemalessReflectSerializer<'a> {
+    /// Creates a serializer for the given value.
+    pub fn new(value: &'a dyn PartialReflect) -> Self {
+        Self { value }
+    }
+}
+
+macro_rules! serialize_primitives {
+    ($value:expr, $serializer:ident, $($ty:ty => $method:ident),* $(,)?) => {
+        $(
+            if let Some(value) = $value.try_downcast_ref::<$ty>() {
+                return $serializer.$method(*value);
+            }
+        )*
+    };
+}
+
+impl Serialize for SchemalessReflectSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_primitives!(self.value, serializer,
+            bool => serialize_bool,
+            u8 => serialize_u8,
+            u16 => serialize_u16,
+            u32 => serialize_u32,
+            u64 => serialize_u64,
+            u128 => serialize_u128,
+            i8 => serialize_i8,
+            i16 => serialize_i16,
+            i32 => serialize_i32,
+            i64 => serialize_i64,
+            i128 => serialize_i128,
+            f32 => serialize_f32,
+            f64 => serialize_f64,
+            char => serialize_char,
+        );
+        if let Some(value) = self.value.try_downcast_ref::<String>() {
+            return serializer.serialize_str(value);
+        }
+        if self.value.try_downcast_ref::<()>().is_some() {
+            return serializer.serialize_unit();
+        }
+
+        match self.value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                let mut state = serializer.serialize_map(Some(value.field_len()))?;
+                for index in 0..value.field_len() {
+                    let (Some(name), Some(field)) = (value.name_at(index), value.field_at(index))
+                    else {
+                        continue;
+                    };
+                    state.serialize_entry(name, &SchemalessReflectSerializer::new(field))?;
+                }
+                state.end()
+            }
+            ReflectRef::TupleStruct(value) => {
+                serializer.collect_seq(value.iter_fields().map(SchemalessReflectSerializer::new))
+            }
+            ReflectRef::Tuple(value) => {
+                serializer.collect_seq(value.iter_fields().map(SchemalessReflectSerializer::new))
+            }
+            ReflectRef::List(value) => {
+                serializer.collect_seq(value.iter().map(SchemalessReflectSerializer::new))
+            }
+            ReflectRef::Array(value) => {
+                serializer.collect_seq(value.iter().map(SchemalessReflectSerializer::new))
+            }
+            ReflectRef::Set(value) => {
+                serializer.collect_seq(value.iter().map(SchemalessReflectSerializer::new))
+            }
+            ReflectRef::Map(value) => serializer.collect_map(value.iter().map(|(key, value)| {
+                (
+                    SchemalessReflectSerializer::new(key),
+                    SchemalessReflectSerializer::new(value),
+                )
+            })),
+            ReflectRef::Enum(value) => serialize_enum(value, serializer),
+            _ => Err(make_custom_error(format_args!(
+                "cannot serialize a value of type `{}` without type information",
+                self.value.reflect_type_path()
+            ))),
+        }
+    }
+}
+
+fn serialize_enum<S: Serializer>(value: &dyn Enum, serializer: S) -> Result<S::Ok, S::Error> {
+    match (value.variant_name(), value.field_len()) {
+        ("None", 0) => serializer.serialize_none(),
+        ("Some", 1) if value.name_at(0).is_none() => serializer.serialize_some(
+            &SchemalessReflectSerializer::new(value.field_at(0).unwrap()),
+        ),
+        (name, 0) => serializer.serialize_str(name),
+        (name, _) => {
+            let mut state = serializer.serialize_map(Some(1))?;
+            state.serialize_entry(name, &VariantFieldsSerializer(value))?;
+            state.end()
+        }
+    }
+}
+
+/// Serializes the fields of an enum variant: a single unnamed field as itself,
+/// several unnamed fields as a sequence, and named fields as a map.
+struct VariantFieldsSerializer<'a>(&'a dyn Enum);
+
+impl Serialize for VariantFieldsSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let value = self.0;
+        if value.name_at(0).is_some() {
+            let mut state = serializer.serialize_map(Some(value.field_len()))?;
+            for index in 0..value.field_len() {
+                if let (Some(name), Some(field)) = (value.name_at(index), value.field_at(index)) {
+                    state.serialize_entry(name, &SchemalessReflectSerializer::new(field))?;
+                }
+            }
+            state.end()
+        } else if value.field_len() == 1 {
+            SchemalessReflectSerializer::new(value.field_at(0).unwrap()).serialize(serializer)
+        } else {
+            let mut state = serializer.serialize_seq(Some(value.field_len()))?;
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at(index) {
+                    state.serialize_element(&SchemalessReflectSerializer::new(field))?;
+                }
+            }
+            state.end()
+        }
+    }
+}
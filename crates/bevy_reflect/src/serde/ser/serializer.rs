@@ -7,9 +7,16 @@ use crate::{
         sets::SetSerializer, structs::StructSerializer, tuple_structs::TupleStructSerializer,
         tuples::TupleSerializer,
     },
+    serde::{
+        migration::{VERSIONED_FIELDS, VERSIONED_STRUCT},
+        ReflectVersion,
+    },
     PartialReflect, ReflectRef, TypeRegistry,
 };
-use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde::{
+    ser::{SerializeMap, SerializeStruct},
+    Serialize, Serializer,
+};
 
 use super::ReflectSerializerProcessor;
 
@@ -96,6 +103,21 @@ impl<'a, P: ReflectSerializerProcessor> ReflectSerializer<'a, P> {
     }
 }
 
+impl<'a, P> ReflectSerializer<'a, P> {
+    /// An internal constructor for creating a serializer with an optional processor.
+    pub(super) fn new_internal(
+        value: &'a dyn PartialReflect,
+        registry: &'a TypeRegistry,
+        processor: Option<&'a P>,
+    ) -> Self {
+        Self {
+            value,
+            registry,
+            processor,
+        }
+    }
+}
+
 impl<P: ReflectSerializerProcessor> Serialize for ReflectSerializer<'_, P> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -257,6 +279,35 @@ impl<P: ReflectSerializerProcessor> Serialize for TypedReflectSerializer<'_, P>
             serializer
         };
 
+        let version = self.value.get_represented_type_info().and_then(|info| {
+            self.registry
+                .get_type_data::<ReflectVersion>(info.type_id())
+        });
+        let output = if let Some(version) = version {
+            // Versioned types are wrapped in a struct recording the version of their data.
+            let mut state = serializer.serialize_struct(VERSIONED_STRUCT, 2)?;
+            state.serialize_field(VERSIONED_FIELDS[0], &version.version())?;
+            state.serialize_field(VERSIONED_FIELDS[1], &UnversionedSerializer(self))?;
+            state.end()
+        } else {
+            self.serialize_unversioned(serializer)
+        };
+
+        #[cfg(feature = "debug_stack")]
+        TYPE_INFO_STACK.with_borrow_mut(crate::type_info_stack::TypeInfoStack::pop);
+
+        output
+    }
+}
+
+impl<P: ReflectSerializerProcessor> TypedReflectSerializer<'_, P> {
+    /// Serializes the value itself, without the version of [versioned] types.
+    ///
+    /// [versioned]: ReflectVersion
+    fn serialize_unversioned<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
         // Handle both Value case and types that have a custom `Serialize`
         let (serializer, error) = match try_custom_serialize(self.value, self.registry, serializer)
         {
@@ -264,7 +315,7 @@ impl<P: ReflectSerializerProcessor> Serialize for TypedReflectSerializer<'_, P>
             Err(value) => value,
         };
 
-        let output = match self.value.reflect_ref() {
+        match self.value.reflect_ref() {
             ReflectRef::Struct(struct_value) => StructSerializer {
                 struct_value,
                 registry: self.registry,
@@ -316,11 +367,18 @@ impl<P: ReflectSerializerProcessor> Serialize for TypedReflectSerializer<'_, P>
             #[cfg(feature = "functions")]
             ReflectRef::Function(_) => Err(make_custom_error("functions cannot be serialized")),
             ReflectRef::Opaque(_) => Err(error),
-        };
+        }
+    }
+}
 
-        #[cfg(feature = "debug_stack")]
-        TYPE_INFO_STACK.with_borrow_mut(crate::type_info_stack::TypeInfoStack::pop);
+/// Serializes the value of a [`TypedReflectSerializer`] without its version.
+struct UnversionedSerializer<'a, 'b, P>(&'a TypedReflectSerializer<'b, P>);
 
-        output
+impl<P: ReflectSerializerProcessor> Serialize for UnversionedSerializer<'_, '_, P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize_unversioned(serializer)
     }
 }
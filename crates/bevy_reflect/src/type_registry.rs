@@ -1,6 +1,7 @@
 use crate::{
-    convert::ReflectConvert, serde::Serializable, FromReflect, Reflect, TypeData, TypeInfo,
-    TypePath, Typed,
+    convert::ReflectConvert,
+    serde::{ReflectVersion, Serializable},
+    FromReflect, PartialReflect, Reflect, TypeData, TypeInfo, TypePath, Typed,
 };
 use alloc::{boxed::Box, string::String};
 use bevy_platform::{
@@ -435,6 +436,65 @@ impl TypeRegistry {
             .register_type_conversion::<T, U, _>(|input| Ok(input.into()));
     }
 
+    /// Registers a migration upgrading serialized data of type `T`
+    /// from `from_version` to `from_version + 1`.
+    ///
+    /// `T` must be versioned using `#[reflect(version = N)]`.
+    /// See [`ReflectVersion`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` has not been registered, or is not versioned.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_reflect::{Reflect, TypeRegistry, structs::{DynamicStruct, Struct}};
+    /// #[derive(Reflect)]
+    /// #[reflect(version = 2)]
+    /// struct Player {
+    ///     // Was called `hp` in version 1.
+    ///     health: u32,
+    /// }
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register::<Player>();
+    /// type_registry.register_migration::<Player, _>(1, |value| {
+    ///     let mut player = value
+    ///         .reflect_ref()
+    ///         .as_struct()
+    ///         .map_err(|err| err.to_string())?
+    ///         .to_dynamic_struct();
+    ///     let (_, hp) = player.remove_by_name("hp").ok_or("missing `hp`")?;
+    ///     player.insert_boxed("health", hp);
+    ///     Ok(Box::new(player))
+    /// });
+    /// ```
+    ///
+    /// [`ReflectVersion`]: crate::serde::ReflectVersion
+    pub fn register_migration<T, F>(&mut self, from_version: u32, migration: F)
+    where
+        T: Reflect + TypePath,
+        F: Fn(Box<dyn PartialReflect>) -> Result<Box<dyn PartialReflect>, String>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let data = self.get_mut(TypeId::of::<T>()).unwrap_or_else(|| {
+            panic!(
+                "attempted to call `TypeRegistry::register_migration` for type `{T}` without registering `{T}` first",
+                T = T::type_path(),
+            )
+        });
+        data.data_mut::<ReflectVersion>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "attempted to call `TypeRegistry::register_migration` for type `{T}`, which is not versioned",
+                    T = T::type_path(),
+                )
+            })
+            .add_migration(from_version, migration);
+    }
+
     /// Whether the type with given [`TypeId`] has been registered in this registry.
     pub fn contains(&self, type_id: TypeId) -> bool {
         self.registrations.contains_key(&type_id)
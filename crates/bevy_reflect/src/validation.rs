@@ -0,0 +1,540 @@
+//! Declarative constraints on the fields of reflected types.
+//!
+//! Constraints are [custom attributes] added to fields with `#[reflect(@...)]`:
+//! - [`Range`] requires a number to be within an inclusive range.
+//! - [`NonEmpty`] requires a string or collection to have at least one element.
+//! - [`MaxLen`] limits the number of characters of a string or elements of a collection.
+//!
+//! They are checked by the [`TypedReflectDeserializer`] when deserializing each field,
+//! and can be checked on any value with [`validate`].
+//! The [`Constraints`] of a field can also be read directly from its attributes,
+//! for example to describe them in a schema or an editor.
+//!
+//! Constraints on values they don't apply to, like a [`Range`] on a string, are ignored.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy_reflect::{validation::{validate, MaxLen, NonEmpty, Range, Violation}, Reflect};
+//! #[derive(Reflect)]
+//! struct Player {
+//!     #[reflect(@NonEmpty, @MaxLen(16))]
+//!     name: String,
+//!     #[reflect(@Range(0.0..=1.0))]
+//!     volume: f32,
+//! }
+//!
+//! let player = Player {
+//!     name: String::new(),
+//!     volume: 1.5,
+//! };
+//!
+//! let errors = validate(&player).unwrap_err();
+//! assert_eq!(errors.0.len(), 2);
+//! assert_eq!(errors.0[0].path, ".name");
+//! assert_eq!(errors.0[0].violation, Violation::Empty);
+//! assert_eq!(errors.0[1].path, ".volume");
+//! ```
+//!
+//! [custom attributes]: crate::attributes
+//! [`TypedReflectDeserializer`]: crate::serde::TypedReflectDeserializer
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    ops::RangeInclusive,
+};
+
+use thiserror::Error;
+
+use crate::{
+    attributes::CustomAttributes, enums::VariantInfo, NamedField, PartialReflect, Reflect,
+    ReflectRef, UnnamedField,
+};
+
+/// A constraint requiring a number to be within an inclusive range.
+///
+/// Bounds of any primitive number type can be used, and are compared to numbers of any type.
+///
+/// ```
+/// # use bevy_reflect::{validation::Range, Reflect};
+/// #[derive(Reflect)]
+/// struct Slider {
+///     #[reflect(@Range(0.0..=1.0))]
+///     value: f32,
+///     #[reflect(@Range(1..=10))]
+///     steps: u32,
+/// }
+/// ```
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub struct Range<T>(pub RangeInclusive<T>);
+
+/// A constraint requiring a string or collection not to be empty.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonEmpty;
+
+/// A constraint limiting the number of characters of a string
+/// or the number of elements of a collection.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxLen(pub usize);
+
+/// The validation constraints of a field, read from its [custom attributes](CustomAttributes).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Constraints {
+    /// The range numbers must be within, from a [`Range`] attribute.
+    pub range: Option<RangeInclusive<f64>>,
+    /// Whether strings and collections must not be empty, from a [`NonEmpty`] attribute.
+    pub non_empty: bool,
+    /// The maximum length of strings and collections, from a [`MaxLen`] attribute.
+    pub max_len: Option<usize>,
+}
+
+impl Constraints {
+    /// Reads the constraints from the custom attributes of a field.
+    pub fn from_attributes(attributes: &CustomAttributes) -> Self {
+        if attributes.is_empty() {
+            return Self::default();
+        }
+
+        Self {
+            range: range(attributes),
+            non_empty: attributes.contains::<NonEmpty>(),
+            max_len: attributes.get::<MaxLen>().map(|MaxLen(max)| *max),
+        }
+    }
+
+    /// Returns `true` if there are no constraints.
+    pub fn is_empty(&self) -> bool {
+        self.range.is_none() && !self.non_empty && self.max_len.is_none()
+    }
+
+    /// Checks that `value` satisfies these constraints.
+    ///
+    /// This only checks `value` itself: the constraints of its own fields are checked
+    /// by [`validate`].
+    pub fn check(&self, value: &dyn PartialReflect) -> Result<(), Violation> {
+        if let Some(range) = &self.range
+            && let Some(number) = as_f64(value)
+            && !range.contains(&number)
+        {
+            return Err(Violation::OutOfRange {
+                value: number,
+                min: *range.start(),
+                max: *range.end(),
+            });
+        }
+
+        if !self.non_empty && self.max_len.is_none() {
+            return Ok(());
+        }
+        let Some(len) = len(value) else {
+            return Ok(());
+        };
+        if self.non_empty && len == 0 {
+            return Err(Violation::Empty);
+        }
+        match self.max_len {
+            Some(max) if len > max => Err(Violation::TooLong { len, max }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A way in which a value doesn't satisfy the [`Constraints`] of its field.
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum Violation {
+    /// A number is outside of its [`Range`].
+    #[error("{value} is outside of the range {min}..={max}")]
+    OutOfRange {
+        /// The number.
+        value: f64,
+        /// The start of the range.
+        min: f64,
+        /// The end of the range.
+        max: f64,
+    },
+    /// A [`NonEmpty`] string or collection is empty.
+    #[error("the value must not be empty")]
+    Empty,
+    /// A string or collection is longer than its [`MaxLen`].
+    #[error("the length {len} exceeds the maximum length of {max}")]
+    TooLong {
+        /// The number of characters or elements.
+        len: usize,
+        /// The maximum length.
+        max: usize,
+    },
+}
+
+/// A [`Violation`] found by [`validate`], along with the path to the invalid value.
+#[derive(Error, Clone, Debug, PartialEq)]
+#[error("invalid value at `{path}`: {violation}")]
+pub struct ValidationError {
+    /// The path to the invalid value, such as `.players[0].name`.
+    ///
+    /// Map values are written with their key, such as `.scores["alice"]`.
+    pub path: String,
+    /// How the value is invalid.
+    pub violation: Violation,
+}
+
+/// The errors returned by [`validate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationErrors(pub Vec<ValidationError>);
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, error) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ValidationErrors {}
+
+/// Checks the [`Constraints`] of all fields within `value`, recursively.
+///
+/// Type information is read from [`PartialReflect::get_represented_type_info`],
+/// so this also works with dynamic types representing a type with constraints.
+pub fn validate(value: &dyn PartialReflect) -> Result<(), ValidationErrors> {
+    let mut errors = Vec::new();
+    validate_value(value, &mut String::new(), &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationErrors(errors))
+    }
+}
+
+fn validate_value(
+    value: &dyn PartialReflect,
+    path: &mut String,
+    errors: &mut Vec<ValidationError>,
+) {
+    let info = value.get_represented_type_info();
+    match value.reflect_ref() {
+        ReflectRef::Struct(struct_ref) => {
+            let info = info.and_then(|info| info.as_struct().ok());
+            for (name, field) in struct_ref.iter_fields() {
+                let attributes = info
+                    .and_then(|info| info.field(name))
+                    .map(NamedField::custom_attributes);
+                validate_field(field, attributes, path, format_args!(".{name}"), errors);
+            }
+        }
+        ReflectRef::TupleStruct(tuple_struct) => {
+            let info = info.and_then(|info| info.as_tuple_struct().ok());
+            for (index, field) in tuple_struct.iter_fields().enumerate() {
+                let attributes = info
+                    .and_then(|info| info.field_at(index))
+                    .map(UnnamedField::custom_attributes);
+                validate_field(field, attributes, path, format_args!(".{index}"), errors);
+            }
+        }
+        ReflectRef::Tuple(tuple) => {
+            for (index, field) in tuple.iter_fields().enumerate() {
+                validate_field(field, None, path, format_args!(".{index}"), errors);
+            }
+        }
+        ReflectRef::Enum(enum_ref) => {
+            let variant = info
+                .and_then(|info| info.as_enum().ok())
+                .and_then(|info| info.variant(enum_ref.variant_name()));
+            for (index, field) in enum_ref.iter_fields().enumerate() {
+                match field.name() {
+                    Some(name) => {
+                        let attributes = match variant {
+                            Some(VariantInfo::Struct(variant)) => {
+                                variant.field(name).map(NamedField::custom_attributes)
+                            }
+                            _ => None,
+                        };
+                        validate_field(
+                            field.value(),
+                            attributes,
+                            path,
+                            format_args!(".{name}"),
+                            errors,
+                        );
+                    }
+                    None => {
+                        let attributes = match variant {
+                            Some(VariantInfo::Tuple(variant)) => {
+                                variant.field_at(index).map(UnnamedField::custom_attributes)
+                            }
+                            _ => None,
+                        };
+                        validate_field(
+                            field.value(),
+                            attributes,
+                            path,
+                            format_args!(".{index}"),
+                            errors,
+                        );
+                    }
+                }
+            }
+        }
+        ReflectRef::List(list) => {
+            for (index, element) in list.iter().enumerate() {
+                validate_field(element, None, path, format_args!("[{index}]"), errors);
+            }
+        }
+        ReflectRef::Array(array) => {
+            for (index, element) in array.iter().enumerate() {
+                validate_field(element, None, path, format_args!("[{index}]"), errors);
+            }
+        }
+        ReflectRef::Map(map) => {
+            for (key, element) in map.iter() {
+                validate_field(element, None, path, format_args!("[{key:?}]"), errors);
+            }
+        }
+        ReflectRef::Set(set) => {
+            for element in set.iter() {
+                validate_field(element, None, path, format_args!("[{element:?}]"), errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn validate_field(
+    value: &dyn PartialReflect,
+    attributes: Option<&CustomAttributes>,
+    path: &mut String,
+    segment: fmt::Arguments,
+    errors: &mut Vec<ValidationError>,
+) {
+    let len = path.len();
+    // Writing to a `String` can't fail.
+    let _ = path.write_fmt(segment);
+
+    if let Some(attributes) = attributes
+        && let Err(violation) = Constraints::from_attributes(attributes).check(value)
+    {
+        errors.push(ValidationError {
+            path: path.clone(),
+            violation,
+        });
+    }
+    validate_value(value, path, errors);
+
+    path.truncate(len);
+}
+
+fn range(attributes: &CustomAttributes) -> Option<RangeInclusive<f64>> {
+    macro_rules! ranges {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(Range(range)) = attributes.get::<Range<$ty>>() {
+                    return Some(*range.start() as f64..=*range.end() as f64);
+                }
+            )*
+        };
+    }
+
+    ranges!(f32, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+    attributes
+        .get::<Range<f64>>()
+        .map(|Range(range)| range.clone())
+}
+
+fn as_f64(value: &dyn PartialReflect) -> Option<f64> {
+    macro_rules! numbers {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = value.try_downcast_ref::<$ty>() {
+                    return Some(*value as f64);
+                }
+            )*
+        };
+    }
+
+    numbers!(f32, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+    value.try_downcast_ref::<f64>().copied()
+}
+
+/// Returns the number of characters of a string or the number of elements of a collection.
+fn len(value: &dyn PartialReflect) -> Option<usize> {
+    if let Some(string) = value.try_downcast_ref::<String>() {
+        return Some(string.chars().count());
+    }
+    if let Some(string) = value.try_downcast_ref::<Cow<'static, str>>() {
+        return Some(string.chars().count());
+    }
+    if let Some(string) = value.try_downcast_ref::<&'static str>() {
+        return Some(string.chars().count());
+    }
+    match value.reflect_ref() {
+        ReflectRef::List(list) => Some(list.len()),
+        ReflectRef::Array(array) => Some(array.len()),
+        ReflectRef::Map(map) => Some(map.len()),
+        ReflectRef::Set(set) => Some(set.len()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        serde::TypedReflectDeserializer, structs::DynamicStruct, FromReflect, GetTypeRegistration,
+        TypeRegistry, Typed,
+    };
+    use alloc::{string::ToString, vec};
+    use bevy_platform::collections::HashMap;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Settings {
+        #[reflect(@Range(0.0..=1.0))]
+        volume: f32,
+        #[reflect(@Range(1..=4))]
+        players: u8,
+        #[reflect(@NonEmpty, @MaxLen(8))]
+        name: String,
+        profiles: Vec<Profile>,
+        keys: HashMap<String, Profile>,
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Profile(#[reflect(@MaxLen(2))] Vec<u32>);
+
+    fn settings() -> Settings {
+        Settings {
+            volume: 0.5,
+            players: 2,
+            name: "Bevy".to_string(),
+            profiles: vec![Profile(vec![1, 2])],
+            keys: HashMap::default(),
+        }
+    }
+
+    #[test]
+    fn should_validate_constraints() {
+        assert_eq!(validate(&settings()), Ok(()));
+
+        let mut settings = settings();
+        settings.volume = 1.5;
+        settings.players = 0;
+        settings.name = "Bevy Engine".to_string();
+        settings.profiles.push(Profile(vec![1, 2, 3]));
+        settings
+            .keys
+            .insert("alice".to_string(), Profile(Vec::new()));
+        settings.keys.insert("bob".to_string(), Profile(vec![1; 4]));
+
+        let errors = validate(&settings).unwrap_err().0;
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError {
+                    path: ".volume".to_string(),
+                    violation: Violation::OutOfRange {
+                        value: 1.5,
+                        min: 0.0,
+                        max: 1.0,
+                    },
+                },
+                ValidationError {
+                    path: ".players".to_string(),
+                    violation: Violation::OutOfRange {
+                        value: 0.0,
+                        min: 1.0,
+                        max: 4.0,
+                    },
+                },
+                ValidationError {
+                    path: ".name".to_string(),
+                    violation: Violation::TooLong { len: 11, max: 8 },
+                },
+                ValidationError {
+                    path: ".profiles[1].0".to_string(),
+                    violation: Violation::TooLong { len: 3, max: 2 },
+                },
+                ValidationError {
+                    path: r#".keys["bob"].0"#.to_string(),
+                    violation: Violation::TooLong { len: 4, max: 2 },
+                },
+            ]
+        );
+
+        settings.name.clear();
+        let errors = validate(settings.to_dynamic().as_ref()).unwrap_err().0;
+        assert!(errors.contains(&ValidationError {
+            path: ".name".to_string(),
+            violation: Violation::Empty,
+        }));
+    }
+
+    #[test]
+    fn should_validate_fields_by_name() {
+        let mut settings = DynamicStruct::default();
+        settings.set_represented_type(Some(Settings::type_info()));
+        settings.insert("name", "Bevy Engine".to_string());
+        settings.insert("volume", 1.5_f32);
+
+        let errors = validate(&settings).unwrap_err().0;
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError {
+                    path: ".name".to_string(),
+                    violation: Violation::TooLong { len: 11, max: 8 },
+                },
+                ValidationError {
+                    path: ".volume".to_string(),
+                    violation: Violation::OutOfRange {
+                        value: 1.5,
+                        min: 0.0,
+                        max: 1.0,
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_validate_when_deserializing() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Settings>();
+        registry.register::<Profile>();
+        registry.register::<Vec<Profile>>();
+        registry.register::<HashMap<String, Profile>>();
+        let registration = registry
+            .get(Settings::get_type_registration().type_id())
+            .unwrap();
+
+        let deserialize = |input: &str| {
+            let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+            TypedReflectDeserializer::new(registration, &registry)
+                .deserialize(&mut deserializer)
+                .map(|value| Settings::from_reflect(value.as_ref()).unwrap())
+        };
+
+        let output =
+            deserialize(r#"(volume: 0.25, players: 4, name: "Bevy", profiles: [], keys: {})"#)
+                .unwrap();
+        assert_eq!(output.volume, 0.25);
+
+        let error =
+            deserialize(r#"(volume: 2.0, players: 4, name: "Bevy", profiles: [], keys: {})"#)
+                .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("invalid value for field `volume`: 2 is outside of the range 0..=1"));
+
+        let error = deserialize(
+            r#"(volume: 0.25, players: 4, name: "Bevy", profiles: [([1, 2, 3])], keys: {})"#,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("invalid value for field `0`: the length 3 exceeds the maximum length of 2"));
+    }
+}
@@ -17,14 +17,18 @@ use bevy_ecs::{
     resource::Resource,
     schedule::Schedules,
     system::{In, Local},
-    world::{DeferredWorld, EntityRef, EntityWorldMut, FilteredEntityRef, Mut, World},
+    world::{
+        ChangeJournal, DeferredWorld, EntityRef, EntityWorldMut, FilteredEntityRef, JournalEntry,
+        Mut, World,
+    },
 };
 use bevy_log::warn_once;
 use bevy_platform::collections::HashMap;
 use bevy_reflect::{
     serde::{ReflectSerializer, TypedReflectDeserializer},
     structs::DynamicStruct,
-    GetPath, PartialReflect, Reflect, TypeRegistration, TypeRegistry,
+    validation::{validate, ValidationErrors},
+    GetPath, PartialReflect, Reflect, ReflectPath, TypeRegistration, TypeRegistry,
 };
 use serde::{de::DeserializeSeed as _, de::IntoDeserializer, Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -35,12 +39,28 @@ use crate::{
         json_schema::{export_type, JsonSchemaBevyType},
         open_rpc::OpenRpcDocument,
     },
-    BrpError, BrpResult, PreviousScheduleBuildMetadata,
+    BrpError, BrpResult, PreviousScheduleBuildMetadata, WatchingRequestId,
 };
 
 #[cfg(all(feature = "http", not(target_family = "wasm")))]
 use {crate::schemas::open_rpc::ServerObject, bevy_utils::default};
 
+#[cfg(feature = "bevy_asset")]
+use bevy_asset::AssetServer;
+
+#[cfg(feature = "reflect_functions")]
+use {
+    bevy_ecs::reflect::AppFunctionRegistry,
+    bevy_platform::collections::HashSet,
+    bevy_reflect::{
+        func::{
+            args::{ArgInfo, ArgList, Ownership},
+            Return,
+        },
+        serde::TypedReflectSerializer,
+    },
+};
+
 /// The method path for a `world.get_components` request.
 pub const BRP_GET_COMPONENTS_METHOD: &str = "world.get_components";
 
@@ -107,6 +127,20 @@ pub const BRP_OBSERVE_METHOD: &str = "world.observe+watch";
 /// The method path for a `schedule.graph` request.
 pub const BRP_SCHEDULE_GRAPH: &str = "schedule.graph";
 
+/// The method path for a `world.journal` request.
+pub const BRP_JOURNAL_METHOD: &str = "world.journal";
+
+/// The method path for a `world.journal+watch` request.
+pub const BRP_JOURNAL_AND_WATCH_METHOD: &str = "world.journal+watch";
+
+/// The method path for a `registry.call_function` request.
+#[cfg(feature = "reflect_functions")]
+pub const BRP_CALL_FUNCTION_METHOD: &str = "registry.call_function";
+
+/// The method path for an `asset.dependency_graph` request.
+#[cfg(feature = "bevy_asset")]
+pub const BRP_ASSET_DEPENDENCY_GRAPH_METHOD: &str = "asset.dependency_graph";
+
 /// The method path for a `rpc.discover` request.
 pub const RPC_DISCOVER_METHOD: &str = "rpc.discover";
 
@@ -378,6 +412,44 @@ struct BrpScheduleGraphParams {
     pub schedule_label: String,
 }
 
+/// `world.journal` and `world.journal+watch`: Retrieves the entries of the world's
+/// [`ChangeJournal`](bevy_ecs::world::ChangeJournal).
+///
+/// The server responds with a [`BrpJournalResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpJournalParams {
+    /// Only entries whose sequence number is at least `since` are returned.
+    ///
+    /// For `world.journal+watch`, this defaults to the sequence number of the next entry, so that
+    /// only the entries recorded after the request are streamed.
+    #[serde(default)]
+    pub since: Option<u64>,
+}
+
+/// `registry.call_function`: Calls a function registered in the
+/// [`AppFunctionRegistry`](bevy_ecs::reflect::AppFunctionRegistry).
+///
+/// The server responds with a [`BrpCallFunctionResponse`].
+#[cfg(feature = "reflect_functions")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpCallFunctionParams {
+    /// The name of the function to call.
+    pub function: String,
+
+    /// The serialized values of the arguments of the function, in order.
+    ///
+    /// When `entity` is provided, this excludes the first argument.
+    #[serde(default)]
+    pub args: Vec<Value>,
+
+    /// An entity whose component is passed as the first argument of the function.
+    ///
+    /// This allows calling methods of components, with the component type given by the first
+    /// argument of the function. If it is a mutable reference, the component is modified in place.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<Entity>,
+}
+
 /// Describes the data that is to be fetched in a query.
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct BrpQuery {
@@ -517,6 +589,37 @@ pub type BrpListComponentsResponse = Vec<String>;
 /// The response to a `world.list_resources` request.
 pub type BrpListResourcesResponse = Vec<String>;
 
+/// `asset.dependency_graph`: Returns the dependency graph of the assets managed by the
+/// [`AssetServer`](bevy_asset::AssetServer).
+///
+/// The server responds with the graph in the requested [`BrpAssetDependencyGraphFormat`].
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetDependencyGraphParams {
+    /// The path of an asset to restrict the graph to.
+    ///
+    /// When provided, the graph only contains this asset and its recursive dependencies and
+    /// dependents.
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// The format of the returned graph.
+    #[serde(default)]
+    pub format: BrpAssetDependencyGraphFormat,
+}
+
+/// The format of the graph returned by an `asset.dependency_graph` request.
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpAssetDependencyGraphFormat {
+    /// An array of the serialized [`AssetDependencyNode`](bevy_asset::AssetDependencyNode)s.
+    #[default]
+    Json,
+    /// A string in the [DOT](https://graphviz.org/doc/info/lang.html) format.
+    Dot,
+}
+
 /// A single response from a `world.list_components+watch` request.
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
 pub struct BrpListComponentsWatchingResponse {
@@ -524,6 +627,25 @@ pub struct BrpListComponentsWatchingResponse {
     removed: Vec<String>,
 }
 
+/// The response to a `registry.call_function` request.
+#[cfg(feature = "reflect_functions")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpCallFunctionResponse {
+    /// The serialized value returned by the function, or `null` if it returns `()`.
+    pub value: Value,
+}
+
+/// The response to a `world.journal` request, or a single response from a `world.journal+watch`
+/// request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpJournalResponse {
+    /// The requested entries, oldest first.
+    pub entries: Vec<JournalEntry>,
+    /// The sequence number the next entry will get, to pass as `since` to get the following
+    /// entries.
+    pub next_sequence: u64,
+}
+
 /// The response to a `world.query` request.
 pub type BrpQueryResponse = Vec<BrpQueryRow>;
 
@@ -1215,6 +1337,9 @@ pub fn process_remote_mutate_components_request(
         .deserialize(&value)
         .map_err(BrpError::component_error)?;
 
+    validate_mutation(reflected.as_partial_reflect(), &path, value.as_ref())
+        .map_err(|errors| BrpError::validation_error(&errors))?;
+
     // Apply the mutation.
     reflected
         .reflect_path_mut(path.as_str())
@@ -1270,6 +1395,13 @@ pub fn process_remote_mutate_resources_request(
             .deserialize(&value)
             .map_err(BrpError::resource_error)?;
 
+    validate_mutation(
+        reflected_component.as_partial_reflect(),
+        &field_path,
+        &*deserialized_value,
+    )
+    .map_err(|errors| BrpError::validation_error(&errors))?;
+
     // Apply the value to the resource.
     reflected_component
         .reflect_path_mut(field_path.as_str())
@@ -1280,6 +1412,26 @@ pub fn process_remote_mutate_resources_request(
     Ok(Value::Null)
 }
 
+/// Checks the [validation constraints] of `reflected` as if `value` had been applied at `path`.
+///
+/// The mutation is applied to a dynamic copy, so that `reflected` is left unchanged if it fails.
+/// Errors applying the value are ignored here, and reported when it is applied to `reflected`.
+///
+/// [validation constraints]: bevy_reflect::validation
+fn validate_mutation(
+    reflected: &dyn PartialReflect,
+    path: &str,
+    value: &dyn PartialReflect,
+) -> Result<(), ValidationErrors> {
+    let mut mutated = reflected.to_dynamic();
+    if let Ok(field) = path.reflect_element_mut(mutated.as_mut())
+        && field.try_apply(value).is_ok()
+    {
+        return validate(mutated.as_ref());
+    }
+    Ok(())
+}
+
 /// Handles a `world.remove_components` request (remove components) coming from a client.
 pub fn process_remote_remove_components_request(
     In(params): In<Option<Value>>,
@@ -1664,6 +1816,251 @@ pub fn process_remote_observe_watching_request(
         .map_err(BrpError::internal)
 }
 
+/// Handles a `world.journal` request coming from a client.
+pub fn process_remote_journal_request(In(params): In<Option<Value>>, world: &World) -> BrpResult {
+    let BrpJournalParams { since } = params.map(parse).transpose()?.unwrap_or_default();
+    let response = journal_since(world, since.unwrap_or(0))?;
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Stores the position of each `world.journal+watch` request in the change journal.
+#[derive(Resource, Default)]
+pub struct BrpJournalCursors {
+    /// The sequence number of the next entry to send for each request.
+    pub(crate) cursors: HashMap<WatchingRequestId, u64>,
+}
+
+/// Handles a `world.journal+watch` request coming from a client.
+///
+/// Each poll returns the entries recorded since the previous one for the same request.
+pub fn process_remote_journal_watching_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult<Option<Value>> {
+    let BrpJournalParams { since } = params.map(parse).transpose()?.unwrap_or_default();
+    let request_id = world.get_resource::<WatchingRequestId>().copied();
+    let next_sequence = world
+        .change_journal()
+        .map_or(0, ChangeJournal::next_sequence);
+    let mut cursors = world.get_resource_or_init::<BrpJournalCursors>();
+    let since = match request_id {
+        Some(request_id) => *cursors
+            .cursors
+            .entry(request_id)
+            .or_insert(since.unwrap_or(next_sequence)),
+        None => since.unwrap_or(next_sequence),
+    };
+    let response = journal_since(world, since)?;
+    if let Some(request_id) = request_id {
+        world
+            .resource_mut::<BrpJournalCursors>()
+            .cursors
+            .insert(request_id, response.next_sequence);
+    }
+
+    if response.entries.is_empty() {
+        Ok(None)
+    } else {
+        serde_json::to_value(response)
+            .map(Some)
+            .map_err(BrpError::internal)
+    }
+}
+
+/// Returns the entries of the world's change journal whose sequence number is at least `since`.
+fn journal_since(world: &World, since: u64) -> Result<BrpJournalResponse, BrpError> {
+    let Some(journal) = world.change_journal() else {
+        return Err(BrpError::resource_error(
+            "The change journal is not enabled",
+        ));
+    };
+    Ok(BrpJournalResponse {
+        entries: journal.since(since).cloned().collect(),
+        next_sequence: journal.next_sequence(),
+    })
+}
+
+/// The names of the functions that can be called with a `registry.call_function` request.
+///
+/// Registered functions can have arbitrary effects, so only the functions in this list can be
+/// called remotely. Functions are added with [`RemotePlugin::with_callable_function`] or by
+/// modifying this resource.
+///
+/// [`RemotePlugin::with_callable_function`]: crate::RemotePlugin::with_callable_function
+#[cfg(feature = "reflect_functions")]
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BrpCallableFunctions(pub HashSet<String>);
+
+/// Handles a `registry.call_function` request coming from a client.
+#[cfg(feature = "reflect_functions")]
+pub fn process_remote_call_function_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpCallFunctionParams {
+        function,
+        args,
+        entity,
+    } = parse_some(params)?;
+
+    if !world
+        .get_resource::<BrpCallableFunctions>()
+        .is_some_and(|callable| callable.0.contains(&function))
+    {
+        return Err(BrpError::function_not_callable(&function));
+    }
+
+    let function_registry = world.resource::<AppFunctionRegistry>().clone();
+    let function_registry = function_registry.read();
+    let dynamic_function = function_registry
+        .get(&function)
+        .ok_or_else(|| BrpError::function_not_found(&function))?;
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let arg_count = args.len() + usize::from(entity.is_some());
+    let signature = dynamic_function
+        .info()
+        .signatures()
+        .iter()
+        .find(|signature| signature.arg_count() == arg_count)
+        .ok_or_else(|| {
+            BrpError::function_error(anyhow!(
+                "Function `{function}` does not take {arg_count} arguments"
+            ))
+        })?;
+    let (receiver_info, arg_infos) = match entity {
+        Some(_) => signature
+            .args()
+            .split_first()
+            .map(|(receiver, args)| (Some(receiver), args))
+            .unwrap_or((None, &[])),
+        None => (None, signature.args()),
+    };
+
+    // Deserialize the arguments before building the argument list, which borrows them.
+    let mut values = args
+        .into_iter()
+        .zip(arg_infos)
+        .map(|(value, info)| {
+            let registration = get_arg_registration(info, &type_registry)?;
+            let value = TypedReflectDeserializer::new(registration, &type_registry)
+                .deserialize(&value)
+                .map_err(|err| {
+                    BrpError::function_error(anyhow!("Argument {} is invalid: {err}", info.index()))
+                })?;
+            Ok((info.ownership(), Some(value)))
+        })
+        .collect::<Result<Vec<_>, BrpError>>()?;
+
+    let mut arg_list = ArgList::new();
+    let mut receiver = None;
+    if let (Some(entity), Some(info)) = (entity, receiver_info) {
+        let registration = get_arg_registration(info, &type_registry)?;
+        let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+            BrpError::component_error(anyhow!(
+                "Receiver `{}` is not a reflected component",
+                registration.type_info().type_path()
+            ))
+        })?;
+        let reflected = reflect_component
+            .reflect_mut(get_entity_mut(world, entity)?)
+            .ok_or_else(|| {
+                BrpError::component_not_present(registration.type_info().type_path(), entity)
+            })?;
+        receiver = Some(reflected);
+    }
+    if let (Some(info), Some(receiver)) = (receiver_info, receiver.as_mut()) {
+        match info.ownership() {
+            Ownership::Ref => arg_list.push_ref(receiver.as_partial_reflect()),
+            Ownership::Mut => arg_list.push_mut(receiver.as_partial_reflect_mut()),
+            Ownership::Owned => arg_list.push_boxed(
+                receiver
+                    .reflect_clone()
+                    .map_err(BrpError::component_error)?
+                    .into_partial_reflect(),
+            ),
+        }
+    }
+    for (ownership, value) in &mut values {
+        match ownership {
+            Ownership::Ref => arg_list.push_ref(value.as_deref().unwrap()),
+            Ownership::Mut => arg_list.push_mut(value.as_deref_mut().unwrap()),
+            Ownership::Owned => arg_list.push_boxed(value.take().unwrap()),
+        }
+    }
+
+    let returned = dynamic_function
+        .call(arg_list)
+        .map_err(BrpError::function_error)?;
+    let value = match &returned {
+        _ if returned.is_unit() => Value::Null,
+        Return::Owned(value) => serialize_returned(value.as_ref(), &type_registry)?,
+        Return::Ref(value) => serialize_returned(*value, &type_registry)?,
+        Return::Mut(value) => serialize_returned(&**value, &type_registry)?,
+    };
+    serde_json::to_value(BrpCallFunctionResponse { value }).map_err(BrpError::internal)
+}
+
+/// Returns the registration of the type of a function argument.
+///
+/// For reference arguments, this is the registration of the referenced type.
+#[cfg(feature = "reflect_functions")]
+fn get_arg_registration<'r>(
+    info: &ArgInfo,
+    type_registry: &'r TypeRegistry,
+) -> Result<&'r TypeRegistration, BrpError> {
+    let type_path = info.type_path();
+    let type_path = match info.ownership() {
+        Ownership::Ref => type_path.strip_prefix('&'),
+        Ownership::Mut => type_path.strip_prefix("&mut "),
+        Ownership::Owned => Some(type_path),
+    }
+    .unwrap_or(type_path);
+    type_registry
+        .get_with_type_path(type_path)
+        .ok_or_else(|| BrpError::function_error(anyhow!("Unknown argument type: `{type_path}`")))
+}
+
+/// Serializes the value returned by a function.
+#[cfg(feature = "reflect_functions")]
+fn serialize_returned(value: &dyn PartialReflect, type_registry: &TypeRegistry) -> BrpResult {
+    serde_json::to_value(TypedReflectSerializer::new(value, type_registry))
+        .map_err(BrpError::function_error)
+}
+
+/// Handles an `asset.dependency_graph` request coming from a client.
+#[cfg(feature = "bevy_asset")]
+pub fn process_remote_asset_dependency_graph_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpAssetDependencyGraphParams { path, format } = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
+
+    let asset_server = world
+        .get_resource::<AssetServer>()
+        .ok_or_else(|| BrpError::resource_not_present("AssetServer"))?;
+    let graph = match path {
+        Some(path) => {
+            let id = asset_server
+                .get_path_id(&path)
+                .ok_or_else(|| BrpError::asset_not_found(&path))?;
+            asset_server.dependency_graph_of(id)
+        }
+        None => asset_server.dependency_graph(),
+    };
+
+    match format {
+        BrpAssetDependencyGraphFormat::Json => {
+            serde_json::to_value(graph).map_err(BrpError::internal)
+        }
+        BrpAssetDependencyGraphFormat::Dot => Ok(Value::String(graph.to_dot())),
+    }
+}
+
 /// Handles a `registry.schema` request (list all registry types in form of schema) coming from a client.
 pub fn export_registry_types(In(params): In<Option<Value>>, world: &World) -> BrpResult {
     let filter: BrpJsonSchemaQueryFilter = match params {
@@ -2055,7 +2452,7 @@ mod tests {
         observer::On,
         resource::Resource,
         schedule::{IntoScheduleConfigs as _, Schedule, ScheduleLabel, SystemSet},
-        system::{Commands, Res, ResMut},
+        system::{Commands, IntoSystem, Res, ResMut, System},
     };
     use bevy_reflect::Reflect;
     use serde_json::Value::Null;
@@ -2088,6 +2485,175 @@ mod tests {
         insert_reflected_components(e, deserialized_components).expect("FAIL");
     }
 
+    #[test]
+    fn mutate_component_with_validation() {
+        #[derive(Reflect, Component)]
+        #[reflect(Component)]
+        struct Player {
+            #[reflect(@bevy_reflect::validation::NonEmpty)]
+            name: String,
+            #[reflect(@bevy_reflect::validation::Range(0..=100))]
+            health: u32,
+        }
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<Player>();
+        let mut world = World::new();
+        world.insert_resource(atr);
+        let entity = world
+            .spawn(Player {
+                name: String::from("John"),
+                health: 50,
+            })
+            .id();
+
+        let mutate = |world: &mut World, path: &str, value: Value| {
+            let params = serde_json::json!({
+                "entity": entity,
+                "component": "bevy_remote::builtin_methods::tests::Player",
+                "path": path,
+                "value": value,
+            });
+            process_remote_mutate_components_request(In(Some(params)), world)
+        };
+
+        assert_eq!(mutate(&mut world, ".health", 100.into()), Ok(Null));
+        assert_eq!(world.get::<Player>(entity).unwrap().health, 100);
+
+        let error = mutate(&mut world, ".health", 101.into()).unwrap_err();
+        assert_eq!(error.code, error_codes::VALIDATION_ERROR);
+        assert_eq!(
+            error.data,
+            Some(serde_json::json!([{
+                "path": ".health",
+                "message": "101 is outside of the range 0..=100",
+            }]))
+        );
+        assert_eq!(world.get::<Player>(entity).unwrap().health, 100);
+
+        let error = mutate(&mut world, ".name", "".into()).unwrap_err();
+        assert_eq!(error.code, error_codes::VALIDATION_ERROR);
+        assert_eq!(world.get::<Player>(entity).unwrap().name, "John");
+    }
+
+    #[test]
+    fn stream_change_journal() {
+        #[derive(Component)]
+        struct Marker;
+
+        let mut world = World::new();
+        assert!(process_remote_journal_request(In(None), &world).is_err());
+
+        world.enable_change_journal(ChangeJournal::new(16));
+        let first = world.spawn(Marker).id();
+        let mut watch = IntoSystem::into_system(process_remote_journal_watching_request);
+        watch.initialize(&mut world);
+        let mut poll = |world: &mut World, request_id, params| {
+            world.insert_resource(WatchingRequestId(request_id));
+            watch
+                .run(params, world)
+                .unwrap()
+                .unwrap()
+                .map(|value| parse::<BrpJournalResponse>(value).unwrap())
+        };
+        // Only the entries recorded after the first poll are streamed.
+        assert_eq!(poll(&mut world, 0, None), None);
+
+        let second = world.spawn(Marker).id();
+        let response = poll(&mut world, 0, None).unwrap();
+        assert_eq!(response.next_sequence, 4);
+        assert!(response.entries.iter().all(|entry| entry.entity == second));
+        assert_eq!(poll(&mut world, 0, None), None);
+
+        // Each request streams from its own position.
+        let params = serde_json::to_value(BrpJournalParams { since: Some(0) }).unwrap();
+        let response = poll(&mut world, 1, Some(params.clone())).unwrap();
+        assert_eq!(response.entries[0].entity, first);
+        assert_eq!(poll(&mut world, 1, Some(params)), None);
+        world.spawn(Marker);
+        assert!(poll(&mut world, 0, None).is_some());
+        assert!(poll(&mut world, 1, None).is_some());
+
+        let params = serde_json::to_value(BrpJournalParams { since: Some(1) }).unwrap();
+        let response: BrpJournalResponse =
+            parse(process_remote_journal_request(In(Some(params)), &world).unwrap()).unwrap();
+        assert_eq!(response.entries.len(), 5);
+        assert_eq!(response.entries[0].entity, first);
+        test_serialize_deserialize(response);
+    }
+
+    #[cfg(feature = "reflect_functions")]
+    #[test]
+    fn call_registered_functions() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        impl Health {
+            fn heal(&mut self, amount: u32) -> u32 {
+                self.0 += amount;
+                self.0
+            }
+        }
+
+        fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<Health>();
+            register.register::<i32>();
+            register.register::<u32>();
+        }
+        let functions = AppFunctionRegistry::default();
+        {
+            let mut register = functions.write();
+            register.register_with_name("add", add).unwrap();
+            register.register_with_name("heal", Health::heal).unwrap();
+        }
+        let mut world = World::new();
+        world.insert_resource(atr);
+        world.insert_resource(functions);
+        world.insert_resource(BrpCallableFunctions(HashSet::from_iter([
+            "add".to_owned(),
+            "heal".to_owned(),
+        ])));
+        let entity = world.spawn(Health(5)).id();
+
+        let call = |world: &mut World, function: &str, args: Vec<Value>, entity| {
+            let params = serde_json::to_value(BrpCallFunctionParams {
+                function: function.to_owned(),
+                args,
+                entity,
+            })
+            .expect("FAIL");
+            process_remote_call_function_request(In(Some(params)), world)
+        };
+
+        assert_eq!(
+            call(&mut world, "add", vec![1.into(), 2.into()], None),
+            Ok(serde_json::json!({ "value": 3 }))
+        );
+        assert_eq!(
+            call(&mut world, "heal", vec![10.into()], Some(entity)),
+            Ok(serde_json::json!({ "value": 15 }))
+        );
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 15);
+
+        let error = call(&mut world, "add", vec![1.into()], None).unwrap_err();
+        assert_eq!(error.code, error_codes::FUNCTION_ERROR);
+        let error = call(&mut world, "missing", vec![], None).unwrap_err();
+        assert_eq!(error.code, error_codes::FUNCTION_NOT_CALLABLE);
+        world
+            .resource_mut::<BrpCallableFunctions>()
+            .0
+            .insert("missing".to_owned());
+        let error = call(&mut world, "missing", vec![], None).unwrap_err();
+        assert_eq!(error.code, error_codes::FUNCTION_NOT_FOUND);
+    }
+
     #[test]
     fn trigger_reflect_only_event() {
         #[derive(Event, Reflect)]
@@ -337,6 +337,9 @@
 //!   [`GetPath`](bevy_reflect::GetPath#syntax) for more information on formatting this string.
 //! - `value`: The value to insert at `path`.
 //!
+//! If the mutated component would violate the [validation constraints] of its fields,
+//! it is left unchanged and a [`VALIDATION_ERROR`](error_codes::VALIDATION_ERROR) is returned.
+//!
 //! `result`: null.
 //!
 //! ### `world.reparent_entities`
@@ -445,6 +448,9 @@
 //!   [`GetPath`](bevy_reflect::GetPath#syntax) for more information on formatting this string.
 //! - `value`: The value to be inserted at `path`.
 //!
+//! Like `world.mutate_components`, the resource is left unchanged if the mutation would violate
+//! its [validation constraints].
+//!
 //! `result`: null.
 //!
 //! ### `world.list_resources`
@@ -463,6 +469,33 @@
 //!
 //! `result`: null.
 //!
+//! ### `world.journal`
+//!
+//! Retrieve the entries of the world's [`ChangeJournal`], which records spawns, despawns, and
+//! component insertions and removals. The journal must have been enabled with
+//! [`World::enable_change_journal`].
+//!
+//! `params` (optional):
+//! - `since`: Only entries whose sequence number is at least `since` are returned. Defaults to 0.
+//!
+//! `result`:
+//! - `entries`: An array of the requested [`JournalEntry`]s, oldest first.
+//! - `next_sequence`: The sequence number the next entry will get.
+//!
+//! ### `world.journal+watch`
+//!
+//! Stream the entries of the world's [`ChangeJournal`] as they are recorded.
+//!
+//! `params` (optional):
+//! - `since`: The sequence number of the first entry to stream. Defaults to the sequence number
+//!   of the next entry.
+//!
+//! `result`: The same as `world.journal`, with the entries recorded since the last response.
+//!
+//! [`ChangeJournal`]: bevy_ecs::world::ChangeJournal
+//! [`World::enable_change_journal`]: bevy_ecs::world::World::enable_change_journal
+//! [`JournalEntry`]: bevy_ecs::world::JournalEntry
+//!
 //! ### `registry.schema`
 //!
 //! Retrieve schema information about registered types in the Bevy app's type registry.
@@ -478,6 +511,47 @@
 //! This contains schema information about that type, including field definitions, type information, reflect type information, and other metadata
 //! helpful for understanding the structure of the type.
 //!
+//! ### `registry.call_function`
+//!
+//! Call a function registered in the `AppFunctionRegistry`. This method is only available with
+//! the `reflect_functions` feature, and only the functions added to the `BrpCallableFunctions`
+//! allowlist, for example with `RemotePlugin::with_callable_function`, can be called.
+//!
+//! `params`:
+//! - `function`: The name of the function to call.
+//! - `args` (optional): An array of the values of the arguments of the function, in order.
+//! - `entity` (optional): The ID of an entity whose component is passed as the first argument of
+//!   the function, to call a method of the component. The component type is given by the first
+//!   argument, and it is modified in place if that argument is a mutable reference.
+//!
+//! `result`:
+//! - `value`: The value returned by the function, or null if it returns nothing.
+//!
+//! ### `asset.dependency_graph`
+//!
+//! Retrieve the dependency graph of the assets managed by the `AssetServer`, together with their
+//! load states. This method is only available with the `bevy_asset` feature.
+//!
+//! `params` (optional):
+//! - `path`: The path of an asset. When provided, the graph only contains this asset, its direct
+//!   and indirect dependencies, and the assets that directly or indirectly depend on it.
+//! - `format` (optional): Either `json` (the default) or `dot`.
+//!
+//! `result`: With the `json` format, an array of the assets in the graph, each of which has:
+//! - `key`: A string identifying the asset, made of its type path and index.
+//! - `type_path`: The type path of the asset.
+//! - `path`: The path of the asset, or null if it has none.
+//! - `load_state`, `dependency_load_state`, `recursive_dependency_load_state`: Objects with the
+//!   `state` (`NotLoaded`, `Loading`, `Loaded` or `Failed`) and the `error` message, if any.
+//! - `dependencies`: The keys of the direct dependencies of the asset.
+//! - `dependents`: The keys of the assets that directly depend on the asset.
+//! - `loader_dependencies`: The paths of the assets read by the loader of the asset. These are
+//!   only tracked when watching for changes.
+//!
+//! With the `dot` format, a string containing the graph in the [DOT] format.
+//!
+//! [DOT]: https://graphviz.org/doc/info/lang.html
+//!
 //! ### `rpc.discover`
 //!
 //! Discover available remote methods and server information. This follows the [`OpenRPC` specification for service discovery](https://spec.open-rpc.org/#service-discovery-method).
@@ -531,6 +605,7 @@
 //! [the `serde` documentation]: https://serde.rs/
 //! [fully-qualified type names]: bevy_reflect::TypePath::type_path
 //! [fully-qualified type name]: bevy_reflect::TypePath::type_path
+//! [validation constraints]: bevy_reflect::validation
 
 extern crate alloc;
 
@@ -549,6 +624,7 @@ use bevy_ecs::{
     world::World,
 };
 use bevy_platform::collections::HashMap;
+use bevy_reflect::validation::ValidationErrors;
 #[cfg(feature = "bevy_render")]
 use bevy_render::{Render, RenderApp, RenderScheduleOrder, RenderStartup};
 use bevy_utils::prelude::default;
@@ -574,6 +650,9 @@ pub struct RemotePlugin {
     methods: RwLock<Vec<(String, RemoteMethodHandler)>>,
     /// The verbs that the server will recognize and respond to for the render subapp.
     render_methods: RwLock<Vec<(String, RemoteMethodHandler)>>,
+    /// The functions that can be called with `registry.call_function`.
+    #[cfg(feature = "reflect_functions")]
+    callable_functions: Vec<String>,
 }
 
 impl RemotePlugin {
@@ -583,6 +662,8 @@ impl RemotePlugin {
         Self {
             methods: RwLock::new(vec![]),
             render_methods: RwLock::new(vec![]),
+            #[cfg(feature = "reflect_functions")]
+            callable_functions: vec![],
         }
     }
 
@@ -668,6 +749,17 @@ impl RemotePlugin {
         self
     }
 
+    /// Allow the function registered in the [`AppFunctionRegistry`] with the given `name` to be
+    /// called with the `registry.call_function` method.
+    ///
+    /// [`AppFunctionRegistry`]: bevy_ecs::reflect::AppFunctionRegistry
+    #[cfg(feature = "reflect_functions")]
+    #[must_use]
+    pub fn with_callable_function(mut self, name: impl Into<String>) -> Self {
+        self.callable_functions.push(name.into());
+        self
+    }
+
     /// Create the default list of BRP methods
     fn add_default_methods(self, to_main: bool) -> Self {
         self.with_method(
@@ -770,6 +862,16 @@ impl RemotePlugin {
             builtin_methods::process_remote_observe_watching_request,
             to_main,
         )
+        .with_method(
+            builtin_methods::BRP_JOURNAL_METHOD,
+            builtin_methods::process_remote_journal_request,
+            to_main,
+        )
+        .with_watching_method(
+            builtin_methods::BRP_JOURNAL_AND_WATCH_METHOD,
+            builtin_methods::process_remote_journal_watching_request,
+            to_main,
+        )
         .with_method(
             builtin_methods::BRP_REGISTRY_SCHEMA_METHOD,
             builtin_methods::export_registry_types,
@@ -793,6 +895,22 @@ impl Default for RemotePlugin {
         let mut t = Self::empty();
         t = t.add_default_methods(true);
 
+        #[cfg(feature = "reflect_functions")]
+        {
+            t = t.with_method_main(
+                builtin_methods::BRP_CALL_FUNCTION_METHOD,
+                builtin_methods::process_remote_call_function_request,
+            );
+        }
+
+        #[cfg(feature = "bevy_asset")]
+        {
+            t = t.with_method_main(
+                builtin_methods::BRP_ASSET_DEPENDENCY_GRAPH_METHOD,
+                builtin_methods::process_remote_asset_dependency_graph_request,
+            );
+        }
+
         #[cfg(feature = "bevy_render")]
         {
             t = t.add_default_methods(false);
@@ -834,6 +952,11 @@ impl Plugin for RemotePlugin {
             .resource_mut::<MainScheduleOrder>()
             .insert_after(Last, RemoteLast);
 
+        #[cfg(feature = "reflect_functions")]
+        app.insert_resource(builtin_methods::BrpCallableFunctions(
+            self.callable_functions.iter().cloned().collect(),
+        ));
+
         app.insert_resource(remote_methods)
             .init_resource::<schemas::SchemaTypesMetadata>()
             .init_resource::<RemoteWatchingRequests>()
@@ -994,7 +1117,17 @@ impl RemoteMethods {
 
 /// Holds the [`BrpMessage`]'s of all ongoing watching requests along with their handlers.
 #[derive(Debug, Resource, Default)]
-pub struct RemoteWatchingRequests(Vec<(BrpMessage, RemoteWatchingMethodSystemId)>);
+pub struct RemoteWatchingRequests {
+    requests: Vec<(WatchingRequestId, BrpMessage, RemoteWatchingMethodSystemId)>,
+    next_id: u64,
+}
+
+/// Identifies an ongoing watching request.
+///
+/// While the handler of a watching request runs, its ID is available as a resource,
+/// so that handlers can keep state for each request, such as the position in a stream.
+#[derive(Debug, Resource, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WatchingRequestId(u64);
 
 /// A single request from a Bevy Remote Protocol client to the server,
 /// serialized in JSON.
@@ -1362,6 +1495,46 @@ impl BrpError {
         }
     }
 
+    /// Function wasn't found in the function registry.
+    #[must_use]
+    pub fn function_not_found(function: &str) -> Self {
+        Self {
+            code: error_codes::FUNCTION_NOT_FOUND,
+            message: format!("Function `{function}` not found"),
+            data: None,
+        }
+    }
+
+    /// Function isn't allowed to be called remotely.
+    #[must_use]
+    pub fn function_not_callable(function: &str) -> Self {
+        Self {
+            code: error_codes::FUNCTION_NOT_CALLABLE,
+            message: format!("Function `{function}` is not allowed to be called remotely"),
+            data: None,
+        }
+    }
+
+    /// An arbitrary function error. Possibly related to reflection.
+    #[must_use]
+    pub fn function_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::FUNCTION_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
+    /// Asset wasn't found in the asset server.
+    #[must_use]
+    pub fn asset_not_found(path: &str) -> Self {
+        Self {
+            code: error_codes::ASSET_NOT_FOUND,
+            message: format!("Asset `{path}` not found"),
+            data: None,
+        }
+    }
+
     /// An arbitrary internal error.
     #[must_use]
     pub fn internal<E: ToString>(error: E) -> Self {
@@ -1381,6 +1554,30 @@ impl BrpError {
             data: None,
         }
     }
+
+    /// A value violates the [validation constraints] of its type.
+    ///
+    /// The path and message of each violation are listed in the error data.
+    ///
+    /// [validation constraints]: bevy_reflect::validation
+    #[must_use]
+    pub fn validation_error(errors: &ValidationErrors) -> Self {
+        let violations = errors
+            .0
+            .iter()
+            .map(|error| {
+                serde_json::json!({
+                    "path": error.path,
+                    "message": error.violation.to_string(),
+                })
+            })
+            .collect();
+        Self {
+            code: error_codes::VALIDATION_ERROR,
+            message: errors.to_string(),
+            data: Some(Value::Array(violations)),
+        }
+    }
 }
 
 /// Error codes used by BRP.
@@ -1422,6 +1619,21 @@ pub mod error_codes {
 
     /// Could not find resource in the world.
     pub const RESOURCE_NOT_PRESENT: i16 = -23502;
+
+    /// Could not find function in the function registry.
+    pub const FUNCTION_NOT_FOUND: i16 = -23601;
+
+    /// Function is not allowed to be called remotely.
+    pub const FUNCTION_NOT_CALLABLE: i16 = -23602;
+
+    /// Could not call function or reflect its arguments or return value.
+    pub const FUNCTION_ERROR: i16 = -23603;
+
+    /// A value violates the validation constraints of its type.
+    pub const VALIDATION_ERROR: i16 = -23701;
+
+    /// Could not find asset in the asset server.
+    pub const ASSET_NOT_FOUND: i16 = -23801;
 }
 
 /// The result of a request.
@@ -1513,10 +1725,10 @@ fn process_remote_requests(world: &mut World) {
                 let _ = message.sender.force_send(result);
             }
             RemoteMethodSystemId::Watching(id) => {
-                world
-                    .resource_mut::<RemoteWatchingRequests>()
-                    .0
-                    .push((message, id));
+                let mut requests = world.resource_mut::<RemoteWatchingRequests>();
+                let request_id = WatchingRequestId(requests.next_id);
+                requests.next_id += 1;
+                requests.requests.push((request_id, message, id));
             }
         }
     }
@@ -1526,7 +1738,8 @@ fn process_remote_requests(world: &mut World) {
 /// and handles it if so.
 fn process_ongoing_watching_requests(world: &mut World) {
     world.resource_scope::<RemoteWatchingRequests, ()>(|world, requests| {
-        for (message, system_id) in requests.0.iter() {
+        for (request_id, message, system_id) in requests.requests.iter() {
+            world.insert_resource(*request_id);
             let handler_result = process_single_ongoing_watching_request(world, message, system_id);
             let sender_result = match handler_result {
                 Ok(Some(value)) => message.sender.try_send(Ok(value)),
@@ -1539,6 +1752,7 @@ fn process_ongoing_watching_requests(world: &mut World) {
                 message.sender.close();
             }
         }
+        world.remove_resource::<WatchingRequestId>();
     });
 }
 
@@ -1556,14 +1770,20 @@ fn process_single_ongoing_watching_request(
         })?
 }
 
-fn remove_closed_watching_requests(mut requests: ResMut<RemoteWatchingRequests>) {
-    for i in (0..requests.0.len()).rev() {
-        let Some((message, _)) = requests.0.get(i) else {
+fn remove_closed_watching_requests(
+    mut requests: ResMut<RemoteWatchingRequests>,
+    mut journal_cursors: Option<ResMut<builtin_methods::BrpJournalCursors>>,
+) {
+    for i in (0..requests.requests.len()).rev() {
+        let Some((request_id, message, _)) = requests.requests.get(i) else {
             unreachable!()
         };
 
         if message.sender.is_closed() {
-            requests.0.swap_remove(i);
+            if let Some(journal_cursors) = &mut journal_cursors {
+                journal_cursors.cursors.remove(request_id);
+            }
+            requests.requests.swap_remove(i);
         }
     }
 }
@@ -1583,6 +1803,7 @@ fn cache_schedule_build_metadata(
         // `ScheduleData`.
         warnings: vec![],
         edges_added_by_build_passes: event.build_metadata.edges_added_by_build_passes.clone(),
+        ambiguities: event.build_metadata.ambiguities.clone(),
     };
     metadata.0.insert(event.label, new_metadata);
 }
@@ -5,8 +5,8 @@ use bevy_ecs::component::{Components, StorageType};
 use bevy_ecs::{component::ComponentInfo, relationship::RelationshipAccessor};
 use bevy_platform::collections::HashMap;
 use bevy_reflect::{
-    enums::VariantInfo, GetTypeRegistration, NamedField, OpaqueInfo, TypeInfo, TypeRegistration,
-    TypeRegistry,
+    attributes::CustomAttributes, enums::VariantInfo, validation::Constraints, GetTypeRegistration,
+    NamedField, OpaqueInfo, TypeInfo, TypeRegistration, TypeRegistry,
 };
 use core::any::TypeId;
 use serde::{Deserialize, Serialize};
@@ -101,7 +101,15 @@ impl From<(&TypeRegistration, &SchemaTypesMetadata, &Components)> for JsonSchema
             TypeInfo::Struct(info) => {
                 typed_schema.properties = info
                     .iter()
-                    .map(|field| (field.name().to_owned(), field.ty().ref_type()))
+                    .map(|field| {
+                        let mut reference = field.ty().ref_type();
+                        add_constraints(
+                            &mut reference,
+                            field.custom_attributes(),
+                            field.type_info(),
+                        );
+                        (field.name().to_owned(), reference)
+                    })
                     .collect::<HashMap<_, _>>();
                 typed_schema.required = info
                     .iter()
@@ -444,14 +452,51 @@ impl SchemaJsonReference for &bevy_reflect::Type {
 impl SchemaJsonReference for &bevy_reflect::UnnamedField {
     fn ref_type(self) -> Value {
         let path = self.type_path();
-        json!({"type": json!({ "$ref": format!("#/$defs/{path}") })})
+        let mut reference = json!({"type": json!({ "$ref": format!("#/$defs/{path}") })});
+        add_constraints(&mut reference, self.custom_attributes(), self.type_info());
+        reference
     }
 }
 
 impl SchemaJsonReference for &NamedField {
     fn ref_type(self) -> Value {
         let type_path = self.type_path();
-        json!({"type": json!({ "$ref": format!("#/$defs/{type_path}") }), "typePath": self.name()})
+        let mut reference = json!({"type": json!({ "$ref": format!("#/$defs/{type_path}") }), "typePath": self.name()});
+        add_constraints(&mut reference, self.custom_attributes(), self.type_info());
+        reference
+    }
+}
+
+/// Adds the JSON Schema keywords for the [validation constraints] of a field to its reference.
+///
+/// [validation constraints]: bevy_reflect::validation
+fn add_constraints(
+    reference: &mut Value,
+    attributes: &CustomAttributes,
+    type_info: Option<&TypeInfo>,
+) {
+    let constraints = Constraints::from_attributes(attributes);
+    if constraints.is_empty() {
+        return;
+    }
+    let Some(reference) = reference.as_object_mut() else {
+        return;
+    };
+
+    if let Some(range) = constraints.range {
+        reference.insert("minimum".to_owned(), json!(range.start()));
+        reference.insert("maximum".to_owned(), json!(range.end()));
+    }
+    let (min, max) = match type_info {
+        Some(TypeInfo::List(_) | TypeInfo::Array(_) | TypeInfo::Set(_)) => ("minItems", "maxItems"),
+        Some(TypeInfo::Map(_)) => ("minProperties", "maxProperties"),
+        _ => ("minLength", "maxLength"),
+    };
+    if constraints.non_empty {
+        reference.insert(min.to_owned(), json!(1));
+    }
+    if let Some(max_len) = constraints.max_len {
+        reference.insert(max.to_owned(), json!(max_len));
     }
 }
 
@@ -506,6 +551,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reflect_export_struct_with_constraints() {
+        use bevy_reflect::validation::{MaxLen, NonEmpty, Range};
+
+        #[derive(Reflect, Component)]
+        struct Player {
+            #[reflect(@NonEmpty, @MaxLen(32))]
+            name: String,
+            #[reflect(@Range(0.0..=1.0))]
+            volume: f32,
+            #[reflect(@MaxLen(4))]
+            tags: Vec<String>,
+        }
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<Player>();
+        let type_registry = atr.read();
+        let registration = type_registry
+            .get(TypeId::of::<Player>())
+            .expect("SHOULD BE REGISTERED");
+        let (_, schema) = export_type(
+            registration,
+            &SchemaTypesMetadata::default(),
+            &Components::default(),
+        );
+
+        let name = schema.properties.get("name").expect("Missing `name` field");
+        assert_eq!(name["minLength"], json!(1));
+        assert_eq!(name["maxLength"], json!(32));
+        let volume = schema
+            .properties
+            .get("volume")
+            .expect("Missing `volume` field");
+        assert_eq!(volume["minimum"], json!(0.0));
+        assert_eq!(volume["maximum"], json!(1.0));
+        let tags = schema.properties.get("tags").expect("Missing `tags` field");
+        assert_eq!(tags["maxItems"], json!(4));
+        assert!(tags.get("minItems").is_none());
+    }
+
     #[test]
     fn reflect_export_enum() {
         #[derive(Reflect, Component, Default, Deserialize, Serialize)]
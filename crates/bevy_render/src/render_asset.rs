@@ -317,9 +317,10 @@ pub(crate) fn extract_render_asset<A: RenderAsset>(
                     }
                     AssetEvent::Removed { .. } => {
                         // We don't care that the asset was removed from Assets<T> in the main world.
-                        // An asset is only removed from RenderAssets<T> when its last handle is dropped (AssetEvent::Unused).
+                        // An asset is only removed from RenderAssets<T> when its last handle is dropped (AssetEvent::Unused),
+                        // or when it is evicted to free memory (AssetEvent::Evicted).
                     }
-                    AssetEvent::Unused { id } => {
+                    AssetEvent::Unused { id } | AssetEvent::Evicted { id } => {
                         needs_extracting.remove(id);
                         extracted_assets.modified.remove(id);
                         extracted_assets.removed.insert(*id);
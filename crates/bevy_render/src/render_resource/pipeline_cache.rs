@@ -777,7 +777,8 @@ impl PipelineCache {
                     }
                 }
                 AssetEvent::Removed { id } => cache.remove_shader(*id),
-                AssetEvent::Unused { .. } => {}
+                // Evicted shaders are kept in the cache, and replaced once they are reloaded.
+                AssetEvent::Unused { .. } | AssetEvent::Evicted { .. } => {}
                 AssetEvent::LoadedWithDependencies { .. } => {
                     // TODO: handle this
                 }
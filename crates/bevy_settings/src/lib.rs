@@ -4,6 +4,7 @@
 //! Refer to [`SettingsPlugin`] for detailed usage information.
 
 use core::any::TypeId;
+use core::fmt::Display;
 use core::time::Duration;
 use std::collections::HashMap;
 
@@ -18,8 +19,13 @@ use bevy_ecs::{
 pub use bevy_ecs_macros::SettingsGroup;
 use bevy_log::warn;
 use bevy_reflect::{
+    attributes::CustomAttributes,
     prelude::ReflectDefault,
-    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    serde::{
+        ReflectVersion, SchemalessReflectDeserializer, SchemalessReflectSerializer,
+        TypedReflectDeserializer, TypedReflectSerializer,
+    },
+    validation::Constraints,
     CreateTypeData, FromReflect, PartialReflect, ReflectMut, TypeInfo, TypePath, TypeRegistration,
     TypeRegistry,
 };
@@ -136,6 +142,12 @@ impl Plugin for SettingsPlugin {
 /// `settings_group(file = "<filename>")`. This should be the base name of the file without the
 /// extension. The default name is `settings`, which will cause the settings to be written out
 /// to `settings.toml` in the app's settings directory.
+///
+/// Settings can be versioned using `#[reflect(version = N)]`, in which case settings files
+/// written by older versions of the app are upgraded using the migrations registered with
+/// [`TypeRegistry::register_migration`]. The versions are recorded in a separate `"$versions"`
+/// table of the settings file, keyed by type path. Settings with no recorded version are
+/// assumed to be up to date.
 pub trait SettingsGroup: Resource {
     /// The name of the logical section within the settings file.
     fn settings_group_name() -> &'static str;
@@ -176,6 +188,9 @@ impl<T: SettingsGroup + FromReflect + TypePath> CreateTypeData<T> for ReflectSet
     }
 }
 
+/// The key of the table recording the versions of [versioned](ReflectVersion) settings.
+const VERSIONS_KEY: &str = "$versions";
+
 /// List of resource types that will be associated with a specific settings file.
 /// Also tracks when that file was last written or read.
 #[derive(Default)]
@@ -312,6 +327,7 @@ fn resources_to_toml(
     manifest: &SettingsFileManifest,
 ) -> toml::map::Map<String, toml::Value> {
     let mut table = toml::Table::new();
+    let mut versions = toml::Table::new();
 
     for tid in manifest.resource_types.iter() {
         let ty = types.get(*tid).unwrap();
@@ -340,16 +356,26 @@ fn resources_to_toml(
         };
 
         let serializer = TypedReflectSerializer::new(reflect.as_partial_reflect(), types);
+        let mut value = toml::Value::try_from(serializer).unwrap();
+
+        if let Some(version) = ty.data::<ReflectVersion>() {
+            // Keep the settings flat by recording the version in a separate table.
+            value = value
+                .as_table_mut()
+                .and_then(|table| table.remove("value"))
+                .unwrap();
+            versions.insert(
+                ty.type_info().type_path().to_string(),
+                toml::Value::Integer(version.version().into()),
+            );
+        }
 
         let toml_value = if let Some(settings_key) = settings_key {
             // convert toml value into a key value pair if settings_key is set. settings_key is only set for enums
-            toml::Value::Table(toml::Table::from_iter([(
-                settings_key.to_string(),
-                toml::Value::try_from(serializer).unwrap(),
-            )]))
+            toml::Value::Table(toml::Table::from_iter([(settings_key.to_string(), value)]))
         } else {
             // Otherwise, the whole struct is serialized into toml
-            toml::Value::try_from(serializer).unwrap()
+            value
         };
 
         match (
@@ -370,6 +396,10 @@ fn resources_to_toml(
         };
     }
 
+    if !versions.is_empty() {
+        table.insert(VERSIONS_KEY.to_string(), toml::Value::Table(versions));
+    }
+
     table
 }
 
@@ -481,7 +511,8 @@ fn apply_settings_to_world(
                     value
                 };
 
-                load_properties(value, &mut *reflect, types);
+                let migrated = migrate_properties(value, toml, ty);
+                load_properties(migrated.as_ref().unwrap_or(value), &mut *reflect, types);
             }
         } else {
             // The resource does not exist, so create a default.
@@ -501,7 +532,12 @@ fn apply_settings_to_world(
                     value
                 };
 
-                load_properties(value, &mut *default_value, types);
+                let migrated = migrate_properties(value, toml, ty);
+                load_properties(
+                    migrated.as_ref().unwrap_or(value),
+                    &mut *default_value,
+                    types,
+                );
             }
 
             // Now add the new resource to the world.
@@ -510,6 +546,48 @@ fn apply_settings_to_world(
     }
 }
 
+/// Upgrades the properties of a [versioned](ReflectVersion) resource which were saved
+/// with an older version.
+///
+/// Returns `None` if the properties are up to date, or could not be migrated.
+fn migrate_properties(
+    value: &toml::Value,
+    toml: &toml::Table,
+    ty: &TypeRegistration,
+) -> Option<toml::Value> {
+    let version = ty.data::<ReflectVersion>()?;
+    let type_path = ty.type_info().type_path();
+    let saved_version = toml
+        .get(VERSIONS_KEY)?
+        .get(type_path)?
+        .as_integer()
+        .and_then(|version| u32::try_from(version).ok())?;
+    if saved_version == version.version() {
+        return None;
+    }
+
+    let migrated = SchemalessReflectDeserializer
+        .deserialize(value.clone())
+        .map_err(|err| err.to_string())
+        .and_then(|value| {
+            version
+                .migrate(value, saved_version)
+                .map_err(|err| err.to_string())
+        })
+        .and_then(|value| {
+            toml::Value::try_from(SchemalessReflectSerializer::new(&*value))
+                .map_err(|err| err.to_string())
+        });
+
+    match migrated {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("Failed to migrate settings for `{type_path}`: {err}");
+            None
+        }
+    }
+}
+
 fn load_properties(value: &toml::Value, resource: &mut dyn PartialReflect, types: &TypeRegistry) {
     let Some(tinfo) = resource.get_represented_type_info() else {
         return;
@@ -525,13 +603,16 @@ fn load_properties(value: &toml::Value, resource: &mut dyn PartialReflect, types
                     if let Some(toml_field_value) = table.get(*field)
                         && let Some(field_info) = stinfo.field_at(idx)
                         && let Some(field_type) = types.get(field_info.type_id())
+                        && let Some(field_value) = deserialize_property(
+                            toml_field_value,
+                            field_type,
+                            Some(field_info.custom_attributes()),
+                            format_args!("{}::{field}", stinfo.type_path()),
+                            types,
+                        )
                     {
-                        let deserializer = TypedReflectDeserializer::new(field_type, types);
-                        if let Ok(field_value) = deserializer.deserialize(toml_field_value.clone())
-                        {
-                            // Should be safe to unwrap here since we know the field exists (above).
-                            st_reflect.field_at_mut(idx).unwrap().apply(&*field_value);
-                        }
+                        // Should be safe to unwrap here since we know the field exists (above).
+                        st_reflect.field_at_mut(idx).unwrap().apply(&*field_value);
                     }
                 }
             }
@@ -545,43 +626,79 @@ fn load_properties(value: &toml::Value, resource: &mut dyn PartialReflect, types
                     for (idx, toml_field_value) in array.iter().enumerate() {
                         if let Some(field_info) = tstinfo.field_at(idx)
                             && let Some(field_type) = types.get(field_info.type_id())
+                            && let Some(field_value) = deserialize_property(
+                                toml_field_value,
+                                field_type,
+                                Some(field_info.custom_attributes()),
+                                format_args!("{}::{idx}", tstinfo.type_path()),
+                                types,
+                            )
                         {
-                            let deserializer = TypedReflectDeserializer::new(field_type, types);
-                            if let Ok(field_value) =
-                                deserializer.deserialize(toml_field_value.clone())
-                            {
-                                // Should be safe to unwrap here since we know the field exists (above).
-                                tst_reflect.field_mut(idx).unwrap().apply(&*field_value);
-                            }
+                            // Should be safe to unwrap here since we know the field exists (above).
+                            tst_reflect.field_mut(idx).unwrap().apply(&*field_value);
                         }
                     }
                 } else if tst_reflect.field_len() == 1
                     && let Some(field_info) = tstinfo.field_at(0)
                     && let Some(field_type) = types.get(field_info.type_id())
+                    && let Some(field_value) = deserialize_property(
+                        value,
+                        field_type,
+                        Some(field_info.custom_attributes()),
+                        format_args!("{}::0", tstinfo.type_path()),
+                        types,
+                    )
                 {
-                    let deserializer = TypedReflectDeserializer::new(field_type, types);
-                    if let Ok(field_value) = deserializer.deserialize(value.clone()) {
-                        // Should be safe to unwrap here since we know the field exists (above).
-                        tst_reflect.field_mut(0).unwrap().apply(&*field_value);
-                    }
+                    // Should be safe to unwrap here since we know the field exists (above).
+                    tst_reflect.field_mut(0).unwrap().apply(&*field_value);
                 }
             }
         }
         TypeInfo::Enum(einfo) => {
             if let ReflectMut::Enum(en_reflect) = resource.reflect_mut()
                 && let Some(variant_type) = types.get(einfo.type_id())
+                && let Some(variant_value) =
+                    deserialize_property(value, variant_type, None, einfo.type_path(), types)
             {
-                let deserializer = TypedReflectDeserializer::new(variant_type, types);
-
-                if let Ok(variant_value) = deserializer.deserialize(value.clone()) {
-                    en_reflect.apply(&*variant_value);
-                }
+                en_reflect.apply(&*variant_value);
             }
         }
         _ => {}
     }
 }
 
+/// Deserializes a property loaded from a settings file.
+///
+/// Returns `None`, so that the current value is kept, if the property can't be deserialized
+/// or violates the [validation constraints](bevy_reflect::validation) of its field.
+fn deserialize_property(
+    value: &toml::Value,
+    ty: &TypeRegistration,
+    attributes: Option<&CustomAttributes>,
+    name: impl Display,
+    types: &TypeRegistry,
+) -> Option<Box<dyn PartialReflect>> {
+    let deserialized = TypedReflectDeserializer::new(ty, types)
+        .deserialize(value.clone())
+        .map_err(|err| err.to_string())
+        .and_then(|value| {
+            if let Some(attributes) = attributes {
+                Constraints::from_attributes(attributes)
+                    .check(&*value)
+                    .map_err(|violation| violation.to_string())?;
+            }
+            Ok(value)
+        });
+
+    match deserialized {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("Ignoring invalid setting `{name}`: {err}");
+            None
+        }
+    }
+}
+
 fn handle_delayed_save(
     mut settings: ResMut<SettingsFileRegistry>,
     time: Res<Time>,
@@ -1050,4 +1167,118 @@ mod tests {
         let refresh_rate = world.get_resource::<CounterRefreshRateSettings>().unwrap();
         assert_eq!(*refresh_rate, CounterRefreshRateSettings::Fast);
     }
+
+    #[test]
+    fn test_invalid_properties_are_ignored() {
+        use bevy_reflect::validation::{NonEmpty, Range};
+
+        #[derive(Resource, SettingsGroup, Reflect)]
+        #[reflect(Resource, SettingsGroup, Default)]
+        struct ValidatedAudioSettings {
+            #[reflect(@Range(0.0..=1.0))]
+            volume: f32,
+            #[reflect(@NonEmpty)]
+            device: String,
+        }
+
+        impl Default for ValidatedAudioSettings {
+            fn default() -> Self {
+                Self {
+                    volume: 0.5,
+                    device: "default".to_string(),
+                }
+            }
+        }
+
+        let mut world = World::new();
+        let mut types = TypeRegistry::default();
+        types.register::<ValidatedAudioSettings>();
+        world.insert_resource(ValidatedAudioSettings::default());
+
+        let manifest = SettingsFileManifest {
+            last_save: Tick::new(0),
+            resource_types: vec![TypeId::of::<ValidatedAudioSettings>()],
+        };
+
+        let table: toml::Table = toml::from_str(
+            r#"
+            [validated_audio_settings]
+            volume = 0.8
+            device = ""
+            "#,
+        )
+        .unwrap();
+        apply_settings_to_world(&mut world, Some(&table), &manifest, &types);
+        let settings = world.resource::<ValidatedAudioSettings>();
+        assert_eq!(settings.volume, 0.8);
+        assert_eq!(settings.device, "default");
+
+        let table: toml::Table = toml::from_str(
+            r#"
+            [validated_audio_settings]
+            volume = 1.5
+            device = "headphones"
+            "#,
+        )
+        .unwrap();
+        apply_settings_to_world(&mut world, Some(&table), &manifest, &types);
+        let settings = world.resource::<ValidatedAudioSettings>();
+        assert_eq!(settings.volume, 0.8);
+        assert_eq!(settings.device, "headphones");
+    }
+
+    #[test]
+    fn test_versioned_settings_are_migrated() {
+        /// Version 1 stored the volume as a percentage.
+        #[derive(Resource, SettingsGroup, Reflect, Default)]
+        #[reflect(Resource, SettingsGroup, Default, version = 2)]
+        struct VersionedAudioSettings {
+            volume: f32,
+        }
+
+        let mut world = World::new();
+        let mut types = TypeRegistry::default();
+        types.register::<VersionedAudioSettings>();
+        types.register_migration::<VersionedAudioSettings, _>(1, |value| {
+            let mut settings = value
+                .reflect_ref()
+                .as_struct()
+                .map_err(|err| err.to_string())?
+                .to_dynamic_struct();
+            let (_, volume) = settings.remove_by_name("volume").ok_or("missing volume")?;
+            let volume = volume.try_downcast_ref::<u64>().ok_or("invalid volume")?;
+            settings.insert("volume", *volume as f64 / 100.0);
+            Ok(Box::new(settings))
+        });
+
+        let manifest = SettingsFileManifest {
+            last_save: Tick::new(0),
+            resource_types: vec![TypeId::of::<VersionedAudioSettings>()],
+        };
+
+        let table: toml::Table = toml::from_str(
+            r#"
+            ["$versions"]
+            "bevy_settings::tests::VersionedAudioSettings" = 1
+
+            [versioned_audio_settings]
+            volume = 50
+            "#,
+        )
+        .unwrap();
+        apply_settings_to_world(&mut world, Some(&table), &manifest, &types);
+        assert_eq!(world.resource::<VersionedAudioSettings>().volume, 0.5);
+
+        // Saving records the current version, without wrapping the settings.
+        let table = resources_to_toml(&world, &types, &manifest);
+        let versions = table.get(VERSIONS_KEY).unwrap().as_table().unwrap();
+        assert_eq!(versions.values().next().unwrap().as_integer(), Some(2));
+        let section = table.get("versioned_audio_settings").unwrap();
+        assert_eq!(section.get("volume").unwrap().as_float(), Some(0.5));
+
+        // Settings which are up to date are loaded as is.
+        world.resource_mut::<VersionedAudioSettings>().volume = 1.0;
+        apply_settings_to_world(&mut world, Some(&table), &manifest, &types);
+        assert_eq!(world.resource::<VersionedAudioSettings>().volume, 0.5);
+    }
 }
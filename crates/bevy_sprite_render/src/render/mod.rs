@@ -655,7 +655,10 @@ pub fn prepare_sprite_image_bind_groups(
             AssetEvent::Added { .. } |
             // Images don't have dependencies
             AssetEvent::LoadedWithDependencies { .. } => {}
-            AssetEvent::Unused { id } | AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
+            AssetEvent::Unused { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::Removed { id }
+            | AssetEvent::Evicted { id } => {
                 image_bind_groups.values.remove(id);
             }
         };
@@ -0,0 +1,273 @@
+use alloc::vec::Vec;
+use bevy_app::App;
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    world::World,
+};
+
+use crate::{fixed::Fixed, time::Time, virt::Virtual};
+
+/// The schedules that run at their own fixed rate, along with their clocks.
+///
+/// Each schedule has its own [`Time<Fixed>`] clock, with its own timestep, overstep and elapsed
+/// time, that follows [`Time<Virtual>`] independently of the [`FixedMain`](bevy_app::FixedMain)
+/// clock and of the other fixed-rate schedules. This allows, for example, running AI at 10 Hz,
+/// physics at 60 Hz and networking at 30 Hz.
+///
+/// The schedules run in the [`RunFixedMainLoop`](bevy_app::RunFixedMainLoop) schedule, in the
+/// [`FixedMainLoop`](bevy_app::RunFixedMainLoopSystems::FixedMainLoop) system set, after the
+/// [`FixedMain`](bevy_app::FixedMain) schedule. They run in the order they were added, each one
+/// running zero to many times depending on its accumulated [`overstep()`](Time::overstep), just
+/// like [`FixedMain`](bevy_app::FixedMain). While a schedule runs, the generic [`Time`] resource
+/// holds its clock.
+///
+/// Use [`FixedRateAppExt::add_fixed_rate_schedule`] to add a schedule. The
+/// [`overstep_fraction()`](Time::overstep_fraction) of a clock can be used to interpolate between
+/// the last two steps of its schedule.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::ScheduleLabel;
+/// # use bevy_time::prelude::*;
+/// use bevy_time::{FixedRateAppExt, FixedRateSchedules};
+///
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// struct AiTick;
+///
+/// fn think(time: Res<Time>) {
+///     // `time.delta()` is 100 milliseconds here.
+/// }
+///
+/// fn animate(schedules: Res<FixedRateSchedules>) {
+///     let alpha = schedules.get(AiTick).unwrap().overstep_fraction();
+///     // Interpolate between the last two AI states with `alpha`.
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(bevy_time::TimePlugin)
+///     .add_fixed_rate_schedule(AiTick, Time::<Fixed>::from_hz(10.0))
+///     .add_systems(AiTick, think)
+///     .add_systems(Update, animate);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct FixedRateSchedules {
+    schedules: Vec<(InternedScheduleLabel, Time<Fixed>)>,
+}
+
+impl FixedRateSchedules {
+    /// Adds the schedule with the given `label`, run at the rate of `clock`.
+    ///
+    /// If the schedule was already added, its clock is replaced but it keeps its position in the
+    /// run order.
+    pub fn insert(&mut self, label: impl ScheduleLabel, clock: Time<Fixed>) {
+        let label = label.intern();
+        match self.get_mut(label) {
+            Some(current) => *current = clock,
+            None => self.schedules.push((label, clock)),
+        }
+    }
+
+    /// Stops running the schedule with the given `label` at a fixed rate, returning its clock.
+    pub fn remove(&mut self, label: impl ScheduleLabel) -> Option<Time<Fixed>> {
+        let label = label.intern();
+        let index = self
+            .schedules
+            .iter()
+            .position(|(current, _)| *current == label)?;
+        Some(self.schedules.remove(index).1)
+    }
+
+    /// Returns the clock of the schedule with the given `label`.
+    pub fn get(&self, label: impl ScheduleLabel) -> Option<&Time<Fixed>> {
+        let label = label.intern();
+        self.schedules
+            .iter()
+            .find_map(|(current, clock)| (*current == label).then_some(clock))
+    }
+
+    /// Returns the clock of the schedule with the given `label` mutably, for example to change its
+    /// [`timestep()`](Time::timestep).
+    pub fn get_mut(&mut self, label: impl ScheduleLabel) -> Option<&mut Time<Fixed>> {
+        let label = label.intern();
+        self.schedules
+            .iter_mut()
+            .find_map(|(current, clock)| (*current == label).then_some(clock))
+    }
+
+    /// Returns an iterator over the schedules and their clocks, in the order they run.
+    pub fn iter(&self) -> impl Iterator<Item = (InternedScheduleLabel, &Time<Fixed>)> {
+        self.schedules.iter().map(|(label, clock)| (*label, clock))
+    }
+}
+
+/// Adds schedules running at their own fixed rate to an [`App`].
+pub trait FixedRateAppExt {
+    /// Adds the schedule with the given `label` to the [`FixedRateSchedules`], so that it runs at
+    /// the rate of `clock`, creating the schedule if it doesn't exist.
+    fn add_fixed_rate_schedule(
+        &mut self,
+        label: impl ScheduleLabel,
+        clock: Time<Fixed>,
+    ) -> &mut Self;
+}
+
+impl FixedRateAppExt for App {
+    fn add_fixed_rate_schedule(
+        &mut self,
+        label: impl ScheduleLabel,
+        clock: Time<Fixed>,
+    ) -> &mut Self {
+        let label = label.intern();
+        self.init_schedule(label)
+            .init_resource::<FixedRateSchedules>()
+            .world_mut()
+            .resource_mut::<FixedRateSchedules>()
+            .insert(label, clock);
+        self
+    }
+}
+
+/// Runs each of the [`FixedRateSchedules`] until the time accumulated by its clock has been
+/// consumed.
+pub fn run_fixed_rate_schedules(world: &mut World) {
+    let delta = world.resource::<Time<Virtual>>().delta();
+    let labels: Vec<_> = world
+        .resource_mut::<FixedRateSchedules>()
+        .schedules
+        .iter_mut()
+        .map(|(label, clock)| {
+            clock.accumulate_overstep(delta);
+            *label
+        })
+        .collect();
+
+    for label in labels {
+        let _ = world.try_schedule_scope(label, |world, schedule| {
+            while let Some(clock) = world
+                .resource_mut::<FixedRateSchedules>()
+                .get_mut(label)
+                .and_then(|clock| clock.expend().then(|| clock.as_generic()))
+            {
+                *world.resource_mut::<Time>() = clock;
+                schedule.run(world);
+            }
+        });
+    }
+
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use core::time::Duration;
+
+    use bevy_app::{App, FixedUpdate};
+    use bevy_ecs::{prelude::*, schedule::ScheduleLabel};
+
+    use crate::{Fixed, FixedRateAppExt, FixedRateSchedules, Time, TimePlugin, TimeUpdateStrategy};
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct AiTick;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct NetworkTick;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<(&'static str, Duration)>);
+
+    fn log(name: &'static str) -> impl FnMut(Res<Time>, ResMut<Log>) {
+        move |time, mut log| log.0.push((name, time.delta()))
+    }
+
+    #[test]
+    fn schedules_run_at_their_own_rate() {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .init_resource::<Log>()
+            .insert_resource(Time::<Fixed>::from_hz(5.0))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .add_fixed_rate_schedule(AiTick, Time::<Fixed>::from_hz(10.0))
+            .add_fixed_rate_schedule(NetworkTick, Time::<Fixed>::from_hz(20.0))
+            .add_systems(FixedUpdate, log("fixed"))
+            .add_systems(AiTick, log("ai"))
+            .add_systems(NetworkTick, log("network"));
+
+        // The first update doesn't advance the clocks.
+        app.update();
+        assert!(app.world().resource::<Log>().0.is_empty());
+
+        app.update();
+        let ms = Duration::from_millis;
+        assert_eq!(
+            app.world_mut()
+                .resource_mut::<Log>()
+                .0
+                .drain(..)
+                .collect::<Vec<_>>(),
+            vec![("ai", ms(100)), ("network", ms(50)), ("network", ms(50))]
+        );
+
+        // The `FixedMain` schedule runs first.
+        app.update();
+        assert_eq!(
+            app.world().resource::<Log>().0,
+            vec![
+                ("fixed", ms(200)),
+                ("ai", ms(100)),
+                ("network", ms(50)),
+                ("network", ms(50))
+            ]
+        );
+
+        let schedules = app.world().resource::<FixedRateSchedules>();
+        let ai = schedules.get(AiTick).unwrap();
+        assert_eq!(ai.elapsed(), ms(200));
+        assert_eq!(ai.overstep_fraction(), 0.0);
+        assert_eq!(schedules.iter().count(), 2);
+    }
+
+    #[test]
+    fn overstep_is_kept_per_schedule() {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .init_resource::<Log>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                75,
+            )))
+            .add_fixed_rate_schedule(AiTick, Time::<Fixed>::from_hz(10.0))
+            .add_systems(AiTick, log("ai"));
+
+        app.update();
+        app.update();
+        let ai = *app
+            .world()
+            .resource::<FixedRateSchedules>()
+            .get(AiTick)
+            .unwrap();
+        assert!(app.world().resource::<Log>().0.is_empty());
+        assert_eq!(ai.overstep_fraction(), 0.75);
+
+        app.update();
+        let ai = *app
+            .world()
+            .resource::<FixedRateSchedules>()
+            .get(AiTick)
+            .unwrap();
+        assert_eq!(app.world().resource::<Log>().0.len(), 1);
+        assert_eq!(ai.overstep_fraction(), 0.5);
+
+        // Removed schedules no longer run.
+        app.world_mut()
+            .resource_mut::<FixedRateSchedules>()
+            .remove(AiTick)
+            .unwrap();
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Log>().0.len(), 1);
+    }
+}
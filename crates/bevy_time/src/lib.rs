@@ -16,6 +16,7 @@ extern crate alloc;
 pub mod common_conditions;
 mod delayed_commands;
 mod fixed;
+mod fixed_rate;
 mod real;
 mod stopwatch;
 mod time;
@@ -24,6 +25,7 @@ mod virt;
 
 pub use delayed_commands::*;
 pub use fixed::*;
+pub use fixed_rate::*;
 pub use real::*;
 pub use stopwatch::*;
 pub use time::*;
@@ -35,7 +37,9 @@ pub use virt::*;
 /// This includes the most common types in this crate, re-exported for your convenience.
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{DelayedCommandsExt, Fixed, Real, Time, Timer, TimerMode, Virtual};
+    pub use crate::{
+        DelayedCommandsExt, Fixed, FixedRateAppExt, Real, Time, Timer, TimerMode, Virtual,
+    };
 }
 
 use bevy_app::{prelude::*, OnAppExitSystems, RunFixedMainLoop};
@@ -69,6 +73,7 @@ impl Plugin for TimePlugin {
             .init_resource::<Time<Real>>()
             .init_resource::<Time<Virtual>>()
             .init_resource::<Time<Fixed>>()
+            .init_resource::<FixedRateSchedules>()
             .init_resource::<TimeUpdateStrategy>();
 
         #[cfg(feature = "bevy_reflect")]
@@ -88,7 +93,9 @@ impl Plugin for TimePlugin {
         .add_systems(PreUpdate, check_delayed_command_queues)
         .add_systems(
             RunFixedMainLoop,
-            run_fixed_main_schedule.in_set(RunFixedMainLoopSystems::FixedMainLoop),
+            (run_fixed_main_schedule, run_fixed_rate_schedules)
+                .chain()
+                .in_set(RunFixedMainLoopSystems::FixedMainLoop),
         )
         .add_systems(
             Last,
@@ -1594,7 +1594,7 @@ pub fn prepare_uinodes(
             AssetEvent::Unused { .. } |
             // Images don't have dependencies
             AssetEvent::LoadedWithDependencies { .. } => {}
-            AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
+            AssetEvent::Modified { id } | AssetEvent::Removed { id } | AssetEvent::Evicted { id } => {
                 image_bind_groups.values.remove(id);
             }
         };
@@ -381,7 +381,7 @@ pub fn prepare_ui_slices(
             AssetEvent::Unused { .. } |
             // Images don't have dependencies
             AssetEvent::LoadedWithDependencies { .. } => {}
-            AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
+            AssetEvent::Modified { id } | AssetEvent::Removed { id } | AssetEvent::Evicted { id } => {
                 image_bind_groups.values.remove(id);
             }
         };
@@ -12,7 +12,11 @@ use bevy_ecs::component::ComponentCloneBehavior;
 use bevy_ecs::relationship::RelationshipHookMode;
 
 #[cfg(feature = "serialize")]
-use {crate::serde::DynamicWorldSerializer, serde::Serialize};
+use {
+    crate::serde::DynamicWorldSerializer,
+    bevy_reflect::serde::{to_binary, BinaryError, BinaryOptions},
+    serde::Serialize,
+};
 
 /// A collection of serializable resources and dynamic entities.
 ///
@@ -229,6 +233,22 @@ impl DynamicWorld {
     pub fn serialize(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
         serialize_ron(DynamicWorldSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic world into the binary Bevy world format (`.scn.bin`).
+    ///
+    /// The binary format is much smaller and faster to read and write than the RON format,
+    /// at the cost of not being human-readable. See [`to_binary`] for details and `options`.
+    /// To deserialize the format, use the [`WorldAssetLoader`].
+    ///
+    /// [`WorldAssetLoader`]: crate::WorldAssetLoader
+    #[cfg(feature = "serialize")]
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistry,
+        options: BinaryOptions,
+    ) -> Result<Vec<u8>, BinaryError> {
+        to_binary(&DynamicWorldSerializer::new(self, registry), options)
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
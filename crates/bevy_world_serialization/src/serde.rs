@@ -559,7 +559,10 @@ mod tests {
         reflect::AppTypeRegistry,
         world::FromWorld,
     };
-    use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+    use bevy_reflect::{
+        serde::{from_binary_seed, BinaryOptions},
+        Reflect, ReflectDeserialize, ReflectSerialize,
+    };
     use core::any::TypeId;
     use ron;
     use serde::{de::DeserializeSeed, Deserialize, Serialize};
@@ -809,6 +812,72 @@ mod tests {
         assert_eq!(1, dst_world.query::<&FakeMesh3d>().iter(&dst_world).count());
     }
 
+    #[test]
+    fn should_migrate_versioned_components() {
+        use bevy_reflect::serde::TypedReflectSerializer;
+
+        /// Version 1 called `current` `hp`.
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component, version = 2)]
+        struct Health {
+            current: u32,
+        }
+
+        let world = create_world();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Health>();
+            registry.register_migration::<Health, _>(1, |value| {
+                let mut health = value
+                    .reflect_ref()
+                    .as_struct()
+                    .map_err(|err| err.to_string())?
+                    .to_dynamic_struct();
+                let (_, hp) = health.remove_by_name("hp").ok_or("missing `hp`")?;
+                health.insert_boxed("current", hp);
+                Ok(Box::new(health))
+            });
+        }
+
+        let input = r#"(
+  resources: {},
+  entities: {
+    8589934591: (
+      components: {
+        "bevy_world_serialization::serde::tests::Foo": (123),
+        "bevy_world_serialization::serde::tests::Health": (
+          version: 1,
+          value: (
+            hp: 50,
+          ),
+        ),
+      },
+    ),
+  },
+)"#;
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let world_deserializer = WorldDeserializer {
+            type_registry: &world.resource::<AppTypeRegistry>().read(),
+            load_from_path: &mut FakeHandleCreator,
+        };
+        let dynamic_world = world_deserializer.deserialize(&mut deserializer).unwrap();
+
+        let mut map = EntityHashMap::default();
+        let mut dst_world = World::new();
+        dst_world.insert_resource(world.resource::<AppTypeRegistry>().clone());
+        dynamic_world
+            .write_to_world(&mut dst_world, &mut map)
+            .unwrap();
+
+        let health = dst_world.query::<&Health>().single(&dst_world).unwrap();
+        assert_eq!(health.current, 50);
+
+        // Components are written back with their current version.
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let output = ron::to_string(&TypedReflectSerializer::new(health, &registry)).unwrap();
+        assert_eq!(output, "(version:2,value:(current:50))");
+    }
+
     fn roundtrip_ron(world: &World) -> (DynamicWorld, DynamicWorld) {
         let dynamic_world = DynamicWorld::from_world(world);
         let registry = world.resource::<AppTypeRegistry>().read();
@@ -987,6 +1056,50 @@ mod tests {
         assert_world_eq(&dynamic_world, &deserialized_world);
     }
 
+    #[test]
+    fn should_roundtrip_binary() {
+        let mut world = create_world();
+
+        for _ in 0..2 {
+            world.spawn(MyComponent {
+                foo: [1, 2, 3],
+                bar: (1.3, 3.7),
+                baz: MyEnum::Tuple("Hello World!".to_string()),
+            });
+        }
+
+        let registry = world.resource::<AppTypeRegistry>();
+        let registry = &registry.read();
+
+        let dynamic_world = DynamicWorld::from_world(&world);
+
+        for field_names in [false, true] {
+            let serialized_world = dynamic_world
+                .serialize_binary(registry, BinaryOptions { field_names })
+                .unwrap();
+
+            // The component type path is only stored once, in the string table.
+            let type_path = b"bevy_world_serialization::serde::tests::MyComponent";
+            assert_eq!(
+                1,
+                serialized_world
+                    .windows(type_path.len())
+                    .filter(|window| window == type_path)
+                    .count()
+            );
+
+            let world_deserializer = WorldDeserializer {
+                type_registry: registry,
+                load_from_path: &mut FakeHandleCreator,
+            };
+            let deserialized_world =
+                from_binary_seed(&serialized_world, world_deserializer).unwrap();
+
+            assert_eq!(2, deserialized_world.entities.len());
+            assert_world_eq(&dynamic_world, &deserialized_world);
+        }
+    }
+
     /// A crude equality checker for [`DynamicWorld`], used solely for testing purposes.
     fn assert_world_eq(expected: &DynamicWorld, received: &DynamicWorld) {
         assert_eq!(
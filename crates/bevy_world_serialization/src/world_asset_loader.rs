@@ -8,13 +8,15 @@ use bevy_reflect::{TypePath, TypeRegistryArc};
 use {
     crate::{serde::WorldDeserializer, DynamicWorld},
     bevy_asset::{io::Reader, AssetLoader, LoadContext},
+    bevy_reflect::serde::{from_binary_seed, BinaryError, BINARY_MAGIC},
     serde::de::DeserializeSeed,
     thiserror::Error,
 };
 
-/// Asset loader for a Bevy dynamic world (`.scn` / `.scn.ron`).
+/// Asset loader for a Bevy dynamic world (`.scn` / `.scn.ron` / `.scn.bin`).
 ///
-/// The loader handles assets serialized with [`DynamicWorld::serialize`].
+/// The loader handles assets serialized with [`DynamicWorld::serialize`] and,
+/// recognized by their header, [`DynamicWorld::serialize_binary`].
 #[derive(Debug, TypePath)]
 pub struct WorldAssetLoader {
     #[cfg_attr(
@@ -44,6 +46,9 @@ pub enum WorldAssetLoaderError {
     /// A [RON Error](ron::error::SpannedError)
     #[error("Could not parse RON: {0}")]
     RonSpannedError(#[from] ron::error::SpannedError),
+    /// A [binary format error](BinaryError)
+    #[error("Could not parse binary world: {0}")]
+    Binary(#[from] BinaryError),
 }
 
 #[cfg(feature = "serialize")]
@@ -60,6 +65,14 @@ impl AssetLoader for WorldAssetLoader {
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
+        if bytes.starts_with(&BINARY_MAGIC) {
+            let scene_deserializer = WorldDeserializer {
+                type_registry: &self.type_registry.read(),
+                load_from_path: load_context,
+            };
+            return Ok(from_binary_seed(&bytes, scene_deserializer)?);
+        }
+
         let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
         let scene_deserializer = WorldDeserializer {
             type_registry: &self.type_registry.read(),
@@ -71,6 +84,6 @@ impl AssetLoader for WorldAssetLoader {
     }
 
     fn extensions(&self) -> &[&str] {
-        &["scn", "scn.ron"]
+        &["scn", "scn.ron", "scn.bin"]
     }
 }